@@ -0,0 +1,45 @@
+//! Times the JSON grammar on a generated document, run with
+//! `cargo run --release --example json_bench`
+use std::{hint::black_box, time::Instant};
+
+use pepser::json::{json_value, json_value_ref};
+
+fn document(items: usize) -> String {
+    let items = (0..items)
+        .map(|i| {
+            format!(
+                "{{\"id\": {}, \"name\": \"item {}\", \"tags\": [\"a\", \"b\"], \"active\": {}, \"parent\": null}}",
+                i,
+                i,
+                i % 2 == 0
+            )
+        })
+        .collect::<Vec<_>>();
+    format!("[{}]", items.join(", "))
+}
+
+fn main() {
+    let input = document(2_000);
+    let iterations = 20;
+    time("owned", &input, iterations, |input| {
+        black_box(json_value(input).unwrap());
+    });
+    time("borrowed", &input, iterations, |input| {
+        black_box(json_value_ref(input).unwrap());
+    });
+}
+
+fn time(name: &str, input: &str, iterations: u32, mut parse: impl FnMut(&str)) {
+    let start = Instant::now();
+    for _ in 0..iterations {
+        parse(black_box(input));
+    }
+    let elapsed = start.elapsed() / iterations;
+    println!(
+        "{}: parsed {} bytes in {:?} ({:.1} MB/s)",
+        name,
+        input.len(),
+        elapsed,
+        input.len() as f64 / elapsed.as_secs_f64() / 1e6
+    );
+}
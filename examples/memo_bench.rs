@@ -0,0 +1,57 @@
+//! Times a grammar whose alternatives all start with the same rule, on nested parentheses,
+//! with and without packrat memoization. Run with `cargo run --release --example memo_bench`
+use std::{hint::black_box, time::Instant};
+
+use pepser::{
+    grammar::Grammar,
+    impls::{sequence, take_while1},
+    traits::{wrapped, Parser},
+};
+
+/// `expr` tries `term + expr`, `term - expr` and `term`, and `term` nests `expr`, so each
+/// parenthesis triples the work of an unmemoized parse
+fn grammar<'a>() -> Grammar<'a, &'a str, i64> {
+    let grammar = Grammar::new();
+    let g = grammar.clone();
+    grammar.rule("expr", move || {
+        g.call("term")
+            .skip(sequence("+"))
+            .and(g.call("expr"))
+            .map(|(a, b)| a + b)
+            .or(g
+                .call("term")
+                .skip(sequence("-"))
+                .and(g.call("expr"))
+                .map(|(a, b)| a - b))
+            .or(g.call("term"))
+    });
+    let g = grammar.clone();
+    grammar.rule("term", move || {
+        take_while1(|c| c.is_ascii_digit())
+            .map(|digits: &str| digits.parse().unwrap())
+            .or(wrapped(sequence("("), g.call("expr"), sequence(")")))
+    });
+    grammar
+}
+
+fn main() {
+    println!("{:>6} {:>14} {:>14}", "depth", "backtracking", "memoized");
+    for depth in [4, 8, 12, 14] {
+        let input = format!("{}1{}", "(".repeat(depth), ")".repeat(depth));
+        let plain = grammar();
+        let start = Instant::now();
+        black_box(plain.parse("expr", black_box(&input))).unwrap();
+        let backtracking = start.elapsed();
+
+        let memoized = grammar();
+        memoized.memoize();
+        let start = Instant::now();
+        black_box(memoized.parse("expr", black_box(&input))).unwrap();
+        println!(
+            "{:>6} {:>14?} {:>14?}",
+            depth,
+            backtracking,
+            start.elapsed()
+        );
+    }
+}
@@ -0,0 +1,60 @@
+//! Compares the byte scanning of `none_of` to a `take_while1` on the same set over a
+//! multi-megabyte document, run with `cargo run --release --example scan_bench`
+use std::{
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+use pepser::{
+    impls::{none_of, take_while1},
+    json::json_value,
+    traits::Parser,
+};
+
+fn time<F: FnMut()>(iterations: u32, mut f: F) -> Duration {
+    let start = Instant::now();
+    for _ in 0..iterations {
+        f();
+    }
+    start.elapsed() / iterations
+}
+
+fn report(name: &str, bytes: usize, elapsed: Duration) {
+    println!(
+        "{:<12} {:>10?} ({:.1} MB/s)",
+        name,
+        elapsed,
+        bytes as f64 / elapsed.as_secs_f64() / 1e6
+    );
+}
+
+fn main() {
+    let text = "lorem ipsum dolor sit amet, consectetur adipiscing élit ".repeat(80_000) + "\"";
+    let iterations = 20;
+    report(
+        "take_while1",
+        text.len(),
+        time(iterations, || {
+            black_box(take_while1(|c| !"\"\\".contains(c)).parse(black_box(&text))).unwrap();
+        }),
+    );
+    report(
+        "none_of",
+        text.len(),
+        time(iterations, || {
+            black_box(none_of("\"\\").parse(black_box(&text))).unwrap();
+        }),
+    );
+
+    let strings = (0..20_000)
+        .map(|i| format!("\"{} {}\"", text[..200].trim_end_matches('é'), i))
+        .collect::<Vec<_>>();
+    let document = format!("[{}]", strings.join(","));
+    report(
+        "json",
+        document.len(),
+        time(iterations, || {
+            black_box(json_value(black_box(&document))).unwrap();
+        }),
+    );
+}
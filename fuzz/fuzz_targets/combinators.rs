@@ -0,0 +1,26 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use pepser::{
+    impls::{
+        any, escaped_transform, identifier_except, keywords, none_of, not, sequence, take_until,
+        take_until_consuming_escaped, take_while1, token, ws,
+    },
+    traits::{drop_until, sep_by, Parser},
+};
+
+fuzz_target!(|input: &str| {
+    let _ = sequence("é日").parse(input);
+    let _ = take_while1(char::is_alphanumeric).parse(input);
+    let _ = take_until("日").parse(input);
+    let _ = take_until_consuming_escaped("日", 'é').parse(input);
+    let _ = keywords(&["日", "é日", "éa", ""]).parse(input);
+    let _ = identifier_except(&["é", "a日"]).parse(input);
+    let _ = none_of("\"\\é").parse(input);
+    let _ = not('a').many0().parse(input);
+    let _ = ws().and(any("xé")).parse(input);
+    let _ = drop_until(sequence("é")).parse(input);
+    let _ = sep_by(take_while1(|c| c != ','), sequence(",")).parse(input);
+    let _ = escaped_transform(none_of("\\"), '\\', any("né")).parse(input);
+    let _ = drop_until(token(0)).parse(input.as_bytes());
+});
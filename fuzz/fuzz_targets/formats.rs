@@ -0,0 +1,33 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use pepser::{
+    datetime, dotenv, git, http, icalendar, mqtt, multipart, nmea, protobuf, resp, robots, semver,
+    sexpr, traits::Parser, uri, websocket, xml, yaml,
+};
+
+fuzz_target!(|input: &str| {
+    let _ = yaml::yaml(input);
+    let _ = xml::xml_document(input);
+    let _ = sexpr::sexprs(input);
+    let _ = uri::uri(input);
+    let _ = git::config(input);
+    let _ = dotenv::dotenv(input);
+    git::gitignore(input).is_ignored(input, false);
+    let _ = http::http_request(input);
+    let _ = http::parse_bytes(http::http_response, input.as_bytes());
+    let _ = http::cookie::set_cookie(input);
+    let _ = http::cookie::cookies(input);
+    let _ = http::media::accept(input);
+    let _ = icalendar::components(input);
+    let _ = multipart::multipart("b").parse(input);
+    let _ = semver::version_req(input);
+    let _ = datetime::rfc3339(input);
+    let _ = nmea::nmea(input);
+    let _ = mqtt::packet(input.as_bytes());
+    let _ = protobuf::message(input.as_bytes());
+    let _ = resp::frame(input.as_bytes());
+    robots::robots(input).is_allowed(input, input);
+    let _ = robots::sitemap(input);
+    let _ = websocket::frame(input.as_bytes());
+});
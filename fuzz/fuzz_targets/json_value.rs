@@ -0,0 +1,11 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use pepser::json::{json_resilient, json_value};
+
+fuzz_target!(|input: &str| {
+    if let Err(error) = json_value(input) {
+        let _ = error.render(input);
+    }
+    let _ = json_resilient(input);
+});
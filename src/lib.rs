@@ -1,2 +1,5 @@
-mod parser;
+#![cfg_attr(not(feature = "std"), no_std)]
+extern crate alloc;
+
+pub mod parser;
 pub use self::parser::*;
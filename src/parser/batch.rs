@@ -0,0 +1,71 @@
+use super::traits::{Input, ParseResult, Parser};
+
+/// Parses each input independently with the same parser, returning the results in order
+///
+/// # Examples
+/// ```rust
+///
+/// use pepser::parser::batch::parse_batch;
+/// use pepser::parser::impls::take_while1;
+/// let results = parse_batch(take_while1(|c| c.is_ascii_digit()), &["12a", "b", "3"]);
+///
+/// assert_eq!(results[0], Ok(("a", "12")));
+/// assert!(results[1].is_err());
+/// assert_eq!(results[2], Ok(("", "3")));
+///
+///
+/// ```
+pub fn parse_batch<I, P>(mut parser: P, inputs: &[I]) -> Vec<ParseResult<I, P::Output>>
+where
+    I: Input,
+    P: Parser<I>,
+{
+    inputs
+        .iter()
+        .map(|input| parser.parse(input.clone()))
+        .collect()
+}
+
+/// Parses the inputs on all available cores, returning the results in order. Parsers hold
+/// mutable state so each thread builds its own with `factory`, and the inputs are split in
+/// one contiguous chunk per thread
+///
+/// # Examples
+/// ```rust
+///
+/// use pepser::parser::batch::par_parse_batch;
+/// use pepser::parser::json::json_value;
+/// let lines: Vec<String> = (0..1000).map(|i| format!("{{\"id\": {}}}", i)).collect();
+/// let inputs: Vec<&str> = lines.iter().map(String::as_str).collect();
+/// let results = par_parse_batch(|| json_value, &inputs);
+///
+/// assert_eq!(results.len(), 1000);
+/// assert!(results.iter().all(Result::is_ok));
+///
+///
+/// ```
+#[cfg(feature = "parallel")]
+pub fn par_parse_batch<I, P, F>(factory: F, inputs: &[I]) -> Vec<ParseResult<I, P::Output>>
+where
+    I: Input + Send + Sync,
+    P: Parser<I>,
+    P::Output: Send,
+    F: Fn() -> P + Sync,
+{
+    let threads = std::thread::available_parallelism().map_or(1, |threads| threads.get());
+    let chunk = inputs.len().div_ceil(threads).max(1);
+    std::thread::scope(|scope| {
+        let handles: Vec<_> = inputs
+            .chunks(chunk)
+            .map(|inputs| scope.spawn(|| parse_batch(factory(), inputs)))
+            .collect();
+        handles
+            .into_iter()
+            .flat_map(|handle| {
+                handle
+                    .join()
+                    .unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+            })
+            .collect()
+    })
+}
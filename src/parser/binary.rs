@@ -0,0 +1,93 @@
+use super::{
+    errors::{ErrorSource, ParserError},
+    traits::{ParseResult, Parser},
+};
+
+/// Takes the next `count` bytes
+pub fn take_bytes<'a>(count: usize) -> impl Parser<&'a [u8], Output = &'a [u8]> {
+    move |input: &'a [u8]| {
+        if input.len() < count {
+            return Err(ParserError::new(
+                input.len(),
+                ErrorSource::TakeWhile,
+                "unexpected end of input",
+            ));
+        }
+        let (parsed, remainder) = input.split_at(count);
+        Ok((remainder, parsed))
+    }
+}
+
+/// Parses a length with `length` then takes that many bytes, as in length prefixed fields
+///
+/// # Examples
+/// ```rust
+///
+/// use pepser::parser::binary::{be_u16, length_data};
+/// use pepser::parser::traits::Parser;
+/// let mut field = length_data(be_u16);
+///
+/// assert_eq!(field.parse(&[0, 2, b'o', b'k', 1][..]), Ok((&[1][..], &b"ok"[..])));
+/// assert!(field.parse(&[0, 3, b'o', b'k'][..]).is_err());
+///
+///
+/// ```
+pub fn length_data<'a, L>(mut length: L) -> impl Parser<&'a [u8], Output = &'a [u8]>
+where
+    L: Parser<&'a [u8]>,
+    L::Output: TryInto<usize>,
+{
+    move |input: &'a [u8]| {
+        let (remainder, len) = length.parse(input)?;
+        let offset = input.len() - remainder.len();
+        let Ok(len) = len.try_into() else {
+            return Err(ParserError::new(
+                0,
+                ErrorSource::TakeWhile,
+                "length does not fit in memory",
+            ));
+        };
+        take_bytes(len)
+            .parse(remainder)
+            .map_err(|error| ParserError::from_error(error, offset))
+    }
+}
+
+/// Defines the big and little endian parsers of a number type, read from its bytes
+macro_rules! number_parsers {
+    ($($number:ident $be:ident $le:ident;)*) => {$(
+        #[doc = concat!("Parses a big endian `", stringify!($number), "`")]
+        pub fn $be(input: &[u8]) -> ParseResult<&[u8], $number> {
+            let (remainder, bytes) = take_bytes(core::mem::size_of::<$number>()).parse(input)?;
+            let bytes = bytes.try_into().expect("the size of the number was taken");
+            Ok((remainder, $number::from_be_bytes(bytes)))
+        }
+
+        #[doc = concat!("Parses a little endian `", stringify!($number), "`")]
+        pub fn $le(input: &[u8]) -> ParseResult<&[u8], $number> {
+            let (remainder, bytes) = take_bytes(core::mem::size_of::<$number>()).parse(input)?;
+            let bytes = bytes.try_into().expect("the size of the number was taken");
+            Ok((remainder, $number::from_le_bytes(bytes)))
+        }
+    )*};
+}
+
+number_parsers! {
+    u16 be_u16 le_u16;
+    u32 be_u32 le_u32;
+    u64 be_u64 le_u64;
+    i16 be_i16 le_i16;
+    i32 be_i32 le_i32;
+    i64 be_i64 le_i64;
+    f32 be_f32 le_f32;
+    f64 be_f64 le_f64;
+}
+
+pub fn u8(input: &[u8]) -> ParseResult<&[u8], u8> {
+    let (remainder, bytes) = take_bytes(1).parse(input)?;
+    Ok((remainder, bytes[0]))
+}
+
+pub fn i8(input: &[u8]) -> ParseResult<&[u8], i8> {
+    u8(input).map(|(remainder, byte)| (remainder, byte as i8))
+}
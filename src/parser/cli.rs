@@ -0,0 +1,89 @@
+use alloc::{string::String, vec::Vec};
+
+use super::{
+    errors::{ErrorSource, ParserError},
+    shlex::{blanks, word},
+    traits::ParseResult,
+};
+
+/// An argument of a command line, as read by [`arguments`]
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Argument {
+    /// `--name` or `--name=value`
+    Long {
+        name: String,
+        value: Option<String>,
+    },
+    /// One flag of a `-abc` bundle
+    Short(char),
+    Positional(String),
+}
+
+/// Splits a command line into words as [`split`](super::shlex::split) does, then reads each
+/// word as flags or a positional argument. `-abc` is the bundle of the `a`, `b` and `c`
+/// short flags, a lone `-` and words such as `-1` are positional, and every word after `--`
+/// is positional
+///
+/// # Examples
+/// ```rust
+///
+/// use pepser::parser::cli::{arguments, Argument};
+/// let (_, parsed) = arguments("grep -rn --color=auto 'fn main' -- -v").unwrap();
+///
+/// assert_eq!(
+///     parsed,
+///     [
+///         Argument::Positional("grep".to_string()),
+///         Argument::Short('r'),
+///         Argument::Short('n'),
+///         Argument::Long { name: "color".to_string(), value: Some("auto".to_string()) },
+///         Argument::Positional("fn main".to_string()),
+///         Argument::Positional("-v".to_string()),
+///     ]
+/// );
+/// assert_eq!(arguments("run --=1").unwrap_err().index, 4);
+///
+///
+/// ```
+pub fn arguments(input: &str) -> ParseResult<&str, Vec<Argument>> {
+    let mut parsed = Vec::new();
+    let mut options = true;
+    let mut remainder = blanks(input);
+    while !remainder.is_empty() {
+        let offset = input.len() - remainder.len();
+        let (next, word) =
+            word(remainder).map_err(|error| ParserError::from_error(error, offset))?;
+        remainder = blanks(next);
+        if !options {
+            parsed.push(Argument::Positional(word));
+            continue;
+        }
+        if word == "--" {
+            options = false;
+        } else if let Some(long) = word.strip_prefix("--") {
+            let (name, value) = match long.split_once('=') {
+                Some((name, value)) => (name, Some(value.into())),
+                None => (long, None),
+            };
+            if name.is_empty() {
+                return Err(ParserError::new(
+                    offset,
+                    ErrorSource::TakeWhile,
+                    "empty flag name",
+                ));
+            }
+            parsed.push(Argument::Long {
+                name: name.into(),
+                value,
+            });
+        } else if word.len() > 1
+            && word.starts_with('-')
+            && !word[1..].starts_with(|c: char| c.is_ascii_digit())
+        {
+            parsed.extend(word[1..].chars().map(Argument::Short));
+        } else {
+            parsed.push(Argument::Positional(word));
+        }
+    }
+    Ok((remainder, parsed))
+}
@@ -0,0 +1,15 @@
+//! The [`Parser`] and [`Input`] traits with the combinators building parsers out of others,
+//! as functions and as the structs returned by the methods of [`Parser`]. The items are
+//! defined in [`traits`](super::traits), whose paths keep working
+#[cfg(feature = "std")]
+pub use super::impls::detect_nullable_loops;
+#[cfg(feature = "std")]
+pub use super::traits::Traced;
+pub use super::traits::{
+    chainl1, chainr1, discard, dispatch, drop_until, fold_many0, followed_by, not_followed_by, opt,
+    pair, parse_if, sep_by, separated_pair, skip_until, terminated, value, wrapped, And,
+    BoxedParser, ByRef, ChainL, ChainR, Checkpoint, CountExact, Cut, Discard, DropUntil, Expect,
+    Fold, FollowedBy, Input, Many, Map, MapErr, NotFollowedBy, Opt, Or, OrFailWith, OrLongest,
+    ParseIf, ParseResult, Parser, Peek, PeekOut, RecoverWith, Sep, Skip, SkipUntil, Value,
+    WithSpan, Wrapped,
+};
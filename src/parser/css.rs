@@ -0,0 +1,201 @@
+use super::{
+    errors::{ErrorSource, ParserError},
+    impls::{sequence, take_while1, ws},
+    traits::{discard, ParseResult, Parser},
+};
+
+/// A color with 8 bits per channel, an opaque color has an `alpha` of 255
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Rgba {
+    pub red: u8,
+    pub green: u8,
+    pub blue: u8,
+    pub alpha: u8,
+}
+
+fn invalid<'a>(index: usize, reason: &'static str) -> ParserError<&'a str> {
+    ParserError::new(index, ErrorSource::TakeWhile, reason)
+}
+
+/// Parses a CSS color: `#rgb`, `#rgba`, `#rrggbb` and `#rrggbbaa` hex colors, and the
+/// `rgb()`, `rgba()`, `hsl()` and `hsla()` functions with comma or space separated arguments.
+/// Out of range components are clamped as CSS does
+///
+/// # Examples
+/// ```rust
+///
+/// use pepser::parser::css::{color, Rgba};
+/// let orange = Rgba { red: 255, green: 165, blue: 0, alpha: 255 };
+///
+/// assert_eq!(color("#FFA500;"), Ok((";", orange)));
+/// assert_eq!(color("rgb(255, 165, 0)"), Ok(("", orange)));
+/// assert_eq!(color("hsl(38.8deg 100% 50%)"), Ok(("", orange)));
+/// assert_eq!(color("rgba(100%, 0%, 0%, .5)").unwrap().1.alpha, 128);
+/// assert_eq!(color("#12345").unwrap_err().index, 1);
+///
+///
+/// ```
+pub fn color(input: &str) -> ParseResult<&str, Rgba> {
+    if let Some(digits) = input.strip_prefix('#') {
+        return hex_color(digits).map_err(|error| ParserError::from_error(error, 1));
+    }
+    let function = input.find('(').map(|end| &input[..end]);
+    let named = |names: [&str; 2]| {
+        function
+            .is_some_and(|function| names.iter().any(|name| name.eq_ignore_ascii_case(function)))
+    };
+    let (remainder, color) = if named(["rgb", "rgba"]) {
+        let (remainder, [red, green, blue, alpha]) = arguments(input, rgb_component)?;
+        (
+            remainder,
+            [channel(red), channel(green), channel(blue), channel(alpha)],
+        )
+    } else if named(["hsl", "hsla"]) {
+        let (remainder, [hue, saturation, lightness, alpha]) = arguments(input, hsl_component)?;
+        let [red, green, blue] = hsl_to_rgb(hue, saturation, lightness);
+        (remainder, [red, green, blue, channel(alpha)])
+    } else {
+        return Err(invalid(0, "expected a color"));
+    };
+    let [red, green, blue, alpha] = color;
+    Ok((
+        remainder,
+        Rgba {
+            red,
+            green,
+            blue,
+            alpha,
+        },
+    ))
+}
+
+fn hex_color(input: &str) -> ParseResult<&str, Rgba> {
+    let (remainder, digits) = take_while1(|c| c.is_ascii_hexdigit()).parse(input)?;
+    let value = |start: usize, length: usize| {
+        u8::from_str_radix(&digits[start..start + length], 16).unwrap_or_default()
+    };
+    // a missing alpha is opaque
+    let [red, green, blue, alpha] = match digits.len() {
+        3 | 4 => [0, 1, 2, 3].map(|index| match index < digits.len() {
+            true => value(index, 1) * 17,
+            false => u8::MAX,
+        }),
+        6 | 8 => [0, 2, 4, 6].map(|index| match index < digits.len() {
+            true => value(index, 2),
+            false => u8::MAX,
+        }),
+        _ => return Err(invalid(0, "expected 3, 4, 6 or 8 hex digits")),
+    };
+    Ok((
+        remainder,
+        Rgba {
+            red,
+            green,
+            blue,
+            alpha,
+        },
+    ))
+}
+
+/// Parses `name(a, b, c)`, `name(a, b, c, alpha)`, `name(a b c)` or `name(a b c / alpha)`,
+/// returning the three components and the alpha, 1 when absent
+fn arguments<'a, C>(input: &'a str, mut component: C) -> ParseResult<&'a str, [f64; 4]>
+where
+    C: FnMut(usize, &'a str) -> ParseResult<&'a str, f64>,
+{
+    let start = input.find('(').map_or(0, |open| open + 1);
+    let mut remainder = ws().parse(&input[start..])?.0;
+    let mut components = [1.0; 4];
+    let mut commas = false;
+    for (position, value) in components.iter_mut().enumerate() {
+        if position == 1 {
+            commas = discard(ws(), sequence(",")).parse(remainder).is_ok();
+        }
+        if position > 0 {
+            let separator = match (commas, position) {
+                (true, _) => Some(","),
+                (false, 3) => Some("/"),
+                (false, _) => None,
+            };
+            if let Some(separator) = separator {
+                match discard(ws(), sequence(separator)).parse(remainder) {
+                    Ok((next, _)) => remainder = next,
+                    Err(_) if position == 3 => break,
+                    Err(_) => return Err(invalid(input.len() - remainder.len(), "expected ','")),
+                }
+            }
+            remainder = ws().parse(remainder)?.0;
+        }
+        let offset = input.len() - remainder.len();
+        let parsed = match position {
+            3 => alpha_value(remainder),
+            _ => component(position, remainder),
+        };
+        let (next, parsed) = parsed.map_err(|error| ParserError::from_error(error, offset))?;
+        *value = parsed;
+        remainder = next;
+    }
+    let offset = input.len() - remainder.len();
+    let (remainder, _) = discard(ws(), sequence(")"))
+        .parse(remainder)
+        .map_err(|_| invalid(offset, "expected ')'"))?;
+    Ok((remainder, components))
+}
+
+/// A decimal number, possibly signed and with a fractional part
+fn number(input: &str) -> ParseResult<&str, f64> {
+    let (remainder, number) =
+        take_while1(|c| c.is_ascii_digit() || matches!(c, '.' | '+' | '-')).parse(input)?;
+    number
+        .parse()
+        .map(|value| (remainder, value))
+        .map_err(|_| invalid(0, "invalid number"))
+}
+
+/// A number, or a percentage when followed by `%`, as a fraction of `range`
+fn scaled(input: &str, range: f64) -> ParseResult<&str, f64> {
+    let (remainder, value) = number(input)?;
+    match remainder.strip_prefix('%') {
+        Some(remainder) => Ok((remainder, value / 100.0)),
+        None => Ok((remainder, value / range)),
+    }
+}
+
+fn rgb_component(_: usize, input: &str) -> ParseResult<&str, f64> {
+    scaled(input, 255.0)
+}
+
+/// The hue in degrees, then the saturation and lightness as percentages
+fn hsl_component(position: usize, input: &str) -> ParseResult<&str, f64> {
+    if position == 0 {
+        let (remainder, hue) = number(input)?;
+        let remainder = remainder.strip_prefix("deg").unwrap_or(remainder);
+        return Ok((remainder, hue));
+    }
+    let (remainder, value) = number(input)?;
+    match remainder.strip_prefix('%') {
+        Some(remainder) => Ok((remainder, value / 100.0)),
+        None => Err(invalid(input.len() - remainder.len(), "expected '%'")),
+    }
+}
+
+fn alpha_value(input: &str) -> ParseResult<&str, f64> {
+    scaled(input, 1.0)
+}
+
+/// Converts a fraction of the range of a channel, clamped to it, to its 8 bits value
+fn channel(fraction: f64) -> u8 {
+    (fraction.clamp(0.0, 1.0) * 255.0 + 0.5) as u8
+}
+
+/// Converts a hue in degrees and a saturation and a lightness as fractions to RGB
+fn hsl_to_rgb(hue: f64, saturation: f64, lightness: f64) -> [u8; 3] {
+    let hue = (hue % 360.0 + 360.0) % 360.0;
+    let saturation = saturation.clamp(0.0, 1.0);
+    let lightness = lightness.clamp(0.0, 1.0);
+    let amplitude = saturation * lightness.min(1.0 - lightness);
+    [0.0, 8.0, 4.0].map(|offset: f64| {
+        let k = (offset + hue / 30.0) % 12.0;
+        channel(lightness - amplitude * (k - 3.0).min(9.0 - k).clamp(-1.0, 1.0))
+    })
+}
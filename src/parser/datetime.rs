@@ -0,0 +1,354 @@
+use alloc::format;
+use core::time::Duration;
+
+use super::{
+    errors::{ErrorSource, ParserError},
+    impls::{fixed_digits, sequence, take_while1},
+    traits::{discard, opt, ParseResult, Parser},
+};
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+pub struct Date {
+    pub year: u16,
+    pub month: u8,
+    pub day: u8,
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+pub struct Time {
+    pub hour: u8,
+    pub minute: u8,
+    /// Up to 60 to allow leap seconds
+    pub second: u8,
+    pub nanosecond: u32,
+}
+
+/// Offset from UTC in minutes, `Z` is an offset of zero
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+pub struct UtcOffset {
+    pub minutes: i16,
+}
+
+/// A date and time with an optional offset: RFC 3339 timestamps always have one,
+/// ISO 8601 local date-times do not
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct DateTime {
+    pub date: Date,
+    pub time: Time,
+    pub offset: Option<UtcOffset>,
+}
+
+/// An ISO 8601 duration such as `P1Y2M3DT4H5M6.5S` or `P2W`, components are kept as written
+#[derive(Debug, PartialEq, Eq, Default, Clone, Copy)]
+pub struct IsoDuration {
+    pub years: u32,
+    pub months: u32,
+    pub weeks: u32,
+    pub days: u32,
+    pub hours: u32,
+    pub minutes: u32,
+    pub seconds: u32,
+    pub nanoseconds: u32,
+}
+
+pub fn is_leap_year(year: u16) -> bool {
+    year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400))
+}
+
+pub fn days_in_month(year: u16, month: u8) -> u8 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Parses an extended format calendar date `YYYY-MM-DD`
+pub fn date(input: &str) -> ParseResult<&str, Date> {
+    let (remainder, ((year, month), day)) = fixed_digits(4)
+        .and(discard(sequence("-"), fixed_digits(2)))
+        .and(discard(sequence("-"), fixed_digits(2)))
+        .parse(input)?;
+    let (year, month, day) = (year as u16, month as u8, day as u8);
+    if !(1..=12).contains(&month) {
+        return Err(out_of_range(5, "month"));
+    }
+    if day == 0 || day > days_in_month(year, month) {
+        return Err(out_of_range(8, "day"));
+    }
+    Ok((remainder, Date { year, month, day }))
+}
+
+/// Parses an extended format time `hh:mm:ss` with an optional decimal fraction of a second
+pub fn time(input: &str) -> ParseResult<&str, Time> {
+    let (remainder, (((hour, minute), second), nanosecond)) = fixed_digits(2)
+        .and(discard(sequence(":"), fixed_digits(2)))
+        .and(discard(sequence(":"), fixed_digits(2)))
+        .and(opt(discard(sequence(".").or(sequence(",")), fraction)).map(Option::unwrap_or_default))
+        .parse(input)?;
+    if hour > 23 {
+        return Err(out_of_range(0, "hour"));
+    }
+    if minute > 59 {
+        return Err(out_of_range(3, "minute"));
+    }
+    if second > 60 {
+        return Err(out_of_range(6, "second"));
+    }
+    Ok((
+        remainder,
+        Time {
+            hour: hour as u8,
+            minute: minute as u8,
+            second: second as u8,
+            nanosecond,
+        },
+    ))
+}
+
+/// Parses `Z` or a `+hh:mm`/`-hh:mm` offset
+pub fn offset(input: &str) -> ParseResult<&str, UtcOffset> {
+    if let Ok((remainder, _)) = sequence("Z").or(sequence("z")).parse(input) {
+        return Ok((remainder, UtcOffset { minutes: 0 }));
+    }
+    let (remainder, ((sign, hours), minutes)) = sequence("+")
+        .or(sequence("-"))
+        .and(fixed_digits(2))
+        .and(discard(sequence(":"), fixed_digits(2)))
+        .parse(input)?;
+    if hours > 23 {
+        return Err(out_of_range(1, "offset hour"));
+    }
+    if minutes > 59 {
+        return Err(out_of_range(4, "offset minute"));
+    }
+    let minutes = (hours * 60 + minutes) as i16;
+    Ok((
+        remainder,
+        UtcOffset {
+            minutes: if sign == "-" { -minutes } else { minutes },
+        },
+    ))
+}
+
+/// Parses a date and a time separated by `T` (or a space, as RFC 3339 allows), followed by
+/// an optional offset
+pub fn datetime(input: &str) -> ParseResult<&str, DateTime> {
+    date.and(discard(
+        sequence("T").or(sequence("t")).or(sequence(" ")),
+        time,
+    ))
+    .and(opt(offset))
+    .map(|((date, time), offset)| DateTime { date, time, offset })
+    .parse(input)
+}
+
+/// Parses an RFC 3339 timestamp, which requires an offset
+pub fn rfc3339(input: &str) -> ParseResult<&str, DateTime> {
+    let (remainder, datetime) = datetime(input)?;
+    match datetime.offset {
+        Some(_) => Ok((remainder, datetime)),
+        None => Err(ParserError::new(
+            input.len() - remainder.len(),
+            ErrorSource::Sequence("Z"),
+            "missing offset",
+        )),
+    }
+}
+
+/// Parses an ISO 8601 duration, at least one component must be present
+pub fn duration(input: &str) -> ParseResult<&str, IsoDuration> {
+    let (remainder, _) = sequence("P").parse(input)?;
+    let (remainder, (((years, months), weeks), days)) = opt(component("Y"))
+        .and(opt(component("M")))
+        .and(opt(component("W")))
+        .and(opt(component("D")))
+        .parse(remainder)?;
+    let (remainder, time) = opt(discard(
+        sequence("T"),
+        opt(component("H"))
+            .and(opt(component("M")))
+            .and(opt(seconds)),
+    ))
+    .parse(remainder)?;
+
+    if matches!(time, Some(((None, None), None))) {
+        return Err(ParserError::new(
+            input.len() - remainder.len(),
+            ErrorSource::TakeWhile,
+            "duration time designator has no components",
+        ));
+    }
+    let ((hours, minutes), seconds) = time.unwrap_or_default();
+    let components = [years, months, weeks, days, hours, minutes];
+    if components.iter().all(Option::is_none) && seconds.is_none() {
+        return Err(ParserError::new(
+            1,
+            ErrorSource::TakeWhile,
+            "duration has no components",
+        ));
+    }
+    let (seconds, nanoseconds) = seconds.unwrap_or_default();
+    Ok((
+        remainder,
+        IsoDuration {
+            years: years.unwrap_or_default(),
+            months: months.unwrap_or_default(),
+            weeks: weeks.unwrap_or_default(),
+            days: days.unwrap_or_default(),
+            hours: hours.unwrap_or_default(),
+            minutes: minutes.unwrap_or_default(),
+            seconds,
+            nanoseconds,
+        },
+    ))
+}
+
+/// Units of [`human_duration`] with their length in nanoseconds, longer names first so that
+/// `ms` is not read as `m`
+const UNITS: &[(&str, u128)] = &[
+    ("nanoseconds", 1),
+    ("nanosecond", 1),
+    ("ns", 1),
+    ("microseconds", 1_000),
+    ("microsecond", 1_000),
+    ("us", 1_000),
+    ("\u{b5}s", 1_000),
+    ("milliseconds", 1_000_000),
+    ("millisecond", 1_000_000),
+    ("ms", 1_000_000),
+    ("seconds", NANOS_PER_SECOND),
+    ("second", NANOS_PER_SECOND),
+    ("secs", NANOS_PER_SECOND),
+    ("sec", NANOS_PER_SECOND),
+    ("s", NANOS_PER_SECOND),
+    ("minutes", 60 * NANOS_PER_SECOND),
+    ("minute", 60 * NANOS_PER_SECOND),
+    ("mins", 60 * NANOS_PER_SECOND),
+    ("min", 60 * NANOS_PER_SECOND),
+    ("m", 60 * NANOS_PER_SECOND),
+    ("hours", 3_600 * NANOS_PER_SECOND),
+    ("hour", 3_600 * NANOS_PER_SECOND),
+    ("hrs", 3_600 * NANOS_PER_SECOND),
+    ("hr", 3_600 * NANOS_PER_SECOND),
+    ("h", 3_600 * NANOS_PER_SECOND),
+    ("days", 86_400 * NANOS_PER_SECOND),
+    ("day", 86_400 * NANOS_PER_SECOND),
+    ("d", 86_400 * NANOS_PER_SECOND),
+    ("weeks", 604_800 * NANOS_PER_SECOND),
+    ("week", 604_800 * NANOS_PER_SECOND),
+    ("w", 604_800 * NANOS_PER_SECOND),
+];
+
+const NANOS_PER_SECOND: u128 = 1_000_000_000;
+
+/// Parses a human readable duration made of numbers followed by units, such as `1h30m`,
+/// `250ms` or `1.5 days`, and sums them. Whitespace may separate numbers, units and
+/// components. Units go from `ns` to `weeks`, fractions smaller than a nanosecond are
+/// truncated and a total which does not fit a [`Duration`] is an error
+///
+/// # Examples
+/// ```rust
+///
+/// use std::time::Duration;
+/// use pepser::parser::datetime::human_duration;
+///
+/// assert_eq!(human_duration("1h30m"), Ok(("", Duration::from_secs(5400))));
+/// assert_eq!(human_duration("2 days, 3h"), Ok((", 3h", Duration::from_secs(172_800))));
+/// assert_eq!(human_duration("1.5s 250ms"), Ok(("", Duration::from_millis(1750))));
+/// assert_eq!(human_duration("3 parsecs").unwrap_err().index, 2);
+///
+///
+/// ```
+pub fn human_duration(input: &str) -> ParseResult<&str, Duration> {
+    let mut nanoseconds = 0u128;
+    let mut remainder = input;
+    loop {
+        let offset = input.len() - remainder.len();
+        let (next, value) = duration_component(remainder)
+            .map_err(|error| ParserError::from_error(error, offset))?;
+        nanoseconds = nanoseconds
+            .checked_add(value)
+            .filter(|total| total / NANOS_PER_SECOND <= u128::from(u64::MAX))
+            .ok_or_else(|| ParserError::new(offset, ErrorSource::TakeWhile, "duration overflow"))?;
+        remainder = next;
+        // whitespace before the next component, kept when none follows
+        match next.trim_start() {
+            next if next.starts_with(|c: char| c.is_ascii_digit()) => remainder = next,
+            _ => break,
+        }
+    }
+    let seconds = (nanoseconds / NANOS_PER_SECOND) as u64;
+    let nanoseconds = (nanoseconds % NANOS_PER_SECOND) as u32;
+    Ok((remainder, Duration::new(seconds, nanoseconds)))
+}
+
+/// A number, an optional fraction and a unit, in nanoseconds
+fn duration_component(input: &str) -> ParseResult<&str, u128> {
+    let (remainder, digits) = take_while1(|c| c.is_ascii_digit()).parse(input)?;
+    let (remainder, fraction) =
+        opt(discard(sequence("."), take_while1(|c| c.is_ascii_digit()))).parse(remainder)?;
+    let overflow = || ParserError::new(0, ErrorSource::TakeWhile, "duration overflow");
+    let whole = digits.parse::<u64>().map_err(|_| overflow())?;
+
+    let unit_start = remainder.trim_start();
+    let offset = input.len() - unit_start.len();
+    let (unit, length) = UNITS
+        .iter()
+        .find(|(unit, _)| {
+            unit_start.starts_with(unit)
+                && !unit_start[unit.len()..].starts_with(|c: char| c.is_alphabetic())
+        })
+        .ok_or_else(|| ParserError::new(offset, ErrorSource::TakeWhile, "expected a unit"))?;
+
+    // digits past the fifteenth cannot add a nanosecond, even to a week
+    let fraction = fraction.unwrap_or_default();
+    let fraction = &fraction[..fraction.len().min(15)];
+    let scale = 10u128.pow(fraction.len() as u32);
+    let numerator = fraction.parse::<u128>().unwrap_or_default();
+    let value = u128::from(whole) * length + numerator * length / scale;
+    Ok((&unit_start[unit.len()..], value))
+}
+
+fn component<'a>(designator: &'a str) -> impl Parser<&'a str, Output = u32> {
+    move |input: &'a str| {
+        let (remainder, (value, _)) = number.and(sequence(designator)).parse(input)?;
+        Ok((remainder, value))
+    }
+}
+
+fn seconds(input: &str) -> ParseResult<&str, (u32, u32)> {
+    number
+        .and(opt(discard(sequence(".").or(sequence(",")), fraction)).map(Option::unwrap_or_default))
+        .skip(sequence("S"))
+        .parse(input)
+}
+
+fn number(input: &str) -> ParseResult<&str, u32> {
+    let (remainder, digits) = take_while1(|c| c.is_ascii_digit()).parse(input)?;
+    digits
+        .parse::<u32>()
+        .map(|value| (remainder, value))
+        .map_err(|_| ParserError::new(0, ErrorSource::TakeWhile, "number overflow"))
+}
+
+/// Parses the digits of a decimal fraction into nanoseconds, digits past the ninth are
+/// truncated
+fn fraction(input: &str) -> ParseResult<&str, u32> {
+    let (remainder, digits) = take_while1(|c| c.is_ascii_digit()).parse(input)?;
+    let nanoseconds = digits
+        .bytes()
+        .chain(core::iter::repeat(b'0'))
+        .take(9)
+        .fold(0, |value, digit| value * 10 + (digit - b'0') as u32);
+    Ok((remainder, nanoseconds))
+}
+
+fn out_of_range<'a>(index: usize, field: &str) -> ParserError<&'a str> {
+    ParserError::new(
+        index,
+        ErrorSource::TakeWhile,
+        format!("{} is out of range", field),
+    )
+}
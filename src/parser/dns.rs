@@ -0,0 +1,296 @@
+use alloc::{string::String, vec::Vec};
+use core::net::{Ipv4Addr, Ipv6Addr};
+
+use super::{
+    binary::{be_u16, be_u32, length_data, take_bytes, u8},
+    errors::{ErrorSource, ParserError},
+    traits::{ParseResult, Parser},
+};
+
+/// Longest name allowed by RFC 1035, in its dotted form
+const MAX_NAME_LENGTH: usize = 255;
+
+/// The fixed header of a message, the section counts are the lengths of the sections of
+/// [`Message`]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Header {
+    pub id: u16,
+    /// Set in responses, unset in queries
+    pub response: bool,
+    pub opcode: u8,
+    pub authoritative: bool,
+    pub truncated: bool,
+    pub recursion_desired: bool,
+    pub recursion_available: bool,
+    pub rcode: u8,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Question {
+    /// Dotted name without the trailing dot, the root is the empty name
+    pub name: String,
+    pub qtype: u16,
+    pub qclass: u16,
+}
+
+/// Data of the usual record types, other types keep their raw bytes
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum RecordData<'a> {
+    A(Ipv4Addr),
+    Aaaa(Ipv6Addr),
+    Ns(String),
+    Cname(String),
+    Ptr(String),
+    Mx { preference: u16, exchange: String },
+    Txt(Vec<&'a [u8]>),
+    Other(&'a [u8]),
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Record<'a> {
+    pub name: String,
+    pub rtype: u16,
+    pub class: u16,
+    pub ttl: u32,
+    pub data: RecordData<'a>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Message<'a> {
+    pub header: Header,
+    pub questions: Vec<Question>,
+    pub answers: Vec<Record<'a>>,
+    pub authorities: Vec<Record<'a>>,
+    pub additionals: Vec<Record<'a>>,
+}
+
+/// Parses a DNS query or response (RFC 1035). Compressed names are followed through their
+/// pointers, which must point before the name using them so that no loop can occur. Error
+/// indices are offsets in the message
+///
+/// # Examples
+/// ```rust
+///
+/// use pepser::parser::dns::{message, RecordData};
+/// let packet = [
+///     0x12, 0x34, 0x81, 0x80, 0, 1, 0, 1, 0, 0, 0, 0,
+///     2, b'i', b'o', 0, 0, 1, 0, 1,
+///     0xC0, 12, 0, 1, 0, 1, 0, 0, 0x0E, 0x10, 0, 4, 10, 0, 0, 1,
+/// ];
+/// let (_, response) = message(&packet).unwrap();
+///
+/// assert!(response.header.response);
+/// assert_eq!(response.questions[0].name, "io");
+/// assert_eq!(response.answers[0].name, "io");
+/// assert_eq!(response.answers[0].data, RecordData::A([10, 0, 0, 1].into()));
+///
+///
+/// ```
+pub fn message(input: &[u8]) -> ParseResult<&[u8], Message<'_>> {
+    let packet = input;
+    let (input, header) = header(packet, input)?;
+    let mut counts = [0; 4];
+    let mut input = input;
+    for count in &mut counts {
+        let (remainder, parsed) = within(packet, input, be_u16)?;
+        *count = usize::from(parsed);
+        input = remainder;
+    }
+    let [questions, answers, authorities, additionals] = counts;
+
+    let mut parsed = Vec::new();
+    for _ in 0..questions {
+        let (remainder, parsed_question) = question(packet, input)?;
+        parsed.push(parsed_question);
+        input = remainder;
+    }
+    let (input, answers) = records(packet, input, answers)?;
+    let (input, authorities) = records(packet, input, authorities)?;
+    let (input, additionals) = records(packet, input, additionals)?;
+    Ok((
+        input,
+        Message {
+            header,
+            questions: parsed,
+            answers,
+            authorities,
+            additionals,
+        },
+    ))
+}
+
+fn header<'a>(packet: &'a [u8], input: &'a [u8]) -> ParseResult<&'a [u8], Header> {
+    let (input, id) = be_u16(input)?;
+    let (remainder, flags) = within(packet, input, be_u16)?;
+    let flag = |bit: u16| flags & (1 << bit) != 0;
+    Ok((
+        remainder,
+        Header {
+            id,
+            response: flag(15),
+            opcode: (flags >> 11) as u8 & 0x0F,
+            authoritative: flag(10),
+            truncated: flag(9),
+            recursion_desired: flag(8),
+            recursion_available: flag(7),
+            rcode: flags as u8 & 0x0F,
+        },
+    ))
+}
+
+/// Offset of `input` in `packet`, of which it is a slice
+fn position(packet: &[u8], input: &[u8]) -> usize {
+    input.as_ptr() as usize - packet.as_ptr() as usize
+}
+
+fn invalid<'a>(index: usize, reason: &'static str) -> ParserError<&'a [u8]> {
+    ParserError::new(index, ErrorSource::TakeWhile, reason)
+}
+
+/// Runs a parser on a part of the message, offsetting its errors to the message
+fn within<'a, O>(
+    packet: &'a [u8],
+    input: &'a [u8],
+    mut parser: impl Parser<&'a [u8], Output = O>,
+) -> ParseResult<&'a [u8], O> {
+    parser
+        .parse(input)
+        .map_err(|error| ParserError::from_error(error, position(packet, input)))
+}
+
+fn question<'a>(packet: &'a [u8], input: &'a [u8]) -> ParseResult<&'a [u8], Question> {
+    let (input, name) = name(packet, input)?;
+    let (input, qtype) = within(packet, input, be_u16)?;
+    let (input, qclass) = within(packet, input, be_u16)?;
+    Ok((
+        input,
+        Question {
+            name,
+            qtype,
+            qclass,
+        },
+    ))
+}
+
+fn records<'a>(
+    packet: &'a [u8],
+    mut input: &'a [u8],
+    count: usize,
+) -> ParseResult<&'a [u8], Vec<Record<'a>>> {
+    let mut records = Vec::new();
+    for _ in 0..count {
+        let (remainder, record) = record(packet, input)?;
+        records.push(record);
+        input = remainder;
+    }
+    Ok((input, records))
+}
+
+fn record<'a>(packet: &'a [u8], input: &'a [u8]) -> ParseResult<&'a [u8], Record<'a>> {
+    let (input, name) = name(packet, input)?;
+    let (input, rtype) = within(packet, input, be_u16)?;
+    let (input, class) = within(packet, input, be_u16)?;
+    let (input, ttl) = within(packet, input, be_u32)?;
+    let (input, data) = within(packet, input, length_data(be_u16))?;
+    let data = record_data(packet, rtype, data)?;
+    Ok((
+        input,
+        Record {
+            name,
+            rtype,
+            class,
+            ttl,
+            data,
+        },
+    ))
+}
+
+fn record_data<'a>(
+    packet: &'a [u8],
+    rtype: u16,
+    data: &'a [u8],
+) -> Result<RecordData<'a>, ParserError<&'a [u8]>> {
+    let start = position(packet, data);
+    // names in the data may be compressed, but must end with it
+    let data_name = |data: &'a [u8]| {
+        let end = position(packet, data) + data.len();
+        match name(packet, data) {
+            Ok((remainder, name)) if position(packet, remainder) == end => Ok(name),
+            Ok(_) => Err(invalid(start, "invalid record data")),
+            Err(error) => Err(error),
+        }
+    };
+    match rtype {
+        1 => <[u8; 4]>::try_from(data)
+            .map(|address| RecordData::A(address.into()))
+            .map_err(|_| invalid(start, "invalid record data")),
+        28 => <[u8; 16]>::try_from(data)
+            .map(|address| RecordData::Aaaa(address.into()))
+            .map_err(|_| invalid(start, "invalid record data")),
+        2 => data_name(data).map(RecordData::Ns),
+        5 => data_name(data).map(RecordData::Cname),
+        12 => data_name(data).map(RecordData::Ptr),
+        15 => {
+            let (exchange, preference) = within(packet, data, be_u16)?;
+            let exchange = data_name(exchange)?;
+            Ok(RecordData::Mx {
+                preference,
+                exchange,
+            })
+        }
+        16 => {
+            let (remainder, strings) = within(packet, data, length_data(u8).many0())?;
+            if !remainder.is_empty() {
+                return Err(invalid(position(packet, remainder), "invalid record data"));
+            }
+            Ok(RecordData::Txt(strings))
+        }
+        _ => Ok(RecordData::Other(data)),
+    }
+}
+
+/// Parses a possibly compressed name, the remainder follows the name at its original place
+fn name<'a>(packet: &'a [u8], input: &'a [u8]) -> ParseResult<&'a [u8], String> {
+    let mut name = String::new();
+    let mut offset = position(packet, input);
+    // where parsing resumes once the name is read, after the first pointer
+    let mut resume = None;
+    // pointers must point strictly before the previous one
+    let mut limit = offset;
+    loop {
+        let Some(&length) = packet.get(offset) else {
+            return Err(invalid(offset, "unexpected end of input"));
+        };
+        match length >> 6 {
+            0b00 if length == 0 => {
+                offset += 1;
+                break;
+            }
+            0b00 => {
+                let (_, label) = within(packet, &packet[offset + 1..], take_bytes(length.into()))?;
+                if !name.is_empty() {
+                    name.push('.');
+                }
+                name.push_str(&String::from_utf8_lossy(label));
+                if name.len() > MAX_NAME_LENGTH {
+                    return Err(invalid(offset, "name too long"));
+                }
+                offset += 1 + label.len();
+            }
+            0b11 => {
+                let Some(&low) = packet.get(offset + 1) else {
+                    return Err(invalid(offset, "unexpected end of input"));
+                };
+                let target = (usize::from(length & 0x3F) << 8) | usize::from(low);
+                if target >= limit {
+                    return Err(invalid(offset, "invalid compression pointer"));
+                }
+                resume.get_or_insert(offset + 2);
+                limit = target;
+                offset = target;
+            }
+            _ => return Err(invalid(offset, "unsupported label type")),
+        }
+    }
+    Ok((&packet[resume.unwrap_or(offset)..], name))
+}
@@ -0,0 +1,318 @@
+use alloc::{string::String, vec::Vec};
+
+use super::{
+    errors::{ErrorSource, ParserError},
+    span::{Span, Spanned},
+    traits::ParseResult,
+};
+
+/// A variable of a `.env` file. The span of the value covers it as written, quotes included
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Variable<'a> {
+    pub key: Spanned<&'a str>,
+    /// The value with its quotes, escapes and references processed
+    pub value: Spanned<String>,
+}
+
+/// The variables of a `.env` file in the order they are first set. Setting a key again
+/// replaces its value in place
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct Dotenv<'a> {
+    pub variables: Vec<Variable<'a>>,
+}
+
+impl<'a> Dotenv<'a> {
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.variable(key)
+            .map(|variable| variable.value.value.as_str())
+    }
+
+    pub fn variable(&self, key: &str) -> Option<&Variable<'a>> {
+        self.variables
+            .iter()
+            .find(|variable| variable.key.value == key)
+    }
+
+    /// The keys and values in their order
+    pub fn iter(&self) -> impl Iterator<Item = (&'a str, &str)> {
+        self.variables
+            .iter()
+            .map(|variable| (variable.key.value, variable.value.value.as_str()))
+    }
+
+    fn insert(&mut self, variable: Variable<'a>) {
+        match self
+            .variables
+            .iter_mut()
+            .find(|set| set.key.value == variable.key.value)
+        {
+            Some(set) => *set = variable,
+            None => self.variables.push(variable),
+        }
+    }
+}
+
+/// Parses a `.env` file, `KEY=value` lines with an optional `export` prefix and comments
+/// starting with `#`. Values are unquoted up to a comment or the end of the line, between
+/// single quotes taken as written, or between double quotes which escape `\n`, `\r`, `\t`,
+/// `\"`, `\\` and `\$`. Quoted values may span lines. Outside of single quotes, `$VAR`,
+/// `${VAR}` and `${VAR:-default}` are replaced by the variables set before them, unknown ones
+/// by nothing. Error indices are offsets in the input
+///
+/// # Examples
+/// ```rust
+///
+/// use pepser::parser::dotenv::dotenv;
+/// let (_, env) = dotenv(
+///     "# database\nexport HOST=localhost\nPORT = 5432 # default\n\
+///     URL=\"postgres://${HOST}:$PORT/${NAME:-app}\"\nRAW='$HOST\\n'\n",
+/// )
+/// .unwrap();
+///
+/// assert_eq!(env.get("URL"), Some("postgres://localhost:5432/app"));
+/// assert_eq!(env.get("RAW"), Some("$HOST\\n"));
+/// let port = env.variable("PORT").unwrap();
+/// assert_eq!((port.key.span.start, port.value.span.start), (33, 40));
+/// assert_eq!(dotenv("A=1\nB\n").unwrap_err().index, 5);
+///
+///
+/// ```
+pub fn dotenv(input: &str) -> ParseResult<&str, Dotenv<'_>> {
+    dotenv_with(input, |_| None)
+}
+
+/// Parses a `.env` file as [`dotenv`] does, looking up with `env` the references to variables
+/// the file does not set before them, such as those of the process environment
+///
+/// # Examples
+/// ```rust
+///
+/// use pepser::parser::dotenv::dotenv_with;
+/// let env = |key: &str| (key == "HOME").then(|| "/home/me".to_string());
+///
+/// let (_, parsed) = dotenv_with("CACHE=$HOME/.cache\nHOME=/tmp\nTMP=$HOME", env).unwrap();
+/// assert_eq!(parsed.get("CACHE"), Some("/home/me/.cache"));
+/// assert_eq!(parsed.get("TMP"), Some("/tmp"));
+///
+///
+/// ```
+pub fn dotenv_with<F>(input: &str, mut env: F) -> ParseResult<&str, Dotenv<'_>>
+where
+    F: FnMut(&str) -> Option<String>,
+{
+    let mut dotenv = Dotenv::default();
+    let mut index = 0;
+    loop {
+        index += blanks(&input[index..]);
+        match input.as_bytes().get(index) {
+            None => return Ok(("", dotenv)),
+            Some(b'\r' | b'\n') => index += 1,
+            Some(b'#') => index = line_end(input, index),
+            Some(_) => {
+                let mut reader = Reader {
+                    input,
+                    dotenv: &dotenv,
+                    env: &mut env,
+                };
+                let (next, variable) = reader.variable(index)?;
+                dotenv.insert(variable);
+                index = next;
+            }
+        }
+    }
+}
+
+fn invalid<'a>(index: usize, reason: &'static str) -> ParserError<&'a str> {
+    ParserError::new(index, ErrorSource::TakeWhile, reason)
+}
+
+/// Number of spaces and tabs starting `input`
+fn blanks(input: &str) -> usize {
+    input.len() - input.trim_start_matches([' ', '\t']).len()
+}
+
+/// Index after the line break ending the line of `index`
+fn line_end(input: &str, index: usize) -> usize {
+    input[index..]
+        .find('\n')
+        .map_or(input.len(), |end| index + end + 1)
+}
+
+/// Number of bytes of the key starting `input`, a letter or an underscore followed by
+/// letters, digits, underscores and dots
+fn key_length(input: &str) -> usize {
+    match input.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_') {
+        true => input
+            .find(|c: char| !c.is_ascii_alphanumeric() && !"_.".contains(c))
+            .unwrap_or(input.len()),
+        false => 0,
+    }
+}
+
+/// Reads the variables of a file, with what it set so far for references
+struct Reader<'a, 'd, F> {
+    input: &'a str,
+    dotenv: &'d Dotenv<'a>,
+    env: &'d mut F,
+}
+
+impl<'a, F> Reader<'a, '_, F>
+where
+    F: FnMut(&str) -> Option<String>,
+{
+    /// A variable from its key to the end of its line, or of its closing quote's line
+    fn variable(&mut self, start: usize) -> Result<(usize, Variable<'a>), ParserError<&'a str>> {
+        let input = self.input;
+        let mut start = start;
+        if let Some(rest) = input[start..].strip_prefix("export") {
+            if rest.starts_with([' ', '\t']) {
+                start += "export".len() + blanks(rest);
+            }
+        }
+        let key_end = start + key_length(&input[start..]);
+        if key_end == start {
+            return Err(invalid(start, "invalid key"));
+        }
+        let equal = key_end + blanks(&input[key_end..]);
+        if !input[equal..].starts_with('=') {
+            return Err(invalid(equal, "expected = after the key"));
+        }
+        let value_start = equal + 1 + blanks(&input[equal + 1..]);
+        let (value_start, value_end, value) = match input.as_bytes().get(value_start) {
+            Some(b'\'') => match input[value_start + 1..].find('\'') {
+                Some(end) => {
+                    let end = value_start + 1 + end;
+                    (value_start, end + 1, input[value_start + 1..end].into())
+                }
+                None => return Err(invalid(value_start, "unterminated quote")),
+            },
+            Some(b'"') => {
+                let (value_end, value) = self.double_quoted(value_start)?;
+                (value_start, value_end, value)
+            }
+            _ => self.unquoted(equal + 1)?,
+        };
+        let key = Spanned {
+            span: Span {
+                start,
+                end: key_end,
+            },
+            value: &input[start..key_end],
+        };
+        let value = Spanned {
+            span: Span {
+                start: value_start,
+                end: value_end,
+            },
+            value,
+        };
+        let rest = value_end + blanks(&input[value_end..]);
+        match input.as_bytes().get(rest) {
+            None | Some(b'\r' | b'\n' | b'#') => {
+                Ok((line_end(input, rest), Variable { key, value }))
+            }
+            Some(_) => Err(invalid(rest, "unexpected text after the value")),
+        }
+    }
+
+    /// The value after the `=` at `equal`, up to a comment, which a space or a tab precedes,
+    /// or the end of the line. Returns where it starts and ends once spaces and tabs around
+    /// it are removed
+    fn unquoted(&mut self, equal: usize) -> Result<(usize, usize, String), ParserError<&'a str>> {
+        let input = self.input;
+        let line = &input[equal..];
+        let line = &line[..line.find(['\r', '\n']).unwrap_or(line.len())];
+        let content = match line.find(" #").or_else(|| line.find("\t#")) {
+            Some(comment) => &line[..comment],
+            None => line,
+        };
+        let trimmed = content.trim_start_matches([' ', '\t']);
+        let start = equal + content.len() - trimmed.len();
+        let end = start + trimmed.trim_end_matches([' ', '\t']).len();
+        let mut value = String::new();
+        let mut index = start;
+        while let Some(dollar) = input[index..end].find('$') {
+            value.push_str(&input[index..index + dollar]);
+            index = self.reference(index + dollar, end, &mut value)?;
+        }
+        value.push_str(&input[index..end]);
+        Ok((start, end, value))
+    }
+
+    fn double_quoted(&mut self, start: usize) -> Result<(usize, String), ParserError<&'a str>> {
+        let input = self.input;
+        let mut value = String::new();
+        let mut index = start + 1;
+        loop {
+            let Some(c) = input[index..].chars().next() else {
+                return Err(invalid(start, "unterminated quote"));
+            };
+            match c {
+                '"' => return Ok((index + 1, value)),
+                '$' => index = self.reference(index, input.len(), &mut value)?,
+                '\\' => {
+                    let escaped = match input[index + 1..].chars().next() {
+                        Some('n') => '\n',
+                        Some('r') => '\r',
+                        Some('t') => '\t',
+                        Some(escaped @ ('"' | '\\' | '$')) => escaped,
+                        _ => return Err(invalid(index, "invalid escape")),
+                    };
+                    value.push(escaped);
+                    index += 2;
+                }
+                _ => {
+                    value.push(c);
+                    index += c.len_utf8();
+                }
+            }
+        }
+    }
+
+    /// Replaces the reference at `start`, a `$` in a value ending before `end`, returning the
+    /// index after it. A `$` which starts no reference is kept
+    fn reference(
+        &mut self,
+        start: usize,
+        end: usize,
+        value: &mut String,
+    ) -> Result<usize, ParserError<&'a str>> {
+        let input = &self.input[..end];
+        let after = start + 1;
+        let (next, key, default) = match input[after..].strip_prefix('{') {
+            Some(braced) => {
+                let Some(close) = braced.find('}') else {
+                    return Err(invalid(start, "unterminated variable reference"));
+                };
+                let reference = &braced[..close];
+                let (key, default) = match reference.split_once(":-") {
+                    Some((key, default)) => (key, Some(default)),
+                    None => (reference, None),
+                };
+                if key.is_empty() || key_length(key) != key.len() {
+                    return Err(invalid(start, "invalid variable reference"));
+                }
+                (after + 1 + close + 1, key, default)
+            }
+            None => {
+                let length = key_length(&input[after..]);
+                if length == 0 {
+                    value.push('$');
+                    return Ok(after);
+                }
+                (after + length, &input[after..after + length], None)
+            }
+        };
+        let found = match self.dotenv.get(key) {
+            Some(found) => Some(found.into()),
+            None => (self.env)(key),
+        };
+        match (found, default) {
+            (Some(found), Some(_)) if !found.is_empty() => value.push_str(&found),
+            (_, Some(default)) => value.push_str(default),
+            (Some(found), None) => value.push_str(&found),
+            (None, None) => {}
+        }
+        Ok(next)
+    }
+}
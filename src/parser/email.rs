@@ -0,0 +1,142 @@
+use alloc::string::String;
+use core::net::{Ipv4Addr, Ipv6Addr};
+
+use super::{
+    errors::{ErrorSource, ParserError},
+    impls::{sequence, take_while1},
+    net::{ipv4, ipv6},
+    traits::{ParseResult, Parser},
+};
+
+/// Characters of an atom besides letters and digits
+const ATEXT: &str = "!#$%&'*+-/=?^_`{|}~";
+
+/// An RFC 5322 `addr-spec`, comments and obsolete forms aside
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Email<'a> {
+    pub local: LocalPart<'a>,
+    pub domain: Domain<'a>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum LocalPart<'a> {
+    /// Atoms separated by dots, such as `first.last`
+    DotAtom(&'a str),
+    /// The content of a quoted string, with its quoted pairs unescaped
+    Quoted(String),
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Domain<'a> {
+    Name(&'a str),
+    /// `[192.0.2.1]`
+    Ipv4(Ipv4Addr),
+    /// `[IPv6:2001:db8::1]`
+    Ipv6(Ipv6Addr),
+    /// Any other domain literal, without its brackets
+    Literal(&'a str),
+}
+
+fn invalid<'a>(index: usize, reason: &'static str) -> ParserError<&'a str> {
+    ParserError::new(index, ErrorSource::TakeWhile, reason)
+}
+
+/// Parses `local@domain`, the local part is a dot-atom or a quoted string and the domain a
+/// dot-atom or a literal between brackets
+///
+/// # Examples
+/// ```rust
+///
+/// use pepser::parser::email::{email, Domain, LocalPart};
+/// let (_, address) = email("\"john doe\"@[IPv6:::1]").unwrap();
+///
+/// assert_eq!(address.local, LocalPart::Quoted("john doe".to_string()));
+/// assert_eq!(address.domain, Domain::Ipv6("::1".parse().unwrap()));
+/// assert_eq!(email("a.b@example.com>").unwrap().0, ">");
+/// assert_eq!(email("a..b@example.com").unwrap_err().index, 2);
+///
+///
+/// ```
+pub fn email(input: &str) -> ParseResult<&str, Email<'_>> {
+    let (remainder, local) = if input.starts_with('"') {
+        let (remainder, quoted) = quoted_string(input)?;
+        (remainder, LocalPart::Quoted(quoted))
+    } else {
+        let (remainder, atom) = dot_atom(input)?;
+        (remainder, LocalPart::DotAtom(atom))
+    };
+    let offset = input.len() - remainder.len();
+    let (remainder, _) = sequence("@")
+        .parse(remainder)
+        .map_err(|_| invalid(offset, "expected '@'"))?;
+    let offset = offset + 1;
+    let (remainder, domain) = if remainder.starts_with('[') {
+        domain_literal(remainder)
+    } else {
+        dot_atom(remainder).map(|(remainder, name)| (remainder, Domain::Name(name)))
+    }
+    .map_err(|error| ParserError::from_error(error, offset))?;
+    Ok((remainder, Email { local, domain }))
+}
+
+/// Parses atoms separated by single dots
+pub fn dot_atom(input: &str) -> ParseResult<&str, &str> {
+    let atom =
+        |input| take_while1(|c: char| c.is_ascii_alphanumeric() || ATEXT.contains(c)).parse(input);
+    let (mut remainder, _) = atom(input).map_err(|_| invalid(0, "expected an atom"))?;
+    while let Some(next) = remainder.strip_prefix('.') {
+        let offset = input.len() - next.len();
+        remainder = atom(next)
+            .map_err(|_| invalid(offset, "expected an atom after '.'"))?
+            .0;
+    }
+    let (atoms, remainder) = input.split_at(input.len() - remainder.len());
+    Ok((remainder, atoms))
+}
+
+/// Parses a double quoted string, where a backslash quotes the next character
+fn quoted_string(input: &str) -> ParseResult<&str, String> {
+    let mut content = String::new();
+    let mut chars = input.char_indices().skip(1);
+    while let Some((position, c)) = chars.next() {
+        match c {
+            '"' => return Ok((&input[position + 1..], content)),
+            '\\' => match chars.next() {
+                Some((_, quoted))
+                    if quoted == ' ' || quoted == '\t' || quoted.is_ascii_graphic() =>
+                {
+                    content.push(quoted)
+                }
+                _ => return Err(invalid(position, "invalid quoted pair")),
+            },
+            c if c == ' ' || c == '\t' || c.is_ascii_graphic() => content.push(c),
+            _ => return Err(invalid(position, "invalid character in quoted string")),
+        }
+    }
+    Err(invalid(input.len(), "unterminated quoted string"))
+}
+
+/// Parses `[...]`, recognizing IPv4 and `IPv6:` addresses
+fn domain_literal(input: &str) -> ParseResult<&str, Domain<'_>> {
+    let content = &input[1..];
+    let end = content
+        .bytes()
+        .position(|byte| !(33..=126).contains(&byte) || matches!(byte, b'[' | b']' | b'\\'))
+        .unwrap_or(content.len());
+    let (literal, remainder) = content.split_at(end);
+    let Some(remainder) = remainder.strip_prefix(']') else {
+        return Err(invalid(end + 1, "expected ']'"));
+    };
+    let domain = if let Some(address) = literal.strip_prefix("IPv6:") {
+        match ipv6(address) {
+            Ok(("", address)) => Domain::Ipv6(address),
+            _ => return Err(invalid(1 + "IPv6:".len(), "invalid IPv6 address")),
+        }
+    } else {
+        match ipv4(literal) {
+            Ok(("", address)) => Domain::Ipv4(address),
+            _ => Domain::Literal(literal),
+        }
+    };
+    Ok((remainder, domain))
+}
@@ -0,0 +1,134 @@
+use alloc::{vec, vec::Vec};
+
+use super::{
+    errors::{ErrorSource, ParserError},
+    traits::{ParseResult, Parser},
+};
+
+const STANDARD: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+const URL_SAFE: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// How strictly base64 parsers treat the `=` padding of the last group
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Padding {
+    /// The last group must be padded to four characters
+    Required,
+    /// The last group may be padded, but then correctly
+    Optional,
+    /// The last group must not be padded, the `=` is left unparsed
+    Forbidden,
+}
+
+/// Decodes standard base64 (RFC 4648), up to the first character outside of its alphabet
+///
+/// # Examples
+/// ```rust
+///
+/// use pepser::parser::encoding::{base64, Padding};
+/// use pepser::parser::traits::Parser;
+///
+/// assert_eq!(base64(Padding::Required).parse("aGk=\""), Ok(("\"", b"hi".to_vec())));
+/// assert_eq!(base64(Padding::Optional).parse("aGk"), Ok(("", b"hi".to_vec())));
+/// assert!(base64(Padding::Required).parse("aGk").is_err());
+///
+///
+/// ```
+pub fn base64<'a>(padding: Padding) -> impl Parser<&'a str, Output = Vec<u8>> {
+    let values = base64_values(STANDARD);
+    move |input| decode_base64(&values, padding, input)
+}
+
+/// Decodes the URL and file name safe variant of base64, where `-` and `_` replace `+` and `/`
+pub fn base64url<'a>(padding: Padding) -> impl Parser<&'a str, Output = Vec<u8>> {
+    let values = base64_values(URL_SAFE);
+    move |input| decode_base64(&values, padding, input)
+}
+
+/// Decodes pairs of hexadecimal digits of any case, up to the first character which is not
+/// one. An odd number of digits is an error
+///
+/// # Examples
+/// ```rust
+///
+/// use pepser::parser::encoding::hex;
+///
+/// assert_eq!(hex("00fF7a g"), Ok((" g", vec![0x00, 0xFF, 0x7A])));
+/// assert_eq!(hex("abc").unwrap_err().index, 2);
+///
+///
+/// ```
+pub fn hex(input: &str) -> ParseResult<&str, Vec<u8>> {
+    let digits = input
+        .bytes()
+        .position(|byte| !byte.is_ascii_hexdigit())
+        .unwrap_or(input.len());
+    if digits % 2 == 1 {
+        return Err(invalid(digits - 1, "odd number of hex digits"));
+    }
+    let (encoded, remainder) = input.split_at(digits);
+    let decoded = encoded
+        .as_bytes()
+        .chunks(2)
+        .map(|pair| (hex_value(pair[0]) << 4) | hex_value(pair[1]))
+        .collect();
+    Ok((remainder, decoded))
+}
+
+fn hex_value(digit: u8) -> u8 {
+    match digit {
+        b'0'..=b'9' => digit - b'0',
+        b'a'..=b'f' => digit - b'a' + 10,
+        _ => digit - b'A' + 10,
+    }
+}
+
+fn invalid<'a>(index: usize, reason: &'static str) -> ParserError<&'a str> {
+    ParserError::new(index, ErrorSource::TakeWhile, reason)
+}
+
+/// Value of each byte in the alphabet, `u8::MAX` for the bytes outside of it
+fn base64_values(alphabet: &[u8; 64]) -> [u8; 256] {
+    let mut values = [u8::MAX; 256];
+    for (value, byte) in alphabet.iter().enumerate() {
+        values[usize::from(*byte)] = value as u8;
+    }
+    values
+}
+
+fn decode_base64<'a>(
+    values: &[u8; 256],
+    padding: Padding,
+    input: &'a str,
+) -> ParseResult<&'a str, Vec<u8>> {
+    let length = input
+        .bytes()
+        .position(|byte| values[usize::from(byte)] == u8::MAX)
+        .unwrap_or(input.len());
+    if length % 4 == 1 {
+        return Err(invalid(length - 1, "truncated base64 group"));
+    }
+
+    let missing = (4 - length % 4) % 4;
+    let padded = input[length..]
+        .bytes()
+        .take_while(|byte| *byte == b'=')
+        .count();
+    let end = match (padding, padded) {
+        (Padding::Forbidden, _) | (Padding::Optional, 0) => length,
+        (Padding::Required | Padding::Optional, padded) if padded == missing => length + padded,
+        _ => return Err(invalid(length, "invalid base64 padding")),
+    };
+
+    let mut decoded = vec![];
+    let mut bits = 0u32;
+    let mut count = 0;
+    for byte in input[..length].bytes() {
+        bits = ((bits << 6) | u32::from(values[usize::from(byte)])) & 0xFFFF;
+        count += 6;
+        if count >= 8 {
+            count -= 8;
+            decoded.push((bits >> count) as u8);
+        }
+    }
+    Ok((&input[end..], decoded))
+}
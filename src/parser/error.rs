@@ -0,0 +1,3 @@
+//! The errors returned by the parsers. The items are defined in [`errors`](super::errors),
+//! whose paths keep working
+pub use super::errors::{line_column, ErrorSnippet, ErrorSource, ParserError};
@@ -1,30 +1,151 @@
+use alloc::{borrow::Cow, boxed::Box, format, string::String};
+use core::{
+    error::Error,
+    fmt::{self, Debug, Display, Formatter, Write},
+};
+
 use super::traits::Input;
 
-#[derive(Debug, PartialEq)]
+/// The kind of parser which produced an error. Combinators such as `and`, `or` or `map`
+/// return the error of the parser which failed unchanged, so only the parsers which reject
+/// input have a kind. Parsers outside of this crate name theirs with [`ErrorSource::Custom`].
+/// New kinds are added as the crate grows, so matches outside of this crate need a wildcard arm
+#[derive(Debug, PartialEq, Clone)]
+#[non_exhaustive]
 pub enum ErrorSource<E: Input> {
     Many,
     Sequence(E),
     TakeWhile,
+    TakeUntil(E),
     EOF,
     DropUntil,
+    Regex,
+    Token,
+    Lexer,
+    State,
+    Rule,
+    Checksum,
+    LimitExceeded,
+    /// A single character was expected, e.g. by `any_char`
+    Char,
+    /// A fixed number of digits was expected, e.g. by `fixed_digits`
+    Digits,
+    /// A negative predicate matched, e.g. by `not_followed_by`
+    Predicate,
+    /// None of the keywords of `keywords` starts the input
+    Keyword,
+    /// A kind defined by a parser outside of this crate, built by [`ParserError::custom`]
+    Custom(Cow<'static, str>),
 }
 
-#[derive(Debug, PartialEq)]
+/// Errors are built with [`ParserError::new`], fields may be added in future versions.
+/// Parsers keep the details of a failure in `source` and `index` and use a static `reason`,
+/// so that errors discarded while backtracking cost no allocation. The message is only
+/// assembled by `Display`
+#[derive(Debug, PartialEq, Clone)]
+#[non_exhaustive]
 pub struct ParserError<E: Input> {
+    /// Where the parser failed, relative to the input it was given. Combinators which run a
+    /// parser on what a previous one left offset its errors, so that nested failures are
+    /// reported from the start of the outermost parser
     pub index: usize,
     pub source: ErrorSource<E>,
-    pub reason: String,
+    pub reason: Cow<'static, str>,
+    /// What the parser expected at `index`, set by [`Parser::expect`](crate::parser::traits::Parser::expect)
+    /// and merged by `or` when both branches fail there
+    pub expected: Box<[&'static str]>,
+    /// Set by [`Parser::cut`](crate::parser::traits::Parser::cut), the combinators which
+    /// backtrack on errors return committed ones as is
+    pub committed: bool,
 }
 
 impl<E> ParserError<E>
 where
     E: Input,
 {
-    pub fn new(index: usize, source: ErrorSource<E>, reason: &str) -> Self {
+    pub fn new<R>(index: usize, source: ErrorSource<E>, reason: R) -> Self
+    where
+        R: Into<Cow<'static, str>>,
+    {
         ParserError {
             index,
             source,
-            reason: reason.to_string(),
+            reason: reason.into(),
+            expected: Box::new([]),
+            committed: false,
+        }
+    }
+
+    /// An error of a parser defined outside of this crate, `kind` names the parser and is
+    /// shown by `Display` where the built in kinds are
+    ///
+    /// # Examples
+    /// ```rust
+    ///
+    /// use pepser::parser::errors::{ErrorSource, ParserError};
+    /// use pepser::parser::traits::ParseResult;
+    /// fn even(input: &str) -> ParseResult<&str, u32> {
+    ///     match input.parse::<u32>() {
+    ///         Ok(value) if value % 2 == 0 => Ok(("", value)),
+    ///         _ => Err(ParserError::custom(0, "even", "expected an even number")),
+    ///     }
+    /// }
+    /// let error = even("3").unwrap_err();
+    ///
+    /// assert_eq!(error.source, ErrorSource::Custom("even".into()));
+    /// assert_eq!(error.to_string(), "expected an even number at index 0 (even)");
+    ///
+    ///
+    /// ```
+    pub fn custom<K, R>(index: usize, kind: K, reason: R) -> Self
+    where
+        K: Into<Cow<'static, str>>,
+        R: Into<Cow<'static, str>>,
+    {
+        ParserError::new(index, ErrorSource::Custom(kind.into()), reason)
+    }
+
+    /// The expectations as `expected a, b or c` when there are some, the reason otherwise
+    pub fn message(&self) -> Cow<'_, str> {
+        match &*self.expected {
+            [] => Cow::Borrowed(&self.reason),
+            [expected] => Cow::Owned(format!("expected {}", expected)),
+            [init @ .., last] => Cow::Owned(format!("expected {} or {}", init.join(", "), last)),
+        }
+    }
+
+    /// Converts the input type carried by the error source, e.g. to report `&str` errors on `&[u8]`
+    pub fn map_input<F, M>(self, f: M) -> ParserError<F>
+    where
+        F: Input,
+        M: FnOnce(E) -> F,
+    {
+        let source = match self.source {
+            ErrorSource::Many => ErrorSource::Many,
+            ErrorSource::Sequence(matcher) => ErrorSource::Sequence(f(matcher)),
+            ErrorSource::TakeWhile => ErrorSource::TakeWhile,
+            ErrorSource::TakeUntil(delimiter) => ErrorSource::TakeUntil(f(delimiter)),
+            ErrorSource::EOF => ErrorSource::EOF,
+            ErrorSource::DropUntil => ErrorSource::DropUntil,
+            ErrorSource::Regex => ErrorSource::Regex,
+            ErrorSource::Token => ErrorSource::Token,
+            ErrorSource::Lexer => ErrorSource::Lexer,
+            ErrorSource::State => ErrorSource::State,
+            ErrorSource::Rule => ErrorSource::Rule,
+            ErrorSource::Checksum => ErrorSource::Checksum,
+            ErrorSource::LimitExceeded => ErrorSource::LimitExceeded,
+            ErrorSource::Char => ErrorSource::Char,
+            ErrorSource::Digits => ErrorSource::Digits,
+            ErrorSource::Predicate => ErrorSource::Predicate,
+            ErrorSource::Keyword => ErrorSource::Keyword,
+            ErrorSource::Custom(kind) => ErrorSource::Custom(kind),
+        };
+        ParserError {
+            index: self.index,
+            source,
+            reason: self.reason,
+            expected: self.expected,
+            committed: self.committed,
         }
     }
 
@@ -33,6 +154,205 @@ where
             index: error.index + index,
             source: error.source,
             reason: error.reason,
+            expected: error.expected,
+            committed: error.committed,
+        }
+    }
+}
+
+/// 1-based line and column of a byte index in `input`, columns count characters. Indices past
+/// the end or inside a character are clamped to the previous character boundary
+pub fn line_column(input: &str, index: usize) -> (usize, usize) {
+    let before = &input[..floor_char_boundary(input, index)];
+    let line_start = before.rfind('\n').map_or(0, |newline| newline + 1);
+    (
+        before.matches('\n').count() + 1,
+        before[line_start..].chars().count() + 1,
+    )
+}
+
+/// The character boundary of `input` at or before `index`, its length past the end
+fn floor_char_boundary(input: &str, index: usize) -> usize {
+    let mut index = index.min(input.len());
+    while !input.is_char_boundary(index) {
+        index -= 1;
+    }
+    index
+}
+
+/// An excerpt of the input where an error occurred, for messages such as `found "lett x..."`.
+/// It starts at the error index, moved back to a character boundary, and holds at most
+/// `length` characters, so it never splits a character whatever the index
+///
+/// # Examples
+/// ```rust
+///
+/// use pepser::parser::errors::ErrorSnippet;
+/// let input = "let x = 日本語";
+///
+/// assert_eq!(ErrorSnippet::new(input, 4, 5).to_string(), "\"x = 日...\"");
+/// // 9 is inside `日`, the excerpt starts with it
+/// assert_eq!(ErrorSnippet::new(input, 9, 10).text, "日本語");
+/// assert_eq!(ErrorSnippet::new(input, 99, 10).to_string(), "end of input");
+///
+///
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct ErrorSnippet<'a> {
+    pub text: &'a str,
+    /// Whether the input goes on after `text`
+    pub truncated: bool,
+}
+
+impl<'a> ErrorSnippet<'a> {
+    /// The number of characters of [`ParserError::snippet`]
+    pub const DEFAULT_LENGTH: usize = 10;
+
+    pub fn new(input: &'a str, index: usize, length: usize) -> Self {
+        let rest = &input[floor_char_boundary(input, index)..];
+        let end = rest
+            .char_indices()
+            .nth(length)
+            .map_or(rest.len(), |(end, _)| end);
+        ErrorSnippet {
+            text: &rest[..end],
+            truncated: end < rest.len(),
+        }
+    }
+}
+
+/// Writes the excerpt quoted and escaped, followed by `...` when it is truncated, or
+/// `end of input` when it is empty
+impl Display for ErrorSnippet<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match (self.text, self.truncated) {
+            ("", false) => f.write_str("end of input"),
+            (text, truncated) => {
+                let ellipsis = if truncated { "..." } else { "" };
+                write!(f, "\"{}{}\"", text.escape_debug(), ellipsis)
+            }
+        }
+    }
+}
+
+impl<E> ParserError<E>
+where
+    E: Input,
+{
+    /// The excerpt of `input` at the error index, which works for the errors of every parser
+    /// as long as `input` is the text the index refers to
+    ///
+    /// # Examples
+    /// ```rust
+    ///
+    /// use pepser::parser::impls::sequence;
+    /// use pepser::parser::traits::Parser;
+    /// let input = "let x = 1 + 2 + 3";
+    /// let error = sequence("let y").parse(input).unwrap_err();
+    ///
+    /// assert_eq!(format!("found {}", error.snippet(input)), "found \"x = 1 + 2 ...\"");
+    ///
+    ///
+    /// ```
+    pub fn snippet<'a>(&self, input: &'a str) -> ErrorSnippet<'a> {
+        ErrorSnippet::new(input, self.index, ErrorSnippet::DEFAULT_LENGTH)
+    }
+}
+
+impl<E> ParserError<E>
+where
+    E: Input + Debug,
+{
+    /// Renders the error as a diagnostic showing the offending line of `input`, which must be
+    /// the text the error index refers to, with a caret under the error position
+    ///
+    /// # Examples
+    /// ```rust
+    ///
+    /// use pepser::parser::impls::sequence;
+    /// use pepser::parser::traits::Parser;
+    /// let input = "let x = 1\nlet y = 2";
+    /// let error = sequence("let x = 1\nlet y = 3").parse(input).unwrap_err();
+    ///
+    /// assert_eq!(
+    ///     error.render(input),
+    ///     "error: could not parse sequence\n \
+    ///      --> 2:9\n  \
+    ///       |\n\
+    ///      2 | let y = 2\n  \
+    ///       |         ^ expected \"let x = 1\\nlet y = 3\"\n"
+    /// );
+    ///
+    ///
+    /// ```
+    pub fn render(&self, input: &str) -> String {
+        let (line, column) = line_column(input, self.index);
+        let text = input.lines().nth(line - 1).unwrap_or_default();
+        let number = format!("{}", line);
+        let gutter = " ".repeat(number.len());
+        let mut rendered = String::new();
+        // writing to a String cannot fail
+        let _ = write!(
+            rendered,
+            "error: {}\n{}--> {}:{}\n{} |\n{} | {}\n{} | {}^ {}\n",
+            self.message(),
+            gutter,
+            line,
+            column,
+            gutter,
+            number,
+            text,
+            gutter,
+            " ".repeat(column - 1),
+            self.source
+        );
+        rendered
+    }
+}
+
+impl<E> Display for ErrorSource<E>
+where
+    E: Input + Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ErrorSource::Many => f.write_str("many"),
+            ErrorSource::Sequence(matcher) => write!(f, "expected {:?}", matcher),
+            ErrorSource::TakeWhile => f.write_str("take while"),
+            ErrorSource::TakeUntil(delimiter) => write!(f, "expected {:?} ahead", delimiter),
+            ErrorSource::EOF => f.write_str("expected end of input"),
+            ErrorSource::DropUntil => f.write_str("drop until"),
+            ErrorSource::Regex => f.write_str("regex"),
+            ErrorSource::Token => f.write_str("unexpected token"),
+            ErrorSource::Lexer => f.write_str("unexpected character"),
+            ErrorSource::State => f.write_str("rejected by the parser state"),
+            ErrorSource::Rule => f.write_str("grammar rule"),
+            ErrorSource::Checksum => f.write_str("checksum"),
+            ErrorSource::LimitExceeded => f.write_str("limit exceeded"),
+            ErrorSource::Char => f.write_str("expected a character"),
+            ErrorSource::Digits => f.write_str("digits"),
+            ErrorSource::Predicate => f.write_str("negative predicate"),
+            ErrorSource::Keyword => f.write_str("expected a keyword"),
+            ErrorSource::Custom(kind) => f.write_str(kind),
         }
     }
 }
+
+/// Formats as `<reason> at index <index> (<source>)`. The error borrows the input through its
+/// source, so for a `Box<dyn Error>` outliving the input convert it to a `String` first
+impl<E> Display for ParserError<E>
+where
+    E: Input + Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} at index {} ({})",
+            self.message(),
+            self.index,
+            self.source
+        )
+    }
+}
+
+impl<E> Error for ParserError<E> where E: Input + Debug {}
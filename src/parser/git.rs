@@ -0,0 +1,575 @@
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use super::{
+    errors::{ErrorSource, ParserError},
+    traits::ParseResult,
+};
+
+/// A variable of a git config file, with the section it was set in
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Entry {
+    /// Compared ignoring its ASCII case
+    pub section: String,
+    /// The quoted name of `[remote "origin"]`, compared with its case
+    pub subsection: Option<String>,
+    /// Compared ignoring its ASCII case
+    pub key: String,
+    /// The value with its quotes and escapes processed, `None` for a key without `=`, which
+    /// is a true boolean
+    pub value: Option<String>,
+}
+
+/// The variables of a git config file in the order they are set. A key set several times is
+/// multi-valued, single-valued lookups take its last value
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct Config {
+    pub entries: Vec<Entry>,
+}
+
+/// A file to include from an `[include]` or `[includeIf "<condition>"]` section
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Include<'a> {
+    pub path: &'a str,
+    /// Such as `gitdir:~/work/`, `None` for an unconditional include
+    pub condition: Option<&'a str>,
+}
+
+impl Entry {
+    /// Whether this is the variable `name`, `section.key` or `section.subsection.key`
+    pub fn is(&self, name: &str) -> bool {
+        let Some((section, rest)) = name.split_once('.') else {
+            return false;
+        };
+        let (subsection, key) = match rest.rsplit_once('.') {
+            Some((subsection, key)) => (Some(subsection), key),
+            None => (None, rest),
+        };
+        self.section.eq_ignore_ascii_case(section)
+            && self.subsection.as_deref() == subsection
+            && self.key.eq_ignore_ascii_case(key)
+    }
+
+    /// The value read as a boolean as git does: `true`, `yes`, `on` and `1` are true, `false`,
+    /// `no`, `off`, `0` and the empty string are false, a key without value is true
+    pub fn boolean(&self) -> Option<bool> {
+        let Some(value) = &self.value else {
+            return Some(true);
+        };
+        let is = |words: &[&str]| words.iter().any(|word| value.eq_ignore_ascii_case(word));
+        match value.as_str() {
+            _ if is(&["true", "yes", "on", "1"]) => Some(true),
+            _ if is(&["false", "no", "off", "0", ""]) => Some(false),
+            _ => None,
+        }
+    }
+}
+
+impl Config {
+    /// Returns the last entry of the variable `name`, see [`Entry::is`]
+    pub fn get(&self, name: &str) -> Option<&Entry> {
+        self.entries.iter().rev().find(|entry| entry.is(name))
+    }
+
+    /// Returns every entry of the variable `name`, in the order they are set
+    pub fn get_all<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Entry> {
+        self.entries.iter().filter(move |entry| entry.is(name))
+    }
+
+    /// Returns the files this config includes, which are not read
+    pub fn includes(&self) -> impl Iterator<Item = Include<'_>> {
+        self.entries.iter().filter_map(|entry| {
+            let condition = match entry.section.to_ascii_lowercase().as_str() {
+                "include" => None,
+                "includeif" => Some(entry.subsection.as_deref()?),
+                _ => return None,
+            };
+            match entry.key.eq_ignore_ascii_case("path") {
+                true => Some(Include {
+                    path: entry.value.as_deref()?,
+                    condition,
+                }),
+                false => None,
+            }
+        })
+    }
+}
+
+/// Parses a git config file: `[section]` and `[section "subsection"]` headers followed by
+/// `key = value` variables, comments starting with `#` or `;`. Values may be quoted, escape
+/// `\n`, `\t`, `\b`, `\"` and `\\`, and continue on the next line after a backslash. Error
+/// indices are offsets in the input
+///
+/// # Examples
+/// ```rust
+///
+/// use pepser::parser::git::config;
+/// let (_, parsed) = config(
+///     "[core]\n\tbare = false # not a bare repository\n\
+///     [remote \"origin\"]\n\turl = git@example.com:repo.git\n\
+///     \tfetch = +refs/heads/*:refs/remotes/origin/*\n\tfetch = +refs/tags/*:refs/tags/*\n\
+///     [alias]\n\tlg = \"log --graph \\\n\t  --oneline\"\n",
+/// )
+/// .unwrap();
+///
+/// assert_eq!(parsed.get("core.bare").unwrap().boolean(), Some(false));
+/// assert_eq!(parsed.get_all("remote.origin.fetch").count(), 2);
+/// assert_eq!(
+///     parsed.get("alias.lg").unwrap().value.as_deref(),
+///     Some("log --graph \t  --oneline")
+/// );
+/// assert_eq!(config("[core\n").unwrap_err().index, 5);
+///
+///
+/// ```
+pub fn config(input: &str) -> ParseResult<&str, Config> {
+    let mut entries = Vec::new();
+    let mut section: Option<Section> = None;
+    let mut index = 0;
+    loop {
+        index += blanks(&input[index..]);
+        match input.as_bytes().get(index) {
+            None => return Ok(("", Config { entries })),
+            Some(b'\r' | b'\n') => index += 1,
+            Some(b'#' | b';') => index = line_end(input, index),
+            Some(b'[') => {
+                let (next, header) = section_header(input, index)?;
+                section = Some(header);
+                index = next;
+            }
+            Some(_) => {
+                let Some((name, subsection)) = &section else {
+                    return Err(invalid(index, "variable outside of a section"));
+                };
+                let (next, key, value) = variable(input, index)?;
+                entries.push(Entry {
+                    section: name.clone(),
+                    subsection: subsection.clone(),
+                    key: key.to_string(),
+                    value,
+                });
+                index = next;
+            }
+        }
+    }
+}
+
+/// A pattern of a `.gitignore` file
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Pattern {
+    /// `!pattern`, which includes back what a previous pattern ignores
+    pub negated: bool,
+    /// `pattern/`, which only matches directories
+    pub directory_only: bool,
+    /// Set when a slash starts the pattern or separates its segments, it then matches from
+    /// the directory of the `.gitignore` only, rather than at any depth
+    pub anchored: bool,
+    /// The segments between slashes
+    pub segments: Vec<Segment>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Segment {
+    /// `**`, any number of directories. It matches at least one path component when it ends
+    /// the pattern
+    AnyDirectories,
+    /// A segment matching a single path component
+    Glob(Vec<Token>),
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Token {
+    /// Characters matched as they are, escapes removed
+    Literal(String),
+    /// `*`
+    AnyChars,
+    /// `?`
+    AnyChar,
+    /// `[a-z_]` or `[!0-9]`, with the inclusive ranges of characters it holds
+    Class {
+        negated: bool,
+        ranges: Vec<(char, char)>,
+    },
+}
+
+/// The patterns of a `.gitignore` file in their order, later patterns override earlier ones
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct Gitignore {
+    pub patterns: Vec<Pattern>,
+}
+
+impl Pattern {
+    /// Whether the pattern matches `path`, relative to the directory of the `.gitignore` with
+    /// slashes between its components. The directories holding `path` are not considered, see
+    /// [`Gitignore::is_ignored`]
+    pub fn matches(&self, path: &str, is_dir: bool) -> bool {
+        self.matches_components(&components(path), is_dir)
+    }
+
+    fn matches_components(&self, components: &[&str], is_dir: bool) -> bool {
+        if self.directory_only && !is_dir {
+            return false;
+        }
+        match self.anchored {
+            true => match_segments(&self.segments, components),
+            false => (0..components.len())
+                .any(|start| match_segments(&self.segments, &components[start..])),
+        }
+    }
+}
+
+impl Gitignore {
+    /// Whether `path` is ignored: the last pattern matching it decides, and what an ignored
+    /// directory holds is ignored too, as git does not look inside it
+    ///
+    /// # Examples
+    /// ```rust
+    ///
+    /// use pepser::parser::git::gitignore;
+    /// let ignore = gitignore("# build output\ntarget/\n*.log\n!keep.log\n/docs/**/*.html\n");
+    ///
+    /// assert!(ignore.is_ignored("target", true));
+    /// assert!(ignore.is_ignored("target/debug/app", false));
+    /// assert!(!ignore.is_ignored("src/target", false));
+    /// assert!(ignore.is_ignored("logs/app.log", false));
+    /// assert!(!ignore.is_ignored("logs/keep.log", false));
+    /// assert!(ignore.is_ignored("docs/api/v1/index.html", false));
+    /// assert!(!ignore.is_ignored("src/docs/index.html", false));
+    ///
+    ///
+    /// ```
+    pub fn is_ignored(&self, path: &str, is_dir: bool) -> bool {
+        let components = components(path);
+        let decides = |components: &[&str], is_dir: bool| {
+            self.patterns
+                .iter()
+                .rev()
+                .find(|pattern| pattern.matches_components(components, is_dir))
+                .is_some_and(|pattern| !pattern.negated)
+        };
+        (1..components.len()).any(|end| decides(&components[..end], true))
+            || decides(&components, is_dir)
+    }
+}
+
+/// Parses the patterns of a `.gitignore` file, skipping blank lines and comments. Like git,
+/// no line is an error: a `[` without its `]` is taken literally
+pub fn gitignore(input: &str) -> Gitignore {
+    Gitignore {
+        patterns: input.lines().filter_map(ignore_pattern).collect(),
+    }
+}
+
+/// Parses a line of a `.gitignore` file, `None` when it is blank or a comment. Trailing spaces
+/// are removed unless escaped, a backslash makes the next character literal
+///
+/// # Examples
+/// ```rust
+///
+/// use pepser::parser::git::{ignore_pattern, Segment, Token};
+/// let pattern = ignore_pattern("!/build/**/*.o").unwrap();
+///
+/// assert!(pattern.negated && pattern.anchored);
+/// assert_eq!(pattern.segments[1], Segment::AnyDirectories);
+/// assert_eq!(
+///     pattern.segments[2],
+///     Segment::Glob(vec![Token::AnyChars, Token::Literal(".o".to_string())])
+/// );
+/// assert_eq!(ignore_pattern("# comment"), None);
+/// assert!(!ignore_pattern("\\#literal").unwrap().negated);
+///
+///
+/// ```
+pub fn ignore_pattern(line: &str) -> Option<Pattern> {
+    if line.starts_with('#') {
+        return None;
+    }
+    let mut line = line.strip_suffix('\r').unwrap_or(line);
+    while line.ends_with(' ') && !line[..line.len() - 1].ends_with('\\') {
+        line = &line[..line.len() - 1];
+    }
+    let (negated, line) = match line.strip_prefix('!') {
+        Some(line) => (true, line),
+        None => (false, line),
+    };
+    let (directory_only, line) = match line.strip_suffix('/') {
+        Some(line) => (true, line),
+        None => (false, line),
+    };
+    if line.is_empty() {
+        return None;
+    }
+    let anchored = line.contains('/');
+    let line = line.strip_prefix('/').unwrap_or(line);
+    let mut segments = Vec::new();
+    for segment in line.split('/').filter(|segment| !segment.is_empty()) {
+        match segment {
+            "**" if segments.last() == Some(&Segment::AnyDirectories) => {}
+            "**" => segments.push(Segment::AnyDirectories),
+            _ => segments.push(Segment::Glob(tokens(segment))),
+        }
+    }
+    Some(Pattern {
+        negated,
+        directory_only,
+        anchored,
+        segments,
+    })
+}
+
+/// The name of a section and its subsection
+type Section = (String, Option<String>);
+
+fn invalid<'a>(index: usize, reason: &'static str) -> ParserError<&'a str> {
+    ParserError::new(index, ErrorSource::TakeWhile, reason)
+}
+
+/// Number of spaces and tabs starting `input`
+fn blanks(input: &str) -> usize {
+    input.len() - input.trim_start_matches([' ', '\t']).len()
+}
+
+/// Index after the line break ending the line of `index`
+fn line_end(input: &str, index: usize) -> usize {
+    input[index..]
+        .find('\n')
+        .map_or(input.len(), |end| index + end + 1)
+}
+
+/// `[section]`, `[section "subsection"]` or the deprecated `[section.subsection]`, whose
+/// subsection git compares lowercased
+fn section_header(input: &str, start: usize) -> Result<(usize, Section), ParserError<&str>> {
+    let name_start = start + 1;
+    let name_end = input[name_start..]
+        .find(|c: char| !c.is_ascii_alphanumeric() && !"-.".contains(c))
+        .map_or(input.len(), |end| name_start + end);
+    let name = &input[name_start..name_end];
+    if name.is_empty() || name.starts_with('.') || name.ends_with('.') {
+        return Err(invalid(name_start, "invalid section name"));
+    }
+    let after = name_end + blanks(&input[name_end..]);
+    match input.as_bytes().get(after) {
+        Some(b']') => {
+            let header = match name.split_once('.') {
+                Some((section, subsection)) => {
+                    (section.to_string(), Some(subsection.to_ascii_lowercase()))
+                }
+                None => (name.to_string(), None),
+            };
+            Ok((after + 1, header))
+        }
+        Some(b'"') if !name.contains('.') => {
+            let (end, subsection) = subsection(input, after + 1)?;
+            match input.as_bytes().get(end) {
+                Some(b']') => Ok((end + 1, (name.to_string(), Some(subsection)))),
+                _ => Err(invalid(end, "expected ] after the subsection")),
+            }
+        }
+        _ => Err(invalid(name_end, "expected ] after the section name")),
+    }
+}
+
+/// The quoted subsection of a header up to its closing quote, where a backslash makes the
+/// next character literal
+fn subsection(input: &str, start: usize) -> Result<(usize, String), ParserError<&str>> {
+    let mut subsection = String::new();
+    let mut chars = input[start..].char_indices();
+    while let Some((offset, c)) = chars.next() {
+        match c {
+            '"' => return Ok((start + offset + 1, subsection)),
+            '\n' => break,
+            '\\' => match chars.next() {
+                Some((_, '\n')) | None => break,
+                Some((_, escaped)) => subsection.push(escaped),
+            },
+            _ => subsection.push(c),
+        }
+    }
+    Err(invalid(start - 1, "unterminated subsection"))
+}
+
+/// `key`, `key = value` or `key = value # comment`, up to the end of its line
+fn variable(input: &str, start: usize) -> Result<(usize, &str, Option<String>), ParserError<&str>> {
+    let key_end = input[start..]
+        .find(|c: char| !c.is_ascii_alphanumeric() && c != '-')
+        .map_or(input.len(), |end| start + end);
+    let key = &input[start..key_end];
+    if !key.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        return Err(invalid(start, "invalid key"));
+    }
+    let after = key_end + blanks(&input[key_end..]);
+    match input.as_bytes().get(after) {
+        None | Some(b'\r' | b'\n' | b'#' | b';') => Ok((line_end(input, after), key, None)),
+        Some(b'=') => {
+            let (end, value) = value(input, after + 1)?;
+            Ok((end, key, Some(value)))
+        }
+        _ => Err(invalid(after, "expected = after the key")),
+    }
+}
+
+/// A value up to the end of its line, or of the last line it continues on. Spaces around it
+/// are removed, those between its words and in its quotes are kept
+fn value(input: &str, start: usize) -> Result<(usize, String), ParserError<&str>> {
+    let start = start + blanks(&input[start..]);
+    let mut value = String::new();
+    // Spaces kept unless the value ends after them
+    let mut pending = String::new();
+    let mut quote = None;
+    let mut chars = input[start..].char_indices().peekable();
+    while let Some((offset, c)) = chars.next() {
+        let index = start + offset;
+        match c {
+            '\r' if chars.peek().is_some_and(|(_, next)| *next == '\n') => {}
+            '\n' if quote.is_some() => break,
+            '\n' => return Ok((index + 1, value)),
+            '#' | ';' if quote.is_none() => return Ok((line_end(input, index), value)),
+            ' ' | '\t' if quote.is_none() => pending.push(c),
+            '"' => {
+                value.push_str(&pending);
+                pending.clear();
+                quote = match quote {
+                    Some(_) => None,
+                    None => Some(index),
+                };
+            }
+            '\\' => {
+                let escaped = match chars.next() {
+                    Some((_, '\n')) => continue,
+                    Some((_, '\r')) if chars.next_if(|(_, next)| *next == '\n').is_some() => {
+                        continue
+                    }
+                    Some((_, 'n')) => '\n',
+                    Some((_, 't')) => '\t',
+                    Some((_, 'b')) => '\u{8}',
+                    Some((_, escaped @ ('"' | '\\'))) => escaped,
+                    _ => return Err(invalid(index, "invalid escape")),
+                };
+                value.push_str(&pending);
+                pending.clear();
+                value.push(escaped);
+            }
+            _ => {
+                value.push_str(&pending);
+                pending.clear();
+                value.push(c);
+            }
+        }
+    }
+    match quote {
+        Some(quote) => Err(invalid(quote, "unterminated quote")),
+        None => Ok((input.len(), value)),
+    }
+}
+
+fn components(path: &str) -> Vec<&str> {
+    path.split('/')
+        .filter(|component| !component.is_empty())
+        .collect()
+}
+
+fn match_segments(segments: &[Segment], components: &[&str]) -> bool {
+    match segments.split_first() {
+        None => components.is_empty(),
+        Some((Segment::AnyDirectories, [])) => !components.is_empty(),
+        Some((Segment::AnyDirectories, rest)) => {
+            (0..=components.len()).any(|skipped| match_segments(rest, &components[skipped..]))
+        }
+        Some((Segment::Glob(tokens), rest)) => {
+            components.split_first().is_some_and(|(component, others)| {
+                match_glob(tokens, component) && match_segments(rest, others)
+            })
+        }
+    }
+}
+
+fn match_glob(tokens: &[Token], text: &str) -> bool {
+    let Some((token, rest)) = tokens.split_first() else {
+        return text.is_empty();
+    };
+    let mut chars = text.chars();
+    match token {
+        Token::AnyChars => text
+            .char_indices()
+            .map(|(index, _)| index)
+            .chain([text.len()])
+            .any(|index| match_glob(rest, &text[index..])),
+        Token::Literal(literal) => text
+            .strip_prefix(literal.as_str())
+            .is_some_and(|text| match_glob(rest, text)),
+        Token::AnyChar => chars.next().is_some() && match_glob(rest, chars.as_str()),
+        Token::Class { negated, ranges } => {
+            chars.next().is_some_and(|c| {
+                ranges.iter().any(|(low, high)| (*low..=*high).contains(&c)) != *negated
+            }) && match_glob(rest, chars.as_str())
+        }
+    }
+}
+
+/// The tokens of a segment of a pattern
+fn tokens(segment: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut chars = segment.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                if let Some(escaped) = chars.next() {
+                    literal(&mut tokens, escaped);
+                }
+            }
+            '*' if tokens.last() == Some(&Token::AnyChars) => {}
+            '*' => tokens.push(Token::AnyChars),
+            '?' => tokens.push(Token::AnyChar),
+            '[' => match class(chars.as_str()) {
+                Some((rest, class)) => {
+                    tokens.push(class);
+                    chars = rest.chars();
+                }
+                None => literal(&mut tokens, '['),
+            },
+            _ => literal(&mut tokens, c),
+        }
+    }
+    tokens
+}
+
+/// A class after its `[`, up to its `]`. A `]` first in the class and a `-` at its ends are
+/// literal
+fn class(input: &str) -> Option<(&str, Token)> {
+    let (negated, input) = match input.strip_prefix(['!', '^']) {
+        Some(input) => (true, input),
+        None => (false, input),
+    };
+    let mut ranges = Vec::new();
+    let mut chars = input.chars();
+    let mut first = true;
+    loop {
+        let low = match chars.next()? {
+            ']' if !first => return Some((chars.as_str(), Token::Class { negated, ranges })),
+            '\\' => chars.next()?,
+            c => c,
+        };
+        first = false;
+        let high = match chars.as_str().strip_prefix('-') {
+            Some(after) if !after.is_empty() && !after.starts_with(']') => {
+                chars = after.chars();
+                match chars.next()? {
+                    '\\' => chars.next()?,
+                    c => c,
+                }
+            }
+            _ => low,
+        };
+        ranges.push((low, high));
+    }
+}
+
+/// Appends a character to the literal ending `tokens`
+fn literal(tokens: &mut Vec<Token>, c: char) {
+    match tokens.last_mut() {
+        Some(Token::Literal(text)) => text.push(c),
+        _ => tokens.push(Token::Literal(c.to_string())),
+    }
+}
@@ -0,0 +1,381 @@
+use alloc::{
+    collections::{BTreeMap, BTreeSet},
+    format,
+    rc::Rc,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use core::cell::RefCell;
+
+use super::{
+    errors::{ErrorSource, ParserError},
+    traits::{BoxedParser, Input, ParseResult, Parser},
+};
+
+/// A set of named rules producing the same output type, which refer to each other with
+/// [`Grammar::call`]. References are resolved when parsing, so rules may be registered in
+/// any order and be mutually recursive. Clones share the same rules
+///
+/// # Examples
+/// ```rust
+///
+/// use pepser::parser::grammar::Grammar;
+/// use pepser::parser::impls::{sequence, take_while1};
+/// use pepser::parser::traits::{sep_by, wrapped, Parser};
+/// let grammar = Grammar::new();
+/// let list = grammar.clone();
+/// grammar
+///     .rule("value", move || list.call("number").or(list.call("list")))
+///     .rule("number", || {
+///         take_while1(|c| c.is_ascii_digit()).map(|digits: &str| digits.len())
+///     });
+/// let values = grammar.clone();
+/// grammar.rule("list", move || {
+///     wrapped(sequence("["), sep_by(values.call("value"), sequence(",")), sequence("]"))
+///         .map(|lengths| lengths.into_iter().sum())
+/// });
+///
+/// assert_eq!(grammar.parse("value", "[1,[22,333]]"), Ok(("", 6)));
+/// assert_eq!(grammar.reachable("list"), vec!["list", "value", "number"]);
+///
+///
+/// ```
+pub struct Grammar<'a, I: Input, O> {
+    rules: Rc<RefCell<Vec<Rule<'a, I, O>>>>,
+    /// The rule whose first instance is being built, its calls are recorded as references
+    building: Rc<RefCell<Option<usize>>>,
+    memo: Rc<RefCell<Memo<I, O>>>,
+}
+
+/// The results of the rules during a parse, by rule index, address and length of the input
+struct Memo<I: Input, O> {
+    /// Set by [`Grammar::memoize`], which requires outputs to be cloned
+    clone: Option<fn(&O) -> O>,
+    /// Number of calls being parsed, the results are dropped when the outermost one returns
+    depth: usize,
+    results: BTreeMap<(usize, usize, usize), Entry<I, O>>,
+}
+
+enum Entry<I: Input, O> {
+    /// The rule is being parsed at this position, `recursed` once it called itself there
+    Parsing {
+        recursed: bool,
+    },
+    Done(ParseResult<I, O>),
+}
+
+fn copy<I: Input, O>(result: &ParseResult<I, O>, clone: fn(&O) -> O) -> ParseResult<I, O> {
+    match result {
+        Ok((remainder, output)) => Ok((remainder.clone(), clone(output))),
+        Err(error) => Err(error.clone()),
+    }
+}
+
+type Factory<'a, I, O> = Rc<dyn Fn() -> BoxedParser<'a, I, O> + 'a>;
+
+struct Rule<'a, I, O> {
+    name: String,
+    factory: Factory<'a, I, O>,
+    /// Instances not currently parsing, a recursive call builds a new one
+    idle: Vec<BoxedParser<'a, I, O>>,
+    references: Vec<String>,
+}
+
+/// A reference to a rule of a [`Grammar`], looked up each time it parses
+pub struct Call<'a, I: Input, O> {
+    grammar: Grammar<'a, I, O>,
+    name: String,
+}
+
+impl<'a, I: Input, O> Clone for Grammar<'a, I, O> {
+    fn clone(&self) -> Self {
+        Grammar {
+            rules: self.rules.clone(),
+            building: self.building.clone(),
+            memo: self.memo.clone(),
+        }
+    }
+}
+
+impl<'a, I: Input, O> Default for Grammar<'a, I, O> {
+    fn default() -> Self {
+        Grammar {
+            rules: Rc::new(RefCell::new(vec![])),
+            building: Rc::new(RefCell::new(None)),
+            memo: Rc::new(RefCell::new(Memo {
+                clone: None,
+                depth: 0,
+                results: BTreeMap::new(),
+            })),
+        }
+    }
+}
+
+impl<'a, I, O> Grammar<'a, I, O>
+where
+    I: Input + 'a,
+    O: 'a,
+{
+    pub fn new() -> Self {
+        Grammar::default()
+    }
+
+    /// Registers a rule, replacing any rule with the same name. `factory` builds the parser of
+    /// the rule, it is called again when the rule is entered recursively. The calls made while
+    /// it runs are the references of the rule used by [`Grammar::reachable`]
+    pub fn rule<F, P>(&self, name: &str, factory: F) -> &Self
+    where
+        F: Fn() -> P + 'a,
+        P: Parser<I, Output = O> + 'a,
+    {
+        let factory: Factory<'a, I, O> = Rc::new(move || factory().boxed());
+        let index = {
+            let mut rules = self.rules.borrow_mut();
+            let rule = Rule {
+                name: name.to_string(),
+                factory: factory.clone(),
+                idle: vec![],
+                references: vec![],
+            };
+            match rules.iter().position(|rule| rule.name == name) {
+                Some(index) => {
+                    rules[index] = rule;
+                    index
+                }
+                None => {
+                    rules.push(rule);
+                    rules.len() - 1
+                }
+            }
+        };
+        let previous = self.building.replace(Some(index));
+        let parser = factory();
+        self.building.replace(previous);
+        self.rules.borrow_mut()[index].idle.push(parser);
+        self
+    }
+
+    /// Returns a parser running the rule with the given name, failing if it is not registered
+    pub fn call(&self, name: &str) -> Call<'a, I, O> {
+        if let Some(index) = *self.building.borrow() {
+            let mut rules = self.rules.borrow_mut();
+            let references = &mut rules[index].references;
+            if !references.iter().any(|reference| reference == name) {
+                references.push(name.to_string());
+            }
+        }
+        Call {
+            grammar: self.clone(),
+            name: name.to_string(),
+        }
+    }
+
+    /// Turns on packrat parsing: the result of each rule at each position of the input is
+    /// kept until the outermost call returns, so that backtracking alternatives which enter
+    /// the same rule at the same position reuse it. This bounds the work of the grammar by
+    /// the number of rules times the length of the input, at the cost of memory. Rules must not
+    /// depend on anything but their input, such as the state of a `Stateful` input, and texts
+    /// they build and parse, such as unescaped strings, must outlive the outermost call.
+    ///
+    /// Memoized rules may also be directly left recursive, like `sum = sum "+" number | number`:
+    /// a rule calling itself at the position it started from fails at first, then the rule is
+    /// parsed again with that call getting the previous result, for as long as it consumes more.
+    /// Left recursion through other rules is not supported
+    ///
+    /// ```rust
+    ///
+    /// use pepser::parser::grammar::Grammar;
+    /// use pepser::parser::impls::{sequence, take_while1};
+    /// use pepser::parser::traits::Parser;
+    /// let grammar = Grammar::new();
+    /// let g = grammar.clone();
+    /// grammar
+    ///     .rule("difference", move || {
+    ///         g.call("difference")
+    ///             .skip(sequence("-"))
+    ///             .and(g.call("number"))
+    ///             .map(|(a, b)| a - b)
+    ///             .or(g.call("number"))
+    ///     })
+    ///     .rule("number", || {
+    ///         take_while1(|c| c.is_ascii_digit()).map(|n: &str| n.parse().unwrap())
+    ///     });
+    /// grammar.memoize();
+    ///
+    /// assert_eq!(grammar.parse("difference", "10-2-3"), Ok(("", 5)));
+    ///
+    ///
+    /// ```
+    ///
+    /// # Examples
+    /// ```rust
+    ///
+    /// use std::cell::Cell;
+    /// use pepser::parser::grammar::Grammar;
+    /// use pepser::parser::impls::sequence;
+    /// use pepser::parser::traits::Parser;
+    /// let calls = Cell::new(0);
+    /// let grammar = Grammar::new();
+    /// let g = grammar.clone();
+    /// grammar
+    ///     .rule("word", || sequence("ab").inspect_input(|_| calls.set(calls.get() + 1)))
+    ///     .rule("line", move || {
+    ///         g.call("word").skip(sequence("!")).or(g.call("word").skip(sequence("?")))
+    ///     });
+    ///
+    /// assert_eq!(grammar.parse("line", "ab?"), Ok(("", "ab")));
+    /// assert_eq!(calls.get(), 2);
+    /// grammar.memoize();
+    /// assert_eq!(grammar.parse("line", "ab?"), Ok(("", "ab")));
+    /// assert_eq!(calls.get(), 3);
+    ///
+    ///
+    /// ```
+    pub fn memoize(&self) -> &Self
+    where
+        O: Clone,
+    {
+        self.memo.borrow_mut().clone = Some(O::clone);
+        self
+    }
+
+    /// Parses the input with the given rule
+    pub fn parse(&self, rule: &str, input: I) -> ParseResult<I, O> {
+        self.call(rule).parse(input)
+    }
+
+    /// Names of the registered rules, in registration order
+    pub fn rules(&self) -> Vec<String> {
+        self.rules
+            .borrow()
+            .iter()
+            .map(|rule| rule.name.clone())
+            .collect()
+    }
+
+    /// Rules called directly by the given rule
+    pub fn references(&self, rule: &str) -> Vec<String> {
+        self.rules
+            .borrow()
+            .iter()
+            .find(|registered| registered.name == rule)
+            .map(|registered| registered.references.clone())
+            .unwrap_or_default()
+    }
+
+    /// Rules which can be entered from `start`, including itself, in breadth first order
+    pub fn reachable(&self, start: &str) -> Vec<String> {
+        let mut found = vec![start.to_string()];
+        let mut position = 0;
+        while position < found.len() {
+            for reference in self.references(&found[position]) {
+                if !found.contains(&reference) {
+                    found.push(reference);
+                }
+            }
+            position += 1;
+        }
+        found
+    }
+
+    /// Rules which are called but not registered
+    pub fn undefined(&self) -> Vec<String> {
+        let rules = self.rules.borrow();
+        let defined: BTreeSet<&str> = rules.iter().map(|rule| rule.name.as_str()).collect();
+        let mut undefined: Vec<String> = vec![];
+        for reference in rules.iter().flat_map(|rule| &rule.references) {
+            if !defined.contains(reference.as_str()) && !undefined.contains(reference) {
+                undefined.push(reference.clone());
+            }
+        }
+        undefined
+    }
+}
+
+impl<'a, I, O> Parser<I> for Call<'a, I, O>
+where
+    I: Input + 'a,
+    O: 'a,
+{
+    type Output = O;
+
+    fn parse(&mut self, input: I) -> ParseResult<I, O> {
+        let Some(index) = self
+            .grammar
+            .rules
+            .borrow()
+            .iter()
+            .position(|rule| rule.name == self.name)
+        else {
+            return Err(ParserError::new(
+                0,
+                ErrorSource::Rule,
+                format!("undefined rule {}", self.name),
+            ));
+        };
+        let key = (index, input.address(), input.input_len());
+        let clone = {
+            let mut memo = self.grammar.memo.borrow_mut();
+            if let Some(clone) = memo.clone {
+                match memo.results.get_mut(&key) {
+                    Some(Entry::Done(result)) => return copy(result, clone),
+                    Some(Entry::Parsing { recursed }) => {
+                        *recursed = true;
+                        return Err(ParserError::new(0, ErrorSource::Rule, "left recursion"));
+                    }
+                    None => {
+                        memo.results.insert(key, Entry::Parsing { recursed: false });
+                    }
+                }
+            }
+            memo.depth += 1;
+            memo.clone
+        };
+        let (factory, idle) = {
+            let rule = &mut self.grammar.rules.borrow_mut()[index];
+            (rule.factory.clone(), rule.idle.pop())
+        };
+        // the rules must not stay borrowed while parsing, as the rule may call itself
+        let mut parser = idle.unwrap_or_else(|| factory());
+        #[cfg(feature = "std")]
+        let _scope = super::impls::RuleScope::enter(&self.name);
+        let mut result = parser.parse(input.clone());
+        if let Some(clone) = clone {
+            let recursed = matches!(
+                self.grammar.memo.borrow().results.get(&key),
+                Some(Entry::Parsing { recursed: true })
+            );
+            // grows the seed: the left recursive call gets the last result, until parsing
+            // again fails or consumes no more
+            while let (true, Ok((remainder, _))) = (recursed, &result) {
+                let length = remainder.input_len();
+                self.grammar
+                    .memo
+                    .borrow_mut()
+                    .results
+                    .insert(key, Entry::Done(copy(&result, clone)));
+                match parser.parse(input.clone()) {
+                    Ok(grown) if grown.0.input_len() < length => result = Ok(grown),
+                    _ => break,
+                }
+            }
+        }
+        if let Some(rule) = self.grammar.rules.borrow_mut().get_mut(index) {
+            // the rule may have been replaced while parsing
+            if Rc::ptr_eq(&rule.factory, &factory) {
+                rule.idle.push(parser);
+            }
+        }
+        let mut memo = self.grammar.memo.borrow_mut();
+        memo.depth -= 1;
+        match (memo.depth, clone) {
+            (0, _) => memo.results.clear(),
+            (_, Some(clone)) => {
+                memo.results.insert(key, Entry::Done(copy(&result, clone)));
+            }
+            (_, None) => {}
+        }
+        result
+    }
+}
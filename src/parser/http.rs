@@ -0,0 +1,194 @@
+pub mod cookie;
+pub mod media;
+
+use super::{
+    errors::{ErrorSource, ParserError},
+    impls::{any, fixed_digits, none_of, sequence, take_while1},
+    traits::{discard, opt, parse_rest, wrapped, ParseResult, Parser},
+};
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct HttpVersion {
+    pub major: u8,
+    pub minor: u8,
+}
+
+/// Header fields in the order they were received. Lookups ignore the ASCII case of names
+#[derive(Debug, PartialEq, Default)]
+pub struct Headers(pub Vec<(String, String)>);
+
+#[derive(Debug, PartialEq)]
+pub struct Request {
+    pub method: String,
+    pub target: String,
+    pub version: HttpVersion,
+    pub headers: Headers,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Response {
+    pub version: HttpVersion,
+    pub status: u16,
+    pub reason: String,
+    pub headers: Headers,
+}
+
+impl Headers {
+    /// Returns the value of the first header with the given name
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.0
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Returns the values of every header with the given name
+    pub fn get_all<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a str> {
+        self.0
+            .iter()
+            .filter(move |(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// Parses a request line followed by its headers and the empty line ending the head.
+/// The remainder is the message body
+pub fn http_request(input: &str) -> ParseResult<&str, Request> {
+    request_line
+        .and(headers)
+        .map(|((method, target, version), headers)| Request {
+            method: method.to_string(),
+            target: target.to_string(),
+            version,
+            headers,
+        })
+        .parse(input)
+}
+
+/// Parses a status line followed by its headers and the empty line ending the head.
+/// The remainder is the message body
+pub fn http_response(input: &str) -> ParseResult<&str, Response> {
+    status_line
+        .and(headers)
+        .map(|((version, status, reason), headers)| Response {
+            version,
+            status,
+            reason: reason.to_string(),
+            headers,
+        })
+        .parse(input)
+}
+
+pub fn request_line(input: &str) -> ParseResult<&str, (&str, &str, HttpVersion)> {
+    let (rest, method) = token(input)?;
+    let mut target = discard(sequence(" "), take_while1(|c| c != ' '));
+    let (rest, target) = parse_rest(&mut target, &input, rest)?;
+    let mut version = wrapped(sequence(" "), http_version, line_end);
+    let (rest, version) = parse_rest(&mut version, &input, rest)?;
+    Ok((rest, (method, target, version)))
+}
+
+pub fn status_line(input: &str) -> ParseResult<&str, (HttpVersion, u16, &str)> {
+    let (rest, version) = http_version(input)?;
+    let mut status = discard(sequence(" "), fixed_digits(3));
+    let (rest, status) = parse_rest(&mut status, &input, rest)?;
+    let mut reason = wrapped(
+        opt(sequence(" ")),
+        opt(none_of("\r\n")).map(Option::unwrap_or_default),
+        line_end,
+    );
+    let (rest, reason) = parse_rest(&mut reason, &input, rest)?;
+    Ok((rest, (version, status as u16, reason)))
+}
+
+pub fn http_version(input: &str) -> ParseResult<&str, HttpVersion> {
+    discard(sequence("HTTP/"), fixed_digits(1))
+        .and(discard(sequence("."), fixed_digits(1)))
+        .map(|(major, minor)| HttpVersion {
+            major: major as u8,
+            minor: minor as u8,
+        })
+        .parse(input)
+}
+
+/// Parses header fields up to and including the empty line ending the head.
+/// Obsolete line folding is supported: continuation lines starting with a space or a tab
+/// are appended to the previous value, separated by a single space. A line which is neither
+/// a header nor the empty line fails with the error of the header
+pub fn headers(input: &str) -> ParseResult<&str, Headers> {
+    let mut fields = vec![];
+    let mut rest = input;
+    loop {
+        if let Ok((rest, _)) = line_end(rest) {
+            return Ok((rest, Headers(fields)));
+        }
+        let (next, field) = parse_rest(&mut header, &input, rest)?;
+        fields.push(field);
+        rest = next;
+    }
+}
+
+pub fn header(input: &str) -> ParseResult<&str, (String, String)> {
+    let (rest, name) = token(input)?;
+    let mut first = wrapped(
+        discard(sequence(":"), opt(any(" \t"))),
+        field_content,
+        line_end,
+    );
+    let (rest, first) = parse_rest(&mut first, &input, rest)?;
+    let mut continuations = wrapped(any(" \t"), field_content, line_end).many0();
+    let (rest, continuations) = parse_rest(&mut continuations, &input, rest)?;
+
+    let value = continuations
+        .into_iter()
+        .filter(|line| !line.is_empty())
+        .fold(first.to_string(), |mut value, line| {
+            if !value.is_empty() {
+                value.push(' ');
+            }
+            value.push_str(line);
+            value
+        });
+    Ok((rest, (name.to_string(), value)))
+}
+
+/// Parses the size line of a chunk in a chunked transfer-encoded body, ignoring chunk
+/// extensions
+pub fn chunk_size(input: &str) -> ParseResult<&str, usize> {
+    let (remainder, size) = take_while1(|c| c.is_ascii_hexdigit()).parse(input)?;
+    let mut extensions = discard(opt(discard(sequence(";"), opt(none_of("\r\n")))), line_end);
+    let (remainder, _) = parse_rest(&mut extensions, &input, remainder)?;
+    usize::from_str_radix(size, 16)
+        .map(|size| (remainder, size))
+        .map_err(|_| ParserError::new(0, ErrorSource::TakeWhile, "chunk size overflow"))
+}
+
+/// Runs a text parser over the UTF-8 prefix of a byte buffer, for heads received on a socket
+/// whose body may not be valid UTF-8. The remainder is returned as bytes
+pub fn parse_bytes<'a, O, P>(mut parser: P, input: &'a [u8]) -> ParseResult<&'a [u8], O>
+where
+    P: Parser<&'a str, Output = O>,
+{
+    let text = match std::str::from_utf8(input) {
+        Ok(text) => text,
+        Err(error) => std::str::from_utf8(&input[..error.valid_up_to()]).unwrap_or_default(),
+    };
+    let (remainder, output) = parser
+        .parse(text)
+        .map_err(|error| error.map_input(str::as_bytes))?;
+    Ok((&input[text.len() - remainder.len()..], output))
+}
+
+fn token(input: &str) -> ParseResult<&str, &str> {
+    take_while1(|c| c.is_ascii_alphanumeric() || "!#$%&'*+-.^_`|~".contains(c)).parse(input)
+}
+
+fn field_content(input: &str) -> ParseResult<&str, &str> {
+    opt(none_of("\r\n"))
+        .map(|content| content.unwrap_or_default().trim_end_matches([' ', '\t']))
+        .parse(input)
+}
+
+fn line_end(input: &str) -> ParseResult<&str, &str> {
+    sequence("\r\n").or(sequence("\n")).parse(input)
+}
@@ -0,0 +1,286 @@
+use crate::parser::{
+    datetime::{days_in_month, time, Date, DateTime, UtcOffset},
+    errors::{ErrorSource, ParserError},
+    impls::{fixed_digits, take_while1},
+    traits::{parse_rest, ParseResult, Parser},
+};
+
+const WEEKDAYS: [&str; 7] = [
+    "Monday",
+    "Tuesday",
+    "Wednesday",
+    "Thursday",
+    "Friday",
+    "Saturday",
+    "Sunday",
+];
+
+const MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum SameSite {
+    Strict,
+    Lax,
+    None,
+}
+
+/// A cookie set by a `Set-Cookie` header. When both are present, `max_age` takes precedence
+/// over `expires`
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct SetCookie {
+    pub name: String,
+    pub value: String,
+    /// The `Expires` date, in UTC
+    pub expires: Option<DateTime>,
+    /// The `Max-Age` in seconds, zero or less expires the cookie at once
+    pub max_age: Option<i64>,
+    /// The `Domain` in lowercase, without a leading dot
+    pub domain: Option<String>,
+    /// The `Path`, values which do not start with `/` are left out
+    pub path: Option<String>,
+    pub secure: bool,
+    pub http_only: bool,
+    pub same_site: Option<SameSite>,
+}
+
+/// Parses the value of a `Cookie` header, `name=value` pairs separated by `;`. Values lose
+/// the double quotes around them. Error indices are offsets in the input
+///
+/// # Examples
+/// ```rust
+///
+/// use pepser::parser::http::cookie::cookies;
+/// let (_, pairs) = cookies("session=abc123; theme=\"dark\"").unwrap();
+///
+/// assert_eq!(pairs[0], ("session".to_string(), "abc123".to_string()));
+/// assert_eq!(pairs[1], ("theme".to_string(), "dark".to_string()));
+/// assert_eq!(cookies("a=1; b").unwrap_err().index, 5);
+///
+///
+/// ```
+pub fn cookies(input: &str) -> ParseResult<&str, Vec<(String, String)>> {
+    let pairs = fields(input)
+        .filter(|(_, field)| !field.is_empty())
+        .map(|(offset, field)| pair(offset, field))
+        .collect::<Result<_, _>>()?;
+    Ok(("", pairs))
+}
+
+/// Parses the value of a `Set-Cookie` header, a `name=value` pair followed by `;` separated
+/// attributes. Attribute names ignore their case and unknown ones are skipped, but an
+/// invalid `Expires`, `Max-Age` or `SameSite` value is an error. Error indices are offsets in
+/// the input
+///
+/// # Examples
+/// ```rust
+///
+/// use pepser::parser::http::cookie::{set_cookie, SameSite};
+/// let (_, cookie) = set_cookie(
+///     "id=a3fWa; Expires=Wed, 21 Oct 2015 07:28:00 GMT; Max-Age=3600; \
+///     Domain=.Example.com; Path=/docs; Secure; HttpOnly; SameSite=Lax",
+/// )
+/// .unwrap();
+///
+/// assert_eq!((cookie.name.as_str(), cookie.value.as_str()), ("id", "a3fWa"));
+/// assert_eq!(cookie.expires.unwrap().date.year, 2015);
+/// assert_eq!(cookie.max_age, Some(3600));
+/// assert_eq!(cookie.domain.as_deref(), Some("example.com"));
+/// assert!(cookie.secure && cookie.http_only);
+/// assert_eq!(cookie.same_site, Some(SameSite::Lax));
+/// assert_eq!(set_cookie("id=1; Max-Age=soon").unwrap_err().index, 14);
+///
+///
+/// ```
+pub fn set_cookie(input: &str) -> ParseResult<&str, SetCookie> {
+    let mut fields = fields(input);
+    let (offset, first) = fields.next().unwrap_or_default();
+    let (name, value) = pair(offset, first)?;
+    let mut cookie = SetCookie {
+        name,
+        value,
+        ..SetCookie::default()
+    };
+    for (offset, field) in fields.filter(|(_, field)| !field.is_empty()) {
+        let (name, value) = match field.split_once('=') {
+            Some((name, value)) => {
+                let trimmed = value.trim_start_matches([' ', '\t']);
+                let value_offset = offset + field.len() - trimmed.len();
+                (
+                    name.trim_end_matches([' ', '\t']),
+                    Some((value_offset, trimmed)),
+                )
+            }
+            None => (field, None),
+        };
+        let required = || value.ok_or_else(|| invalid(offset, "expected a value"));
+        match name.to_ascii_lowercase().as_str() {
+            "expires" => {
+                let (offset, value) = required()?;
+                match http_date(value) {
+                    Ok(("", date)) => cookie.expires = Some(date),
+                    Ok((rest, _)) => {
+                        return Err(invalid(offset + value.len() - rest.len(), "invalid date"))
+                    }
+                    Err(error) => return Err(ParserError::from_error(error, offset)),
+                }
+            }
+            "max-age" => {
+                let (offset, value) = required()?;
+                cookie.max_age = Some(
+                    value
+                        .strip_prefix('-')
+                        .unwrap_or(value)
+                        .starts_with(|c: char| c.is_ascii_digit())
+                        .then(|| value.parse().ok())
+                        .flatten()
+                        .ok_or_else(|| invalid(offset, "invalid max-age"))?,
+                );
+            }
+            "domain" => {
+                let domain = value.map(|(_, domain)| domain.trim_start_matches('.'));
+                cookie.domain = domain
+                    .filter(|domain| !domain.is_empty())
+                    .map(str::to_ascii_lowercase);
+            }
+            "path" => {
+                cookie.path = value
+                    .map(|(_, path)| path)
+                    .filter(|path| path.starts_with('/'))
+                    .map(String::from);
+            }
+            "secure" => cookie.secure = true,
+            "httponly" => cookie.http_only = true,
+            "samesite" => {
+                let (offset, value) = required()?;
+                cookie.same_site = Some(match value.to_ascii_lowercase().as_str() {
+                    "strict" => SameSite::Strict,
+                    "lax" => SameSite::Lax,
+                    "none" => SameSite::None,
+                    _ => return Err(invalid(offset, "invalid samesite")),
+                });
+            }
+            _ => {}
+        }
+    }
+    Ok(("", cookie))
+}
+
+/// Parses an HTTP date in the IMF-fixdate format, `Sun, 06 Nov 1994 08:49:37 GMT`. As cookies
+/// do, the weekday may be written in full, dashes may separate the day, the month and the year,
+/// and two digit years are from 1970 to 2069. The weekday is not checked against the date
+///
+/// # Examples
+/// ```rust
+///
+/// use pepser::parser::http::cookie::http_date;
+/// let (_, date) = http_date("Sunday, 06-Nov-94 08:49:37 GMT").unwrap();
+///
+/// assert_eq!((date.date.year, date.date.month, date.date.day), (1994, 11, 6));
+/// assert_eq!(date.offset.unwrap().minutes, 0);
+/// assert_eq!(http_date("Sun, 31 Feb 1994 08:49:37 GMT").unwrap_err().index, 5);
+///
+///
+/// ```
+pub fn http_date(input: &str) -> ParseResult<&str, DateTime> {
+    let (rest, weekday) = take_while1(|c| c.is_ascii_alphabetic()).parse(input)?;
+    if !WEEKDAYS
+        .iter()
+        .any(|day| weekday.eq_ignore_ascii_case(day) || weekday.eq_ignore_ascii_case(&day[..3]))
+    {
+        return Err(invalid(0, "invalid weekday"));
+    }
+    let rest = rest
+        .strip_prefix(", ")
+        .ok_or_else(|| invalid(weekday.len(), "expected a comma after the weekday"))?;
+    let day_start = input.len() - rest.len();
+    let (rest, day) = parse_rest(&mut fixed_digits(2), &input, rest)?;
+    let rest = separator(input, rest)?;
+
+    let month = rest
+        .get(..3)
+        .and_then(|month| {
+            MONTHS
+                .iter()
+                .position(|name| month.eq_ignore_ascii_case(name))
+        })
+        .ok_or_else(|| invalid(input.len() - rest.len(), "invalid month"))?;
+    let rest = separator(input, &rest[3..])?;
+
+    let year_start = input.len() - rest.len();
+    let (rest, year) = parse_rest(&mut take_while1(|c| c.is_ascii_digit()), &input, rest)?;
+    let year = match (year.len(), year.parse::<u16>()) {
+        (4, Ok(year)) => year,
+        (2, Ok(year)) if year >= 70 => 1900 + year,
+        (2, Ok(year)) => 2000 + year,
+        _ => return Err(invalid(year_start, "invalid year")),
+    };
+    let rest = rest
+        .strip_prefix(' ')
+        .ok_or_else(|| invalid(input.len() - rest.len(), "expected a space before the time"))?;
+    let (rest, time) = parse_rest(&mut time, &input, rest)?;
+    let rest = rest
+        .strip_prefix(" GMT")
+        .ok_or_else(|| invalid(input.len() - rest.len(), "expected GMT"))?;
+
+    let month = month as u8 + 1;
+    if day == 0 || day > days_in_month(year, month).into() {
+        return Err(invalid(day_start, "day is out of range"));
+    }
+    let date = Date {
+        year,
+        month,
+        day: day as u8,
+    };
+    Ok((
+        rest,
+        DateTime {
+            date,
+            time,
+            offset: Some(UtcOffset { minutes: 0 }),
+        },
+    ))
+}
+
+fn invalid<'a>(index: usize, reason: &'static str) -> ParserError<&'a str> {
+    ParserError::new(index, ErrorSource::TakeWhile, reason)
+}
+
+/// Skips the space or the dash separating the parts of a date
+fn separator<'a>(input: &'a str, rest: &'a str) -> Result<&'a str, ParserError<&'a str>> {
+    rest.strip_prefix([' ', '-'])
+        .ok_or_else(|| invalid(input.len() - rest.len(), "expected a space or a dash"))
+}
+
+/// The `;` separated fields of a header value without the spaces and tabs around them, with
+/// the offset where each starts
+fn fields(input: &str) -> impl Iterator<Item = (usize, &str)> {
+    let mut start = 0;
+    input.split(';').map(move |field| {
+        let trimmed = field.trim_start_matches([' ', '\t']);
+        let offset = start + field.len() - trimmed.len();
+        start += field.len() + 1;
+        (offset, trimmed.trim_end_matches([' ', '\t']))
+    })
+}
+
+/// A `name=value` pair starting at `offset`, whose name must be a token
+fn pair<'a>(offset: usize, field: &str) -> Result<(String, String), ParserError<&'a str>> {
+    let Some((name, value)) = field.split_once('=') else {
+        return Err(invalid(offset, "expected = in the cookie"));
+    };
+    let name = name.trim_end_matches([' ', '\t']);
+    if !matches!(super::token(name), Ok(("", _))) {
+        return Err(invalid(offset, "invalid cookie name"));
+    }
+    let value = value.trim_start_matches([' ', '\t']);
+    let value = match value
+        .strip_prefix('"')
+        .and_then(|value| value.strip_suffix('"'))
+    {
+        Some(unquoted) => unquoted,
+        None => value,
+    };
+    Ok((name.to_string(), value.to_string()))
+}
@@ -0,0 +1,314 @@
+use std::fmt;
+
+use crate::parser::{
+    errors::{ErrorSource, ParserError},
+    traits::ParseResult,
+};
+
+/// A media type such as `text/html; charset=utf-8`. The type, the subtype and the parameter
+/// names are lowercase, parameter values are kept as written without their quotes
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct MediaType {
+    pub kind: String,
+    pub subtype: String,
+    pub parameters: Vec<(String, String)>,
+}
+
+/// A media range of an `Accept` header with its quality, in thousandths from `0` to `1000`
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct MediaRange {
+    pub media_type: MediaType,
+    pub quality: u16,
+}
+
+impl MediaType {
+    /// The value of the first parameter with the given name, ignoring its ASCII case
+    pub fn parameter(&self, name: &str) -> Option<&str> {
+        self.parameters
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// The type and the subtype without the parameters, `text/html`
+    pub fn essence(&self) -> String {
+        format!("{}/{}", self.kind, self.subtype)
+    }
+
+    /// The structured syntax suffix of the subtype, `json` for `application/ld+json`
+    pub fn suffix(&self) -> Option<&str> {
+        self.subtype.rsplit_once('+').map(|(_, suffix)| suffix)
+    }
+
+    /// Whether this media type belongs to `range`: its type and subtype are equal or `*`, and
+    /// every parameter of the range has the same value here. `charset` values ignore their case
+    ///
+    /// # Examples
+    /// ```rust
+    ///
+    /// use pepser::parser::http::media::media_type;
+    /// let (_, html) = media_type("text/html; charset=UTF-8").unwrap();
+    ///
+    /// assert!(html.matches(&media_type("text/*").unwrap().1));
+    /// assert!(html.matches(&media_type("*/*; charset=utf-8").unwrap().1));
+    /// assert!(!html.matches(&media_type("text/plain").unwrap().1));
+    ///
+    ///
+    /// ```
+    pub fn matches(&self, range: &MediaType) -> bool {
+        (range.kind == "*" || range.kind == self.kind)
+            && (range.subtype == "*" || range.subtype == self.subtype)
+            && range.parameters.iter().all(|(name, value)| {
+                self.parameter(name).is_some_and(|own| {
+                    own == value || (name == "charset" && own.eq_ignore_ascii_case(value))
+                })
+            })
+    }
+}
+
+impl fmt::Display for MediaType {
+    /// Writes the media type back, quoting the parameter values which are not tokens
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.kind, self.subtype)?;
+        for (name, value) in &self.parameters {
+            match super::token(value) {
+                Ok(("", _)) => write!(f, "; {}={}", name, value)?,
+                _ => {
+                    write!(f, "; {}=\"", name)?;
+                    for c in value.chars() {
+                        if c == '"' || c == '\\' {
+                            f.write_str("\\")?;
+                        }
+                        write!(f, "{}", c)?;
+                    }
+                    f.write_str("\"")?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Parses a media type, `type/subtype` followed by `;` separated `name=value` parameters whose
+/// values are tokens or quoted strings. The remainder starts after the last parameter. Error
+/// indices are offsets in the input
+///
+/// # Examples
+/// ```rust
+///
+/// use pepser::parser::http::media::media_type;
+/// let (_, parsed) = media_type("Text/HTML; Charset=\"utf-8\"").unwrap();
+///
+/// assert_eq!(parsed.essence(), "text/html");
+/// assert_eq!(parsed.parameter("charset"), Some("utf-8"));
+/// assert_eq!(parsed.to_string(), "text/html; charset=utf-8");
+/// assert_eq!(media_type("text/plain; format").unwrap_err().index, 18);
+///
+///
+/// ```
+pub fn media_type(input: &str) -> ParseResult<&str, MediaType> {
+    let (rest, (kind, subtype)) = essence(input)?;
+    let (rest, parameters) = parameters(input, rest)?;
+    Ok((
+        rest,
+        MediaType {
+            kind,
+            subtype,
+            parameters: parameters
+                .into_iter()
+                .map(|(_, name, value)| (name, value))
+                .collect(),
+        },
+    ))
+}
+
+/// Parses the value of an `Accept` header, comma separated media ranges such as `text/*` or
+/// `*/*` with an optional `q` parameter, `1` when there is none. Parameters after `q` are
+/// extensions, which are skipped. An empty header is an empty list, which [`negotiate`] takes
+/// as accepting everything. Error indices are offsets in the input
+///
+/// # Examples
+/// ```rust
+///
+/// use pepser::parser::http::media::accept;
+/// let (_, ranges) = accept("text/html, application/*;q=0.8, */*;q=0.1").unwrap();
+///
+/// assert_eq!(ranges[1].media_type.essence(), "application/*");
+/// assert_eq!(ranges.iter().map(|range| range.quality).collect::<Vec<_>>(), [1000, 800, 100]);
+/// assert_eq!(accept("text/html;q=2").unwrap_err().index, 12);
+///
+///
+/// ```
+pub fn accept(input: &str) -> ParseResult<&str, Vec<MediaRange>> {
+    let mut ranges = Vec::new();
+    let mut rest = input;
+    loop {
+        rest = rest.trim_start_matches([' ', '\t', ',']);
+        if rest.is_empty() {
+            return Ok((rest, ranges));
+        }
+        let start = input.len() - rest.len();
+        let (next, (kind, subtype)) =
+            essence(rest).map_err(|error| ParserError::from_error(error, start))?;
+        if kind == "*" && subtype != "*" {
+            return Err(invalid(start, "wildcard type with a subtype"));
+        }
+        let (next, parameters) = parameters(input, next)?;
+        let q = parameters.iter().position(|(_, name, _)| name == "q");
+        let quality = match q {
+            Some(q) => {
+                let (offset, _, value) = &parameters[q];
+                quality(value).ok_or_else(|| invalid(*offset, "invalid quality"))?
+            }
+            None => 1000,
+        };
+        let parameters = parameters
+            .into_iter()
+            .take(q.unwrap_or(usize::MAX))
+            .map(|(_, name, value)| (name, value))
+            .collect();
+        ranges.push(MediaRange {
+            media_type: MediaType {
+                kind,
+                subtype,
+                parameters,
+            },
+            quality,
+        });
+        rest = next.trim_start_matches([' ', '\t']);
+        if !rest.is_empty() && !rest.starts_with(',') {
+            return Err(invalid(input.len() - rest.len(), "expected a comma"));
+        }
+    }
+}
+
+/// The media type of `available` which the ranges accept with the highest quality, or the
+/// first of them on a tie. Each media type takes the quality of the most specific range it
+/// matches, and those with a quality of `0` or which no range matches are not acceptable.
+/// An empty list of ranges accepts everything
+///
+/// # Examples
+/// ```rust
+///
+/// use pepser::parser::http::media::{accept, media_type, negotiate};
+/// let (_, ranges) = accept("text/*;q=0.5, text/html;q=0, application/json").unwrap();
+/// let available = ["text/html", "text/plain", "application/json"]
+///     .map(|media| media_type(media).unwrap().1);
+///
+/// assert_eq!(negotiate(&ranges, &available), Some(&available[2]));
+/// assert_eq!(negotiate(&ranges, &available[..2]), Some(&available[1]));
+/// assert_eq!(negotiate(&ranges, &available[..1]), None);
+///
+///
+/// ```
+pub fn negotiate<'a>(ranges: &[MediaRange], available: &'a [MediaType]) -> Option<&'a MediaType> {
+    let quality = |media: &MediaType| match ranges.is_empty() {
+        true => 1000,
+        false => ranges
+            .iter()
+            .filter(|range| media.matches(&range.media_type))
+            .max_by_key(|range| {
+                let range = &range.media_type;
+                (
+                    range.kind != "*",
+                    range.subtype != "*",
+                    range.parameters.len(),
+                )
+            })
+            .map_or(0, |range| range.quality),
+    };
+    let mut best = None;
+    for media in available {
+        let quality = quality(media);
+        if quality > 0 && best.is_none_or(|(_, best)| quality > best) {
+            best = Some((media, quality));
+        }
+    }
+    best.map(|(media, _)| media)
+}
+
+fn invalid<'a>(index: usize, reason: &'static str) -> ParserError<&'a str> {
+    ParserError::new(index, ErrorSource::TakeWhile, reason)
+}
+
+/// The lowercase type and subtype starting `input`
+fn essence(input: &str) -> ParseResult<&str, (String, String)> {
+    let (rest, kind) = super::token(input)?;
+    let Some(subtype) = rest.strip_prefix('/') else {
+        return Err(invalid(kind.len(), "expected / after the type"));
+    };
+    let (rest, subtype) =
+        super::token(subtype).map_err(|error| ParserError::from_error(error, kind.len() + 1))?;
+    Ok((
+        rest,
+        (kind.to_ascii_lowercase(), subtype.to_ascii_lowercase()),
+    ))
+}
+
+/// Parameter names and values with the offset of each value
+type Parameters = Vec<(usize, String, String)>;
+
+/// The parameters starting `rest`, the end of `input`. Empty parameters between two `;` are
+/// skipped
+fn parameters<'a>(
+    input: &'a str,
+    mut rest: &'a str,
+) -> Result<(&'a str, Parameters), ParserError<&'a str>> {
+    let at = |rest: &str| input.len() - rest.len();
+    let mut parameters = Vec::new();
+    loop {
+        let Some(next) = rest.trim_start_matches([' ', '\t']).strip_prefix(';') else {
+            return Ok((rest, parameters));
+        };
+        rest = next.trim_start_matches([' ', '\t']);
+        let Ok((after, name)) = super::token(rest) else {
+            continue;
+        };
+        let Some(after) = after.strip_prefix('=') else {
+            return Err(invalid(at(after), "expected = after the parameter name"));
+        };
+        let offset = at(after);
+        let (after, value) = match after.strip_prefix('"') {
+            Some(quoted) => quoted_string(offset, quoted)?,
+            None => {
+                let (after, value) =
+                    super::token(after).map_err(|error| ParserError::from_error(error, offset))?;
+                (after, value.to_string())
+            }
+        };
+        parameters.push((offset, name.to_ascii_lowercase(), value));
+        rest = after;
+    }
+}
+
+/// The content of a quoted string opening at `start`, `input` being what follows the quote
+fn quoted_string(start: usize, input: &str) -> Result<(&str, String), ParserError<&str>> {
+    let mut value = String::new();
+    let mut chars = input.char_indices();
+    while let Some((index, c)) = chars.next() {
+        match c {
+            '"' => return Ok((&input[index + 1..], value)),
+            '\\' => match chars.next() {
+                Some((_, escaped)) => value.push(escaped),
+                None => break,
+            },
+            '\r' | '\n' => break,
+            _ => value.push(c),
+        }
+    }
+    Err(invalid(start, "unterminated quoted string"))
+}
+
+/// A q-value, `0` to `1` with up to three decimals, in thousandths
+fn quality(value: &str) -> Option<u16> {
+    let (whole, fraction) = value.split_once('.').unwrap_or((value, ""));
+    if fraction.len() > 3 || !fraction.bytes().all(|digit| digit.is_ascii_digit()) {
+        return None;
+    }
+    let thousandths = format!("{:0<3}", fraction).parse::<u16>().ok()?;
+    match whole {
+        "0" => Some(thousandths),
+        "1" if thousandths == 0 => Some(1000),
+        _ => None,
+    }
+}
@@ -0,0 +1,339 @@
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use super::{
+    errors::{ErrorSource, ParserError},
+    traits::ParseResult,
+};
+
+/// Deepest nesting of components accepted, so that a hostile document cannot exhaust the stack
+const MAX_DEPTH: usize = 64;
+
+/// A content line once unfolded, the property of a component. Names keep their case, the
+/// lookups ignore it
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Property {
+    pub name: String,
+    pub parameters: Vec<Parameter>,
+    /// The value as written, see [`Property::text`] for the escapes of TEXT values
+    pub value: String,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Parameter {
+    pub name: String,
+    /// The values separated by commas, without their quotes
+    pub values: Vec<String>,
+}
+
+/// The lines between `BEGIN:<name>` and `END:<name>`, such as a `VCALENDAR`, the `VEVENT`
+/// or `VTODO` it holds, or a `VCARD`
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Component {
+    pub name: String,
+    pub properties: Vec<Property>,
+    pub components: Vec<Component>,
+}
+
+impl Property {
+    /// Returns the first value of the parameter with the given name
+    pub fn parameter(&self, name: &str) -> Option<&str> {
+        self.parameters
+            .iter()
+            .find(|parameter| parameter.name.eq_ignore_ascii_case(name))
+            .and_then(|parameter| parameter.values.first())
+            .map(String::as_str)
+    }
+
+    /// The value read as TEXT: `\n` and `\N` are line breaks, `\\`, `\;` and `\,` the
+    /// character escaped. Other backslashes are kept
+    pub fn text(&self) -> String {
+        unescape(&self.value)
+    }
+
+    /// The value read as a list of TEXT separated by `separator`, `,` for `CATEGORIES` or
+    /// `;` for the parts of a vCard `N`. Escaped separators do not split the value
+    pub fn split(&self, separator: char) -> Vec<String> {
+        let mut parts = Vec::new();
+        let mut start = 0;
+        let mut escaped = false;
+        for (index, c) in self.value.char_indices() {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                _ if c == separator => {
+                    parts.push(unescape(&self.value[start..index]));
+                    start = index + c.len_utf8();
+                }
+                _ => {}
+            }
+        }
+        parts.push(unescape(&self.value[start..]));
+        parts
+    }
+}
+
+impl Component {
+    /// Returns the first property with the given name
+    pub fn property(&self, name: &str) -> Option<&Property> {
+        self.properties
+            .iter()
+            .find(|property| property.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Returns the nested components with the given name
+    pub fn children<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Component> {
+        self.components
+            .iter()
+            .filter(move |component| component.name.eq_ignore_ascii_case(name))
+    }
+}
+
+/// Parses a content line of iCalendar (RFC 5545) or vCard (RFC 6350), `NAME;PARAM=a,"b":value`,
+/// unfolding it first: a line break followed by a space or a tab continues the line, both are
+/// removed. Lines end with `\r\n`, `\n` or the end of the input. Error indices are offsets in
+/// the folded input
+///
+/// # Examples
+/// ```rust
+///
+/// use pepser::parser::icalendar::content_line;
+/// let (remainder, property) =
+///     content_line("ATTENDEE;ROLE=CHAIR;CN=\"Doe, J\":mailto:j@exa\r\n mple.com\r\nEND").unwrap();
+///
+/// assert_eq!(property.name, "ATTENDEE");
+/// assert_eq!(property.parameter("cn"), Some("Doe, J"));
+/// assert_eq!(property.value, "mailto:j@example.com");
+/// assert_eq!(remainder, "END");
+/// assert_eq!(content_line("SUMMARY\r\n ;X=\"a").unwrap_err().index, 13);
+///
+///
+/// ```
+pub fn content_line(input: &str) -> ParseResult<&str, Property> {
+    let (remainder, line) = unfold(input);
+    let property =
+        property(&line.text).map_err(|(index, reason)| invalid(line.position(index), reason))?;
+    Ok((remainder, property))
+}
+
+/// Parses a component from its `BEGIN` line to the matching `END` line, with the components
+/// nested in it. Error indices are offsets in the input
+///
+/// # Examples
+/// ```rust
+///
+/// use pepser::parser::icalendar::component;
+/// let calendar = "BEGIN:VCALENDAR\r\nVERSION:2.0\r\nBEGIN:VEVENT\r\n\
+///     SUMMARY:Launch\\, at last\r\nEND:VEVENT\r\nEND:VCALENDAR\r\n";
+/// let (_, calendar) = component(calendar).unwrap();
+///
+/// assert_eq!(calendar.property("version").unwrap().value, "2.0");
+/// let event = calendar.children("VEVENT").next().unwrap();
+/// assert_eq!(event.property("SUMMARY").unwrap().text(), "Launch, at last");
+/// assert_eq!(component("BEGIN:VTODO\r\nEND:VEVENT").unwrap_err().index, 13);
+///
+///
+/// ```
+pub fn component(input: &str) -> ParseResult<&str, Component> {
+    component_at(input, input, 0)
+}
+
+/// Parses the components up to the end of the input, such as the `VCARD`s of a `.vcf` file
+pub fn components(input: &str) -> ParseResult<&str, Vec<Component>> {
+    let mut components = Vec::new();
+    let mut remainder = input;
+    while !remainder.is_empty() {
+        let (next, parsed) = component_at(input, remainder, 0)?;
+        components.push(parsed);
+        remainder = next;
+    }
+    Ok((remainder, components))
+}
+
+/// Offset of `input` in `document`, of which it is a suffix
+fn position(document: &str, input: &str) -> usize {
+    document.len() - input.len()
+}
+
+fn invalid<'a>(index: usize, reason: &'static str) -> ParserError<&'a str> {
+    ParserError::new(index, ErrorSource::TakeWhile, reason)
+}
+
+/// A content line once unfolded, with where each of its physical lines starts in the input
+struct Unfolded {
+    text: String,
+    /// The offset of each physical line in `text` and in the input
+    lines: Vec<(usize, usize)>,
+}
+
+impl Unfolded {
+    /// Offset in the input of the character at `index` in the text
+    fn position(&self, index: usize) -> usize {
+        let (text, input) = self
+            .lines
+            .iter()
+            .rev()
+            .find(|(text, _)| *text <= index)
+            .copied()
+            .unwrap_or_default();
+        input + index - text
+    }
+}
+
+/// Takes a content line and its line break, joining the lines which continue it
+fn unfold(input: &str) -> (&str, Unfolded) {
+    let mut unfolded = Unfolded {
+        text: String::new(),
+        lines: Vec::new(),
+    };
+    let mut start = 0;
+    loop {
+        let end = input[start..]
+            .find('\n')
+            .map_or(input.len(), |end| start + end);
+        let line = &input[start..end];
+        unfolded.lines.push((unfolded.text.len(), start));
+        unfolded
+            .text
+            .push_str(line.strip_suffix('\r').unwrap_or(line));
+        start = (end + 1).min(input.len());
+        match input[start..].chars().next() {
+            Some(' ' | '\t') => start += 1,
+            _ => return (&input[start..], unfolded),
+        }
+    }
+}
+
+fn property(line: &str) -> Result<Property, (usize, &'static str)> {
+    if let Some(control) = line.find(|c: char| c.is_control() && c != '\t') {
+        return Err((control, "control character in a content line"));
+    }
+    let (mut index, name) = name(line, 0)?;
+    let mut parameters = Vec::new();
+    while line[index..].starts_with(';') {
+        let (next, parsed) = parameter(line, index + 1)?;
+        parameters.push(parsed);
+        index = next;
+    }
+    match line[index..].strip_prefix(':') {
+        Some(value) => Ok(Property {
+            name: name.to_string(),
+            parameters,
+            value: value.to_string(),
+        }),
+        None => Err((index, "expected a colon before the value")),
+    }
+}
+
+/// A property or parameter name, letters, digits and dashes
+fn name(line: &str, start: usize) -> Result<(usize, &str), (usize, &'static str)> {
+    let end = line[start..]
+        .find(|c: char| !c.is_ascii_alphanumeric() && c != '-')
+        .map_or(line.len(), |end| start + end);
+    match end > start {
+        true => Ok((end, &line[start..end])),
+        false => Err((start, "expected a name")),
+    }
+}
+
+fn parameter(line: &str, start: usize) -> Result<(usize, Parameter), (usize, &'static str)> {
+    let (mut index, name) = name(line, start)?;
+    if !line[index..].starts_with('=') {
+        return Err((index, "expected = after a parameter name"));
+    }
+    let mut values = Vec::new();
+    loop {
+        // Skips the `=` or the `,` before the value
+        index += 1;
+        let (next, value) = match line[index..].strip_prefix('"') {
+            Some(quoted) => match quoted.find('"') {
+                Some(end) => (index + end + 2, &quoted[..end]),
+                None => return Err((index, "unterminated quoted value")),
+            },
+            None => {
+                let end = line[index..]
+                    .find(['"', ';', ':', ','])
+                    .map_or(line.len(), |end| index + end);
+                (end, &line[index..end])
+            }
+        };
+        values.push(value.to_string());
+        index = next;
+        if !line[index..].starts_with(',') {
+            let name = name.to_string();
+            return Ok((index, Parameter { name, values }));
+        }
+    }
+}
+
+fn component_at<'a>(
+    document: &'a str,
+    input: &'a str,
+    depth: usize,
+) -> ParseResult<&'a str, Component> {
+    let start = position(document, input);
+    let (mut input, begin) = within(document, input)?;
+    let valid_name = |c: char| c.is_ascii_alphanumeric() || c == '-';
+    if !begin.name.eq_ignore_ascii_case("BEGIN") {
+        return Err(invalid(start, "expected the BEGIN of a component"));
+    }
+    if begin.value.is_empty() || !begin.value.chars().all(valid_name) {
+        return Err(invalid(start, "invalid component name"));
+    }
+    if depth >= MAX_DEPTH {
+        return Err(invalid(start, "component nested too deeply"));
+    }
+
+    let mut component = Component {
+        name: begin.value,
+        properties: Vec::new(),
+        components: Vec::new(),
+    };
+    loop {
+        if input.is_empty() {
+            return Err(invalid(document.len(), "component without an END"));
+        }
+        let line_start = input;
+        let (remainder, property) = within(document, input)?;
+        if property.name.eq_ignore_ascii_case("BEGIN") {
+            let (remainder, nested) = component_at(document, line_start, depth + 1)?;
+            component.components.push(nested);
+            input = remainder;
+        } else if property.name.eq_ignore_ascii_case("END") {
+            return match property.value.eq_ignore_ascii_case(&component.name) {
+                true => Ok((remainder, component)),
+                false => Err(invalid(
+                    position(document, line_start),
+                    "END of another component",
+                )),
+            };
+        } else {
+            component.properties.push(property);
+            input = remainder;
+        }
+    }
+}
+
+/// Parses a content line of the document, offsetting its errors to the document
+fn within<'a>(document: &'a str, input: &'a str) -> ParseResult<&'a str, Property> {
+    content_line(input).map_err(|error| ParserError::from_error(error, position(document, input)))
+}
+
+fn unescape(value: &str) -> String {
+    let mut text = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        match (c, chars.clone().next()) {
+            ('\\', Some('n' | 'N')) => text.push('\n'),
+            ('\\', Some(escaped @ ('\\' | ';' | ','))) => text.push(escaped),
+            _ => {
+                text.push(c);
+                continue;
+            }
+        }
+        chars.next();
+    }
+    text
+}
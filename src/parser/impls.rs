@@ -1,10 +1,19 @@
-use std::cmp;
+use alloc::{borrow::Cow, boxed::Box, string::String, vec, vec::Vec};
+#[cfg(feature = "std")]
+use std::{
+    cell::{Cell, RefCell},
+    fmt::Debug,
+};
 
 use super::{
     errors::{ErrorSource, ParserError},
     traits::{
-        opt, And, Discard, DropUntil, Input, Many, Map, Or, ParseResult, Parser, Peek, PeekOut, Sep,
+        parse_rest, And, BoxedParser, ByRef, ChainL, ChainR, CountExact, Cut, Discard, DropUntil,
+        Expect, Fold, FollowedBy, Input, Many, Map, MapErr, NotFollowedBy, Opt, Or, OrFailWith,
+        OrLongest, ParseIf, ParseResult, Parser, Peek, PeekOut, RecoverWith, Sep, Skip, SkipUntil,
+        Value, Wrapped,
     },
+    xid,
 };
 
 impl<I, P, D, O> Parser<I> for Discard<D, P>
@@ -16,8 +25,97 @@ where
     type Output = O;
 
     fn parse(&mut self, input: I) -> ParseResult<I, Self::Output> {
-        let (i, _) = self.discard.parse(input)?;
-        self.parser.parse(i)
+        let (i, _) = self.discard.parse(input.clone())?;
+        parse_rest(&mut self.parser, &input, i)
+    }
+}
+
+impl<I, P, S, O> Parser<I> for Skip<P, S>
+where
+    P: Parser<I, Output = O>,
+    S: Parser<I>,
+    I: Input,
+{
+    type Output = O;
+
+    fn parse(&mut self, input: I) -> ParseResult<I, Self::Output> {
+        let (i, parsed) = self.parser.parse(input.clone())?;
+        let (i, _) = parse_rest(&mut self.skipped, &input, i)?;
+        Ok((i, parsed))
+    }
+}
+
+impl<I, L, P, R, O> Parser<I> for Wrapped<L, P, R>
+where
+    L: Parser<I>,
+    P: Parser<I, Output = O>,
+    R: Parser<I>,
+    I: Input,
+{
+    type Output = O;
+
+    fn parse(&mut self, input: I) -> ParseResult<I, Self::Output> {
+        let (i, _) = self.left.parse(input.clone())?;
+        let (i, parsed) = parse_rest(&mut self.parser, &input, i)?;
+        let (i, _) = parse_rest(&mut self.right, &input, i)?;
+        Ok((i, parsed))
+    }
+}
+
+impl<I, P, O> Parser<I> for Opt<P>
+where
+    P: Parser<I, Output = O>,
+    I: Input,
+{
+    type Output = Option<O>;
+
+    fn parse(&mut self, mut input: I) -> ParseResult<I, Self::Output> {
+        let checkpoint = input.checkpoint();
+        match self.parser.parse(input.clone()) {
+            Ok((i, parsed)) => Ok((i, Some(parsed))),
+            Err(error) if error.committed => Err(error),
+            Err(_) => {
+                input.rewind(checkpoint);
+                Ok((input, None))
+            }
+        }
+    }
+}
+
+impl<I, V, P, O> Parser<I> for Value<V, P>
+where
+    V: Clone,
+    P: Parser<I, Output = O>,
+    I: Input,
+{
+    type Output = V;
+
+    fn parse(&mut self, input: I) -> ParseResult<I, Self::Output> {
+        let (i, _) = self.parser.parse(input)?;
+        Ok((i, self.value.clone()))
+    }
+}
+
+impl<I, C, P, O> Parser<I> for ParseIf<C, P>
+where
+    C: Parser<I>,
+    P: Parser<I, Output = O>,
+    I: Input,
+{
+    type Output = Option<O>;
+
+    fn parse(&mut self, mut input: I) -> ParseResult<I, Self::Output> {
+        let checkpoint = input.checkpoint();
+        match self.cond.parse(input.clone()) {
+            Ok((i, _)) => {
+                parse_rest(&mut self.parser, &input, i).map(|(i, parsed)| (i, Some(parsed)))
+            }
+            Err(error) if error.committed => Err(error),
+            Err(_) => {
+                input.rewind(checkpoint);
+                Ok((input, None))
+            }
+        }
     }
 }
 
@@ -35,7 +133,7 @@ where
 
 impl<I, F, P> Parser<I> for Peek<F, P>
 where
-    F: FnMut(&I) -> (),
+    F: FnMut(&I),
     P: Parser<I>,
     I: Input,
 {
@@ -48,7 +146,7 @@ where
 
 impl<I, O, F, P> Parser<I> for PeekOut<F, P>
 where
-    F: FnMut(&ParseResult<I, O>) -> (),
+    F: FnMut(&ParseResult<I, O>),
     P: Parser<I, Output = O>,
     I: Input,
 {
@@ -56,10 +154,157 @@ where
     fn parse(&mut self, input: I) -> ParseResult<I, P::Output> {
         let res = self.parser.parse(input);
         (self.f)(&res);
-        return res;
+        res
     }
 }
 
+#[cfg(feature = "std")]
+use super::traits::Traced;
+
+/// Number of elements of the input shown by [`Parser::trace`]
+#[cfg(feature = "std")]
+const TRACE_PREVIEW: usize = 16;
+
+#[cfg(feature = "std")]
+thread_local! {
+    static TRACE_DEPTH: Cell<usize> = const { Cell::new(0) };
+}
+
+#[cfg(feature = "std")]
+fn preview<I: Input>(input: &I) -> I {
+    let mut len = 0;
+    for _ in 0..TRACE_PREVIEW {
+        match input.drop(len).first_len() {
+            0 => break,
+            size => len += size,
+        }
+    }
+    input.take(len)
+}
+
+#[cfg(feature = "std")]
+impl<I, P, L> Parser<I> for Traced<P, L>
+where
+    P: Parser<I>,
+    L: FnMut(&str),
+    I: Input + Debug,
+{
+    type Output = P::Output;
+    fn parse(&mut self, input: I) -> ParseResult<I, P::Output> {
+        let depth = TRACE_DEPTH.with(|depth| depth.replace(depth.get() + 1));
+        let indent = "  ".repeat(depth);
+        (self.log)(&format!("{}-> {} {:?}", indent, self.name, preview(&input)));
+        let res = self.parser.parse(input);
+        TRACE_DEPTH.with(|trace_depth| trace_depth.set(depth));
+        match &res {
+            Ok((i, _)) => (self.log)(&format!("{}<- {} ok {:?}", indent, self.name, preview(i))),
+            Err(error) => (self.log)(&format!("{}<- {} failed: {}", indent, self.name, error)),
+        }
+        res
+    }
+}
+
+#[cfg(feature = "std")]
+thread_local! {
+    /// The rules being parsed, innermost last, while nullable loops are detected
+    static NULLABLE_LOOPS: RefCell<Option<Vec<String>>> = const { RefCell::new(None) };
+}
+
+/// Runs `f` with the detection of nullable loops on the current thread. Repetitions such as
+/// `many0`, `fold`, `sep_by` or `chainl1` stop when their parser succeeds without
+/// consuming anything, which hides a grammar bug: while `f` runs, they fail instead with a
+/// committed [`ErrorSource::Many`] error naming the grammar rules they were parsed in
+///
+/// # Examples
+/// ```rust
+///
+/// use pepser::parser::{
+///     grammar::Grammar,
+///     impls::{detect_nullable_loops, sequence, take_while0},
+///     traits::Parser,
+/// };
+/// let grammar = Grammar::new();
+/// let g = grammar.clone();
+/// grammar.rule("words", move || {
+///     g.call("word").skip(sequence(" ").many0()).many0().map(|words| words.len())
+/// });
+/// grammar.rule("word", || take_while0(|c: char| c.is_alphabetic()).map(str::len));
+///
+/// assert!(grammar.parse("words", "ab cd, ef").is_ok());
+/// let error = detect_nullable_loops(|| grammar.parse("words", "ab cd, ef")).unwrap_err();
+/// assert_eq!(error.index, 5);
+/// assert!(error.reason.ends_with("in rule words"));
+///
+///
+/// ```
+#[cfg(feature = "std")]
+pub fn detect_nullable_loops<R>(f: impl FnOnce() -> R) -> R {
+    struct Restore(Option<Vec<String>>);
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            NULLABLE_LOOPS.with(|rules| *rules.borrow_mut() = self.0.take());
+        }
+    }
+    let _restore = Restore(NULLABLE_LOOPS.with(|rules| rules.replace(Some(vec![]))));
+    f()
+}
+
+/// Keeps `rule` on the stack of the rules being parsed until dropped, while nullable loops
+/// are detected
+#[cfg(feature = "std")]
+pub(crate) struct RuleScope(bool);
+
+#[cfg(feature = "std")]
+impl RuleScope {
+    pub(crate) fn enter(rule: &str) -> Self {
+        RuleScope(
+            NULLABLE_LOOPS.with(|rules| match rules.borrow_mut().as_mut() {
+                Some(rules) => {
+                    rules.push(rule.into());
+                    true
+                }
+                None => false,
+            }),
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl Drop for RuleScope {
+    fn drop(&mut self) {
+        if self.0 {
+            NULLABLE_LOOPS.with(|rules| rules.borrow_mut().as_mut().map(Vec::pop));
+        }
+    }
+}
+
+/// The error of a repetition whose parser consumed nothing at `index`, when nullable loops
+/// are detected
+#[cfg(feature = "std")]
+fn nullable_loop<I: Input>(index: usize, repetition: &str) -> Option<ParserError<I>> {
+    NULLABLE_LOOPS.with(|rules| {
+        let rules = rules.borrow();
+        let rules = rules.as_ref()?;
+        let mut reason = format!("{repetition} repeated a parser which consumed nothing");
+        for (depth, rule) in rules.iter().rev().enumerate() {
+            reason += if depth == 0 {
+                " in rule "
+            } else {
+                ", called from "
+            };
+            reason += rule;
+        }
+        let mut error = ParserError::new(index, ErrorSource::Many, reason);
+        error.committed = true;
+        Some(error)
+    })
+}
+
+#[cfg(not(feature = "std"))]
+fn nullable_loop<I: Input>(_: usize, _: &str) -> Option<ParserError<I>> {
+    None
+}
+
 impl<I, O, F> Parser<I> for F
 where
     F: FnMut(I) -> ParseResult<I, O>,
@@ -71,7 +316,7 @@ where
     }
 }
 
-impl<I, O> Parser<I> for Box<dyn Parser<I, Output = O>>
+impl<'a, I, O> Parser<I> for BoxedParser<'a, I, O>
 where
     I: Input,
 {
@@ -81,6 +326,75 @@ where
     }
 }
 
+impl<I, P> Parser<I> for ByRef<'_, P>
+where
+    P: Parser<I>,
+    I: Input,
+{
+    type Output = P::Output;
+    fn parse(&mut self, input: I) -> ParseResult<I, P::Output> {
+        self.parser.parse(input)
+    }
+}
+
+impl<I, P, S> Parser<I> for RecoverWith<P, S>
+where
+    P: Parser<I>,
+    S: Parser<I>,
+    I: Input,
+{
+    type Output = Result<P::Output, ParserError<I>>;
+    fn parse(&mut self, mut input: I) -> ParseResult<I, Self::Output> {
+        let checkpoint = input.checkpoint();
+        let error = match self.parser.parse(input.clone()) {
+            Ok((i, res)) => return Ok((i, Ok(res))),
+            Err(error) => error,
+        };
+        input.rewind(checkpoint);
+        let mut i = input;
+        while i.input_len() > 0 && self.sync.parse(i.clone()).is_err() {
+            i = i.drop(i.first_len());
+        }
+        Ok((i, Err(error)))
+    }
+}
+
+impl<I, R, P, H, F> Parser<I> for Fold<P, H, F>
+where
+    P: Parser<I>,
+    H: FnMut() -> R,
+    F: FnMut(R, P::Output) -> R,
+    I: Input,
+{
+    type Output = R;
+    fn parse(&mut self, input: I) -> ParseResult<I, R> {
+        let mut acc = (self.init)();
+        let mut ipt = input.clone();
+        while ipt.input_len() > 0 {
+            let checkpoint = ipt.checkpoint();
+            match parse_rest(&mut self.parser, &input, ipt.clone()) {
+                Ok((i, res)) if i.input_len() != ipt.input_len() => {
+                    ipt = i;
+                    acc = (self.f)(acc, res);
+                }
+                Err(error) if error.committed => return Err(error),
+                Ok(_) => {
+                    if let Some(error) = nullable_loop(ipt.offset_from(&input), "fold") {
+                        return Err(error);
+                    }
+                    ipt.rewind(checkpoint);
+                    break;
+                }
+                Err(_) => {
+                    ipt.rewind(checkpoint);
+                    break;
+                }
+            }
+        }
+        Ok((ipt, acc))
+    }
+}
+
 impl<I, P> Parser<I> for Many<P>
 where
     P: Parser<I>,
@@ -89,20 +403,31 @@ where
     type Output = Vec<P::Output>;
     fn parse(&mut self, input: I) -> ParseResult<I, Vec<P::Output>> {
         let mut parsed: Vec<P::Output> = vec![];
-        let mut ipt = input;
-        loop {
-            if ipt.input_len() == 0 {
-                break;
-            }
-            match self.parser.parse(ipt.clone()) {
-                Ok((i, res)) => {
-                    if i.input_len() == ipt.input_len() {
-                        break;
-                    }
+        let mut ipt = input.clone();
+        while ipt.input_len() > 0 || parsed.len() < self.min {
+            let checkpoint = ipt.checkpoint();
+            match parse_rest(&mut self.parser, &input, ipt.clone()) {
+                Ok((i, res)) if i.input_len() != ipt.input_len() => {
                     ipt = i;
                     parsed.push(res);
                 }
+                Err(error) if error.committed || parsed.len() < self.min => return Err(error),
+                Ok(_) if parsed.len() < self.min => {
+                    return Err(ParserError::new(
+                        ipt.offset_from(&input),
+                        ErrorSource::Many,
+                        "repetition consumed nothing",
+                    ))
+                }
+                Ok(_) => {
+                    if let Some(error) = nullable_loop(ipt.offset_from(&input), "many") {
+                        return Err(error);
+                    }
+                    ipt.rewind(checkpoint);
+                    break;
+                }
                 Err(_) => {
+                    ipt.rewind(checkpoint);
                     break;
                 }
             }
@@ -112,6 +437,119 @@ where
     }
 }
 
+impl<I, P> Parser<I> for Cut<P>
+where
+    P: Parser<I>,
+    I: Input,
+{
+    type Output = P::Output;
+    fn parse(&mut self, input: I) -> ParseResult<I, P::Output> {
+        self.parser.parse(input).map_err(|mut error| {
+            error.committed = true;
+            error
+        })
+    }
+}
+
+impl<I, P, const N: usize> Parser<I> for CountExact<P, N>
+where
+    P: Parser<I>,
+    I: Input,
+{
+    type Output = [P::Output; N];
+    fn parse(&mut self, input: I) -> ParseResult<I, [P::Output; N]> {
+        let mut remainder = Some(input.clone());
+        let mut error = None;
+        let outputs: [Option<P::Output>; N] = core::array::from_fn(|_| {
+            let (next, output) = match parse_rest(&mut self.parser, &input, remainder.take()?) {
+                Ok(parsed) => parsed,
+                Err(failure) => {
+                    error = Some(failure);
+                    return None;
+                }
+            };
+            remainder = Some(next);
+            Some(output)
+        });
+        match (remainder, error) {
+            (Some(remainder), None) => Ok((
+                remainder,
+                outputs.map(|output| output.expect("every repetition parsed")),
+            )),
+            (_, error) => Err(error.expect("a repetition failed")),
+        }
+    }
+}
+
+impl<I, O, F, P, S> Parser<I> for ChainL<P, S>
+where
+    F: FnOnce(O, O) -> O,
+    P: Parser<I, Output = O>,
+    S: Parser<I, Output = F>,
+    I: Input,
+{
+    type Output = O;
+    fn parse(&mut self, input: I) -> ParseResult<I, O> {
+        let (mut i, mut acc) = self.term.parse(input.clone())?;
+        while let (next, Some((f, rhs))) = chain_step(&mut self.op, &mut self.term, &input, &i)? {
+            acc = f(acc, rhs);
+            i = next;
+        }
+        Ok((i, acc))
+    }
+}
+
+impl<I, O, F, P, S> Parser<I> for ChainR<P, S>
+where
+    F: FnOnce(O, O) -> O,
+    P: Parser<I, Output = O>,
+    S: Parser<I, Output = F>,
+    I: Input,
+{
+    type Output = O;
+    fn parse(&mut self, input: I) -> ParseResult<I, O> {
+        let (mut i, first) = self.term.parse(input.clone())?;
+        let mut operands = vec![first];
+        let mut ops: Vec<F> = vec![];
+        while let (next, Some((f, rhs))) = chain_step(&mut self.op, &mut self.term, &input, &i)? {
+            ops.push(f);
+            operands.push(rhs);
+            i = next;
+        }
+        let mut acc = operands.pop().expect("at least one operand was parsed");
+        while let (Some(f), Some(lhs)) = (ops.pop(), operands.pop()) {
+            acc = f(lhs, acc);
+        }
+        Ok((i, acc))
+    }
+}
+
+/// Parses an operator followed by a term from `input`, what the chain left of `base`. The
+/// chain stops if either fails or nothing was consumed, unless the error is committed
+fn chain_step<I, O, F, P, S>(
+    op: &mut S,
+    term: &mut P,
+    base: &I,
+    input: &I,
+) -> ParseResult<I, Option<(F, O)>>
+where
+    P: Parser<I, Output = O>,
+    S: Parser<I, Output = F>,
+    I: Input,
+{
+    let step = parse_rest(op, base, input.clone())
+        .and_then(|(i, f)| parse_rest(term, base, i).map(|(i, rhs)| (i, (f, rhs))));
+    match step {
+        Ok((i, step)) if i.input_len() != input.input_len() => Ok((i, Some(step))),
+        Err(error) if error.committed => Err(error),
+        Ok(_) => match nullable_loop(input.offset_from(base), "chain") {
+            Some(error) => Err(error),
+            None => Ok((input.clone(), None)),
+        },
+        Err(_) => Ok((input.clone(), None)),
+    }
+}
+
 impl<I, P, S> Parser<I> for Sep<P, S>
 where
     P: Parser<I>,
@@ -121,21 +559,42 @@ where
     type Output = Vec<P::Output>;
     fn parse(&mut self, input: I) -> ParseResult<I, Vec<P::Output>> {
         let mut ans: Vec<P::Output> = vec![];
-        let mut i = input;
+        let mut i = input.clone();
         loop {
-            if let Ok((next, res)) = self.parser.parse(i.clone()) {
-                ans.push(res);
-                i = next;
-            } else {
-                break;
+            let start = i.input_len();
+            let checkpoint = i.checkpoint();
+            match parse_rest(&mut self.parser, &input, i.clone()) {
+                Ok((next, res)) => {
+                    ans.push(res);
+                    i = next;
+                }
+                Err(error) if error.committed => return Err(error),
+                Err(_) => {
+                    i.rewind(checkpoint);
+                    return Ok((i, ans));
+                }
             }
-            if let Ok((next, _)) = self.separator.parse(i.clone()) {
-                i = next;
-            } else {
-                break;
+            let checkpoint = i.checkpoint();
+            match parse_rest(&mut self.separator, &input, i.clone()) {
+                // an element and a separator which consume nothing would repeat forever
+                Ok((next, _)) if next.input_len() == start => {
+                    let index = i.offset_from(&input);
+                    return match nullable_loop(index, "sep_by") {
+                        Some(error) => Err(error),
+                        None => {
+                            i.rewind(checkpoint);
+                            Ok((i, ans))
+                        }
+                    };
+                }
+                Ok((next, _)) => i = next,
+                Err(error) if error.committed => return Err(error),
+                Err(_) => {
+                    i.rewind(checkpoint);
+                    return Ok((i, ans));
+                }
             }
         }
-        Ok((i, ans))
     }
 }
 
@@ -147,13 +606,37 @@ where
 {
     type Output = (F::Output, S::Output);
     fn parse(&mut self, input: I) -> ParseResult<I, (F::Output, S::Output)> {
-        let (input, first) = self.first.parse(input)?;
-        let (input, second) = self.second.parse(input)?;
-        return Ok((input, (first, second)));
+        let (i, first) = self.first.parse(input.clone())?;
+        let (i, second) = parse_rest(&mut self.second, &input, i)?;
+        Ok((i, (first, second)))
     }
 }
 
 impl<I, O, F, S> Parser<I> for Or<F, S>
+where
+    F: Parser<I, Output = O>,
+    S: Parser<I, Output = O>,
+    I: Input,
+{
+    type Output = O;
+    fn parse(&mut self, mut input: I) -> ParseResult<I, O> {
+        let checkpoint = input.checkpoint();
+        let first = match self.first.parse(input.clone()) {
+            Ok(parsed) => return Ok(parsed),
+            Err(error) if error.committed => return Err(error),
+            Err(error) => error,
+        };
+        input.rewind(checkpoint);
+        self.second.parse(input).map_err(|mut error| {
+            if error.index == first.index && !first.expected.is_empty() {
+                error.expected = [&*first.expected, &*error.expected].concat().into();
+            }
+            error
+        })
+    }
+}
+
+impl<I, O, F, S> Parser<I> for OrLongest<F, S>
 where
     F: Parser<I, Output = O>,
     S: Parser<I, Output = O>,
@@ -161,8 +644,104 @@ where
 {
     type Output = O;
     fn parse(&mut self, input: I) -> ParseResult<I, O> {
-        self.first.parse(input.clone()).or_else(|_| {
-            return self.second.parse(input);
+        let first = match self.first.parse(input.clone()) {
+            Err(error) if error.committed => return Err(error),
+            first => first,
+        };
+        match (first, self.second.parse(input)) {
+            (_, Err(error)) if error.committed => Err(error),
+            (Ok(first), Ok(second)) if second.0.input_len() < first.0.input_len() => Ok(second),
+            (Ok(parsed), _) | (Err(_), Ok(parsed)) => Ok(parsed),
+            (Err(first), Err(mut second)) => {
+                if first.index > second.index {
+                    return Err(first);
+                }
+                if first.index == second.index && !first.expected.is_empty() {
+                    second.expected = [&*first.expected, &*second.expected].concat().into();
+                }
+                Err(second)
+            }
+        }
+    }
+}
+
+impl<I, P> Parser<I> for FollowedBy<P>
+where
+    P: Parser<I>,
+    I: Input,
+{
+    type Output = P::Output;
+    fn parse(&mut self, mut input: I) -> ParseResult<I, P::Output> {
+        let checkpoint = input.checkpoint();
+        let (_, output) = self.parser.parse(input.clone())?;
+        input.rewind(checkpoint);
+        Ok((input, output))
+    }
+}
+
+impl<I, P> Parser<I> for NotFollowedBy<P>
+where
+    P: Parser<I>,
+    I: Input,
+{
+    type Output = ();
+    fn parse(&mut self, mut input: I) -> ParseResult<I, ()> {
+        let checkpoint = input.checkpoint();
+        match self.parser.parse(input.clone()) {
+            Ok(_) => Err(ParserError::new(
+                0,
+                ErrorSource::Predicate,
+                "unexpected match of a negative predicate",
+            )),
+            Err(error) if error.committed => Err(error),
+            Err(_) => {
+                input.rewind(checkpoint);
+                Ok((input, ()))
+            }
+        }
+    }
+}
+
+impl<I, P> Parser<I> for Expect<P>
+where
+    P: Parser<I>,
+    I: Input,
+{
+    type Output = P::Output;
+    fn parse(&mut self, input: I) -> ParseResult<I, P::Output> {
+        self.parser.parse(input).map_err(|mut error| {
+            if error.index == 0 {
+                error.expected = Box::new([self.expected]);
+            }
+            error
+        })
+    }
+}
+
+impl<I, F, P> Parser<I> for MapErr<F, P>
+where
+    F: FnMut(ParserError<I>) -> ParserError<I>,
+    P: Parser<I>,
+    I: Input,
+{
+    type Output = P::Output;
+    fn parse(&mut self, input: I) -> ParseResult<I, P::Output> {
+        self.parser.parse(input).map_err(&mut self.f)
+    }
+}
+
+impl<I, F, R, P> Parser<I> for OrFailWith<F, P>
+where
+    F: FnMut(&ParserError<I>) -> R,
+    R: Into<Cow<'static, str>>,
+    P: Parser<I>,
+    I: Input,
+{
+    type Output = P::Output;
+    fn parse(&mut self, input: I) -> ParseResult<I, P::Output> {
+        self.parser.parse(input).map_err(|mut error| {
+            error.reason = (self.f)(&error).into();
+            error
         })
     }
 }
@@ -173,19 +752,42 @@ where
 {
     type Output = S::Output;
     fn parse(&mut self, input: I) -> ParseResult<I, S::Output> {
-        let mut offset = 0;
-        loop {
-            if input.input_len() < offset {
+        scan_until(&mut self.until, input).map(|(remainder, (_, found))| (remainder, found))
+    }
+}
+
+impl<I, S> Parser<I> for SkipUntil<S>
+where
+    S: Parser<I>,
+    I: Input,
+{
+    type Output = (I, S::Output);
+    fn parse(&mut self, input: I) -> ParseResult<I, (I, S::Output)> {
+        scan_until(&mut self.until, input)
+    }
+}
+
+/// Tries `until` at each position of the input, outputting the prefix before the first match
+/// along with the output of `until`
+fn scan_until<I, S>(until: &mut S, input: I) -> ParseResult<I, (I, S::Output)>
+where
+    S: Parser<I>,
+    I: Input,
+{
+    let mut skipped = 0;
+    loop {
+        let remainder = input.drop(skipped);
+        match until.parse(remainder.clone()) {
+            Ok((next, found)) => return Ok((next, (input.take(skipped), found))),
+            Err(_) if remainder.input_len() == 0 => {
                 return Err(ParserError::new(
                     0,
                     ErrorSource::DropUntil,
                     "could not find any match for drop until",
-                ));
-            }
-            match self.until.parse(input.drop(offset)) {
-                Ok(res) => return Ok(res),
-                Err(_) => offset += 1,
+                ))
             }
+            // steps over whole elements, a char for `&str`
+            Err(_) => skipped += remainder.first_len(),
         }
     }
 }
@@ -200,90 +802,809 @@ pub fn sequence<'a>(matcher: &'a str) -> impl Parser<&'a str, Output = &'a str>
             ));
         }
         match input
-            .chars()
+            .char_indices()
             .zip(matcher.chars())
-            .position(|(first, second)| first != second)
+            .find(|((_, first), second)| first != second)
         {
-            Some(position) => Err(ParserError::new(
+            Some(((position, _), _)) => Err(ParserError::new(
                 position,
                 ErrorSource::Sequence(matcher),
-                format!(
-                    "could not parse sequence '{}'",
-                    &input[position..cmp::min(position + 10, input.len())]
-                )
-                .as_str(),
+                "could not parse sequence",
+            )),
+            None if input.len() < matcher.len() => Err(ParserError::new(
+                input.len(),
+                ErrorSource::Sequence(matcher),
+                "unexpected end of input",
             )),
             None => {
                 let (parsed, remainder) = input.split_at(matcher.len());
-                return Ok((remainder, parsed));
+                Ok((remainder, parsed))
             }
         }
     }
 }
 
+/// Succeeds only at the end of the input, to anchor a grammar which must match all of it
+///
+/// # Examples
+/// ```rust
+///
+/// use pepser::parser::impls::{eof, sequence};
+/// use pepser::parser::traits::Parser;
+/// let mut keyword = sequence("let").skip(eof());
+///
+/// assert_eq!(keyword.parse("let"), Ok(("", "let")));
+/// assert_eq!(keyword.parse("lets").unwrap_err().index, 3);
+///
+///
+/// ```
 pub fn eof<I>() -> impl Parser<I, Output = ()>
 where
     I: Input,
 {
     move |input: I| {
         if input.input_len() == 0 {
-            return Ok((input, ()));
+            Ok((input, ()))
         } else {
-            let mut error_message = String::from("input is not empty");
-            error_message.push_str(input.to_string_value().as_str());
+            Err(ParserError::new(0, ErrorSource::EOF, "input is not empty"))
+        }
+    }
+}
+
+/// Takes everything left in the input, possibly nothing, so that it never fails
+///
+/// # Examples
+/// ```rust
+///
+/// use pepser::parser::impls::{rest, sequence};
+/// use pepser::parser::traits::Parser;
+/// let mut comment = sequence("//").with(rest());
+///
+/// assert_eq!(comment.parse("// the end"), Ok(("", " the end")));
+/// assert_eq!(rest().parse(&[1, 2][..]), Ok((&[][..], &[1, 2][..])));
+///
+///
+/// ```
+pub fn rest<I>() -> impl Parser<I, Output = I>
+where
+    I: Input,
+{
+    move |input: I| {
+        let (parsed, remainder) = input.split_at(input.input_len());
+        Ok((remainder, parsed))
+    }
+}
+
+/// Takes the characters for which the predicate holds, possibly none, so that it never fails
+///
+/// # Examples
+/// ```rust
+///
+/// use pepser::parser::impls::take_while0;
+/// use pepser::parser::traits::Parser;
+/// let mut digits = take_while0(|c| c.is_ascii_digit());
+///
+/// assert_eq!(digits.parse("12ab"), Ok(("ab", "12")));
+/// assert_eq!(digits.parse("ab"), Ok(("ab", "")));
+/// assert_eq!(digits.parse(""), Ok(("", "")));
+///
+///
+/// ```
+pub fn take_while0<'a, P>(mut predicate: P) -> impl Parser<&'a str, Output = &'a str>
+where
+    P: FnMut(char) -> bool,
+{
+    move |input: &'a str| {
+        let (parsed, remainder) = input.split_at(matched(input, &mut predicate));
+        Ok((remainder, parsed))
+    }
+}
+
+/// Takes the characters for which the predicate holds, failing unless there is at least one
+///
+/// # Examples
+/// ```rust
+///
+/// use pepser::parser::impls::take_while1;
+/// use pepser::parser::traits::Parser;
+/// let mut digits = take_while1(|c| c.is_ascii_digit());
+///
+/// assert_eq!(digits.parse("12ab"), Ok(("ab", "12")));
+/// assert_eq!(digits.parse("ab").unwrap_err().reason, "unexpected character");
+/// assert_eq!(digits.parse("").unwrap_err().reason, "empty sequence");
+///
+///
+/// ```
+pub fn take_while1<'a, P>(mut predicate: P) -> impl Parser<&'a str, Output = &'a str>
+where
+    P: FnMut(char) -> bool,
+{
+    move |input: &'a str| split_taken(input, matched(input, &mut predicate))
+}
+
+/// Takes at most `max` characters for which the predicate holds, failing unless there are at
+/// least `min` of them. The error is indexed at the first character which did not match
+///
+/// # Examples
+/// ```rust
+///
+/// use pepser::parser::impls::take_while_m_n;
+/// use pepser::parser::traits::Parser;
+/// let mut hex = take_while_m_n(4, 4, |c| c.is_ascii_hexdigit());
+///
+/// assert_eq!(hex.parse("00e9ff"), Ok(("ff", "00e9")));
+/// assert_eq!(hex.parse("0e\"").unwrap_err().index, 2);
+/// assert_eq!(take_while_m_n(1, 2, char::is_alphabetic).parse("日本語"), Ok(("語", "日本")));
+///
+///
+/// ```
+pub fn take_while_m_n<'a, P>(
+    min: usize,
+    max: usize,
+    mut predicate: P,
+) -> impl Parser<&'a str, Output = &'a str>
+where
+    P: FnMut(char) -> bool,
+{
+    move |input: &'a str| {
+        let (count, position) = input
+            .char_indices()
+            .take(max)
+            .take_while(|(_, c)| predicate(*c))
+            .fold((0, 0), |(count, _), (position, c)| {
+                (count + 1, position + c.len_utf8())
+            });
+        if count < min {
             return Err(ParserError::new(
-                0,
-                ErrorSource::EOF,
-                error_message.as_str(),
+                position,
+                ErrorSource::TakeWhile,
+                "not enough characters",
             ));
         }
+        let (parsed, remainder) = input.split_at(position);
+        Ok((remainder, parsed))
     }
 }
 
-pub fn take_while<'a, P>(mut predicate: P) -> impl Parser<&'a str, Output = &'a str>
+/// Length in bytes of the prefix of characters for which the predicate holds
+fn matched<P>(input: &str, predicate: &mut P) -> usize
 where
     P: FnMut(char) -> bool,
 {
+    input
+        .char_indices()
+        .find(|(_, c)| !(predicate)(*c))
+        .map_or(input.len(), |(position, _)| position)
+}
+
+/// Splits the input after the `position` bytes matched by a `take_while1`
+fn split_taken(input: &str, position: usize) -> ParseResult<&str, &str> {
+    if input.is_empty() {
+        return Err(ParserError::new(
+            0,
+            ErrorSource::TakeWhile,
+            "empty sequence",
+        ));
+    }
+    if position == 0 {
+        return Err(ParserError::new(
+            0,
+            ErrorSource::TakeWhile,
+            "unexpected character",
+        ));
+    }
+    let (parsed, remainder) = input.split_at(position);
+    Ok((remainder, parsed))
+}
+
+/// Lookup table of an ASCII character set, `None` if the set has other characters
+fn ascii_set(chars: &str) -> Option<[bool; 128]> {
+    let mut set = [false; 128];
+    for c in chars.chars() {
+        *set.get_mut(c as usize)? = true;
+    }
+    Some(set)
+}
+
+/// Takes the bytes whose membership in the ASCII `set` is `inside`, bytes of multibyte
+/// characters are never in the set. Stopping at an ASCII byte, or at the first byte of a
+/// multibyte character when taking members, always leaves a char boundary
+fn scan_ascii<'a>(
+    input: &'a str,
+    set: &[bool; 128],
+    inside: bool,
+) -> ParseResult<&'a str, &'a str> {
+    let position = input
+        .bytes()
+        .position(|byte| set.get(byte as usize).is_some_and(|member| *member) != inside)
+        .unwrap_or(input.len());
+    split_taken(input, position)
+}
+
+/// Takes the input up to the first `delimiter`, which is left in the remainder. Same as
+/// [`take_until_keeping`]
+pub fn take_until<'a>(delimiter: &'a str) -> impl Parser<&'a str, Output = &'a str> {
+    move |input: &'a str| match input.find(delimiter) {
+        Some(position) => {
+            let (parsed, remainder) = input.split_at(position);
+            Ok((remainder, parsed))
+        }
+        None => Err(missing_delimiter(delimiter)),
+    }
+}
+
+/// Takes the input up to the first `delimiter` and leaves the delimiter in the remainder
+///
+/// # Examples
+/// ```rust
+///
+/// use pepser::parser::impls::take_until_keeping;
+/// use pepser::parser::traits::Parser;
+///
+/// assert_eq!(take_until_keeping("-->").parse("note --> rest"), Ok(("--> rest", "note ")));
+/// assert!(take_until_keeping("-->").parse("note").is_err());
+///
+///
+/// ```
+pub fn take_until_keeping(delimiter: &str) -> impl Parser<&str, Output = &str> {
+    take_until(delimiter)
+}
+
+/// Takes the input up to the first `delimiter` and consumes the delimiter, which is not part
+/// of the output
+///
+/// # Examples
+/// ```rust
+///
+/// use pepser::parser::impls::take_until_consuming;
+/// use pepser::parser::traits::Parser;
+///
+/// assert_eq!(take_until_consuming("-->").parse("note --> rest"), Ok((" rest", "note ")));
+/// assert!(take_until_consuming("-->").parse("note").is_err());
+///
+///
+/// ```
+pub fn take_until_consuming<'a>(delimiter: &'a str) -> impl Parser<&'a str, Output = &'a str> {
+    move |input: &'a str| match input.find(delimiter) {
+        Some(position) => Ok((&input[position + delimiter.len()..], &input[..position])),
+        None => Err(missing_delimiter(delimiter)),
+    }
+}
+
+/// Same as [`take_until_keeping`], but a delimiter right after `escape` does not stop the
+/// parser. An `escape` escapes the character following it, itself included, and the output
+/// keeps the escapes as written
+///
+/// # Examples
+/// ```rust
+///
+/// use pepser::parser::impls::take_until_keeping_escaped;
+/// use pepser::parser::traits::Parser;
+/// let mut content = take_until_keeping_escaped("\"", '\\');
+///
+/// assert_eq!(content.parse(r#"a \"b\" c" d"#), Ok((r#"" d"#, r#"a \"b\" c"#)));
+/// assert_eq!(content.parse(r#"a \\" b"#), Ok((r#"" b"#, r#"a \\"#)));
+/// assert!(content.parse(r#"a \""#).is_err());
+///
+///
+/// ```
+pub fn take_until_keeping_escaped<'a>(
+    delimiter: &'a str,
+    escape: char,
+) -> impl Parser<&'a str, Output = &'a str> {
+    move |input: &'a str| match find_unescaped(input, delimiter, escape) {
+        Some(position) => Ok((&input[position..], &input[..position])),
+        None => Err(missing_delimiter(delimiter)),
+    }
+}
+
+/// Same as [`take_until_consuming`], but a delimiter right after `escape` does not stop the
+/// parser, as with [`take_until_keeping_escaped`]
+///
+/// # Examples
+/// ```rust
+///
+/// use pepser::parser::impls::take_until_consuming_escaped;
+/// use pepser::parser::traits::Parser;
+///
+/// let mut field = take_until_consuming_escaped(",", '\\');
+/// assert_eq!(field.parse(r"a\,b,c"), Ok(("c", r"a\,b")));
+///
+///
+/// ```
+pub fn take_until_consuming_escaped<'a>(
+    delimiter: &'a str,
+    escape: char,
+) -> impl Parser<&'a str, Output = &'a str> {
+    move |input: &'a str| match find_unescaped(input, delimiter, escape) {
+        Some(position) => Ok((&input[position + delimiter.len()..], &input[..position])),
+        None => Err(missing_delimiter(delimiter)),
+    }
+}
+
+fn missing_delimiter(delimiter: &str) -> ParserError<&str> {
+    ParserError::new(
+        0,
+        ErrorSource::TakeUntil(delimiter),
+        "could not find delimiter",
+    )
+}
+
+/// The position of the first `delimiter` of `input` which does not follow an `escape`
+fn find_unescaped(input: &str, delimiter: &str, escape: char) -> Option<usize> {
+    let mut position = 0;
+    loop {
+        let rest = &input[position..];
+        if rest.starts_with(delimiter) {
+            return Some(position);
+        }
+        let mut chars = rest.chars();
+        let c = chars.next()?;
+        position += c.len_utf8();
+        if c == escape {
+            position += chars.next().map_or(0, char::len_utf8);
+        }
+    }
+}
+
+/// Parses a region which starts with `open` and ends with the `close` balancing it, outputting
+/// what lies between them. Regions opened inside must be closed first, and a `close` equal to
+/// `open` is never nested. Unbalanced input is an error at its start
+///
+/// # Examples
+/// ```rust
+///
+/// use pepser::parser::impls::balanced;
+/// use pepser::parser::traits::Parser;
+/// let mut arguments = balanced("(", ")");
+///
+/// assert_eq!(arguments.parse("(a, (b, c)) + d"), Ok((" + d", "a, (b, c)")));
+/// assert_eq!(balanced("{{", "}}").parse("{{ {{x}} }}!"), Ok(("!", " {{x}} ")));
+/// assert_eq!(arguments.parse("(a, (b)").unwrap_err().index, 0);
+///
+///
+/// ```
+pub fn balanced<'a>(open: &'a str, close: &'a str) -> impl Parser<&'a str, Output = &'a str> {
+    move |input: &'a str| scan_balanced(input, open, close, "", None)
+}
+
+/// Parses a balanced region as [`balanced`] does, skipping the delimiters inside string
+/// literals, which start and end with one of the `quotes`, and the characters following
+/// `escape`, inside literals or not. A literal left open is an error at its quote
+///
+/// # Examples
+/// ```rust
+///
+/// use pepser::parser::impls::balanced_with_strings;
+/// use pepser::parser::traits::Parser;
+/// let mut block = balanced_with_strings("{", "}", "\"'", '\\');
+///
+/// assert_eq!(block.parse(r#"{ s = "}"; c = '\''; } rest"#), Ok((" rest", r#" s = "}"; c = '\''; "#)));
+/// assert_eq!(block.parse(r"{ \} }"), Ok(("", r" \} ")));
+/// assert_eq!(block.parse("{ \"} }").unwrap_err().index, 2);
+///
+///
+/// ```
+pub fn balanced_with_strings<'a>(
+    open: &'a str,
+    close: &'a str,
+    quotes: &'a str,
+    escape: char,
+) -> impl Parser<&'a str, Output = &'a str> {
+    move |input: &'a str| scan_balanced(input, open, close, quotes, Some(escape))
+}
+
+fn scan_balanced<'a>(
+    input: &'a str,
+    open: &'a str,
+    close: &'a str,
+    quotes: &str,
+    escape: Option<char>,
+) -> ParseResult<&'a str, &'a str> {
+    if open.is_empty() || close.is_empty() {
+        return Err(ParserError::new(
+            0,
+            ErrorSource::Sequence(open),
+            "empty delimiter",
+        ));
+    }
+    let Some(content) = input.strip_prefix(open) else {
+        return Err(ParserError::new(
+            0,
+            ErrorSource::Sequence(open),
+            "expected an opening delimiter",
+        ));
+    };
+    let mut depth = 0usize;
+    // the quote of the string literal being skipped, with where it starts
+    let mut string: Option<(char, usize)> = None;
+    let mut index = 0;
+    while let Some(c) = content[index..].chars().next() {
+        let rest = &content[index..];
+        index += c.len_utf8();
+        if Some(c) == escape {
+            index += content[index..].chars().next().map_or(0, char::len_utf8);
+            continue;
+        }
+        match string {
+            Some((quote, _)) if c == quote => string = None,
+            Some(_) => {}
+            None if rest.starts_with(close) => {
+                if depth == 0 {
+                    return Ok((&rest[close.len()..], &content[..index - c.len_utf8()]));
+                }
+                depth -= 1;
+                index += close.len() - c.len_utf8();
+            }
+            None if rest.starts_with(open) => {
+                depth += 1;
+                index += open.len() - c.len_utf8();
+            }
+            None if quotes.contains(c) => string = Some((c, open.len() + index - c.len_utf8())),
+            None => {}
+        }
+    }
+    Err(match string {
+        Some((_, start)) => ParserError::new(
+            start,
+            ErrorSource::TakeUntil(close),
+            "unterminated string literal",
+        ),
+        None => ParserError::new(0, ErrorSource::TakeUntil(close), "unbalanced delimiters"),
+    })
+}
+
+/// Parses exactly `count` ASCII digits into their value, `count` must not exceed 9
+pub fn fixed_digits<'a>(count: usize) -> impl Parser<&'a str, Output = u32> {
     move |input: &'a str| {
-        if input.is_empty() {
+        let found = input
+            .bytes()
+            .take(count)
+            .take_while(u8::is_ascii_digit)
+            .count();
+        if found != count {
             return Err(ParserError::new(
-                0,
-                ErrorSource::TakeWhile,
-                "empty sequence",
+                found,
+                ErrorSource::Digits,
+                "not enough digits",
             ));
         }
-        match input.chars().position(|c| !(predicate)(c)) {
-            Some(position) => {
-                if position == 0 {
-                    return Err(ParserError::new(
-                        0,
-                        ErrorSource::TakeWhile,
-                        format!("could not parse for char {}", &input[0..1]).as_str(),
-                    ));
+        let (parsed, remainder) = input.split_at(count);
+        parsed
+            .parse::<u32>()
+            .map(|value| (remainder, value))
+            .map_err(|_| ParserError::new(0, ErrorSource::Digits, "digits overflow"))
+    }
+}
+
+/// Parses text made of runs of `normal` characters interleaved with escape sequences, which
+/// start with `control_char` and are replaced by the output of `transformer`, parsing what
+/// follows the control character. An escape which the transformer rejects is an error, the
+/// text otherwise ends at the first character matched by neither
+///
+/// # Examples
+/// ```rust
+///
+/// use pepser::parser::impls::{escaped_transform, none_of, sequence};
+/// use pepser::parser::traits::Parser;
+/// let mut unquote = escaped_transform(
+///     none_of("\\\""),
+///     '\\',
+///     sequence("n").map(|_| "\n").or(sequence("\\")).or(sequence("\"")),
+/// );
+///
+/// assert_eq!(unquote.parse(r#"a\"b\"\nc" d"#), Ok(("\" d", String::from("a\"b\"\nc"))));
+/// assert_eq!(unquote.parse(r#"a\x"#).unwrap_err().index, 2);
+///
+///
+/// ```
+pub fn escaped_transform<'a, N, T, O>(
+    mut normal: N,
+    control_char: char,
+    mut transformer: T,
+) -> impl Parser<&'a str, Output = String>
+where
+    N: Parser<&'a str, Output = &'a str>,
+    T: Parser<&'a str, Output = O>,
+    O: AsRef<str>,
+{
+    move |input: &'a str| {
+        let mut output = String::new();
+        let mut remainder = input;
+        loop {
+            if let Ok((next, parsed)) = normal.parse(remainder) {
+                if next.len() < remainder.len() {
+                    output.push_str(parsed);
+                    remainder = next;
+                    continue;
                 }
+            }
+            let Some(escape) = remainder.strip_prefix(control_char) else {
+                return Ok((remainder, output));
+            };
+            let (next, parsed) = transformer
+                .parse(escape)
+                .map_err(|error| ParserError::from_error(error, input.len() - escape.len()))?;
+            output.push_str(parsed.as_ref());
+            remainder = next;
+        }
+    }
+}
+
+/// Parses a single token equal to `expected`
+///
+/// # Examples
+/// ```rust
+///
+/// use pepser::parser::impls::token;
+/// use pepser::parser::traits::Parser;
+/// let tokens = ["let", "x", "=", "1"];
+///
+/// assert_eq!(token("let").parse(&tokens[..]), Ok((&tokens[1..], &"let")));
+/// assert_eq!(token("fn").parse(&tokens[..]).is_err(), true);
+///
+///
+/// ```
+pub fn token<'a, T>(expected: T) -> impl Parser<&'a [T], Output = &'a T>
+where
+    T: PartialEq,
+{
+    satisfy_token(move |token| *token == expected)
+}
+
+/// Parses a single token for which the predicate holds
+pub fn satisfy_token<'a, T, P>(mut predicate: P) -> impl Parser<&'a [T], Output = &'a T>
+where
+    P: FnMut(&T) -> bool,
+{
+    move |input: &'a [T]| match input.split_first() {
+        Some((token, remainder)) if predicate(token) => Ok((remainder, token)),
+        Some(_) => Err(ParserError::new(0, ErrorSource::Token, "unexpected token")),
+        None => Err(ParserError::new(
+            0,
+            ErrorSource::Token,
+            "unexpected end of input",
+        )),
+    }
+}
 
-                let (parsed, remainder) = input.split_at(position);
-                return Ok((remainder, parsed));
+/// A node of the trie built by [`keywords`], whose children are sorted by byte
+struct KeywordNode {
+    children: Vec<(u8, usize)>,
+    /// Whether a keyword ends at this node
+    terminal: bool,
+}
+
+/// Parses the longest of `keywords` which starts the input, in a single pass over it through a
+/// prefix trie of the keywords, instead of trying each of them in turn as chained `or` do.
+/// The output is the keyword matched. Keywords are matched as they are, so `true` matches the
+/// start of `trueish`: follow the parser with `not_followed_by` to require a word boundary
+///
+/// # Examples
+/// ```rust
+///
+/// use pepser::parser::impls::keywords;
+/// use pepser::parser::traits::Parser;
+/// let mut keyword = keywords(&["in", "int", "if", "true", "false"]);
+///
+/// assert_eq!(keyword.parse("int x"), Ok((" x", "int")));
+/// assert_eq!(keyword.parse("ink"), Ok(("k", "in")));
+/// assert_eq!(keyword.parse("false").map(|(_, matched)| matched == "true"), Ok(false));
+/// assert!(keyword.parse("i").is_err());
+///
+///
+/// ```
+pub fn keywords<'a>(keywords: &[&str]) -> impl Parser<&'a str, Output = &'a str> {
+    let mut trie = vec![KeywordNode {
+        children: vec![],
+        terminal: false,
+    }];
+    for keyword in keywords {
+        let mut node = 0;
+        for byte in keyword.bytes() {
+            node = match trie[node]
+                .children
+                .binary_search_by_key(&byte, |(byte, _)| *byte)
+            {
+                Ok(child) => trie[node].children[child].1,
+                Err(position) => {
+                    let child = trie.len();
+                    trie[node].children.insert(position, (byte, child));
+                    trie.push(KeywordNode {
+                        children: vec![],
+                        terminal: false,
+                    });
+                    child
+                }
+            };
+        }
+        trie[node].terminal = true;
+    }
+    move |input: &'a str| {
+        let mut node = &trie[0];
+        let mut matched = None;
+        let mut bytes = input.bytes();
+        let mut length = 0;
+        loop {
+            if node.terminal {
+                matched = Some(length);
             }
-            None => {
-                return Ok(("", input));
+            let Some(byte) = bytes.next() else {
+                break;
+            };
+            match node.children.binary_search_by_key(&byte, |(byte, _)| *byte) {
+                Ok(child) => node = &trie[node.children[child].1],
+                Err(_) => break,
             }
+            length += 1;
+        }
+        match matched {
+            // keywords end on char boundaries
+            Some(length) => Ok((&input[length..], &input[..length])),
+            None if input.is_empty() => Err(ParserError::new(
+                0,
+                ErrorSource::Keyword,
+                "unexpected end of input",
+            )),
+            None => Err(ParserError::new(
+                0,
+                ErrorSource::Keyword,
+                "could not parse keyword",
+            )),
+        }
+    }
+}
+
+/// Parses an identifier, an `XID_Start` character or `_` followed by `XID_Continue` ones, as
+/// Rust and Python define them from the classes of Unicode. Letters start an identifier,
+/// combining marks, digits and connector punctuation may follow. ASCII characters are checked
+/// first, so ASCII identifiers take no Unicode lookup: [`ascii_identifier`] also rejects the
+/// others
+///
+/// # Examples
+/// ```rust
+///
+/// use pepser::parser::impls::identifier;
+/// use pepser::parser::traits::Parser;
+///
+/// assert_eq!(identifier().parse("_count2 += 1"), Ok((" += 1", "_count2")));
+/// assert_eq!(identifier().parse("größe = 日本"), Ok((" = 日本", "größe")));
+/// assert_eq!(identifier().parse("e\u{301}t\u{e9}"), Ok(("", "e\u{301}t\u{e9}")));
+/// assert_eq!(identifier().parse("2nd").unwrap_err().reason, "unexpected character");
+///
+///
+/// ```
+pub fn identifier<'a>() -> impl Parser<&'a str, Output = &'a str> {
+    move |input: &'a str| {
+        let mut chars = input.char_indices();
+        let position = match chars.next() {
+            Some((_, first)) if is_identifier_start(first) => chars
+                .find(|(_, c)| !is_identifier_continue(*c))
+                .map_or(input.len(), |(position, _)| position),
+            _ => 0,
         };
+        split_taken(input, position)
     }
 }
 
-pub fn none_of<'a>(chars: &'a str) -> impl Parser<&'a str, Output = &'a str> {
-    take_while(|c| !chars.contains(c))
+fn is_identifier_start(c: char) -> bool {
+    match c.is_ascii() {
+        true => c.is_ascii_alphabetic() || c == '_',
+        false => xid::is_xid_start(c),
+    }
+}
+
+fn is_identifier_continue(c: char) -> bool {
+    match c.is_ascii() {
+        true => c.is_ascii_alphanumeric() || c == '_',
+        false => xid::is_xid_continue(c),
+    }
+}
+
+/// Parses an ASCII identifier, `[A-Za-z_][A-Za-z0-9_]*`, scanning the input byte by byte
+///
+/// # Examples
+/// ```rust
+///
+/// use pepser::parser::impls::ascii_identifier;
+/// use pepser::parser::traits::Parser;
+///
+/// assert_eq!(ascii_identifier().parse("max_len: 3"), Ok((": 3", "max_len")));
+/// assert_eq!(ascii_identifier().parse("naïve"), Ok(("ïve", "na")));
+///
+///
+/// ```
+pub fn ascii_identifier<'a>() -> impl Parser<&'a str, Output = &'a str> {
+    move |input: &'a str| {
+        let bytes = input.as_bytes();
+        let position = match bytes.first() {
+            Some(first) if first.is_ascii_alphabetic() || *first == b'_' => bytes
+                .iter()
+                .position(|byte| !byte.is_ascii_alphanumeric() && *byte != b'_')
+                .unwrap_or(bytes.len()),
+            _ => 0,
+        };
+        // a non ASCII byte starting the rest is the first byte of a character
+        split_taken(input, position)
+    }
+}
+
+/// Parses an [`identifier`] which is none of `keywords`, the reserved words of a language.
+/// Keywords are looked up in a trie as by [`keywords`], and an identifier which only starts
+/// with one, like `iffy` for `if`, is accepted
+///
+/// # Examples
+/// ```rust
+///
+/// use pepser::parser::errors::ErrorSource;
+/// use pepser::parser::impls::identifier_except;
+/// use pepser::parser::traits::Parser;
+/// let mut name = identifier_except(&["if", "else", "fn"]);
+///
+/// assert_eq!(name.parse("iffy()"), Ok(("()", "iffy")));
+/// assert_eq!(name.parse("else {").unwrap_err().source, ErrorSource::Predicate);
+///
+///
+/// ```
+pub fn identifier_except<'a>(reserved: &[&str]) -> impl Parser<&'a str, Output = &'a str> {
+    let mut identifier = identifier();
+    let mut reserved = keywords(reserved);
+    move |input: &'a str| {
+        let (remainder, name) = identifier.parse(input)?;
+        match reserved.parse(name) {
+            Ok(("", _)) => Err(ParserError::new(
+                0,
+                ErrorSource::Predicate,
+                "reserved keyword",
+            )),
+            _ => Ok((remainder, name)),
+        }
+    }
+}
+
+/// Takes the characters which are not in `chars`, ASCII sets are scanned byte by byte
+pub fn none_of(chars: &str) -> impl Parser<&str, Output = &str> {
+    let set = ascii_set(chars);
+    move |input| match &set {
+        Some(set) => scan_ascii(input, set, false),
+        None => take_while1(|c| !chars.contains(c)).parse(input),
+    }
 }
 
 pub fn not<'a>(chr: char) -> impl Parser<&'a str, Output = &'a str> {
-    take_while(move |c| chr != c)
+    move |input: &'a str| {
+        let position = if chr.is_ascii() {
+            input.bytes().position(|byte| byte == chr as u8)
+        } else {
+            input.find(chr)
+        };
+        split_taken(input, position.unwrap_or(input.len()))
+    }
+}
+
+/// Takes the characters which are in `chars`, ASCII sets are scanned byte by byte
+pub fn any(chars: &str) -> impl Parser<&str, Output = &str> {
+    let set = ascii_set(chars);
+    move |input| match &set {
+        Some(set) => scan_ascii(input, set, true),
+        None => take_while1(|c| chars.contains(c)).parse(input),
+    }
 }
 
-pub fn any<'a>(chars: &'a str) -> impl Parser<&'a str, Output = &'a str> {
-    take_while(|c| chars.contains(c))
+/// Parses a single character
+pub fn any_char<'a>() -> impl Parser<&'a str, Output = char> {
+    move |input: &'a str| match input.chars().next() {
+        Some(c) => Ok((&input[c.len_utf8()..], c)),
+        None => Err(ParserError::new(
+            0,
+            ErrorSource::Char,
+            "unexpected end of input",
+        )),
+    }
 }
 
-pub fn ws<'a>() -> impl Parser<&'a str, Output = Option<&'a str>> {
-    opt(take_while(char::is_whitespace))
+/// Takes the whitespace at the start of the input, succeeding without consuming anything
+/// when there is none
+pub fn ws<'a>() -> impl Parser<&'a str, Output = &'a str> {
+    take_while0(char::is_whitespace)
 }
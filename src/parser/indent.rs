@@ -0,0 +1,147 @@
+use alloc::{vec, vec::Vec};
+
+use super::{
+    errors::{ErrorSource, ParserError},
+    stateful::Stateful,
+    traits::{ParseResult, Parser},
+};
+
+/// The stack of indentation widths of the enclosing blocks, the top level is at column 0
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct Indentation {
+    levels: Vec<usize>,
+}
+
+/// Text input tracking indentation, created with `Stateful::new(text, Indentation::default())`
+pub type Indented<'a> = Stateful<&'a str, Indentation>;
+
+impl Indentation {
+    /// Width of the innermost block
+    pub fn current(&self) -> usize {
+        self.levels.last().copied().unwrap_or_default()
+    }
+
+    /// Number of blocks entered
+    pub fn depth(&self) -> usize {
+        self.levels.len()
+    }
+}
+
+/// Width of the indentation at the start of the input, each space or tab counts as one column
+fn width(input: &str) -> usize {
+    input.len() - input.trim_start_matches([' ', '\t']).len()
+}
+
+/// Skips the lines made only of spaces and tabs
+fn skip_blank_lines(mut input: &str) -> &str {
+    while let Some(end) = input.find('\n') {
+        if !input[..end]
+            .trim_start_matches([' ', '\t', '\r'])
+            .is_empty()
+        {
+            break;
+        }
+        input = &input[end + 1..];
+    }
+    input
+}
+
+fn indentation_error<'a>(input: Indented<'a>, reason: &'static str) -> ParserError<Indented<'a>> {
+    ParserError::new(width(input.input), ErrorSource::State, reason)
+}
+
+/// Consumes the indentation of the current line if it matches the innermost block
+pub fn same_indent<'a>() -> impl Parser<Indented<'a>, Output = usize> {
+    move |input: Indented<'a>| {
+        let indentation = width(input.input);
+        if indentation != input.state.current() {
+            return Err(indentation_error(input, "expected the same indentation"));
+        }
+        let remainder = Stateful::new(&input.input[indentation..], input.state);
+        Ok((remainder, indentation))
+    }
+}
+
+/// Consumes the indentation of the current line if it is deeper than the innermost block
+pub fn deeper_indent<'a>() -> impl Parser<Indented<'a>, Output = usize> {
+    move |input: Indented<'a>| {
+        let indentation = width(input.input);
+        if indentation <= input.state.current() {
+            return Err(indentation_error(input, "expected a deeper indentation"));
+        }
+        let remainder = Stateful::new(&input.input[indentation..], input.state);
+        Ok((remainder, indentation))
+    }
+}
+
+/// Parses one or more lines indented deeper than the enclosing block and at the same width as
+/// the first one, as in Python blocks or YAML block collections. `parser` starts after the
+/// indentation and is expected to consume its line break. Blank lines are skipped and the
+/// block ends at the first line with a different indentation
+///
+/// # Examples
+/// ```rust
+///
+/// use pepser::parser::impls::{sequence, take_while1};
+/// use pepser::parser::indent::{indented_block, Indentation};
+/// use pepser::parser::stateful::{lift, Stateful};
+/// use pepser::parser::traits::Parser;
+/// let item = lift(sequence("- ").and(take_while1(char::is_alphanumeric)).and(sequence("\n")))
+///     .map(|((_, item), _)| item);
+/// let mut list = lift(sequence("items:\n")).and(indented_block(item));
+///
+/// let (remainder, (_, items)) = list
+///     .parse(Stateful::new("items:\n  - a\n\n  - b\nnext", Indentation::default()))
+///     .unwrap();
+/// assert_eq!(items, vec!["a", "b"]);
+/// assert_eq!(remainder.input, "next");
+///
+///
+/// ```
+pub fn indented_block<'a, P>(mut parser: P) -> impl Parser<Indented<'a>, Output = Vec<P::Output>>
+where
+    P: Parser<Indented<'a>>,
+{
+    move |input: Indented<'a>| {
+        let Stateful {
+            input: start,
+            mut state,
+        } = input;
+        let mut text = skip_blank_lines(start);
+        let block = width(text);
+        if block <= state.current() {
+            return Err(ParserError::new(
+                start.len() - text.len() + block,
+                ErrorSource::State,
+                "expected an indented block",
+            ));
+        }
+        state.levels.push(block);
+
+        let mut items = vec![];
+        loop {
+            if !items.is_empty() {
+                let next = skip_blank_lines(text);
+                if next.is_empty() || width(next) != block {
+                    break;
+                }
+                text = next;
+            }
+            let (remainder, item) = parser.parse(Stateful::new(&text[block..], state))?;
+            items.push(item);
+            text = remainder.input;
+            state = remainder.state;
+        }
+
+        state.levels.pop();
+        Ok((Stateful::new(text, state), items))
+    }
+}
+
+/// Runs `parser` on indented input, starting at the top level
+pub fn parse_indented<'a, P>(mut parser: P, input: &'a str) -> ParseResult<Indented<'a>, P::Output>
+where
+    P: Parser<Indented<'a>>,
+{
+    parser.parse(Stateful::new(input, Indentation::default()))
+}
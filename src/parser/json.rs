@@ -0,0 +1,1457 @@
+use std::{
+    borrow::Cow,
+    cell::{Cell, RefCell},
+    collections::{hash_map::DefaultHasher, HashMap},
+    error::Error,
+    fmt::{self, Display, Formatter},
+    hash::{Hash, Hasher},
+};
+
+use super::{
+    errors::{ErrorSource, ParserError},
+    impls::{
+        any, any_char, eof, escaped_transform, none_of, sequence, take_while1, take_while_m_n, ws,
+    },
+    traits::{
+        discard, dispatch, opt, parse_if, sep_by, separated_pair, value, wrapped, ParseResult,
+        Parser,
+    },
+};
+
+/// A parsed JSON document. Values are `Eq` and `Hash` so that they can key maps, see
+/// [`JsonNumber`] for how numbers compare. Objects are equal when they have the same members
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum JsonValue {
+    Array(Vec<JsonValue>),
+    Boolean(bool),
+    String(String),
+    Number(JsonNumber),
+    Object(HashMap<String, JsonValue>),
+    Null,
+}
+
+/// A JSON number. Integers without a fraction or an exponent are kept exact when they fit in
+/// 64 bits, as `Int` or as `UInt` above `i64::MAX`, every other number is a `Float`
+///
+/// Numbers compare by value whatever their representation, `1`, `1.0` and `1e0` are equal, as
+/// are `-0.0` and `0.0`. Unlike `f64`, every NaN is equal to every other NaN so that numbers are
+/// `Eq` and `Hash`
+///
+/// # Examples
+/// ```rust
+///
+/// use pepser::parser::json::{json_value, JsonNumber, JsonValue};
+/// let (_, id) = json_value("18446744073709551615").unwrap();
+///
+/// assert_eq!(id, JsonValue::Number(JsonNumber::UInt(u64::MAX)));
+/// assert_eq!(JsonNumber::Float(2.0), JsonNumber::Int(2));
+/// assert_eq!(JsonNumber::Float(2.5).as_i64(), None);
+/// assert_eq!(JsonNumber::Int(-1).to_string(), "-1");
+///
+///
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub enum JsonNumber {
+    Int(i64),
+    UInt(u64),
+    Float(f64),
+}
+
+impl JsonNumber {
+    /// The closest `f64`, integers beyond 2^53 may lose precision
+    pub fn as_f64(self) -> f64 {
+        match self {
+            JsonNumber::Int(int) => int as f64,
+            JsonNumber::UInt(uint) => uint as f64,
+            JsonNumber::Float(float) => float,
+        }
+    }
+
+    /// The number as an `i64`, when it is an integer in range. Floats without a fractional
+    /// part are integers
+    pub fn as_i64(self) -> Option<i64> {
+        self.integer()
+            .and_then(|integer| i64::try_from(integer).ok())
+    }
+
+    /// The number as a `u64`, when it is a non negative integer in range
+    pub fn as_u64(self) -> Option<u64> {
+        self.integer()
+            .and_then(|integer| u64::try_from(integer).ok())
+    }
+
+    /// The exact value of an integer, floats are integers when they have no fractional part
+    /// and lie in the range of `i64` or `u64`
+    fn integer(self) -> Option<i128> {
+        match self {
+            JsonNumber::Int(int) => Some(int.into()),
+            JsonNumber::UInt(uint) => Some(uint.into()),
+            JsonNumber::Float(float)
+                if float.fract() == 0.0 && float >= i64::MIN as f64 && float < u64::MAX as f64 =>
+            {
+                Some(float as i128)
+            }
+            JsonNumber::Float(_) => None,
+        }
+    }
+
+    /// The value of an integer, otherwise the bits of the float with a single NaN
+    fn canonical(self) -> Result<i128, u64> {
+        match (self.integer(), self) {
+            (Some(integer), _) => Ok(integer),
+            (None, JsonNumber::Float(float)) if float.is_nan() => Err(f64::NAN.to_bits()),
+            (None, number) => Err(number.as_f64().to_bits()),
+        }
+    }
+}
+
+impl From<i64> for JsonNumber {
+    fn from(int: i64) -> Self {
+        JsonNumber::Int(int)
+    }
+}
+
+/// `UInt` is only used above `i64::MAX`
+impl From<u64> for JsonNumber {
+    fn from(uint: u64) -> Self {
+        i64::try_from(uint).map_or(JsonNumber::UInt(uint), JsonNumber::Int)
+    }
+}
+
+impl From<f64> for JsonNumber {
+    fn from(float: f64) -> Self {
+        JsonNumber::Float(float)
+    }
+}
+
+impl PartialEq for JsonNumber {
+    fn eq(&self, other: &Self) -> bool {
+        self.canonical() == other.canonical()
+    }
+}
+
+impl Eq for JsonNumber {}
+
+impl Hash for JsonNumber {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.canonical().hash(state)
+    }
+}
+
+/// Integers are written exactly and floats with the shortest representation which parses back
+/// to the same `f64`. JSON has no infinities nor NaN, they are written as `null`
+impl Display for JsonNumber {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            JsonNumber::Int(int) => write!(f, "{int}"),
+            JsonNumber::UInt(uint) => write!(f, "{uint}"),
+            JsonNumber::Float(float) if !float.is_finite() => write!(f, "null"),
+            JsonNumber::Float(float) => write!(f, "{float:?}"),
+        }
+    }
+}
+
+impl Hash for JsonValue {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        core::mem::discriminant(self).hash(state);
+        match self {
+            JsonValue::Array(values) => values.hash(state),
+            JsonValue::Boolean(boolean) => boolean.hash(state),
+            JsonValue::String(string) => string.hash(state),
+            JsonValue::Number(number) => number.hash(state),
+            // The members of a map have no order, so their hashes are combined commutatively
+            JsonValue::Object(members) => {
+                let combined = members.iter().fold(0u64, |combined, member| {
+                    let mut hasher = DefaultHasher::new();
+                    member.hash(&mut hasher);
+                    combined.wrapping_add(hasher.finish())
+                });
+                state.write_usize(members.len());
+                state.write_u64(combined);
+            }
+            JsonValue::Null => {}
+        }
+    }
+}
+
+/// Writes the value as compact JSON, which [`json_value`] parses back to an equal value.
+/// Object members are written in the order of their keys, so that equal values are written
+/// the same
+///
+/// # Examples
+/// ```rust
+///
+/// use pepser::parser::json::json_value;
+/// let (_, value) = json_value(r#"[9007199254740993, 0.5, "a\"b\n"]"#).unwrap();
+///
+/// assert_eq!(value.to_string(), r#"[9007199254740993,0.5,"a\"b\n"]"#);
+/// assert_eq!(json_value(r#"{"b": 1, "a": 2}"#).unwrap().1.to_string(), r#"{"a":2,"b":1}"#);
+///
+///
+/// ```
+impl Display for JsonValue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            JsonValue::Array(values) => {
+                write!(f, "[")?;
+                for (index, value) in values.iter().enumerate() {
+                    if index > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{value}")?;
+                }
+                write!(f, "]")
+            }
+            JsonValue::Boolean(boolean) => write!(f, "{boolean}"),
+            JsonValue::String(string) => write_string(f, string),
+            JsonValue::Number(number) => write!(f, "{number}"),
+            JsonValue::Object(members) => {
+                let mut members: Vec<_> = members.iter().collect();
+                members.sort_unstable_by_key(|(key, _)| *key);
+                write!(f, "{{")?;
+                for (index, (key, value)) in members.into_iter().enumerate() {
+                    if index > 0 {
+                        write!(f, ",")?;
+                    }
+                    write_string(f, key)?;
+                    write!(f, ":{value}")?;
+                }
+                write!(f, "}}")
+            }
+            JsonValue::Null => write!(f, "null"),
+        }
+    }
+}
+
+/// Control characters without a short escape are written as `\u` escapes
+fn write_string(f: &mut Formatter<'_>, string: &str) -> fmt::Result {
+    write!(f, "\"")?;
+    for c in string.chars() {
+        match c {
+            '"' => write!(f, "\\\"")?,
+            '\\' => write!(f, "\\\\")?,
+            '\n' => write!(f, "\\n")?,
+            '\r' => write!(f, "\\r")?,
+            '\t' => write!(f, "\\t")?,
+            '\u{000C}' => write!(f, "\\f")?,
+            '\u{0008}' => write!(f, "\\b")?,
+            c if c.is_ascii_control() => write!(f, "\\u{:04x}", c as u32)?,
+            c => write!(f, "{c}")?,
+        }
+    }
+    write!(f, "\"")
+}
+
+impl JsonValue {
+    /// Looks up a value with a JSON Pointer (RFC 6901) such as `/tests/0/data`, the empty
+    /// pointer refers to the whole document
+    pub fn pointer(&self, pointer: &str) -> Option<&JsonValue> {
+        if pointer.is_empty() {
+            return Some(self);
+        }
+        pointer
+            .strip_prefix('/')?
+            .split('/')
+            .map(|token| token.replace("~1", "/").replace("~0", "~"))
+            .try_fold(self, |value, token| match value {
+                JsonValue::Object(members) => members.get(&token),
+                JsonValue::Array(values) => {
+                    let digits = token.bytes().all(|b| b.is_ascii_digit());
+                    if !digits || token.len() > 1 && token.starts_with('0') {
+                        return None;
+                    }
+                    values.get(token.parse::<usize>().ok()?)
+                }
+                _ => None,
+            })
+    }
+}
+
+/// A JSON value borrowing from the parsed text, see [`json_value_ref`]. Strings without escapes
+/// are slices of the input and objects are vectors of members in document order, so parsing
+/// allocates neither a `String` per string nor a `HashMap` per object
+#[derive(Debug, PartialEq, Clone)]
+pub enum JsonValueRef<'a> {
+    Array(Vec<JsonValueRef<'a>>),
+    Boolean(bool),
+    String(Cow<'a, str>),
+    Number(JsonNumber),
+    Object(Vec<(Cow<'a, str>, JsonValueRef<'a>)>),
+    Null,
+}
+
+impl JsonValueRef<'_> {
+    /// Converts to a [`JsonValue`], when a key is repeated the last member wins as in
+    /// [`json_value`]
+    pub fn into_owned(self) -> JsonValue {
+        match self {
+            JsonValueRef::Array(values) => {
+                JsonValue::Array(values.into_iter().map(JsonValueRef::into_owned).collect())
+            }
+            JsonValueRef::Boolean(boolean) => JsonValue::Boolean(boolean),
+            JsonValueRef::String(string) => JsonValue::String(string.into_owned()),
+            JsonValueRef::Number(number) => JsonValue::Number(number),
+            JsonValueRef::Object(members) => JsonValue::Object(
+                members
+                    .into_iter()
+                    .map(|(key, value)| (key.into_owned(), value.into_owned()))
+                    .collect(),
+            ),
+            JsonValueRef::Null => JsonValue::Null,
+        }
+    }
+
+    /// The value of the last member named `key` of an object
+    pub fn get(&self, key: &str) -> Option<&Self> {
+        match self {
+            JsonValueRef::Object(members) => members
+                .iter()
+                .rev()
+                .find_map(|(name, value)| (name == key).then_some(value)),
+            _ => None,
+        }
+    }
+}
+
+/// Failure of [`from_json`] and [`FromJson`] conversions
+#[derive(Debug, PartialEq, Clone)]
+pub enum JsonError {
+    /// The text is not a single JSON document
+    Parse { index: usize, message: String },
+    /// The value at the JSON Pointer `pointer` is not of the `expected` type
+    Type {
+        pointer: String,
+        expected: &'static str,
+    },
+}
+
+impl JsonError {
+    fn mismatch(expected: &'static str) -> Self {
+        JsonError::Type {
+            pointer: String::new(),
+            expected,
+        }
+    }
+
+    /// Prefixes the pointer of a type error with the token of the enclosing array element or
+    /// object member
+    fn within(self, token: &str) -> Self {
+        match self {
+            JsonError::Type { pointer, expected } => JsonError::Type {
+                pointer: format!(
+                    "/{}{}",
+                    token.replace('~', "~0").replace('/', "~1"),
+                    pointer
+                ),
+                expected,
+            },
+            error => error,
+        }
+    }
+}
+
+impl Display for JsonError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            JsonError::Parse { index, message } => write!(f, "{} at index {}", message, index),
+            JsonError::Type { pointer, expected } if pointer.is_empty() => {
+                write!(f, "expected {}", expected)
+            }
+            JsonError::Type { pointer, expected } => {
+                write!(f, "expected {} at {}", expected, pointer)
+            }
+        }
+    }
+}
+
+impl Error for JsonError {}
+
+/// Conversion of a parsed JSON value into a Rust type
+///
+/// # Examples
+/// ```rust
+///
+/// use std::collections::HashMap;
+/// use pepser::parser::json::{from_json, JsonError};
+/// let scores: HashMap<String, Vec<Option<u8>>> = from_json(r#"{"a": [1, null, 3]}"#).unwrap();
+///
+/// assert_eq!(scores["a"], vec![Some(1), None, Some(3)]);
+/// assert_eq!(
+///     from_json::<Vec<u8>>("[1, 300]"),
+///     Err(JsonError::Type { pointer: "/1".to_string(), expected: "u8" })
+/// );
+///
+///
+/// ```
+pub trait FromJson: Sized {
+    fn from_json(value: &JsonValue) -> Result<Self, JsonError>;
+}
+
+/// Parses a whole JSON document, surrounding whitespace aside, and converts it to `T`
+pub fn from_json<T: FromJson>(input: &str) -> Result<T, JsonError> {
+    let (_, value) = json_value
+        .skip(discard(ws(), eof()))
+        .parse(input)
+        .map_err(|error| JsonError::Parse {
+            index: error.index,
+            message: error.message().into_owned(),
+        })?;
+    T::from_json(&value)
+}
+
+impl FromJson for bool {
+    fn from_json(value: &JsonValue) -> Result<Self, JsonError> {
+        match value {
+            JsonValue::Boolean(boolean) => Ok(*boolean),
+            _ => Err(JsonError::mismatch("a boolean")),
+        }
+    }
+}
+
+impl FromJson for f64 {
+    fn from_json(value: &JsonValue) -> Result<Self, JsonError> {
+        match value {
+            JsonValue::Number(number) => Ok(number.as_f64()),
+            _ => Err(JsonError::mismatch("a number")),
+        }
+    }
+}
+
+impl FromJson for f32 {
+    fn from_json(value: &JsonValue) -> Result<Self, JsonError> {
+        f64::from_json(value).map(|number| number as f32)
+    }
+}
+
+/// Integers only accept numbers without a fractional part which fit in the type
+macro_rules! from_json_integer {
+    ($($integer:ident)*) => {$(
+        impl FromJson for $integer {
+            fn from_json(value: &JsonValue) -> Result<Self, JsonError> {
+                match value {
+                    JsonValue::Number(number) => number
+                        .integer()
+                        .and_then(|integer| $integer::try_from(integer).ok())
+                        .ok_or_else(|| JsonError::mismatch(stringify!($integer))),
+                    _ => Err(JsonError::mismatch(stringify!($integer))),
+                }
+            }
+        }
+    )*};
+}
+
+from_json_integer!(i8 i16 i32 i64 isize u8 u16 u32 u64 usize);
+
+impl FromJson for String {
+    fn from_json(value: &JsonValue) -> Result<Self, JsonError> {
+        match value {
+            JsonValue::String(string) => Ok(string.clone()),
+            _ => Err(JsonError::mismatch("a string")),
+        }
+    }
+}
+
+/// `null` is `None`, any other value is converted to `T`
+impl<T: FromJson> FromJson for Option<T> {
+    fn from_json(value: &JsonValue) -> Result<Self, JsonError> {
+        match value {
+            JsonValue::Null => Ok(None),
+            value => T::from_json(value).map(Some),
+        }
+    }
+}
+
+impl<T: FromJson> FromJson for Vec<T> {
+    fn from_json(value: &JsonValue) -> Result<Self, JsonError> {
+        match value {
+            JsonValue::Array(values) => values
+                .iter()
+                .enumerate()
+                .map(|(index, value)| {
+                    T::from_json(value).map_err(|error| error.within(&index.to_string()))
+                })
+                .collect(),
+            _ => Err(JsonError::mismatch("an array")),
+        }
+    }
+}
+
+impl<T: FromJson> FromJson for HashMap<String, T> {
+    fn from_json(value: &JsonValue) -> Result<Self, JsonError> {
+        let JsonValue::Object(members) = value else {
+            return Err(JsonError::mismatch("an object"));
+        };
+        // members are converted in key order so that the reported error does not depend on
+        // the iteration order of the map
+        let mut keys: Vec<&String> = members.keys().collect();
+        keys.sort();
+        keys.into_iter()
+            .map(|key| {
+                T::from_json(&members[key])
+                    .map(|value| (key.clone(), value))
+                    .map_err(|error| error.within(key))
+            })
+            .collect()
+    }
+}
+
+/// Conversion of a Rust value into a JSON value, used by [`json!`](crate::json!) for the
+/// values it interpolates
+pub trait ToJson {
+    fn to_json(&self) -> JsonValue;
+}
+
+impl ToJson for JsonValue {
+    fn to_json(&self) -> JsonValue {
+        self.clone()
+    }
+}
+
+impl<T: ToJson + ?Sized> ToJson for &T {
+    fn to_json(&self) -> JsonValue {
+        T::to_json(self)
+    }
+}
+
+impl ToJson for bool {
+    fn to_json(&self) -> JsonValue {
+        JsonValue::Boolean(*self)
+    }
+}
+
+/// Integers are converted exactly through the widest type of their signedness
+macro_rules! to_json_number {
+    ($($number:ident as $widest:ident)*) => {$(
+        impl ToJson for $number {
+            fn to_json(&self) -> JsonValue {
+                JsonValue::Number(JsonNumber::from(*self as $widest))
+            }
+        }
+    )*};
+}
+
+to_json_number!(
+    f32 as f64 f64 as f64
+    i8 as i64 i16 as i64 i32 as i64 i64 as i64 isize as i64
+    u8 as u64 u16 as u64 u32 as u64 u64 as u64 usize as u64
+);
+
+impl ToJson for str {
+    fn to_json(&self) -> JsonValue {
+        JsonValue::String(self.to_string())
+    }
+}
+
+impl ToJson for String {
+    fn to_json(&self) -> JsonValue {
+        JsonValue::String(self.clone())
+    }
+}
+
+/// `None` is `null`
+impl<T: ToJson> ToJson for Option<T> {
+    fn to_json(&self) -> JsonValue {
+        self.as_ref().map_or(JsonValue::Null, T::to_json)
+    }
+}
+
+impl<T: ToJson> ToJson for [T] {
+    fn to_json(&self) -> JsonValue {
+        JsonValue::Array(self.iter().map(T::to_json).collect())
+    }
+}
+
+impl<T: ToJson> ToJson for Vec<T> {
+    fn to_json(&self) -> JsonValue {
+        self.as_slice().to_json()
+    }
+}
+
+impl<T: ToJson> ToJson for HashMap<String, T> {
+    fn to_json(&self) -> JsonValue {
+        JsonValue::Object(
+            self.iter()
+                .map(|(key, value)| (key.clone(), value.to_json()))
+                .collect(),
+        )
+    }
+}
+
+/// Deepest nesting of arrays and objects accepted by default, so that a hostile document
+/// cannot exhaust the stack
+const MAX_DEPTH: usize = 128;
+
+/// Bounds on the documents accepted by [`json_limited`], to parse untrusted input without
+/// unbounded recursion or allocation. The default only bounds the depth, to 128 levels
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Limits {
+    /// Nesting depth of arrays and objects, the content of a top level array is at depth 1.
+    /// Above the default, deep documents may exhaust the stack
+    pub max_depth: usize,
+    /// Length in bytes of a decoded string, object keys included
+    pub max_string_length: usize,
+    /// Number of values in the document, nested ones included
+    pub max_elements: usize,
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Limits {
+            max_depth: MAX_DEPTH,
+            max_string_length: usize::MAX,
+            max_elements: usize::MAX,
+        }
+    }
+}
+
+/// Errors met by [`json_resilient`], indices are absolute offsets in the parsed input
+struct Diagnostics<'a> {
+    input_len: usize,
+    errors: RefCell<Vec<ParserError<&'a str>>>,
+}
+
+impl<'a> Diagnostics<'a> {
+    fn push(&self, input: &'a str, error: ParserError<&'a str>) {
+        self.errors
+            .borrow_mut()
+            .push(ParserError::from_error(error, self.input_len - input.len()));
+    }
+}
+
+/// State shared by the parsers of one document
+struct Context<'d, 'a> {
+    /// Set in resilient mode only
+    diagnostics: Option<&'d Diagnostics<'a>>,
+    limits: Limits,
+    input_len: usize,
+    depth: Cell<usize>,
+    elements: Cell<usize>,
+    /// The first limit exceeded, it fails the whole document as `or` would otherwise try the
+    /// next alternative and report its error instead
+    exceeded: RefCell<Option<ParserError<&'a str>>>,
+}
+
+impl<'d, 'a> Context<'d, 'a> {
+    fn new(input: &'a str, diagnostics: Option<&'d Diagnostics<'a>>, limits: Limits) -> Self {
+        Context {
+            diagnostics,
+            limits,
+            input_len: input.len(),
+            depth: Cell::new(0),
+            elements: Cell::new(0),
+            exceeded: RefCell::new(None),
+        }
+    }
+
+    /// Fails when `count` is above `limit`, recording the error at the offset of `input`
+    fn check(
+        &self,
+        input: &'a str,
+        count: usize,
+        limit: usize,
+        reason: &'static str,
+    ) -> Result<(), ParserError<&'a str>> {
+        if count <= limit {
+            return Ok(());
+        }
+        let mut exceeded = self.exceeded.borrow_mut();
+        let index = self.input_len - input.len();
+        exceeded.get_or_insert_with(|| ParserError::new(index, ErrorSource::LimitExceeded, reason));
+        Err(ParserError::new(0, ErrorSource::LimitExceeded, reason))
+    }
+
+    /// Replaces the result of the document by the exceeded limit, if any
+    fn finish<O>(&self, result: ParseResult<&'a str, O>) -> ParseResult<&'a str, O> {
+        match self.exceeded.borrow_mut().take() {
+            Some(error) => Err(error),
+            None => result,
+        }
+    }
+}
+
+/// Parses a whole document without stopping at the first error: invalid array elements and
+/// object members are skipped up to the next `,` or closing bracket, missing closing brackets
+/// are assumed. Returns the partial document along with every error met, ordered by position
+pub fn json_resilient(input: &str) -> (Option<JsonValue>, Vec<ParserError<&str>>) {
+    let diagnostics = Diagnostics {
+        input_len: input.len(),
+        errors: RefCell::new(vec![]),
+    };
+    let context = Context::new(input, Some(&diagnostics), Limits::default());
+    let parsed = match value_with(&context, input) {
+        Ok((remainder, parsed)) => {
+            let remainder = remainder.trim_start();
+            if !remainder.is_empty() {
+                diagnostics.push(
+                    remainder,
+                    ParserError::new(0, ErrorSource::EOF, "unexpected input after the value"),
+                );
+            }
+            Some(parsed)
+        }
+        Err(error) => {
+            diagnostics.push(input, error);
+            None
+        }
+    };
+    let mut errors = diagnostics.errors.into_inner();
+    errors.sort_by_key(|error| error.index);
+    (parsed, errors)
+}
+
+/// Parses a value within the given limits, failing with [`ErrorSource::LimitExceeded`] at
+/// the position where one is exceeded
+///
+/// # Examples
+/// ```rust
+///
+/// use pepser::parser::errors::ErrorSource;
+/// use pepser::parser::json::{json_limited, Limits};
+/// let limits = Limits { max_depth: 2, ..Limits::default() };
+/// let error = json_limited(limits)("[1, [2, [3]]]").unwrap_err();
+///
+/// assert!(json_limited(limits)("[1, [2]]").is_ok());
+/// assert_eq!(error.source, ErrorSource::LimitExceeded);
+/// assert_eq!(error.index, 8);
+///
+///
+/// ```
+pub fn json_limited(limits: Limits) -> impl Fn(&str) -> ParseResult<&str, JsonValue> {
+    move |input| {
+        let context = Context::new(input, None, limits);
+        context.finish(value_with(&context, input))
+    }
+}
+
+pub fn json_object(input: &str) -> ParseResult<&str, JsonValue> {
+    let context = Context::new(input, None, Limits::default());
+    context.finish(object_with(&context, input))
+}
+
+pub fn json_pair(input: &str) -> ParseResult<&str, (String, JsonValue)> {
+    let context = Context::new(input, None, Limits::default());
+    context.finish(pair_with(&context, input))
+}
+
+pub fn json_value(input: &str) -> ParseResult<&str, JsonValue> {
+    let context = Context::new(input, None, Limits::default());
+    context.finish(value_with(&context, input))
+}
+
+pub fn array(input: &str) -> ParseResult<&str, JsonValue> {
+    let context = Context::new(input, None, Limits::default());
+    context.finish(array_with(&context, input))
+}
+
+pub fn null(input: &str) -> ParseResult<&str, JsonValue> {
+    sequence("null").map(|_| JsonValue::Null).parse(input)
+}
+
+fn object_with<'a>(context: &Context<'_, 'a>, input: &'a str) -> ParseResult<&'a str, JsonValue> {
+    let start = input;
+    let (input, _) = sequence("{").parse(input)?;
+    if context.diagnostics.is_some() {
+        if let Ok((input, _)) = discard(ws(), sequence("}")).parse(input) {
+            return Ok((input, JsonValue::Object(HashMap::new())));
+        }
+    }
+    let (input, pairs) = nested(context, start, input, |input| {
+        sep_by(
+            |input| recover(context, ",}", input, |input| pair_with(context, input)),
+            sequence(","),
+        )
+        .parse(input)
+    })
+    .map_err(|error| ParserError::from_error(error, start.len() - input.len()))?;
+    let (input, _) = closing(context, "}", input)
+        .map_err(|error| ParserError::from_error(error, start.len() - input.len()))?;
+    Ok((
+        input,
+        JsonValue::Object(pairs.into_iter().flatten().collect()),
+    ))
+}
+
+fn pair_with<'a>(
+    context: &Context<'_, 'a>,
+    input: &'a str,
+) -> ParseResult<&'a str, (String, JsonValue)> {
+    wrapped(
+        ws(),
+        separated_pair(
+            |input| string_with(context, input),
+            wrapped(ws(), sequence(":"), ws()),
+            |input| value_with(context, input),
+        ),
+        ws(),
+    )
+    .parse(input)
+}
+
+fn value_with<'a>(context: &Context<'_, 'a>, input: &'a str) -> ParseResult<&'a str, JsonValue> {
+    if context.exceeded.borrow().is_some() {
+        return Err(ParserError::new(
+            0,
+            ErrorSource::LimitExceeded,
+            "a limit was exceeded",
+        ));
+    }
+    let start = input;
+    let (input, _) = ws().parse(input)?;
+    let elements = context.elements.get() + 1;
+    context.elements.set(elements);
+    context.check(
+        input,
+        elements,
+        context.limits.max_elements,
+        "too many elements",
+    )?;
+    dispatch(any_char(), |c, input| match c {
+        'n' => null(input),
+        't' | 'f' => boolean(input),
+        '[' => array_with(context, input),
+        '{' => object_with(context, input),
+        '"' => {
+            string_with(context, input).map(|(input, string)| (input, JsonValue::String(string)))
+        }
+        _ => json_number(input),
+    })
+    .parse(input)
+    .map_err(|error| ParserError::from_error(error, start.len() - input.len()))
+}
+
+fn array_with<'a>(context: &Context<'_, 'a>, input: &'a str) -> ParseResult<&'a str, JsonValue> {
+    let start = input;
+    let (input, _) = sequence("[").and(ws()).parse(input)?;
+    if context.diagnostics.is_some() && input.starts_with(']') {
+        return Ok((&input[1..], JsonValue::Array(vec![])));
+    }
+    let (input, values) = nested(context, start, input, |input| {
+        wrapped(
+            ws(),
+            sep_by(
+                |input| recover(context, ",]", input, |input| value_with(context, input)),
+                discard(ws(), sequence(",")),
+            ),
+            ws(),
+        )
+        .parse(input)
+    })
+    .map_err(|error| ParserError::from_error(error, start.len() - input.len()))?;
+    let (input, _) = closing(context, "]", input)
+        .map_err(|error| ParserError::from_error(error, start.len() - input.len()))?;
+    Ok((
+        input,
+        JsonValue::Array(values.into_iter().flatten().collect()),
+    ))
+}
+
+/// Parses the content of an array or object one level deeper, `start` is its opening bracket
+fn nested<'a, O>(
+    context: &Context<'_, 'a>,
+    start: &'a str,
+    input: &'a str,
+    parser: impl FnOnce(&'a str) -> ParseResult<&'a str, O>,
+) -> ParseResult<&'a str, O> {
+    let depth = context.depth.get() + 1;
+    context.check(start, depth, context.limits.max_depth, "too deeply nested")?;
+    context.depth.set(depth);
+    let result = parser(input);
+    context.depth.set(depth - 1);
+    result
+}
+
+/// Runs `parser`, in resilient mode its errors are recorded and the input is skipped up to one
+/// of the `sync` characters
+fn recover<'a, O, P>(
+    context: &Context<'_, 'a>,
+    sync: &'a str,
+    input: &'a str,
+    parser: P,
+) -> ParseResult<&'a str, Option<O>>
+where
+    P: Parser<&'a str, Output = O>,
+{
+    let Some(diagnostics) = context.diagnostics else {
+        return parser.map(Some).parse(input);
+    };
+    let (input, _) = ws().parse(input)?;
+    let (remainder, parsed) = parser.recover_with(any(sync)).parse(input)?;
+    match parsed {
+        Ok(parsed) => Ok((remainder, Some(parsed))),
+        Err(error) => {
+            diagnostics.push(input, error);
+            Ok((remainder, None))
+        }
+    }
+}
+
+/// Parses a closing bracket, in resilient mode a missing one is recorded and assumed
+fn closing<'a>(
+    context: &Context<'_, 'a>,
+    bracket: &'a str,
+    input: &'a str,
+) -> ParseResult<&'a str, ()> {
+    match discard(ws(), sequence(bracket)).parse(input) {
+        Ok((input, _)) => Ok((input, ())),
+        Err(error) => match context.diagnostics {
+            Some(diagnostics) => {
+                diagnostics.push(
+                    input,
+                    ParserError::new(0, error.source, "missing closing bracket"),
+                );
+                Ok((input, ()))
+            }
+            None => Err(error),
+        },
+    }
+}
+
+/// Parses a value like [`json_value`], borrowing its strings from `input`
+///
+/// # Examples
+/// ```rust
+///
+/// use std::borrow::Cow;
+/// use pepser::parser::json::{json_value, json_value_ref, JsonValueRef};
+/// let input = r#"{"name": "pepser", "quote": "\"hi\""}"#;
+/// let (_, value) = json_value_ref(input).unwrap();
+///
+/// assert!(matches!(value.get("name"), Some(JsonValueRef::String(Cow::Borrowed("pepser")))));
+/// assert!(matches!(value.get("quote"), Some(JsonValueRef::String(Cow::Owned(_)))));
+/// assert_eq!(value.into_owned(), json_value(input).unwrap().1);
+///
+///
+/// ```
+pub fn json_value_ref(input: &str) -> ParseResult<&str, JsonValueRef<'_>> {
+    value_ref(input, 0)
+}
+
+/// A value within `depth` arrays and objects
+fn value_ref(input: &str, depth: usize) -> ParseResult<&str, JsonValueRef<'_>> {
+    let value = dispatch(any_char(), |c, input| match c {
+        'n' => sequence("null").map(|_| JsonValueRef::Null).parse(input),
+        't' | 'f' => sequence("true")
+            .or(sequence("false"))
+            .map(|boolean| JsonValueRef::Boolean(boolean == "true"))
+            .parse(input),
+        '[' => array_ref(input, depth + 1),
+        '{' => object_ref(input, depth + 1),
+        '"' => string_ref.map(JsonValueRef::String).parse(input),
+        _ => number.map(JsonValueRef::Number).parse(input),
+    });
+    discard(ws(), value).parse(input)
+}
+
+fn array_ref(input: &str, depth: usize) -> ParseResult<&str, JsonValueRef<'_>> {
+    if depth > MAX_DEPTH {
+        return Err(too_deep(0));
+    }
+    wrapped(
+        sequence("[").and(ws()),
+        sep_by(
+            |input| value_ref(input, depth),
+            discard(ws(), sequence(",")),
+        ),
+        discard(ws(), sequence("]")),
+    )
+    .map(JsonValueRef::Array)
+    .parse(input)
+}
+
+fn object_ref(input: &str, depth: usize) -> ParseResult<&str, JsonValueRef<'_>> {
+    if depth > MAX_DEPTH {
+        return Err(too_deep(0));
+    }
+    wrapped(
+        sequence("{"),
+        sep_by(|input| pair_ref(input, depth), sequence(",")),
+        discard(ws(), sequence("}")),
+    )
+    .map(JsonValueRef::Object)
+    .parse(input)
+}
+
+fn pair_ref(input: &str, depth: usize) -> ParseResult<&str, (Cow<'_, str>, JsonValueRef<'_>)> {
+    wrapped(
+        ws(),
+        separated_pair(string_ref, wrapped(ws(), sequence(":"), ws()), |input| {
+            value_ref(input, depth)
+        }),
+        ws(),
+    )
+    .parse(input)
+}
+
+/// The committed error of an array or object nested deeper than [`MAX_DEPTH`], so that the
+/// enclosing ones do not backtrack over it
+fn too_deep<'a>(index: usize) -> ParserError<&'a str> {
+    let mut error = ParserError::new(index, ErrorSource::LimitExceeded, "too deeply nested");
+    error.committed = true;
+    error
+}
+
+/// Borrows the content of a string without escapes, the others are unescaped by [`string`]
+fn string_ref(input: &str) -> ParseResult<&str, Cow<'_, str>> {
+    if let Some(content) = input.strip_prefix('"') {
+        if let Some(end) = content.find(['"', '\\']) {
+            if content[end..].starts_with('"') {
+                return Ok((&content[end + 1..], Cow::Borrowed(&content[..end])));
+            }
+        }
+    }
+    string.map(Cow::Owned).parse(input)
+}
+
+/// A token of a JSON document, as yielded by [`JsonEvents`]
+#[derive(Debug, PartialEq, Clone)]
+pub enum JsonEvent<'a> {
+    StartObject,
+    /// The key of the object member whose value follows
+    Key(Cow<'a, str>),
+    EndObject,
+    StartArray,
+    EndArray,
+    String(Cow<'a, str>),
+    Number(JsonNumber),
+    Boolean(bool),
+    Null,
+}
+
+/// What the next token of [`JsonEvents`] may be
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Expecting {
+    Value,
+    /// A value or the closing bracket, after `[`
+    FirstElement,
+    /// A key or the closing brace, after `{`
+    FirstKey,
+    Key,
+    /// A comma or a closing bracket, the end of the input at the top level
+    Separator,
+    Done,
+}
+
+/// Pulls the tokens of a document one at a time without building a tree, so that memory only
+/// grows with the nesting depth. Errors are indexed from the start of the document and end
+/// the iteration, as does input left after the top level value
+///
+/// # Examples
+/// ```rust
+///
+/// use pepser::parser::json::{JsonEvent, JsonEvents, JsonNumber};
+/// let mut events = JsonEvents::new(r#"{"id": 7, "tags": ["a", null]}"#);
+///
+/// let id = events
+///     .by_ref()
+///     .skip_while(|event| *event != Ok(JsonEvent::Key("id".into())))
+///     .nth(1);
+/// assert_eq!(id, Some(Ok(JsonEvent::Number(JsonNumber::Int(7)))));
+/// assert_eq!(events.next(), Some(Ok(JsonEvent::Key("tags".into()))));
+/// assert_eq!(events.next(), Some(Ok(JsonEvent::StartArray)));
+/// assert_eq!(JsonEvents::new("[1 2]").last().unwrap().unwrap_err().index, 3);
+///
+///
+/// ```
+pub struct JsonEvents<'a> {
+    input: &'a str,
+    remainder: &'a str,
+    /// The open containers, `true` for objects
+    nesting: Vec<bool>,
+    expecting: Expecting,
+}
+
+impl<'a> JsonEvents<'a> {
+    pub fn new(input: &'a str) -> Self {
+        JsonEvents {
+            input,
+            remainder: input,
+            nesting: vec![],
+            expecting: Expecting::Value,
+        }
+    }
+
+    /// Runs `parser` on the remainder, offsetting its errors from the start of the document
+    fn parse<O>(
+        &mut self,
+        mut parser: impl Parser<&'a str, Output = O>,
+    ) -> Result<O, ParserError<&'a str>> {
+        let offset = self.input.len() - self.remainder.len();
+        let (remainder, parsed) = parser
+            .parse(self.remainder)
+            .map_err(|error| ParserError::from_error(error, offset))?;
+        self.remainder = remainder;
+        Ok(parsed)
+    }
+
+    fn value(&mut self) -> Result<JsonEvent<'a>, ParserError<&'a str>> {
+        self.expecting = Expecting::Separator;
+        let (event, opened) = self.parse(dispatch(any_char(), |c, input| match c {
+            '{' => Ok((&input[1..], (JsonEvent::StartObject, Some(true)))),
+            '[' => Ok((&input[1..], (JsonEvent::StartArray, Some(false)))),
+            '"' => string_ref
+                .map(|string| (JsonEvent::String(string), None))
+                .parse(input),
+            'n' => sequence("null")
+                .map(|_| (JsonEvent::Null, None))
+                .parse(input),
+            't' | 'f' => sequence("true")
+                .or(sequence("false"))
+                .map(|boolean| (JsonEvent::Boolean(boolean == "true"), None))
+                .parse(input),
+            _ => number
+                .map(|number| (JsonEvent::Number(number), None))
+                .parse(input),
+        }))?;
+        if let Some(object) = opened {
+            self.nesting.push(object);
+            self.expecting = match object {
+                true => Expecting::FirstKey,
+                false => Expecting::FirstElement,
+            };
+        }
+        Ok(event)
+    }
+
+    fn key(&mut self) -> Result<JsonEvent<'a>, ParserError<&'a str>> {
+        let key = self.parse(string_ref.skip(discard(ws(), sequence(":"))))?;
+        self.expecting = Expecting::Value;
+        Ok(JsonEvent::Key(key))
+    }
+
+    /// Closes the innermost container if the remainder starts with its bracket
+    fn close(&mut self) -> Option<JsonEvent<'a>> {
+        let (bracket, event) = match self.nesting.last()? {
+            true => ('}', JsonEvent::EndObject),
+            false => (']', JsonEvent::EndArray),
+        };
+        self.remainder = self.remainder.strip_prefix(bracket)?;
+        self.nesting.pop();
+        self.expecting = Expecting::Separator;
+        Some(event)
+    }
+
+    fn step(&mut self) -> Option<Result<JsonEvent<'a>, ParserError<&'a str>>> {
+        self.remainder = self.remainder.trim_start();
+        match self.expecting {
+            Expecting::Done => return None,
+            Expecting::Value => return Some(self.value()),
+            Expecting::Key => return Some(self.key()),
+            Expecting::FirstElement => return Some(self.close().map_or_else(|| self.value(), Ok)),
+            Expecting::FirstKey => return Some(self.close().map_or_else(|| self.key(), Ok)),
+            Expecting::Separator => {}
+        }
+        if let Some(event) = self.close() {
+            return Some(Ok(event));
+        }
+        let error = match self.nesting.last() {
+            None if self.remainder.is_empty() => {
+                self.expecting = Expecting::Done;
+                return None;
+            }
+            None => ParserError::new(0, ErrorSource::EOF, "unexpected input after the value"),
+            Some(object) => match self.remainder.strip_prefix(',') {
+                Some(remainder) => {
+                    self.remainder = remainder;
+                    self.expecting = match object {
+                        true => Expecting::Key,
+                        false => Expecting::Value,
+                    };
+                    return self.step();
+                }
+                None => ParserError::new(0, ErrorSource::Sequence(","), "expected a comma"),
+            },
+        };
+        let offset = self.input.len() - self.remainder.len();
+        Some(Err(ParserError::from_error(error, offset)))
+    }
+}
+
+impl<'a> JsonEvents<'a> {
+    /// The next event, running out of events inside of a value is an error
+    fn next_event(&mut self) -> Result<JsonEvent<'a>, ParserError<&'a str>> {
+        self.next().unwrap_or_else(|| {
+            Err(ParserError::new(
+                self.input.len(),
+                ErrorSource::EOF,
+                "unexpected end of input",
+            ))
+        })
+    }
+
+    /// Builds the value which starts with `event`, up to [`MAX_DEPTH`] levels deep
+    fn build(&mut self, event: JsonEvent<'a>) -> Result<JsonValueRef<'a>, ParserError<&'a str>> {
+        if self.nesting.len() > MAX_DEPTH {
+            let index = self.input.len() - self.remainder.len();
+            return Err(too_deep(index - 1));
+        }
+        Ok(match event {
+            JsonEvent::StartArray => {
+                let mut values = vec![];
+                loop {
+                    match self.next_event()? {
+                        JsonEvent::EndArray => break JsonValueRef::Array(values),
+                        event => values.push(self.build(event)?),
+                    }
+                }
+            }
+            JsonEvent::StartObject => {
+                let mut members = vec![];
+                while let JsonEvent::Key(key) = self.next_event()? {
+                    let event = self.next_event()?;
+                    members.push((key, self.build(event)?));
+                }
+                JsonValueRef::Object(members)
+            }
+            JsonEvent::String(string) => JsonValueRef::String(string),
+            JsonEvent::Number(number) => JsonValueRef::Number(number),
+            JsonEvent::Boolean(boolean) => JsonValueRef::Boolean(boolean),
+            JsonEvent::Null => JsonValueRef::Null,
+            JsonEvent::Key(_) | JsonEvent::EndObject | JsonEvent::EndArray => {
+                unreachable!("values start with a value event")
+            }
+        })
+    }
+
+    /// Skips the rest of the value which started with `event`. Containers are skipped by
+    /// scanning for their closing bracket, only the strings and the balance of the brackets
+    /// are checked
+    fn skip(&mut self, event: JsonEvent<'a>) -> Result<(), ParserError<&'a str>> {
+        if !matches!(event, JsonEvent::StartArray | JsonEvent::StartObject) {
+            return Ok(());
+        }
+        let bytes = self.remainder.as_bytes();
+        let (mut position, mut depth) = (0, 1);
+        while depth > 0 {
+            match bytes.get(position) {
+                None => {
+                    return Err(ParserError::new(
+                        self.input.len(),
+                        ErrorSource::EOF,
+                        "unexpected end of input",
+                    ))
+                }
+                Some(b'"') => {
+                    position += 1;
+                    while let Some(&byte) = bytes.get(position) {
+                        match byte {
+                            b'\\' => position += 2,
+                            b'"' => break,
+                            _ => position += 1,
+                        }
+                    }
+                }
+                Some(b'[' | b'{') => depth += 1,
+                Some(b']' | b'}') => depth -= 1,
+                Some(_) => {}
+            }
+            position += 1;
+        }
+        self.remainder = &self.remainder[position..];
+        self.nesting.pop();
+        self.expecting = Expecting::Separator;
+        Ok(())
+    }
+
+    /// Collects the values matched by `segments` in the value which started with `event`
+    fn extract(
+        &mut self,
+        event: JsonEvent<'a>,
+        segments: &[String],
+        matched: &mut Vec<JsonValueRef<'a>>,
+    ) -> Result<(), ParserError<&'a str>> {
+        let Some((segment, segments)) = segments.split_first() else {
+            matched.push(self.build(event)?);
+            return Ok(());
+        };
+        if !matches!(event, JsonEvent::StartArray | JsonEvent::StartObject) {
+            return Ok(());
+        }
+        let mut index = 0;
+        loop {
+            let (selected, event) = match self.next_event()? {
+                JsonEvent::EndArray | JsonEvent::EndObject => return Ok(()),
+                JsonEvent::Key(key) => (segment == "*" || *segment == key, self.next_event()?),
+                event => {
+                    index += 1;
+                    let selected = segment == "*" || *segment == (index - 1).to_string();
+                    (selected, event)
+                }
+            };
+            match selected {
+                true => self.extract(event, segments, matched)?,
+                false => self.skip(event)?,
+            }
+        }
+    }
+}
+
+/// Parses the values of a document matched by `path`, a JSON Pointer (RFC 6901) where a `*`
+/// segment matches every member of an object or element of an array. The subtrees which
+/// cannot match are skipped without being parsed, so they are only checked for balanced
+/// brackets. Like [`JsonValue::pointer`], a path not starting with `/` matches nothing
+///
+/// # Examples
+/// ```rust
+///
+/// use pepser::parser::json::{json_extract, JsonValueRef};
+/// let input = r#"{"tests": [{"valid": true, "data": [[1], "]"]}, {"valid": false}], "x": {}}"#;
+/// let (_, valid) = json_extract(input, "/tests/*/valid").unwrap();
+///
+/// assert_eq!(valid, vec![JsonValueRef::Boolean(true), JsonValueRef::Boolean(false)]);
+/// assert_eq!(json_extract(input, "/tests/1").unwrap().1.len(), 1);
+/// assert_eq!(json_extract(input, "").unwrap().1.len(), 1);
+///
+///
+/// ```
+pub fn json_extract<'a>(input: &'a str, path: &str) -> ParseResult<&'a str, Vec<JsonValueRef<'a>>> {
+    let segments = match path.strip_prefix('/') {
+        _ if path.is_empty() => vec![],
+        Some(pointer) => pointer
+            .split('/')
+            .map(|token| token.replace("~1", "/").replace("~0", "~"))
+            .collect(),
+        None => return Ok((input, vec![])),
+    };
+    let mut events = JsonEvents::new(input);
+    let mut matched = vec![];
+    let event = events.next_event()?;
+    events.extract(event, &segments, &mut matched)?;
+    Ok((events.remainder, matched))
+}
+
+impl<'a> Iterator for JsonEvents<'a> {
+    type Item = Result<JsonEvent<'a>, ParserError<&'a str>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let event = self.step();
+        if let Some(Err(_)) = event {
+            self.expecting = Expecting::Done;
+        }
+        event
+    }
+}
+
+/// The escape following a backslash
+fn escaped(input: &str) -> ParseResult<&str, Cow<'_, str>> {
+    sequence("\\")
+        .or(sequence("\""))
+        .or(sequence("/"))
+        .or(sequence("n").map(|_| "\n"))
+        .or(sequence("t").map(|_| "\t"))
+        .or(sequence("r").map(|_| "\r"))
+        .or(sequence("f").map(|_| "\u{000C}"))
+        .or(sequence("b").map(|_| "\u{0008}"))
+        .map(Cow::Borrowed)
+        .or(unicode_escape.map(|c| Cow::Owned(c.to_string())))
+        .parse(input)
+}
+
+/// `uXXXX`, the UTF-16 code unit of a character. A high surrogate is followed by `\uXXXX`
+/// with the low surrogate of the pair
+fn unicode_escape(input: &str) -> ParseResult<&str, char> {
+    let code_unit = || {
+        discard(
+            sequence("u"),
+            take_while_m_n(4, 4, |c| c.is_ascii_hexdigit()),
+        )
+        .map(|hex| u32::from_str_radix(hex, 16).unwrap_or_default())
+    };
+    let (remainder, high) = code_unit().parse(input)?;
+    let (remainder, code) = match high {
+        0xD800..=0xDBFF => {
+            let low = discard(sequence("\\"), code_unit())
+                .parse(remainder)
+                .ok()
+                .filter(|(_, low)| (0xDC00..=0xDFFF).contains(low));
+            match low {
+                Some((remainder, low)) => {
+                    (remainder, 0x10000 + (high - 0xD800) * 0x400 + low - 0xDC00)
+                }
+                None => (remainder, high),
+            }
+        }
+        code => (remainder, code),
+    };
+    match char::from_u32(code) {
+        Some(c) => Ok((remainder, c)),
+        None => Err(ParserError::new(0, ErrorSource::Char, "unpaired surrogate")),
+    }
+}
+
+pub fn string(input: &str) -> ParseResult<&str, String> {
+    wrapped(
+        sequence("\""),
+        escaped_transform(none_of("\"\\"), '\\', escaped),
+        sequence("\""),
+    )
+    .parse(input)
+}
+
+fn string_with<'a>(context: &Context<'_, 'a>, input: &'a str) -> ParseResult<&'a str, String> {
+    let (remainder, string) = string(input)?;
+    context.check(
+        input,
+        string.len(),
+        context.limits.max_string_length,
+        "string too long",
+    )?;
+    Ok((remainder, string))
+}
+
+pub fn boolean(input: &str) -> ParseResult<&str, JsonValue> {
+    sequence("true")
+        .or(sequence("false"))
+        .map(|str_bool| JsonValue::Boolean(str_bool == "true"))
+        .parse(input)
+}
+
+pub fn json_number(input: &str) -> ParseResult<&str, JsonValue> {
+    number.map(JsonValue::Number).parse(input)
+}
+
+/// Integers are parsed exactly when they fit, other numbers are rounded to the nearest `f64`
+fn number(input: &str) -> ParseResult<&str, JsonNumber> {
+    let (remainder, ((_, fraction), exponent)) = opt(sequence("-"))
+        .and(sequence("0").or(digits))
+        .and(parse_if(sequence("."), digits))
+        .and(exponent)
+        .parse(input)?;
+    let text = &input[..input.len() - remainder.len()];
+    if fraction.is_none() && exponent.is_none() {
+        if let Ok(int) = text.parse::<i64>() {
+            return Ok((remainder, JsonNumber::Int(int)));
+        }
+        if let Ok(uint) = text.parse::<u64>() {
+            return Ok((remainder, JsonNumber::UInt(uint)));
+        }
+    }
+    match text.parse::<f64>() {
+        Ok(float) => Ok((remainder, JsonNumber::Float(float))),
+        Err(_) => Err(ParserError::new(
+            0,
+            ErrorSource::TakeWhile,
+            "invalid number",
+        )),
+    }
+}
+
+/// An incomplete exponent is left unparsed, one beyond `i32` is an error
+fn exponent(input: &str) -> ParseResult<&str, Option<i32>> {
+    let sign = value(-1, sequence("-")).or(value(1_i32, sequence("+")));
+    let Ok((remainder, (sign, exponent))) = discard(any("eE"), opt(sign).and(digits)).parse(input)
+    else {
+        return Ok((input, None));
+    };
+    match exponent.parse::<i32>() {
+        Ok(exponent) => Ok((remainder, Some(sign.unwrap_or(1) * exponent))),
+        Err(_) => Err(ParserError::new(
+            input.len() - remainder.len() - exponent.len(),
+            ErrorSource::TakeWhile,
+            "exponent overflow",
+        )),
+    }
+}
+
+pub fn digits(input: &str) -> ParseResult<&str, &str> {
+    take_while1(|c| c.is_ascii_digit()).parse(input)
+}
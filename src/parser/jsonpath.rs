@@ -0,0 +1,142 @@
+use super::{
+    errors::ParserError,
+    impls::{eof, none_of, sequence, take_while1},
+    json::JsonValue,
+    traits::{discard, opt, wrapped, ParseResult, Parser},
+};
+
+/// A compiled JSONPath query such as `$.tests[*].valid` or `$..name`. Supported selectors
+/// are member names (`.name`, `['name']`), array indices (`[0]`, `[-1]` from the end),
+/// wildcards (`.*`, `[*]`) and recursive descent (`..name`, `..*`)
+#[derive(Debug, PartialEq, Clone)]
+pub struct JsonPath {
+    segments: Vec<Segment>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+enum Segment {
+    Child(Selector),
+    Descendant(Selector),
+}
+
+#[derive(Debug, PartialEq, Clone)]
+enum Selector {
+    Name(String),
+    Index(i64),
+    Wildcard,
+}
+
+impl JsonPath {
+    pub fn new(path: &str) -> Result<JsonPath, ParserError<&str>> {
+        let (_, path) = json_path.skip(eof()).parse(path)?;
+        Ok(path)
+    }
+
+    /// Returns every value matched by the query, in document order for arrays
+    pub fn query<'v>(&self, value: &'v JsonValue) -> Vec<&'v JsonValue> {
+        self.segments
+            .iter()
+            .fold(vec![value], |values, segment| match segment {
+                Segment::Child(selector) => values
+                    .into_iter()
+                    .flat_map(|value| selector.select(value))
+                    .collect(),
+                Segment::Descendant(selector) => values
+                    .into_iter()
+                    .flat_map(descendants)
+                    .flat_map(|value| selector.select(value))
+                    .collect(),
+            })
+    }
+}
+
+impl Selector {
+    fn select<'v>(&self, value: &'v JsonValue) -> Vec<&'v JsonValue> {
+        match (self, value) {
+            (Selector::Name(name), JsonValue::Object(members)) => {
+                members.get(name).into_iter().collect()
+            }
+            (Selector::Index(index), JsonValue::Array(values)) => {
+                let index = if *index < 0 {
+                    values.len().checked_sub(index.unsigned_abs() as usize)
+                } else {
+                    Some(*index as usize)
+                };
+                index
+                    .and_then(|index| values.get(index))
+                    .into_iter()
+                    .collect()
+            }
+            (Selector::Wildcard, JsonValue::Object(members)) => members.values().collect(),
+            (Selector::Wildcard, JsonValue::Array(values)) => values.iter().collect(),
+            _ => vec![],
+        }
+    }
+}
+
+/// The value itself followed by all of its nested values
+fn descendants(value: &JsonValue) -> Vec<&JsonValue> {
+    let mut found = vec![value];
+    match value {
+        JsonValue::Object(members) => found.extend(members.values().flat_map(descendants)),
+        JsonValue::Array(values) => found.extend(values.iter().flat_map(descendants)),
+        _ => {}
+    }
+    found
+}
+
+/// Parses a JSONPath query, see [`JsonPath`] for the supported syntax
+pub fn json_path(input: &str) -> ParseResult<&str, JsonPath> {
+    discard(sequence("$"), segment.many0())
+        .map(|segments| JsonPath { segments })
+        .parse(input)
+}
+
+fn segment(input: &str) -> ParseResult<&str, Segment> {
+    discard(sequence(".."), name.or(wildcard).or(bracket))
+        .map(Segment::Descendant)
+        .or(discard(sequence("."), name.or(wildcard)).map(Segment::Child))
+        .or(bracket.map(Segment::Child))
+        .parse(input)
+}
+
+fn name(input: &str) -> ParseResult<&str, Selector> {
+    take_while1(|c| c.is_alphanumeric() || c == '_' || c == '-')
+        .map(|name| Selector::Name(name.to_string()))
+        .parse(input)
+}
+
+fn wildcard(input: &str) -> ParseResult<&str, Selector> {
+    sequence("*").map(|_| Selector::Wildcard).parse(input)
+}
+
+fn bracket(input: &str) -> ParseResult<&str, Selector> {
+    wrapped(
+        sequence("["),
+        wildcard.or(index).or(quoted("'")).or(quoted("\"")),
+        sequence("]"),
+    )
+    .parse(input)
+}
+
+fn index(input: &str) -> ParseResult<&str, Selector> {
+    opt(sequence("-"))
+        .and(take_while1(|c| c.is_ascii_digit()))
+        .map(|(sign, digits)| {
+            let index = digits.parse::<i64>().unwrap_or(i64::MAX);
+            Selector::Index(if sign.is_some() { -index } else { index })
+        })
+        .parse(input)
+}
+
+fn quoted<'a>(quote: &'a str) -> impl Parser<&'a str, Output = Selector> {
+    move |input: &'a str| {
+        wrapped(
+            sequence(quote),
+            opt(none_of(quote)).map(Option::unwrap_or_default),
+            sequence(quote),
+        )
+        .map(|name| Selector::Name(name.to_string()))
+        .parse(input)
+    }
+}
@@ -0,0 +1,122 @@
+use alloc::{vec, vec::Vec};
+
+pub use super::span::{Span, Spanned};
+use super::{
+    errors::{ErrorSource, ParserError},
+    impls::satisfy_token,
+    traits::{BoxedParser, Parser},
+};
+
+/// A lexeme along with the kind of the rule which matched it
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Token<'a, K> {
+    pub kind: K,
+    pub text: &'a str,
+}
+
+/// Splits text into tokens using one parser per token kind. At each position the longest
+/// match wins, ties go to the rule declared first so that keywords can be declared before
+/// identifiers. Trivia (whitespace, comments) is skipped between tokens
+pub struct Lexer<'a, K> {
+    rules: Vec<(K, BoxedParser<'a, &'a str, ()>)>,
+    trivia: Vec<BoxedParser<'a, &'a str, ()>>,
+}
+
+impl<'a, K> Default for Lexer<'a, K> {
+    fn default() -> Self {
+        Lexer {
+            rules: vec![],
+            trivia: vec![],
+        }
+    }
+}
+
+impl<'a, K: Clone> Lexer<'a, K> {
+    pub fn new() -> Self {
+        Lexer::default()
+    }
+
+    /// Adds a token kind, its lexeme is the input consumed by the parser
+    pub fn token<P>(mut self, kind: K, parser: P) -> Self
+    where
+        P: Parser<&'a str> + 'a,
+    {
+        self.rules.push((kind, parser.map(|_| ()).boxed()));
+        self
+    }
+
+    /// Adds a parser for input to skip between tokens
+    pub fn trivia<P>(mut self, parser: P) -> Self
+    where
+        P: Parser<&'a str> + 'a,
+    {
+        self.trivia.push(parser.map(|_| ()).boxed());
+        self
+    }
+
+    /// Tokenizes the whole input, failing at the first position where no rule matches
+    pub fn tokenize(
+        &mut self,
+        input: &'a str,
+    ) -> Result<Vec<Spanned<Token<'a, K>>>, ParserError<&'a str>> {
+        let mut tokens = vec![];
+        let mut position = self.skip_trivia(input, 0);
+        while position < input.len() {
+            let rest = &input[position..];
+            let (kind, len) = self
+                .rules
+                .iter_mut()
+                .filter_map(|(kind, rule)| {
+                    let (remainder, _) = rule.parse(rest).ok()?;
+                    Some((kind, rest.len() - remainder.len()))
+                })
+                .fold(
+                    None,
+                    |longest: Option<(&mut K, usize)>, (kind, len)| match longest {
+                        Some(longest) if longest.1 >= len => Some(longest),
+                        _ => Some((kind, len)),
+                    },
+                )
+                .filter(|(_, len)| *len > 0)
+                .ok_or_else(|| {
+                    ParserError::new(position, ErrorSource::Lexer, "no token matches")
+                })?;
+            tokens.push(Spanned {
+                span: Span {
+                    start: position,
+                    end: position + len,
+                },
+                value: Token {
+                    kind: kind.clone(),
+                    text: &rest[..len],
+                },
+            });
+            position = self.skip_trivia(input, position + len);
+        }
+        Ok(tokens)
+    }
+
+    fn skip_trivia(&mut self, input: &'a str, mut position: usize) -> usize {
+        loop {
+            let rest = &input[position..];
+            let skipped = self.trivia.iter_mut().find_map(|trivia| {
+                let (remainder, _) = trivia.parse(rest).ok()?;
+                Some(rest.len() - remainder.len()).filter(|len| *len > 0)
+            });
+            match skipped {
+                Some(len) => position += len,
+                None => return position,
+            }
+        }
+    }
+}
+
+/// Parses a single token of the given kind from the output of [`Lexer::tokenize`]
+pub fn kind<'t, 'a: 't, K>(
+    kind: K,
+) -> impl Parser<&'t [Spanned<Token<'a, K>>], Output = &'t Spanned<Token<'a, K>>>
+where
+    K: PartialEq + 't,
+{
+    satisfy_token(move |token: &Spanned<Token<'a, K>>| token.value.kind == kind)
+}
@@ -0,0 +1,324 @@
+use alloc::{string::String, vec::Vec};
+use core::net::IpAddr;
+
+use super::{
+    datetime::{days_in_month, rfc3339, time, Date, DateTime, UtcOffset},
+    errors::{ErrorSource, ParserError},
+    impls::{fixed_digits, sequence, take_while1},
+    net::ip_addr,
+    traits::{parse_rest, ParseResult, Parser},
+};
+
+/// An RFC 5424 syslog message, the fields logged as `-` are `None`
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct SyslogMessage<'a> {
+    pub facility: u8,
+    pub severity: u8,
+    pub version: u8,
+    pub timestamp: Option<DateTime>,
+    pub hostname: Option<&'a str>,
+    pub app_name: Option<&'a str>,
+    pub proc_id: Option<&'a str>,
+    pub msg_id: Option<&'a str>,
+    pub structured_data: Vec<SdElement<'a>>,
+    pub message: Option<&'a str>,
+}
+
+/// A `[id name="value" ...]` element of the structured data, the values are unescaped
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct SdElement<'a> {
+    pub id: &'a str,
+    pub params: Vec<(&'a str, String)>,
+}
+
+/// A line of an Apache access log in the common or the combined format, the fields logged
+/// as `-` are `None`. The request, referer and user agent are kept as logged, with their
+/// escapes
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct AccessLogEntry<'a> {
+    pub client: IpAddr,
+    pub ident: Option<&'a str>,
+    pub user: Option<&'a str>,
+    pub timestamp: DateTime,
+    pub request: &'a str,
+    pub status: u16,
+    pub size: Option<u64>,
+    /// Only logged by the combined format
+    pub referer: Option<&'a str>,
+    /// Only logged by the combined format
+    pub user_agent: Option<&'a str>,
+}
+
+const MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+fn invalid<'a>(index: usize, reason: &'static str) -> ParserError<&'a str> {
+    ParserError::new(index, ErrorSource::TakeWhile, reason)
+}
+
+/// Parses a syslog message (RFC 5424) up to the end of its line, the message is the text
+/// following the structured data
+///
+/// # Examples
+/// ```rust
+///
+/// use pepser::parser::logs::syslog;
+/// let line = r#"<165>1 2003-10-11T22:14:15.003Z mymachine evntslog - ID47 [exampleSDID@32473 iut="3" eventID="1011"] An application event"#;
+/// let (_, message) = syslog(line).unwrap();
+///
+/// assert_eq!((message.facility, message.severity), (20, 5));
+/// assert_eq!(message.hostname, Some("mymachine"));
+/// assert_eq!(message.proc_id, None);
+/// assert_eq!(message.structured_data[0].params[1], ("eventID", "1011".to_string()));
+/// assert_eq!(message.message, Some("An application event"));
+/// assert_eq!(syslog("<192>1 - - - - - -").unwrap_err().index, 1);
+///
+///
+/// ```
+pub fn syslog(input: &str) -> ParseResult<&str, SyslogMessage<'_>> {
+    let (remainder, _) = sequence("<").parse(input)?;
+    let (remainder, priority) =
+        parse_rest(&mut take_while1(|c| c.is_ascii_digit()), &input, remainder)?;
+    let priority = match priority.parse::<u8>() {
+        Ok(value) if value < 192 && priority.len() <= 3 => value,
+        _ => return Err(invalid(1, "invalid priority")),
+    };
+    let (remainder, _) = parse_rest(&mut sequence(">"), &input, remainder)?;
+    let offset = input.len() - remainder.len();
+    let (remainder, version) =
+        parse_rest(&mut take_while1(|c| c.is_ascii_digit()), &input, remainder)?;
+    let version = match version.parse::<u8>() {
+        Ok(version) if version > 0 && version < 100 => version,
+        _ => return Err(invalid(offset, "invalid version")),
+    };
+
+    let (remainder, _) = parse_rest(&mut sequence(" "), &input, remainder)?;
+    let (remainder, timestamp) = match remainder.strip_prefix('-') {
+        Some(remainder) => (remainder, None),
+        None => {
+            let (remainder, timestamp) = parse_rest(&mut rfc3339, &input, remainder)?;
+            (remainder, Some(timestamp))
+        }
+    };
+    let mut fields = [None; 4];
+    let mut remainder = remainder;
+    for (field, max_length) in fields.iter_mut().zip([255, 48, 128, 32]) {
+        let (next, _) = parse_rest(&mut sequence(" "), &input, remainder)?;
+        let offset = input.len() - next.len();
+        let (next, value) = parse_rest(&mut take_while1(|c| c.is_ascii_graphic()), &input, next)?;
+        if value.len() > max_length {
+            return Err(invalid(offset, "header field too long"));
+        }
+        *field = Some(value).filter(|value| *value != "-");
+        remainder = next;
+    }
+    let [hostname, app_name, proc_id, msg_id] = fields;
+
+    let (remainder, _) = parse_rest(&mut sequence(" "), &input, remainder)?;
+    let (remainder, structured_data) = match remainder.strip_prefix('-') {
+        Some(remainder) => (remainder, Vec::new()),
+        None => {
+            let mut elements = Vec::new();
+            let mut remainder = remainder;
+            while remainder.starts_with('[') || elements.is_empty() {
+                let (next, element) = parse_rest(&mut sd_element, &input, remainder)?;
+                elements.push(element);
+                remainder = next;
+            }
+            (remainder, elements)
+        }
+    };
+    let (remainder, message) = match remainder.strip_prefix(' ') {
+        Some(text) => {
+            let end = text.find(['\r', '\n']).unwrap_or(text.len());
+            (
+                &text[end..],
+                Some(text[..end].trim_start_matches('\u{feff}')),
+            )
+        }
+        None => (remainder, None),
+    };
+    Ok((
+        remainder,
+        SyslogMessage {
+            facility: priority / 8,
+            severity: priority % 8,
+            version,
+            timestamp,
+            hostname,
+            app_name,
+            proc_id,
+            msg_id,
+            structured_data,
+            message,
+        },
+    ))
+}
+
+/// Parses `[id name="value" ...]`, where `\"`, `\\` and `\]` are escapes in values
+fn sd_element(input: &str) -> ParseResult<&str, SdElement<'_>> {
+    let mut name = |input| {
+        take_while1(|c: char| c.is_ascii_graphic() && !matches!(c, '=' | ']' | '"')).parse(input)
+    };
+    let (remainder, _) = sequence("[").parse(input)?;
+    let (mut remainder, id) = parse_rest(&mut name, &input, remainder)?;
+    let mut params = Vec::new();
+    while let Some(next) = remainder.strip_prefix(' ') {
+        let (next, param) = parse_rest(&mut name, &input, next)?;
+        let (next, _) = parse_rest(&mut sequence("=\""), &input, next)?;
+        let mut value = String::new();
+        let mut chars = next.char_indices();
+        let end = loop {
+            match chars.next() {
+                Some((position, '"')) => break position,
+                Some((_, '\\')) => match chars.clone().next() {
+                    Some((_, escaped @ ('"' | '\\' | ']'))) => {
+                        value.push(escaped);
+                        chars.next();
+                    }
+                    _ => value.push('\\'),
+                },
+                Some((_, c)) => value.push(c),
+                None => return Err(invalid(input.len(), "unterminated parameter value")),
+            }
+        };
+        params.push((param, value));
+        remainder = &next[end + 1..];
+    }
+    let (remainder, _) = parse_rest(&mut sequence("]"), &input, remainder)?;
+    Ok((remainder, SdElement { id, params }))
+}
+
+/// Parses a line of an access log in the common log format,
+/// `client ident user [timestamp] "request" status size`
+///
+/// # Examples
+/// ```rust
+///
+/// use pepser::parser::logs::common_log;
+/// let line = r#"127.0.0.1 - frank [10/Oct/2000:13:55:36 -0700] "GET /apache_pb.gif HTTP/1.0" 200 2326"#;
+/// let (_, entry) = common_log(line).unwrap();
+///
+/// assert_eq!(entry.user, Some("frank"));
+/// assert_eq!(entry.timestamp.offset.unwrap().minutes, -420);
+/// assert_eq!(entry.request, "GET /apache_pb.gif HTTP/1.0");
+/// assert_eq!((entry.status, entry.size), (200, Some(2326)));
+///
+///
+/// ```
+pub fn common_log(input: &str) -> ParseResult<&str, AccessLogEntry<'_>> {
+    let (remainder, client) = ip_addr(input)?;
+    let mut fields = [None; 2];
+    let mut remainder = remainder;
+    for field in &mut fields {
+        let (next, _) = parse_rest(&mut sequence(" "), &input, remainder)?;
+        let (next, value) = parse_rest(&mut take_while1(|c| !c.is_whitespace()), &input, next)?;
+        *field = Some(value).filter(|value| *value != "-");
+        remainder = next;
+    }
+    let [ident, user] = fields;
+    let (remainder, _) = parse_rest(&mut sequence(" ["), &input, remainder)?;
+    let (remainder, timestamp) = parse_rest(&mut access_log_timestamp, &input, remainder)?;
+    let (remainder, _) = parse_rest(&mut sequence("] "), &input, remainder)?;
+    let (remainder, request) = parse_rest(&mut quoted, &input, remainder)?;
+    let (remainder, _) = parse_rest(&mut sequence(" "), &input, remainder)?;
+    let offset = input.len() - remainder.len();
+    let (remainder, status) = parse_rest(&mut fixed_digits(3), &input, remainder)?;
+    if !(100..600).contains(&status) {
+        return Err(invalid(offset, "invalid status"));
+    }
+    let (remainder, _) = parse_rest(&mut sequence(" "), &input, remainder)?;
+    let (remainder, size) = match remainder.strip_prefix('-') {
+        Some(remainder) => (remainder, None),
+        None => {
+            let offset = input.len() - remainder.len();
+            let (remainder, size) =
+                parse_rest(&mut take_while1(|c| c.is_ascii_digit()), &input, remainder)?;
+            let size = size.parse().map_err(|_| invalid(offset, "size overflow"))?;
+            (remainder, Some(size))
+        }
+    };
+    Ok((
+        remainder,
+        AccessLogEntry {
+            client,
+            ident,
+            user,
+            timestamp,
+            request,
+            status: status as u16,
+            size,
+            referer: None,
+            user_agent: None,
+        },
+    ))
+}
+
+/// Parses a line of an access log in the combined log format, the common log format followed
+/// by the quoted referer and user agent
+pub fn combined_log(input: &str) -> ParseResult<&str, AccessLogEntry<'_>> {
+    let (remainder, mut entry) = common_log(input)?;
+    let (remainder, _) = parse_rest(&mut sequence(" "), &input, remainder)?;
+    let (remainder, referer) = parse_rest(&mut quoted, &input, remainder)?;
+    let (remainder, _) = parse_rest(&mut sequence(" "), &input, remainder)?;
+    let (remainder, user_agent) = parse_rest(&mut quoted, &input, remainder)?;
+    entry.referer = Some(referer).filter(|referer| *referer != "-");
+    entry.user_agent = Some(user_agent).filter(|user_agent| *user_agent != "-");
+    Ok((remainder, entry))
+}
+
+/// Parses text between double quotes, in which Apache escapes quotes with a backslash
+fn quoted(input: &str) -> ParseResult<&str, &str> {
+    let (remainder, _) = sequence("\"").parse(input)?;
+    let mut escaped = false;
+    for (position, c) in remainder.char_indices() {
+        match c {
+            '"' if !escaped => return Ok((&remainder[position + 1..], &remainder[..position])),
+            '\\' => escaped = !escaped,
+            _ => escaped = false,
+        }
+    }
+    Err(invalid(input.len(), "unterminated quote"))
+}
+
+/// Parses `10/Oct/2000:13:55:36 -0700`
+fn access_log_timestamp(input: &str) -> ParseResult<&str, DateTime> {
+    let (remainder, day) = fixed_digits(2).parse(input)?;
+    let (remainder, _) = parse_rest(&mut sequence("/"), &input, remainder)?;
+    let month = MONTHS
+        .iter()
+        .position(|month| remainder.starts_with(month))
+        .ok_or_else(|| invalid(3, "invalid month"))?;
+    let (remainder, _) = parse_rest(&mut sequence("/"), &input, &remainder[3..])?;
+    let (remainder, year) = parse_rest(&mut fixed_digits(4), &input, remainder)?;
+    let (year, month) = (year as u16, month as u8 + 1);
+    if day == 0 || day > u32::from(days_in_month(year, month)) {
+        return Err(invalid(0, "day is out of range"));
+    }
+    let (remainder, _) = parse_rest(&mut sequence(":"), &input, remainder)?;
+    let (remainder, time) = parse_rest(&mut time, &input, remainder)?;
+    let (remainder, _) = parse_rest(&mut sequence(" "), &input, remainder)?;
+    let offset = input.len() - remainder.len();
+    let (remainder, sign) = parse_rest(&mut sequence("+").or(sequence("-")), &input, remainder)?;
+    let (remainder, hours) = parse_rest(&mut fixed_digits(2), &input, remainder)?;
+    let (remainder, minutes) = parse_rest(&mut fixed_digits(2), &input, remainder)?;
+    if hours > 23 || minutes > 59 {
+        return Err(invalid(offset, "offset is out of range"));
+    }
+    let minutes = (hours * 60 + minutes) as i16;
+    Ok((
+        remainder,
+        DateTime {
+            date: Date {
+                year,
+                month,
+                day: day as u8,
+            },
+            time,
+            offset: Some(UtcOffset {
+                minutes: if sign == "-" { -minutes } else { minutes },
+            }),
+        },
+    ))
+}
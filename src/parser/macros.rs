@@ -0,0 +1,245 @@
+/// Defines text parsers as sequences of elements, expanding to the existing combinators. Each
+/// rule becomes a function `fn name(input: &str) -> ParseResult<&str, Output>` and follows
+/// the syntax `name -> Output = elements => action;`, where an element is
+///
+/// - a string literal, matched with [`sequence`](crate::parser::impls::sequence)
+/// - the name of a parser, e.g. another rule
+/// - any parser expression between parentheses, such as `(ws())`
+///
+/// optionally followed by `*` for zero or more repetitions or `?` to make it optional. The
+/// rule outputs the tuple of its element outputs, or the single output of a one-element rule,
+/// and the optional action maps it to the declared output. When a rule fails before
+/// consuming any input its error reason becomes `expected <name>`
+///
+/// # Examples
+/// ```rust
+///
+/// use pepser::parser::impls::{none_of, sequence, take_while1, ws};
+/// use pepser::rule;
+/// rule! {
+///     key -> &str = "\"" (none_of("\"")) "\"" => |(_, key, _)| key;
+///     number -> u32 = (take_while1(|c| c.is_ascii_digit())) => |digits: &str| digits.parse().unwrap();
+///     /// A `"key": 12` pair
+///     pair -> (&str, u32) = key (ws()) ":" (ws()) number => |(key, _, _, _, value)| (key, value);
+///     pairs -> Vec<(&str, u32)> = "{" pair* "}" => |(_, pairs, _)| pairs;
+/// }
+///
+/// assert_eq!(pair("\"a\": 12"), Ok(("", ("a", 12))));
+/// assert_eq!(pairs("{\"a\":1\"b\":2}"), Ok(("", vec![("a", 1), ("b", 2)])));
+/// assert_eq!(pair("12").unwrap_err().reason, "expected pair");
+///
+///
+/// ```
+#[macro_export]
+macro_rules! rule {
+    () => {};
+    ($(#[$attr:meta])* $vis:vis $name:ident -> $output:ty = $($rest:tt)*) => {
+        $crate::__rule_elements! { [$(#[$attr])* $vis $name $output] [] $($rest)* }
+    };
+}
+
+/// Collects the elements of a rule as parser expressions until its action or the end of the
+/// rule
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __rule_elements {
+    ($head:tt [$($elements:tt)*] $literal:literal * $($rest:tt)*) => {
+        $crate::__rule_elements! {
+            $head [$($elements)* ($crate::parser::traits::Parser::many0($crate::parser::impls::sequence($literal)))] $($rest)*
+        }
+    };
+    ($head:tt [$($elements:tt)*] $literal:literal ? $($rest:tt)*) => {
+        $crate::__rule_elements! {
+            $head [$($elements)* ($crate::parser::traits::opt($crate::parser::impls::sequence($literal)))] $($rest)*
+        }
+    };
+    ($head:tt [$($elements:tt)*] $literal:literal $($rest:tt)*) => {
+        $crate::__rule_elements! {
+            $head [$($elements)* ($crate::parser::impls::sequence($literal))] $($rest)*
+        }
+    };
+    ($head:tt [$($elements:tt)*] $parser:ident * $($rest:tt)*) => {
+        $crate::__rule_elements! {
+            $head [$($elements)* ($crate::parser::traits::Parser::many0($parser))] $($rest)*
+        }
+    };
+    ($head:tt [$($elements:tt)*] $parser:ident ? $($rest:tt)*) => {
+        $crate::__rule_elements! {
+            $head [$($elements)* ($crate::parser::traits::opt($parser))] $($rest)*
+        }
+    };
+    ($head:tt [$($elements:tt)*] $parser:ident $($rest:tt)*) => {
+        $crate::__rule_elements! { $head [$($elements)* ($parser)] $($rest)* }
+    };
+    ($head:tt [$($elements:tt)*] ($parser:expr) * $($rest:tt)*) => {
+        $crate::__rule_elements! {
+            $head [$($elements)* ($crate::parser::traits::Parser::many0($parser))] $($rest)*
+        }
+    };
+    ($head:tt [$($elements:tt)*] ($parser:expr) ? $($rest:tt)*) => {
+        $crate::__rule_elements! {
+            $head [$($elements)* ($crate::parser::traits::opt($parser))] $($rest)*
+        }
+    };
+    ($head:tt [$($elements:tt)*] ($parser:expr) $($rest:tt)*) => {
+        $crate::__rule_elements! { $head [$($elements)* ($parser)] $($rest)* }
+    };
+    ($head:tt [$($elements:tt)*] => $action:expr ; $($rest:tt)*) => {
+        $crate::__rule_define! { $head [$($elements)*] |parsed| ($action)(parsed) }
+        $crate::rule! { $($rest)* }
+    };
+    ($head:tt [$($elements:tt)*] ; $($rest:tt)*) => {
+        $crate::__rule_define! { $head [$($elements)*] |parsed| parsed }
+        $crate::rule! { $($rest)* }
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __rule_define {
+    ([$(#[$attr:meta])* $vis:vis $name:ident $output:ty] [$($elements:tt)*] $action:expr) => {
+        $(#[$attr])*
+        $vis fn $name(input: &str) -> $crate::parser::traits::ParseResult<&str, $output> {
+            let start = input;
+            let parsed = 'rule: {
+                Ok($crate::__rule_sequence!(
+                    'rule start input
+                    [v0 v1 v2 v3 v4 v5 v6 v7 v8 v9 v10 v11 v12 v13 v14 v15]
+                    []
+                    $($elements)*
+                ))
+            };
+            match parsed {
+                Ok((remainder, parsed)) => Ok((remainder, ($action)(parsed))),
+                Err(error) if error.index == 0 => {
+                    let mut expected = $crate::parser::errors::ParserError::new(
+                        0,
+                        error.source,
+                        concat!("expected ", stringify!($name)),
+                    );
+                    expected.committed = error.committed;
+                    Err(expected)
+                }
+                Err(error) => Err(error),
+            }
+        }
+    };
+}
+
+/// Parses the elements one after the other, binding their outputs to the names of the pool.
+/// The first error breaks out of the rule, offset from its start
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __rule_sequence {
+    ($label:lifetime $start:ident $input:ident [$value:ident $($pool:ident)*] [$($bound:ident)*] ($parser:expr) $($rest:tt)*) => {{
+        let ($input, $value) = match $crate::parser::traits::Parser::parse(&mut $parser, $input) {
+            Ok(parsed) => parsed,
+            Err(error) => {
+                let offset = $start.len() - $input.len();
+                break $label Err($crate::parser::errors::ParserError::from_error(error, offset));
+            }
+        };
+        $crate::__rule_sequence!($label $start $input [$($pool)*] [$($bound)* $value] $($rest)*)
+    }};
+    ($label:lifetime $start:ident $input:ident [] [$($bound:ident)*] ($parser:expr) $($rest:tt)*) => {
+        compile_error!("a rule has at most 16 elements")
+    };
+    ($label:lifetime $start:ident $input:ident [$($pool:ident)*] [$($bound:ident)*]) => {
+        ($input, ($($bound),*))
+    };
+}
+
+/// Builds a [`JsonValue`](crate::parser::json::JsonValue) with the JSON syntax. `null`, arrays
+/// and objects are written as in JSON, any other value is a Rust expression converted with
+/// [`ToJson`](crate::parser::json::ToJson). Object keys are string literals or expressions
+/// between parentheses, converted with `ToString`
+///
+/// # Examples
+/// ```rust
+///
+/// use pepser::json;
+/// use pepser::parser::json::{json_value, JsonValue};
+/// let name = "pepser";
+/// let value = json!({
+///     "name": name,
+///     "tags": ["parser", null, 1 + 1],
+///     (format!("{}_version", name)): { "major": 0 },
+///     "stable": false,
+/// });
+///
+/// assert_eq!(json_value(r#"{"name": "pepser", "tags": ["parser", null, 2],
+///     "pepser_version": {"major": 0}, "stable": false}"#), Ok(("", value)));
+/// assert_eq!(json!([]), JsonValue::Array(vec![]));
+///
+///
+/// ```
+#[cfg(feature = "std")]
+#[macro_export]
+macro_rules! json {
+    (null) => {
+        $crate::parser::json::JsonValue::Null
+    };
+    ([$($elements:tt)*]) => {
+        $crate::parser::json::JsonValue::Array($crate::__json_array!([] [] $($elements)*))
+    };
+    ({$($members:tt)*}) => {
+        $crate::parser::json::JsonValue::Object($crate::__json_object!([] $($members)*))
+    };
+    ($value:expr) => {
+        $crate::parser::json::ToJson::to_json(&$value)
+    };
+}
+
+/// Splits the elements of an array on the commas, the tokens of the current element are
+/// collected in the second brackets
+#[cfg(feature = "std")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __json_array {
+    ([$($elements:expr,)*] [$($current:tt)+] , $($rest:tt)*) => {
+        $crate::__json_array!([$($elements,)* $crate::json!($($current)+),] [] $($rest)*)
+    };
+    ([$($elements:expr,)*] [$($current:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::__json_array!([$($elements,)*] [$($current)* $next] $($rest)*)
+    };
+    ([$($elements:expr,)*] []) => {
+        ::std::vec![$($elements),*]
+    };
+    ([$($elements:expr,)*] [$($current:tt)+]) => {
+        ::std::vec![$($elements,)* $crate::json!($($current)+)]
+    };
+}
+
+/// Parses the members of an object one key at a time
+#[cfg(feature = "std")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __json_object {
+    ([$($members:expr,)*]) => {
+        ::core::iter::IntoIterator::into_iter([$($members),*]).collect()
+    };
+    ([$($members:expr,)*] $key:tt : $($rest:tt)*) => {
+        $crate::__json_member!([$($members,)*] $key [] $($rest)*)
+    };
+}
+
+/// Collects the tokens of a member value up to the next comma
+#[cfg(feature = "std")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __json_member {
+    ([$($members:expr,)*] $key:tt [$($value:tt)+] , $($rest:tt)*) => {
+        $crate::__json_object!(
+            [$($members,)* (::std::string::ToString::to_string(&$key), $crate::json!($($value)+)),]
+            $($rest)*
+        )
+    };
+    ([$($members:expr,)*] $key:tt [$($value:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::__json_member!([$($members,)*] $key [$($value)* $next] $($rest)*)
+    };
+    ([$($members:expr,)*] $key:tt [$($value:tt)+]) => {
+        $crate::__json_object!(
+            [$($members,)* (::std::string::ToString::to_string(&$key), $crate::json!($($value)+)),]
+        )
+    };
+}
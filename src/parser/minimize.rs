@@ -0,0 +1,58 @@
+use super::{errors::ErrorSource, traits::ParseResult};
+
+/// Shrinks an input on which `parser` fails into a smaller reproducer failing with the same
+/// [`ErrorSource`]. Returns `None` if the parser succeeds on the original input. The parser
+/// is run on new strings, so its output cannot borrow from the input.
+///
+/// The search is a deterministic delta debugging over characters: the same parser and input
+/// always produce the same reproducer
+pub fn minimize<F, O>(mut parser: F, input: &str) -> Option<String>
+where
+    F: FnMut(&str) -> ParseResult<&str, O>,
+{
+    let expected = describe(&parser(input).err()?.source);
+    Some(minimize_by(input, |candidate| {
+        parser(candidate).is_err_and(|error| describe(&error.source) == expected)
+    }))
+}
+
+/// Shrinks `input` while `predicate` holds, assuming it holds for `input` itself.
+/// The predicate decides what "the same failure" means, e.g. matching the error index too
+pub fn minimize_by<P>(input: &str, mut predicate: P) -> String
+where
+    P: FnMut(&str) -> bool,
+{
+    let mut chars: Vec<char> = input.chars().collect();
+    let mut granularity = 2;
+
+    while chars.len() >= 2 {
+        let chunk = chars.len().div_ceil(granularity);
+        let reduced = (0..chars.len()).step_by(chunk).find_map(|start| {
+            let end = (start + chunk).min(chars.len());
+            let candidate: Vec<char> = chars[..start]
+                .iter()
+                .chain(&chars[end..])
+                .copied()
+                .collect();
+            predicate(&candidate.iter().collect::<String>()).then_some(candidate)
+        });
+
+        match reduced {
+            Some(candidate) => {
+                chars = candidate;
+                granularity = (granularity - 1).max(2);
+            }
+            None if granularity >= chars.len() => break,
+            None => granularity = (granularity * 2).min(chars.len()),
+        }
+    }
+
+    if chars.len() == 1 && predicate("") {
+        chars.clear();
+    }
+    chars.into_iter().collect()
+}
+
+fn describe(source: &ErrorSource<&str>) -> String {
+    format!("{:?}", source)
+}
@@ -1,3 +1,65 @@
+#[cfg(feature = "std")]
+pub mod batch;
+pub mod binary;
+pub mod cli;
+pub mod combinator;
+pub mod css;
+pub mod datetime;
+pub mod dns;
+pub mod dotenv;
+pub mod email;
+pub mod encoding;
+pub mod error;
 pub mod errors;
+pub mod git;
+pub mod grammar;
+#[cfg(feature = "std")]
+pub mod http;
+pub mod icalendar;
 pub mod impls;
+pub mod indent;
+#[cfg(feature = "std")]
+pub mod json;
+#[cfg(feature = "std")]
+pub mod jsonpath;
+pub mod lexer;
+pub mod logs;
+mod macros;
+#[cfg(feature = "std")]
+pub mod minimize;
+pub mod mqtt;
+#[cfg(feature = "std")]
+pub mod multipart;
+pub mod net;
+pub mod nmea;
+pub mod prelude;
+pub mod primitive;
+pub mod protobuf;
+#[cfg(feature = "std")]
+pub mod read;
+#[cfg(feature = "std")]
+pub mod regex;
+pub mod resp;
+#[cfg(feature = "std")]
+pub mod robots;
+pub mod semver;
+pub mod sensitive;
+pub mod sexpr;
+pub mod shlex;
+pub mod size;
+pub mod span;
+pub mod sql;
+pub mod stateful;
+#[cfg(feature = "std")]
+pub mod testing;
+#[cfg(feature = "std")]
+pub mod trace;
 pub mod traits;
+#[cfg(feature = "std")]
+pub mod uri;
+pub mod websocket;
+mod xid;
+#[cfg(feature = "std")]
+pub mod xml;
+#[cfg(feature = "std")]
+pub mod yaml;
@@ -0,0 +1,288 @@
+use alloc::vec::Vec;
+use core::str;
+
+use super::{
+    binary::{be_u16, length_data, take_bytes, u8},
+    errors::{ErrorSource, ParserError},
+    traits::{parse_rest, ParseResult},
+};
+
+pub const CONNECT: u8 = 1;
+pub const PUBLISH: u8 = 3;
+pub const SUBSCRIBE: u8 = 8;
+
+/// The fixed header starting every control packet
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct FixedHeader {
+    /// `1` for CONNECT up to `14` for DISCONNECT
+    pub packet_type: u8,
+    /// The low four bits of the first byte
+    pub flags: u8,
+    /// Length of the variable header and the payload which follow
+    pub remaining_length: usize,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Will<'a> {
+    pub topic: &'a str,
+    pub message: &'a [u8],
+    pub qos: u8,
+    pub retain: bool,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Connect<'a> {
+    /// `MQTT`, or `MQIsdp` for MQTT 3.1
+    pub protocol_name: &'a str,
+    /// `4` for MQTT 3.1.1, `3` for MQTT 3.1
+    pub protocol_level: u8,
+    pub clean_session: bool,
+    /// Seconds, `0` disables the keep alive mechanism
+    pub keep_alive: u16,
+    pub client_id: &'a str,
+    pub will: Option<Will<'a>>,
+    pub username: Option<&'a str>,
+    pub password: Option<&'a [u8]>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Publish<'a> {
+    pub dup: bool,
+    pub qos: u8,
+    pub retain: bool,
+    pub topic: &'a str,
+    /// Only present when `qos` is 1 or 2
+    pub packet_id: Option<u16>,
+    pub payload: &'a [u8],
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Subscribe<'a> {
+    pub packet_id: u16,
+    /// The topic filters with their requested QoS
+    pub filters: Vec<(&'a str, u8)>,
+}
+
+/// A control packet, the types whose body is not decoded keep it as is
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Packet<'a> {
+    Connect(Connect<'a>),
+    Publish(Publish<'a>),
+    Subscribe(Subscribe<'a>),
+    Other { header: FixedHeader, body: &'a [u8] },
+}
+
+/// Parses the variable length encoding of the remaining length, seven bits per byte from the
+/// least significant ones, the high bit of a byte marking that another follows. Four bytes
+/// hold at most 268 435 455
+///
+/// # Examples
+/// ```rust
+///
+/// use pepser::parser::mqtt::remaining_length;
+///
+/// assert_eq!(remaining_length(&[0x7F]), Ok((&[][..], 127)));
+/// assert_eq!(remaining_length(&[0xC1, 0x02, 0xFF]), Ok((&[0xFF][..], 321)));
+/// assert!(remaining_length(&[0xFF, 0xFF, 0xFF, 0xFF, 0x01]).is_err());
+///
+///
+/// ```
+pub fn remaining_length(input: &[u8]) -> ParseResult<&[u8], usize> {
+    let start = input;
+    let mut input = input;
+    let mut length = 0;
+    for shift in (0..4).map(|byte| byte * 7) {
+        let (remainder, byte) = parse_rest(&mut u8, &start, input)?;
+        length |= usize::from(byte & 0x7F) << shift;
+        input = remainder;
+        if byte & 0x80 == 0 {
+            return Ok((input, length));
+        }
+    }
+    Err(invalid(4, "remaining length longer than 4 bytes"))
+}
+
+pub fn fixed_header(input: &[u8]) -> ParseResult<&[u8], FixedHeader> {
+    let (remainder, first) = u8(input)?;
+    let (remainder, remaining_length) = parse_rest(&mut remaining_length, &input, remainder)?;
+    if first >> 4 == 0 || first >> 4 == 15 {
+        return Err(invalid(0, "reserved packet type"));
+    }
+    Ok((
+        remainder,
+        FixedHeader {
+            packet_type: first >> 4,
+            flags: first & 0x0F,
+            remaining_length,
+        },
+    ))
+}
+
+/// Parses an MQTT 3.1.1 control packet, decoding the body of CONNECT, PUBLISH and SUBSCRIBE
+/// which must fill the remaining length exactly. Error indices are offsets in the packet
+///
+/// # Examples
+/// ```rust
+///
+/// use pepser::parser::mqtt::{packet, Packet};
+/// let bytes = [0x32, 0x0A, 0, 3, b'a', b'/', b'b', 0, 7, b'o', b'f', b'f', 0xE0, 0];
+/// let (remainder, parsed) = packet(&bytes).unwrap();
+///
+/// let Packet::Publish(publish) = parsed else { panic!("expected a publish") };
+/// assert_eq!(publish.topic, "a/b");
+/// assert_eq!((publish.qos, publish.packet_id), (1, Some(7)));
+/// assert_eq!(publish.payload, b"off");
+/// assert!(matches!(packet(remainder), Ok((_, Packet::Other { .. }))));
+///
+///
+/// ```
+pub fn packet(input: &[u8]) -> ParseResult<&[u8], Packet<'_>> {
+    let packet = input;
+    let (input, header) = fixed_header(input)?;
+    let (remainder, body) = parse_rest(&mut take_bytes(header.remaining_length), &packet, input)?;
+    // Ends the packet with its body, of which the parts parsed below are suffixes
+    let packet = &packet[..packet.len() - remainder.len()];
+    let parsed = match header.packet_type {
+        CONNECT => Packet::Connect(connect(packet, body)?),
+        PUBLISH => Packet::Publish(publish(packet, header.flags, body)?),
+        SUBSCRIBE => Packet::Subscribe(subscribe(packet, header.flags, body)?),
+        _ => Packet::Other { header, body },
+    };
+    Ok((remainder, parsed))
+}
+
+/// Offset of `input` in `packet`, of which it is a slice
+fn position(packet: &[u8], input: &[u8]) -> usize {
+    input.as_ptr() as usize - packet.as_ptr() as usize
+}
+
+fn invalid<'a>(index: usize, reason: &'static str) -> ParserError<&'a [u8]> {
+    ParserError::new(index, ErrorSource::TakeWhile, reason)
+}
+
+/// A string prefixed by its length in bytes, which must be UTF-8 without null characters
+fn string<'a>(packet: &'a [u8], input: &'a [u8]) -> ParseResult<&'a [u8], &'a str> {
+    let (remainder, bytes) = parse_rest(&mut length_data(be_u16), &packet, input)?;
+    match str::from_utf8(bytes) {
+        Ok(string) if !string.contains('\0') => Ok((remainder, string)),
+        _ => Err(invalid(position(packet, bytes), "invalid UTF-8 string")),
+    }
+}
+
+/// A QoS, read from the byte at `index`
+fn qos<'a>(index: usize, qos: u8) -> Result<u8, ParserError<&'a [u8]>> {
+    match qos {
+        0..=2 => Ok(qos),
+        _ => Err(invalid(index, "invalid QoS")),
+    }
+}
+
+/// Fails unless the body has been parsed up to its end
+fn end<'a>(packet: &'a [u8], input: &'a [u8]) -> Result<(), ParserError<&'a [u8]>> {
+    match input.is_empty() {
+        true => Ok(()),
+        false => Err(invalid(
+            position(packet, input),
+            "data after the end of the packet",
+        )),
+    }
+}
+
+fn connect<'a>(packet: &'a [u8], body: &'a [u8]) -> Result<Connect<'a>, ParserError<&'a [u8]>> {
+    let (input, protocol_name) = string(packet, body)?;
+    let (input, protocol_level) = parse_rest(&mut u8, &packet, input)?;
+    let flags_input = input;
+    let (input, flags) = parse_rest(&mut u8, &packet, input)?;
+    let (input, keep_alive) = parse_rest(&mut be_u16, &packet, input)?;
+    let flag = |bit: u8| flags & (1 << bit) != 0;
+    let will_qos = qos(position(packet, flags_input), (flags >> 3) & 0x03)?;
+    if flag(0) || !flag(2) && (will_qos != 0 || flag(5)) || flag(6) && !flag(7) {
+        return Err(invalid(
+            position(packet, flags_input),
+            "invalid connect flags",
+        ));
+    }
+
+    let (mut input, client_id) = string(packet, input)?;
+    let mut will = None;
+    if flag(2) {
+        let (remainder, topic) = string(packet, input)?;
+        let (remainder, message) = parse_rest(&mut length_data(be_u16), &packet, remainder)?;
+        will = Some(Will {
+            topic,
+            message,
+            qos: will_qos,
+            retain: flag(5),
+        });
+        input = remainder;
+    }
+    let mut username = None;
+    if flag(7) {
+        let (remainder, parsed) = string(packet, input)?;
+        username = Some(parsed);
+        input = remainder;
+    }
+    let mut password = None;
+    if flag(6) {
+        let (remainder, parsed) = parse_rest(&mut length_data(be_u16), &packet, input)?;
+        password = Some(parsed);
+        input = remainder;
+    }
+    end(packet, input)?;
+    Ok(Connect {
+        protocol_name,
+        protocol_level,
+        clean_session: flag(1),
+        keep_alive,
+        client_id,
+        will,
+        username,
+        password,
+    })
+}
+
+fn publish<'a>(
+    packet: &'a [u8],
+    flags: u8,
+    body: &'a [u8],
+) -> Result<Publish<'a>, ParserError<&'a [u8]>> {
+    let qos = qos(0, (flags >> 1) & 0x03)?;
+    let (mut input, topic) = string(packet, body)?;
+    if topic.contains(['+', '#']) {
+        return Err(invalid(position(packet, body), "wildcard in a topic name"));
+    }
+    let mut packet_id = None;
+    if qos > 0 {
+        let (remainder, id) = parse_rest(&mut be_u16, &packet, input)?;
+        packet_id = Some(id);
+        input = remainder;
+    }
+    Ok(Publish {
+        dup: flags & 0x08 != 0,
+        qos,
+        retain: flags & 0x01 != 0,
+        topic,
+        packet_id,
+        payload: input,
+    })
+}
+
+fn subscribe<'a>(
+    packet: &'a [u8],
+    flags: u8,
+    body: &'a [u8],
+) -> Result<Subscribe<'a>, ParserError<&'a [u8]>> {
+    if flags != 0b0010 {
+        return Err(invalid(0, "invalid subscribe flags"));
+    }
+    let (mut input, packet_id) = parse_rest(&mut be_u16, &packet, body)?;
+    let mut filters = Vec::new();
+    while !input.is_empty() || filters.is_empty() {
+        let (remainder, filter) = string(packet, input)?;
+        let index = position(packet, remainder);
+        let (remainder, requested) = parse_rest(&mut u8, &packet, remainder)?;
+        filters.push((filter, qos(index, requested)?));
+        input = remainder;
+    }
+    Ok(Subscribe { packet_id, filters })
+}
@@ -0,0 +1,151 @@
+use super::{
+    errors::{ErrorSource, ParserError},
+    http::{headers, Headers},
+    impls::take_until,
+    traits::{parse_rest, ParseResult, Parser},
+};
+
+/// A part of a multipart body, its headers and its content without the line break before the
+/// next boundary
+#[derive(Debug, PartialEq)]
+pub struct Part<'a> {
+    pub headers: Headers,
+    pub body: &'a str,
+}
+
+impl Part<'_> {
+    /// The form field of the part, the `name` of its `Content-Disposition`
+    pub fn name(&self) -> Option<&str> {
+        parameter(self.headers.get("Content-Disposition")?, "name")
+    }
+
+    /// The name of the uploaded file, the `filename` of its `Content-Disposition`
+    pub fn filename(&self) -> Option<&str> {
+        parameter(self.headers.get("Content-Disposition")?, "filename")
+    }
+}
+
+/// Returns the boundary of a `Content-Type` header value such as
+/// `multipart/form-data; boundary="abc"`
+pub fn boundary(content_type: &str) -> Option<&str> {
+    parameter(content_type, "boundary").filter(|boundary| !boundary.is_empty())
+}
+
+/// Parses a multipart body (RFC 2046) delimited by `boundary` into its parts. The preamble
+/// before the first boundary is ignored and the remainder is the epilogue after the closing
+/// one. Error indices are offsets in the body
+///
+/// # Examples
+/// ```rust
+///
+/// use pepser::parser::multipart::{boundary, multipart};
+/// use pepser::parser::traits::Parser;
+/// let body = "--xyz\r\n\
+///     Content-Disposition: form-data; name=\"title\"\r\n\r\n\
+///     Hello\r\n\
+///     --xyz\r\n\
+///     Content-Disposition: form-data; name=\"file\"; filename=\"a.txt\"\r\n\
+///     Content-Type: text/plain\r\n\r\n\
+///     line one\r\nline two\r\n\
+///     --xyz--\r\n";
+/// let boundary = boundary("multipart/form-data; boundary=xyz").unwrap();
+/// let (_, parts) = multipart(boundary).parse(body).unwrap();
+///
+/// assert_eq!((parts[0].name(), parts[0].body), (Some("title"), "Hello"));
+/// assert_eq!(parts[1].filename(), Some("a.txt"));
+/// assert_eq!(parts[1].headers.get("content-type"), Some("text/plain"));
+/// assert_eq!(parts[1].body, "line one\r\nline two");
+///
+///
+/// ```
+pub fn multipart<'a>(boundary: &'a str) -> impl Parser<&'a str, Output = Vec<Part<'a>>> {
+    move |input: &'a str| {
+        if boundary.is_empty() {
+            return Err(invalid(0, "empty boundary"));
+        }
+        let (mut rest, _) = delimited(input, input, boundary)?;
+        let mut parts = Vec::new();
+        loop {
+            if let Some(epilogue) = rest.strip_prefix("--") {
+                if epilogue.is_empty() {
+                    return Ok((epilogue, parts));
+                }
+                let (epilogue, _) = parse_rest(&mut padding, &input, epilogue)?;
+                return Ok((epilogue, parts));
+            }
+            let (content, _) = parse_rest(&mut padding, &input, rest)?;
+            let (content, headers) = parse_rest(&mut headers, &input, content)?;
+            let (next, body) = delimited(input, content, boundary)?;
+            parts.push(Part { headers, body });
+            rest = next;
+        }
+    }
+}
+
+fn invalid<'a>(index: usize, reason: &'static str) -> ParserError<&'a str> {
+    ParserError::new(index, ErrorSource::TakeWhile, reason)
+}
+
+/// Takes the text up to the next `--boundary` starting a line, which is consumed. The line
+/// break before it belongs to the boundary, occurrences elsewhere belong to the text. Without
+/// a boundary the error is at the start of the text
+fn delimited<'a>(
+    body: &'a str,
+    input: &'a str,
+    boundary: &'a str,
+) -> ParseResult<&'a str, &'a str> {
+    let mut searched = 0;
+    loop {
+        let (_, before) = take_until(boundary)
+            .parse(&input[searched..])
+            .map_err(|error| ParserError::from_error(error, body.len() - input.len()))?;
+        let end = searched + before.len();
+        if let Some(text) = input[..end].strip_suffix("--") {
+            if text.is_empty() || text.ends_with('\n') {
+                let text = text.strip_suffix('\n').unwrap_or(text);
+                let text = text.strip_suffix('\r').unwrap_or(text);
+                return Ok((&input[end + boundary.len()..], text));
+            }
+        }
+        searched = end + boundary.len();
+    }
+}
+
+/// The spaces and tabs a boundary may be followed by, then its line break
+fn padding(input: &str) -> ParseResult<&str, ()> {
+    let padded = input.trim_start_matches([' ', '\t']);
+    match padded
+        .strip_prefix("\r\n")
+        .or_else(|| padded.strip_prefix('\n'))
+    {
+        Some(rest) => Ok((rest, ())),
+        None => Err(invalid(
+            input.len() - padded.len(),
+            "expected a line break after the boundary",
+        )),
+    }
+}
+
+/// The value of the parameter `name` in a header value such as `form-data; name="a"`. Quoted
+/// values may hold semicolons
+fn parameter<'a>(value: &'a str, name: &str) -> Option<&'a str> {
+    let (_, mut rest) = value.split_once(';')?;
+    loop {
+        let (key, value) = rest.split_once('=')?;
+        let value = value.trim_start();
+        let (value, next) = match value.strip_prefix('"') {
+            Some(quoted) => {
+                let (value, after) = quoted.split_once('"')?;
+                (value, after.split_once(';').map_or("", |(_, next)| next))
+            }
+            None => {
+                let (value, next) = value.split_once(';').unwrap_or((value, ""));
+                (value.trim_end(), next)
+            }
+        };
+        if key.trim().eq_ignore_ascii_case(name) {
+            return Some(value);
+        }
+        rest = next;
+    }
+}
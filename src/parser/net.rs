@@ -0,0 +1,188 @@
+use core::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
+
+use super::{
+    errors::{ErrorSource, ParserError},
+    impls::sequence,
+    traits::{discard, ParseResult, Parser},
+};
+
+fn invalid<'a>(index: usize, reason: &'static str) -> ParserError<&'a str> {
+    ParserError::new(index, ErrorSource::TakeWhile, reason)
+}
+
+/// Parses a decimal number of at most `max_digits` digits, without leading zeros
+fn decimal(input: &str, max_digits: usize) -> ParseResult<&str, u32> {
+    let digits = input
+        .bytes()
+        .take(max_digits + 1)
+        .take_while(u8::is_ascii_digit)
+        .count();
+    if digits == 0 {
+        return Err(invalid(0, "expected a number"));
+    }
+    if digits > max_digits || (digits > 1 && input.starts_with('0')) {
+        return Err(invalid(0, "invalid number"));
+    }
+    let (number, remainder) = input.split_at(digits);
+    Ok((
+        remainder,
+        number
+            .bytes()
+            .fold(0, |value, digit| value * 10 + u32::from(digit - b'0')),
+    ))
+}
+
+/// Parses a dotted-quad IPv4 address, octets have no leading zeros
+///
+/// # Examples
+/// ```rust
+///
+/// use std::net::Ipv4Addr;
+/// use pepser::parser::net::ipv4;
+///
+/// assert_eq!(ipv4("192.168.0.1/24"), Ok(("/24", Ipv4Addr::new(192, 168, 0, 1))));
+/// assert_eq!(ipv4("192.168.0.256").unwrap_err().index, 10);
+///
+///
+/// ```
+pub fn ipv4(input: &str) -> ParseResult<&str, Ipv4Addr> {
+    let mut octets = [0; 4];
+    let mut remainder = input;
+    for (position, octet) in octets.iter_mut().enumerate() {
+        if position > 0 {
+            remainder = sequence(".")
+                .parse(remainder)
+                .map_err(|_| invalid(input.len() - remainder.len(), "expected '.'"))?
+                .0;
+        }
+        let offset = input.len() - remainder.len();
+        let (next, value) =
+            decimal(remainder, 3).map_err(|error| ParserError::from_error(error, offset))?;
+        *octet = u8::try_from(value).map_err(|_| invalid(offset, "octet out of range"))?;
+        remainder = next;
+    }
+    Ok((remainder, Ipv4Addr::from(octets)))
+}
+
+/// Parses an IPv6 address in its full or `::` compressed form, possibly ending with an
+/// embedded IPv4 address. A `:` which does not start a group is left unparsed
+///
+/// # Examples
+/// ```rust
+///
+/// use std::net::Ipv6Addr;
+/// use pepser::parser::net::ipv6;
+///
+/// assert_eq!(ipv6("2001:db8::1"), Ok(("", Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1))));
+/// assert_eq!(ipv6("::ffff:10.0.0.1"), Ok(("", "::ffff:10.0.0.1".parse().unwrap())));
+/// assert!(ipv6("1:2:3").is_err());
+///
+///
+/// ```
+pub fn ipv6(input: &str) -> ParseResult<&str, Ipv6Addr> {
+    let mut groups = [0u16; 8];
+    let mut count = 0;
+    // number of groups before the `::`
+    let mut gap = input.starts_with("::").then_some(0);
+    let mut remainder = if gap.is_some() { &input[2..] } else { input };
+    while count < 8 {
+        let offset = input.len() - remainder.len();
+        let digits = remainder.bytes().take_while(u8::is_ascii_hexdigit).count();
+        if digits == 0 {
+            break;
+        }
+        if remainder.as_bytes().get(digits) == Some(&b'.') && count <= 6 {
+            let (next, address) =
+                ipv4(remainder).map_err(|error| ParserError::from_error(error, offset))?;
+            let [a, b, c, d] = address.octets();
+            groups[count] = u16::from_be_bytes([a, b]);
+            groups[count + 1] = u16::from_be_bytes([c, d]);
+            count += 2;
+            remainder = next;
+            break;
+        }
+        if digits > 4 {
+            return Err(invalid(offset, "group of more than four digits"));
+        }
+        let (group, next) = remainder.split_at(digits);
+        groups[count] =
+            u16::from_str_radix(group, 16).map_err(|_| invalid(offset, "invalid group"))?;
+        count += 1;
+        remainder = next;
+        if count == 8 {
+            break;
+        }
+        if gap.is_none() && remainder.starts_with("::") {
+            gap = Some(count);
+            remainder = &remainder[2..];
+        } else if remainder.starts_with(':')
+            && remainder[1..].starts_with(|c: char| c.is_ascii_hexdigit())
+        {
+            remainder = &remainder[1..];
+        } else {
+            break;
+        }
+    }
+    match gap {
+        None if count == 8 => {}
+        Some(gap) if count < 8 => {
+            // the groups after the `::` move to the end
+            groups.copy_within(gap..count, 8 - (count - gap));
+            groups[gap..8 - (count - gap)].fill(0);
+        }
+        _ => {
+            return Err(invalid(
+                input.len() - remainder.len(),
+                "expected 8 groups or '::'",
+            ))
+        }
+    }
+    Ok((remainder, Ipv6Addr::from(groups)))
+}
+
+/// Parses an IPv4 or an IPv6 address
+pub fn ip_addr(input: &str) -> ParseResult<&str, IpAddr> {
+    ipv4.map(IpAddr::V4).or(ipv6.map(IpAddr::V6)).parse(input)
+}
+
+/// Parses a port number, from 0 to 65535
+pub fn port(input: &str) -> ParseResult<&str, u16> {
+    let (remainder, port) = decimal(input, 5)?;
+    u16::try_from(port)
+        .map(|port| (remainder, port))
+        .map_err(|_| invalid(0, "port out of range"))
+}
+
+/// Parses `address:port` for IPv4 and `[address]:port` for IPv6
+///
+/// # Examples
+/// ```rust
+///
+/// use pepser::parser::net::socket_addr;
+///
+/// assert_eq!(socket_addr("127.0.0.1:8080"), Ok(("", "127.0.0.1:8080".parse().unwrap())));
+/// assert_eq!(socket_addr("[::1]:443 "), Ok((" ", "[::1]:443".parse().unwrap())));
+/// assert!(socket_addr("::1:443").is_err());
+///
+///
+/// ```
+pub fn socket_addr(input: &str) -> ParseResult<&str, SocketAddr> {
+    if let Some(bracketed) = input.strip_prefix('[') {
+        let (remainder, address) =
+            ipv6(bracketed).map_err(|error| ParserError::from_error(error, 1))?;
+        let offset = input.len() - remainder.len();
+        let (remainder, port) = discard(sequence("]:"), port)
+            .parse(remainder)
+            .map_err(|error| ParserError::from_error(error, offset))?;
+        return Ok((
+            remainder,
+            SocketAddr::V6(SocketAddrV6::new(address, port, 0, 0)),
+        ));
+    }
+    let (remainder, address) = ipv4(input)?;
+    let offset = input.len() - remainder.len();
+    let (remainder, port) = discard(sequence(":"), port)
+        .parse(remainder)
+        .map_err(|error| ParserError::from_error(error, offset))?;
+    Ok((remainder, SocketAddr::V4(SocketAddrV4::new(address, port))))
+}
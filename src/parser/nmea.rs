@@ -0,0 +1,405 @@
+use alloc::{vec, vec::Vec};
+
+use super::{
+    datetime::{Date, Time},
+    errors::{ErrorSource, ParserError},
+    impls::{fixed_digits, sequence, take_while1},
+    traits::{opt, ParseResult, Parser},
+};
+
+/// The framing of an NMEA 0183 sentence: `$<talker><kind>,<fields>*<checksum>`
+#[derive(Debug, PartialEq, Clone)]
+pub struct Sentence<'a> {
+    /// Source of the sentence, e.g. `GP` for GPS or `GN` for combined systems
+    pub talker: &'a str,
+    pub kind: &'a str,
+    /// Raw fields, empty when the value is not available
+    pub fields: Vec<&'a str>,
+}
+
+/// Position in decimal degrees, south latitudes and west longitudes are negative
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Position {
+    pub latitude: f64,
+    pub longitude: f64,
+}
+
+/// Global positioning system fix data
+#[derive(Debug, PartialEq, Clone)]
+pub struct Gga {
+    pub time: Option<Time>,
+    pub position: Option<Position>,
+    /// 0 when there is no fix, 1 for GPS, 2 for differential GPS...
+    pub quality: u8,
+    pub satellites: Option<u8>,
+    /// Horizontal dilution of precision
+    pub hdop: Option<f32>,
+    /// Altitude above mean sea level in meters
+    pub altitude: Option<f32>,
+    /// Height of the geoid above the WGS84 ellipsoid in meters
+    pub geoid_separation: Option<f32>,
+}
+
+/// Recommended minimum specific GNSS data
+#[derive(Debug, PartialEq, Clone)]
+pub struct Rmc {
+    pub time: Option<Time>,
+    /// `A` status, `V` is a void fix
+    pub active: bool,
+    pub position: Option<Position>,
+    pub speed_knots: Option<f32>,
+    /// Course over ground in degrees from true north
+    pub course: Option<f32>,
+    pub date: Option<Date>,
+    /// Magnetic variation in degrees, negative to the west
+    pub magnetic_variation: Option<f32>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Satellite {
+    pub prn: u16,
+    /// Elevation in degrees, up to 90
+    pub elevation: Option<u8>,
+    /// Azimuth in degrees from true north
+    pub azimuth: Option<u16>,
+    /// Signal to noise ratio in dB, empty when not tracking
+    pub snr: Option<u8>,
+}
+
+/// Satellites in view, spread over several sentences of up to four satellites
+#[derive(Debug, PartialEq, Clone)]
+pub struct Gsv {
+    pub messages: u8,
+    pub message: u8,
+    pub in_view: u8,
+    pub satellites: Vec<Satellite>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Message {
+    Gga(Gga),
+    Rmc(Rmc),
+    Gsv(Gsv),
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Nmea<'a> {
+    pub talker: &'a str,
+    pub message: Message,
+}
+
+/// Parses the framing of a sentence and verifies its checksum, the XOR of the bytes between
+/// `$` and `*`. The checksum may be omitted, a trailing line break is consumed
+pub fn sentence(input: &str) -> ParseResult<&str, Sentence<'_>> {
+    let (body, _) = sequence("$").parse(input)?;
+    let end = body.find(['*', '\r', '\n']).unwrap_or(body.len());
+    let (content, remainder) = body.split_at(end);
+    let mut fields = content.split(',');
+    let address = fields.next().unwrap_or_default();
+    if address.len() != 5 || !address.bytes().all(|byte| byte.is_ascii_alphanumeric()) {
+        return Err(ParserError::new(
+            1,
+            ErrorSource::TakeWhile,
+            "expected a talker and sentence kind",
+        ));
+    }
+
+    let remainder = if remainder.starts_with('*') {
+        let (remainder, checksum) =
+            checksum(remainder).map_err(|error| ParserError::from_error(error, 1 + end))?;
+        if content.bytes().fold(0, |sum, byte| sum ^ byte) != checksum {
+            return Err(ParserError::new(
+                1 + end,
+                ErrorSource::Checksum,
+                "checksum mismatch",
+            ));
+        }
+        remainder
+    } else {
+        remainder
+    };
+    let (remainder, _) = opt(sequence("\r"))
+        .and(opt(sequence("\n")))
+        .parse(remainder)?;
+    Ok((
+        remainder,
+        Sentence {
+            talker: &address[..2],
+            kind: &address[2..],
+            fields: fields.collect(),
+        },
+    ))
+}
+
+/// Parses a GGA, RMC or GSV sentence into its typed fields
+///
+/// # Examples
+/// ```rust
+///
+/// use pepser::parser::nmea::{nmea, Message};
+/// let (_, parsed) =
+///     nmea("$GPGGA,123519,4807.038,N,01131.000,E,1,08,0.9,545.4,M,46.9,M,,*47\r\n").unwrap();
+/// let Message::Gga(gga) = parsed.message else { unreachable!() };
+///
+/// assert_eq!(parsed.talker, "GP");
+/// assert_eq!(gga.satellites, Some(8));
+/// assert!((gga.position.unwrap().latitude - 48.1173).abs() < 1e-9);
+///
+///
+/// ```
+pub fn nmea(input: &str) -> ParseResult<&str, Nmea<'_>> {
+    let (remainder, sentence) = sentence(input)?;
+    let mut fields = Fields {
+        fields: sentence.fields.iter(),
+        offset: 7,
+    };
+    let message = match sentence.kind {
+        "GGA" => gga(&mut fields).map(Message::Gga),
+        "RMC" => rmc(&mut fields).map(Message::Rmc),
+        "GSV" => gsv(&mut fields).map(Message::Gsv),
+        _ => Err(ParserError::new(
+            3,
+            ErrorSource::Sequence(sentence.kind),
+            "unsupported sentence",
+        )),
+    }?;
+    Ok((
+        remainder,
+        Nmea {
+            talker: sentence.talker,
+            message,
+        },
+    ))
+}
+
+fn gga(fields: &mut Fields<'_, '_>) -> Result<Gga, ParserError<&'static str>> {
+    Ok(Gga {
+        time: fields.next(utc_time)?,
+        position: position(fields)?,
+        quality: fields.next(integer)?.unwrap_or_default(),
+        satellites: fields.next(integer)?,
+        hdop: fields.next(decimal)?,
+        altitude: fields.next_with_unit(decimal, "M")?,
+        geoid_separation: fields.next_with_unit(decimal, "M")?,
+    })
+}
+
+fn rmc(fields: &mut Fields<'_, '_>) -> Result<Rmc, ParserError<&'static str>> {
+    let time = fields.next(utc_time)?;
+    let active = fields.next(sequence("A").or(sequence("V")))? == Some("A");
+    let position = position(fields)?;
+    let speed_knots = fields.next(decimal)?;
+    let course = fields.next(decimal)?;
+    let date = fields.next(dmy_date)?;
+    let variation: Option<f32> = fields.optional(decimal)?;
+    let direction = fields.optional(sequence("E").or(sequence("W")))?;
+    Ok(Rmc {
+        time,
+        active,
+        position,
+        speed_knots,
+        course,
+        date,
+        magnetic_variation: variation.map(|v| if direction == Some("W") { -v } else { v }),
+    })
+}
+
+fn gsv(fields: &mut Fields<'_, '_>) -> Result<Gsv, ParserError<&'static str>> {
+    let messages = fields.next(integer)?.unwrap_or_default();
+    let message = fields.next(integer)?.unwrap_or_default();
+    let in_view = fields.next(integer)?.unwrap_or_default();
+    let mut satellites = vec![];
+    // NMEA 4.1 appends a signal id after the last satellite
+    while fields.fields.len() >= 4 {
+        let Some(prn) = fields.next(integer)? else {
+            break;
+        };
+        satellites.push(Satellite {
+            prn,
+            elevation: fields.next(integer)?,
+            azimuth: fields.next(integer)?,
+            snr: fields.next(integer)?,
+        });
+    }
+    Ok(Gsv {
+        messages,
+        message,
+        in_view,
+        satellites,
+    })
+}
+
+/// The fields of a sentence, errors are reported at the offset of the field in the sentence
+struct Fields<'s, 'a> {
+    fields: core::slice::Iter<'s, &'a str>,
+    offset: usize,
+}
+
+impl<'a> Fields<'_, 'a> {
+    /// Parses the next field, which must be entirely consumed, empty fields are `None`
+    fn next<O, P>(&mut self, parser: P) -> Result<Option<O>, ParserError<&'static str>>
+    where
+        P: Parser<&'a str, Output = O>,
+    {
+        if self.fields.len() == 0 {
+            // At the end of the last field, rather than past the end of the sentence
+            return Err(ParserError::new(
+                self.offset - 1,
+                ErrorSource::TakeWhile,
+                "missing field",
+            ));
+        }
+        self.optional(parser)
+    }
+
+    /// Like `next`, a missing field is `None`
+    fn optional<O, P>(&mut self, mut parser: P) -> Result<Option<O>, ParserError<&'static str>>
+    where
+        P: Parser<&'a str, Output = O>,
+    {
+        let offset = self.offset;
+        let Some(field) = self.fields.next() else {
+            return Ok(None);
+        };
+        self.offset += field.len() + 1;
+        if field.is_empty() {
+            return Ok(None);
+        }
+        match parser.parse(field) {
+            Ok(("", value)) => Ok(Some(value)),
+            Ok((remainder, _)) => Err(invalid(offset + field.len() - remainder.len())),
+            Err(error) => Err(invalid(offset + error.index)),
+        }
+    }
+
+    /// Parses a value followed by a unit field such as `M` for meters
+    fn next_with_unit<O, P>(
+        &mut self,
+        parser: P,
+        unit: &'static str,
+    ) -> Result<Option<O>, ParserError<&'static str>>
+    where
+        P: Parser<&'a str, Output = O>,
+    {
+        let value = self.next(parser)?;
+        self.next(sequence(unit))?;
+        Ok(value)
+    }
+}
+
+/// Field errors do not borrow the sentence, as the decoders only hold its fields
+fn invalid(index: usize) -> ParserError<&'static str> {
+    ParserError::new(index, ErrorSource::TakeWhile, "invalid field")
+}
+
+fn checksum(input: &str) -> ParseResult<&str, u8> {
+    let (remainder, _) = sequence("*").parse(input)?;
+    let digits = remainder
+        .get(..2)
+        .filter(|digits| digits.bytes().all(|byte| byte.is_ascii_hexdigit()));
+    match digits.and_then(|digits| u8::from_str_radix(digits, 16).ok()) {
+        Some(checksum) => Ok((&remainder[2..], checksum)),
+        None => Err(ParserError::new(
+            1,
+            ErrorSource::TakeWhile,
+            "expected a hexadecimal checksum",
+        )),
+    }
+}
+
+/// `hhmmss` with an optional decimal fraction of a second
+fn utc_time(input: &str) -> ParseResult<&str, Time> {
+    let (remainder, ((hour, minute), second)) = fixed_digits(2)
+        .and(fixed_digits(2))
+        .and(fixed_digits(2))
+        .parse(input)?;
+    let (remainder, fraction) = opt(sequence(".").and(take_while1(|c| c.is_ascii_digit())))
+        .map(|fraction| fraction.map_or("", |(_, digits)| digits))
+        .parse(remainder)?;
+    if hour > 23 || minute > 59 || second > 60 {
+        return Err(ParserError::new(0, ErrorSource::TakeWhile, "invalid time"));
+    }
+    let nanosecond = fraction
+        .bytes()
+        .chain(core::iter::repeat(b'0'))
+        .take(9)
+        .fold(0, |value, digit| value * 10 + (digit - b'0') as u32);
+    Ok((
+        remainder,
+        Time {
+            hour: hour as u8,
+            minute: minute as u8,
+            second: second as u8,
+            nanosecond,
+        },
+    ))
+}
+
+/// `ddmmyy`, two digit years are taken in the 2000s
+fn dmy_date(input: &str) -> ParseResult<&str, Date> {
+    let (remainder, ((day, month), year)) = fixed_digits(2)
+        .and(fixed_digits(2))
+        .and(fixed_digits(2))
+        .parse(input)?;
+    let date = Date {
+        year: 2000 + year as u16,
+        month: month as u8,
+        day: day as u8,
+    };
+    if !(1..=12).contains(&date.month)
+        || date.day == 0
+        || date.day > super::datetime::days_in_month(date.year, date.month)
+    {
+        return Err(ParserError::new(0, ErrorSource::TakeWhile, "invalid date"));
+    }
+    Ok((remainder, date))
+}
+
+/// A latitude and a longitude, each as `(d)ddmm.mmmm` followed by its hemisphere field
+fn position(fields: &mut Fields<'_, '_>) -> Result<Option<Position>, ParserError<&'static str>> {
+    let latitude = fields.next(angle(2))?;
+    let north = fields.next(sequence("N").or(sequence("S")))?;
+    let longitude = fields.next(angle(3))?;
+    let east = fields.next(sequence("E").or(sequence("W")))?;
+    let (Some(latitude), Some(north), Some(longitude), Some(east)) =
+        (latitude, north, longitude, east)
+    else {
+        return Ok(None);
+    };
+    Ok(Some(Position {
+        latitude: if north == "S" { -latitude } else { latitude },
+        longitude: if east == "W" { -longitude } else { longitude },
+    }))
+}
+
+fn angle<'a>(degree_digits: usize) -> impl Parser<&'a str, Output = f64> {
+    move |input: &'a str| {
+        let (remainder, degrees) = fixed_digits(degree_digits).parse(input)?;
+        let (remainder, minutes) = decimal::<f64>.parse(remainder)?;
+        if minutes >= 60.0 {
+            return Err(ParserError::new(
+                degree_digits,
+                ErrorSource::TakeWhile,
+                "invalid minutes",
+            ));
+        }
+        Ok((remainder, degrees as f64 + minutes / 60.0))
+    }
+}
+
+fn integer<T: core::str::FromStr>(input: &str) -> ParseResult<&str, T> {
+    let (remainder, digits) = take_while1(|c| c.is_ascii_digit()).parse(input)?;
+    digits
+        .parse()
+        .map(|value| (remainder, value))
+        .map_err(|_| ParserError::new(0, ErrorSource::TakeWhile, "number overflow"))
+}
+
+fn decimal<T: core::str::FromStr>(input: &str) -> ParseResult<&str, T> {
+    let (remainder, number) =
+        take_while1(|c| c.is_ascii_digit() || c == '.' || c == '-').parse(input)?;
+    number
+        .parse()
+        .map(|value| (remainder, value))
+        .map_err(|_| ParserError::new(0, ErrorSource::TakeWhile, "invalid number"))
+}
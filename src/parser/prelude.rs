@@ -0,0 +1,26 @@
+//! Everything needed to write parsers in a single import, `use pepser::prelude::*`: the
+//! [`Parser`] trait, the combinator and primitive functions and the error types
+//!
+//! # Examples
+//! ```rust
+//!
+//! use pepser::prelude::*;
+//! let digits = take_while1(|c| c.is_ascii_digit());
+//! let mut list = wrapped(sequence("["), sep_by(digits, sequence(",")), sequence("]"));
+//!
+//! assert_eq!(list.parse("[1,22]"), Ok(("", vec!["1", "22"])));
+//! assert_eq!(list.parse("[1;").unwrap_err().source, ErrorSource::Sequence("]"));
+//!
+//!
+//! ```
+#[cfg(feature = "std")]
+pub use super::combinator::detect_nullable_loops;
+pub use super::{
+    combinator::{
+        chainl1, chainr1, discard, dispatch, drop_until, fold_many0, followed_by, not_followed_by,
+        opt, pair, parse_if, sep_by, separated_pair, skip_until, terminated, value, wrapped,
+        BoxedParser, Input, ParseResult, Parser,
+    },
+    error::{line_column, ErrorSnippet, ErrorSource, ParserError},
+    primitive::*,
+};
@@ -0,0 +1,8 @@
+//! The parsers reading the input itself: sequences, characters, tokens and delimited text.
+//! The items are defined in [`impls`](super::impls), whose paths keep working
+pub use super::impls::{
+    any, any_char, ascii_identifier, balanced, balanced_with_strings, eof, escaped_transform,
+    fixed_digits, identifier, identifier_except, keywords, none_of, not, rest, satisfy_token,
+    sequence, take_until, take_until_consuming, take_until_consuming_escaped, take_until_keeping,
+    take_until_keeping_escaped, take_while0, take_while1, take_while_m_n, token, ws,
+};
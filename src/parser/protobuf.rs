@@ -0,0 +1,166 @@
+use alloc::vec::Vec;
+
+use super::{
+    binary::{le_u32, le_u64, take_bytes},
+    errors::{ErrorSource, ParserError},
+    traits::{parse_rest, ParseResult, Parser},
+};
+
+/// Deepest nesting of groups accepted, the default limit of the reference implementation
+const MAX_DEPTH: usize = 100;
+
+/// Largest field number, tags keep 29 bits for it
+const MAX_FIELD_NUMBER: u32 = (1 << 29) - 1;
+
+/// The value of a field as encoded, without the schema telling what it stands for. A varint
+/// may be an `int32`, a `bool`, an enum or a [`zigzag`] encoded `sint64`, a length delimited
+/// value a string, bytes, a packed repeated field or an embedded message, which [`message`]
+/// parses
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum WireValue<'a> {
+    /// Wire type 0
+    Varint(u64),
+    /// Wire type 1, `fixed64`, `sfixed64` or `double`
+    Fixed64(u64),
+    /// Wire type 2
+    LengthDelimited(&'a [u8]),
+    /// Wire types 3 and 4, the fields between the start and the end of a group
+    Group(Vec<(u32, WireValue<'a>)>),
+    /// Wire type 5, `fixed32`, `sfixed32` or `float`
+    Fixed32(u32),
+}
+
+/// Decodes a `sint32` or `sint64` from its varint
+pub fn zigzag(value: u64) -> i64 {
+    (value >> 1) as i64 ^ -((value & 1) as i64)
+}
+
+/// Parses a base 128 varint, least significant group first, which fits in 64 bits at most
+///
+/// # Examples
+/// ```rust
+///
+/// use pepser::parser::protobuf::{varint, zigzag};
+///
+/// assert_eq!(varint(&[0x96, 0x01, 0xFF]), Ok((&[0xFF][..], 150)));
+/// assert_eq!(zigzag(varint(&[0x03]).unwrap().1), -2);
+/// assert!(varint(&[0xFF; 10]).is_err());
+///
+///
+/// ```
+pub fn varint(input: &[u8]) -> ParseResult<&[u8], u64> {
+    let mut value = 0;
+    for (position, byte) in input.iter().enumerate().take(10) {
+        if position == 9 && *byte > 1 {
+            return Err(invalid(position, "varint overflow"));
+        }
+        value |= u64::from(byte & 0x7F) << (position * 7);
+        if byte & 0x80 == 0 {
+            return Ok((&input[position + 1..], value));
+        }
+    }
+    match input.len() {
+        0..=9 => Err(invalid(input.len(), "unexpected end of input")),
+        _ => Err(invalid(9, "varint overflow")),
+    }
+}
+
+/// Parses the tag of a field, its field number and its wire type
+pub fn tag(input: &[u8]) -> ParseResult<&[u8], (u32, u8)> {
+    let (remainder, tag) = varint(input)?;
+    let field = tag >> 3;
+    if field == 0 || field > u64::from(MAX_FIELD_NUMBER) {
+        return Err(invalid(0, "invalid field number"));
+    }
+    Ok((remainder, (field as u32, (tag & 0x07) as u8)))
+}
+
+/// Parses a field, its number and its value
+pub fn field(input: &[u8]) -> ParseResult<&[u8], (u32, WireValue<'_>)> {
+    field_at(input, input, 0)
+}
+
+/// Parses the fields of a message up to the end of the input
+///
+/// # Examples
+/// ```rust
+///
+/// use pepser::parser::protobuf::{message, WireValue};
+/// let (_, fields) = message(&[0x08, 0x96, 0x01, 0x12, 0x02, b'h', b'i']).unwrap();
+///
+/// assert_eq!(
+///     fields,
+///     vec![(1, WireValue::Varint(150)), (2, WireValue::LengthDelimited(b"hi"))]
+/// );
+/// assert_eq!(message(&[0x08, 0x96]).unwrap_err().index, 2);
+///
+///
+/// ```
+pub fn message(input: &[u8]) -> ParseResult<&[u8], Vec<(u32, WireValue<'_>)>> {
+    let mut fields = Vec::new();
+    let mut remainder = input;
+    while !remainder.is_empty() {
+        let (next, parsed) = field_at(input, remainder, 0)?;
+        fields.push(parsed);
+        remainder = next;
+    }
+    Ok((remainder, fields))
+}
+
+fn invalid<'a>(index: usize, reason: &'static str) -> ParserError<&'a [u8]> {
+    ParserError::new(index, ErrorSource::TakeWhile, reason)
+}
+
+fn field_at<'a>(
+    message: &'a [u8],
+    input: &'a [u8],
+    depth: usize,
+) -> ParseResult<&'a [u8], (u32, WireValue<'a>)> {
+    let start = message.len() - input.len();
+    let (input, (number, wire_type)) = parse_rest(&mut tag, &message, input)?;
+    let (input, value) = match wire_type {
+        0 => parse_rest(&mut varint.map(WireValue::Varint), &message, input)?,
+        1 => parse_rest(&mut le_u64.map(WireValue::Fixed64), &message, input)?,
+        2 => {
+            let (input, length) = parse_rest(&mut varint, &message, input)?;
+            let Ok(length) = usize::try_from(length) else {
+                return Err(invalid(start, "length does not fit in memory"));
+            };
+            let mut bytes = take_bytes(length).map(WireValue::LengthDelimited);
+            parse_rest(&mut bytes, &message, input)?
+        }
+        3 => {
+            if depth >= MAX_DEPTH {
+                return Err(invalid(start, "group nested too deeply"));
+            }
+            let (input, fields) = group(message, input, number, depth)?;
+            (input, WireValue::Group(fields))
+        }
+        4 => return Err(invalid(start, "end of a group which was not started")),
+        5 => parse_rest(&mut le_u32.map(WireValue::Fixed32), &message, input)?,
+        _ => return Err(invalid(start, "invalid wire type")),
+    };
+    Ok((input, (number, value)))
+}
+
+/// The fields of a group up to its end, which has the field number of its start
+fn group<'a>(
+    message: &'a [u8],
+    mut input: &'a [u8],
+    number: u32,
+    depth: usize,
+) -> ParseResult<&'a [u8], Vec<(u32, WireValue<'a>)>> {
+    let mut fields = Vec::new();
+    loop {
+        let start = message.len() - input.len();
+        if let Ok((remainder, (end, 4))) = tag(input) {
+            return match end == number {
+                true => Ok((remainder, fields)),
+                false => Err(invalid(start, "end of a group which was not started")),
+            };
+        }
+        let (remainder, parsed) = field_at(message, input, depth + 1)?;
+        fields.push(parsed);
+        input = remainder;
+    }
+}
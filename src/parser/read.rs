@@ -0,0 +1,266 @@
+use std::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+    fs::File,
+    io::{self, BufReader, Read},
+    path::Path,
+    str,
+};
+
+use super::{
+    errors::ParserError,
+    impls::{eof, ws},
+    traits::{discard, Parser},
+};
+
+/// Failure of [`parse_file`] or [`parse_reader`]. Parse errors borrow the text they were
+/// produced from, so they are kept as their index and message
+#[derive(Debug)]
+pub enum ReadError {
+    Io(io::Error),
+    Parse { index: usize, message: String },
+}
+
+impl Display for ReadError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ReadError::Io(error) => write!(f, "could not read input: {}", error),
+            ReadError::Parse { message, .. } => f.write_str(message),
+        }
+    }
+}
+
+impl Error for ReadError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ReadError::Io(error) => Some(error),
+            ReadError::Parse { .. } => None,
+        }
+    }
+}
+
+impl From<io::Error> for ReadError {
+    fn from(error: io::Error) -> Self {
+        ReadError::Io(error)
+    }
+}
+
+/// Reads the whole reader as UTF-8 text and parses it. The parser must consume all of it but
+/// trailing whitespace, such as the final line break. The output cannot borrow the text,
+/// which is dropped once parsed
+///
+/// # Examples
+/// ```rust
+///
+/// use pepser::parser::json::{json_value, JsonValue};
+/// use pepser::parser::read::parse_reader;
+/// let value = parse_reader(json_value, "[true, null]".as_bytes()).unwrap();
+///
+/// assert_eq!(value, JsonValue::Array(vec![JsonValue::Boolean(true), JsonValue::Null]));
+/// assert!(parse_reader(json_value, "[true] false".as_bytes()).is_err());
+///
+///
+/// ```
+pub fn parse_reader<P, O, R>(parser: P, reader: R) -> Result<O, ReadError>
+where
+    P: for<'a> Parser<&'a str, Output = O>,
+    R: Read,
+{
+    let mut text = String::new();
+    BufReader::new(reader).read_to_string(&mut text)?;
+    parse_text(parser, &text)
+}
+
+/// Reads the file at `path` and parses it like [`parse_reader`]
+pub fn parse_file<P, O, T>(parser: P, path: T) -> Result<O, ReadError>
+where
+    P: for<'a> Parser<&'a str, Output = O>,
+    T: AsRef<Path>,
+{
+    parse_reader(parser, File::open(path)?)
+}
+
+fn parse_text<P, O>(parser: P, text: &str) -> Result<O, ReadError>
+where
+    P: for<'a> Parser<&'a str, Output = O>,
+{
+    parser
+        .and(discard(ws(), eof()))
+        .parse(text)
+        .map(|(_, (output, _))| output)
+        .map_err(|error| ReadError::Parse {
+            index: error.index,
+            message: error.to_string(),
+        })
+}
+
+/// Parses a stream of items from input arriving in chunks, such as the reads of a socket. The
+/// chunks are [`fed`](StreamParser::feed) as they come and the items pulled with
+/// [`next_item`](StreamParser::next_item), which resumes the parser from the start of the
+/// buffered text once more of it has arrived. It does no I/O itself, so an async task can
+/// drive it from whatever its runtime reads
+///
+/// A parser cannot tell whether it failed for want of input, as backtracking combinators report
+/// where they gave up rather than where the input ran out. Until [`finish`](StreamParser::finish)
+/// is called a failure therefore waits for more input, as does an item ending with the buffered
+/// text, unless the error is [committed](super::traits::Parser::cut) which makes it final at
+/// once. Chunks may split UTF-8 characters. Errors are indexed from the start of the stream and
+/// end it, as does an item consuming nothing
+///
+/// # Examples
+/// ```rust
+///
+/// use pepser::parser::json::{json_value, JsonNumber, JsonValue};
+/// use pepser::parser::read::StreamParser;
+/// let mut stream = StreamParser::new(json_value);
+///
+/// stream.feed(b"[1, 2] 3");
+/// assert!(matches!(stream.next_item(), Some(Ok(JsonValue::Array(_)))));
+/// assert!(stream.next_item().is_none());
+/// stream.feed(b"4 [");
+/// assert_eq!(stream.next_item().unwrap().unwrap(), JsonValue::Number(JsonNumber::Int(34)));
+/// stream.finish();
+/// assert!(stream.next_item().unwrap().is_err());
+/// assert!(stream.next_item().is_none());
+///
+///
+/// ```
+pub struct StreamParser<P> {
+    parser: P,
+    buffer: Vec<u8>,
+    /// Bytes of the items already parsed, dropped from the buffer
+    consumed: usize,
+    finished: bool,
+    failed: bool,
+}
+
+impl<P, O> StreamParser<P>
+where
+    P: for<'a> Parser<&'a str, Output = O>,
+{
+    pub fn new(parser: P) -> Self {
+        StreamParser {
+            parser,
+            buffer: Vec::new(),
+            consumed: 0,
+            finished: false,
+            failed: false,
+        }
+    }
+
+    /// Appends a chunk of the input
+    pub fn feed(&mut self, chunk: &[u8]) {
+        self.buffer.extend_from_slice(chunk);
+    }
+
+    /// Marks the end of the input, so that what is buffered is parsed as is
+    pub fn finish(&mut self) {
+        self.finished = true;
+    }
+
+    /// The next complete item, `None` when more input is needed or once the stream has ended,
+    /// after [`finish`](StreamParser::finish) or an error. Whitespace left at the end of the
+    /// input is ignored
+    pub fn next_item(&mut self) -> Option<Result<O, ReadError>> {
+        if self.failed {
+            return None;
+        }
+        let (text, invalid) = match str::from_utf8(&self.buffer) {
+            Ok(text) => (text, None),
+            Err(error) => (
+                str::from_utf8(&self.buffer[..error.valid_up_to()]).unwrap_or_default(),
+                Some(error),
+            ),
+        };
+        // Nothing can extend the text past the end of the input or an invalid character
+        let complete = self.finished || invalid.is_some_and(|error| error.error_len().is_some());
+        if complete && text.trim_start().is_empty() {
+            self.failed = true;
+            let error = invalid?;
+            return Some(Err(io::Error::new(io::ErrorKind::InvalidData, error).into()));
+        }
+        let error = match self.parser.parse(text) {
+            Ok((remainder, _)) if remainder.len() == text.len() => {
+                ParserError::custom(0, "item", "the item consumed nothing")
+            }
+            Ok((remainder, _)) if remainder.is_empty() && !complete => return None,
+            Ok((remainder, output)) => {
+                let parsed = text.len() - remainder.len();
+                self.buffer.drain(..parsed);
+                self.consumed += parsed;
+                return Some(Ok(output));
+            }
+            Err(error) if !error.committed && !complete => return None,
+            Err(error) => error,
+        };
+        self.failed = true;
+        let error = ParserError::from_error(error, self.consumed);
+        Some(Err(ReadError::Parse {
+            index: error.index,
+            message: error.to_string(),
+        }))
+    }
+}
+
+/// Parses the items of a reader with a [`StreamParser`], reading it in chunks as the items
+/// are pulled so that the whole input is never held in memory
+///
+/// # Examples
+/// ```rust
+///
+/// use pepser::parser::json::json_value;
+/// use pepser::parser::read::parse_items;
+/// let lines = "{\"id\": 1}\n{\"id\": 2}\n{\"id\"".as_bytes();
+/// let items = parse_items(json_value, lines).collect::<Vec<_>>();
+///
+/// assert_eq!(items.len(), 3);
+/// assert!(items[..2].iter().all(Result::is_ok));
+/// assert!(items[2].is_err());
+///
+///
+/// ```
+pub fn parse_items<P, O, R>(parser: P, reader: R) -> ReadItems<P, R>
+where
+    P: for<'a> Parser<&'a str, Output = O>,
+    R: Read,
+{
+    ReadItems {
+        stream: StreamParser::new(parser),
+        reader,
+    }
+}
+
+/// Iterator returned by [`parse_items`]
+pub struct ReadItems<P, R> {
+    stream: StreamParser<P>,
+    reader: R,
+}
+
+impl<P, O, R> Iterator for ReadItems<P, R>
+where
+    P: for<'a> Parser<&'a str, Output = O>,
+    R: Read,
+{
+    type Item = Result<O, ReadError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut chunk = [0; 8192];
+        loop {
+            if let Some(item) = self.stream.next_item() {
+                return Some(item);
+            }
+            if self.stream.finished || self.stream.failed {
+                return None;
+            }
+            match self.reader.read(&mut chunk) {
+                Ok(0) => self.stream.finish(),
+                Ok(read) => self.stream.feed(&chunk[..read]),
+                Err(error) if error.kind() == io::ErrorKind::Interrupted => {}
+                Err(error) => {
+                    self.stream.failed = true;
+                    return Some(Err(error.into()));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,401 @@
+use super::{
+    errors::{ErrorSource, ParserError},
+    impls::{eof, sequence, take_while_m_n},
+    traits::{discard, opt, sep_by, wrapped, ParseResult, Parser},
+};
+
+/// A pattern compiled from a small regex subset: literals, `.`, character classes
+/// (`[a-z]`, `[^0-9]`, `\d`, `\w`, `\s` and their negations), groups, alternation, the `*`,
+/// `+` and `?` quantifiers and the `^`/`$` anchors.
+///
+/// As a [`Parser`], a regex always matches at the start of its input (so `^` is implied) and
+/// returns the matched prefix. Quantifiers are greedy and alternatives are tried in order, the
+/// match is the one a backtracking matcher would find. The pattern is compiled to a program
+/// which runs on every alternative at once, so matching takes a time linear in the length of
+/// the input and never recurses on it. A quantifier directly following another one, as in
+/// `a**`, is rejected
+///
+/// # Examples
+/// ```rust
+///
+/// use pepser::parser::regex::regex;
+/// use pepser::parser::traits::Parser;
+///
+/// assert!(regex("a*").unwrap().is_match(&"a".repeat(100_000)));
+/// assert_eq!(regex("a|ab").unwrap().parse("abc"), Ok(("bc", "a")));
+/// assert_eq!(regex("a+*").unwrap_err().index, 2);
+///
+///
+/// ```
+#[derive(Debug, PartialEq, Clone)]
+pub struct Regex {
+    program: Vec<Instruction>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+enum Node {
+    Empty,
+    Char(char),
+    Any,
+    Class {
+        items: Vec<ClassItem>,
+        negated: bool,
+    },
+    Concat(Vec<Node>),
+    Alternation(Vec<Node>),
+    Repeat {
+        node: Box<Node>,
+        min: usize,
+        max: Option<usize>,
+    },
+    Start,
+    End,
+}
+
+/// A step of a compiled pattern. `Split` continues at both targets, the first one taking
+/// precedence
+#[derive(Debug, PartialEq, Clone)]
+enum Instruction {
+    Char(char),
+    Any,
+    Class {
+        items: Vec<ClassItem>,
+        negated: bool,
+    },
+    Split(usize, usize),
+    Jump(usize),
+    Start,
+    End,
+    Match,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+enum ClassItem {
+    Range(char, char),
+    Digit(bool),
+    Word(bool),
+    Space(bool),
+}
+
+impl Regex {
+    /// Compiles a pattern, failing if any part of it is not understood
+    pub fn new(pattern: &str) -> Result<Regex, ParserError<&str>> {
+        let (_, node) = (|input| alternation(input, 0)).skip(eof()).parse(pattern)?;
+        let mut program = vec![];
+        node.compile(&mut program);
+        program.push(Instruction::Match);
+        Ok(Regex { program })
+    }
+
+    /// Returns whether the whole input matches the pattern
+    pub fn is_match(&self, input: &str) -> bool {
+        self.run(input, true).is_some()
+    }
+
+    /// Runs the program on every thread at once, one character of `input` at a time. Threads
+    /// are kept by precedence, so that the first one to match hides the ones after it. Returns
+    /// the end of the match, which must be the end of the input when `whole` is set
+    fn run(&self, input: &str, whole: bool) -> Option<usize> {
+        let mut threads = Threads::new(self.program.len());
+        let mut next = Threads::new(self.program.len());
+        let mut matched = None;
+        let mut position = 0;
+        threads.add(&self.program, 0, input, position);
+        loop {
+            let c = input[position..].chars().next();
+            let end = position + c.map_or(0, char::len_utf8);
+            for &pc in &threads.pcs {
+                let accepted = match &self.program[pc] {
+                    Instruction::Match if !whole || c.is_none() => {
+                        matched = Some(position);
+                        break;
+                    }
+                    Instruction::Char(expected) => c == Some(*expected),
+                    Instruction::Any => c.is_some_and(|c| c != '\n'),
+                    Instruction::Class { items, negated } => {
+                        c.is_some_and(|c| items.iter().any(|item| item.contains(c)) != *negated)
+                    }
+                    _ => false,
+                };
+                if accepted {
+                    next.add(&self.program, pc + 1, input, end);
+                }
+            }
+            if c.is_none() || next.pcs.is_empty() {
+                return matched;
+            }
+            core::mem::swap(&mut threads, &mut next);
+            next.clear();
+            position = end;
+        }
+    }
+}
+
+/// The threads waiting on one position of the input, as the program counters they went
+/// through in order of precedence. Only the instructions which read a character or match
+/// move them forward
+struct Threads {
+    pcs: Vec<usize>,
+    added: Vec<bool>,
+}
+
+impl Threads {
+    fn new(size: usize) -> Self {
+        Threads {
+            pcs: vec![],
+            added: vec![false; size],
+        }
+    }
+
+    fn clear(&mut self) {
+        for &pc in &self.pcs {
+            self.added[pc] = false;
+        }
+        self.pcs.clear();
+    }
+
+    /// Follows the jumps, splits and anchors from `pc` at `position`. A thread which reaches
+    /// an instruction already added has a lower precedence than the one there and stops,
+    /// which also ends loops on the empty string such as `(a*)*`
+    fn add(&mut self, program: &[Instruction], pc: usize, input: &str, position: usize) {
+        let mut stack = vec![pc];
+        while let Some(pc) = stack.pop() {
+            if self.added[pc] {
+                continue;
+            }
+            self.added[pc] = true;
+            self.pcs.push(pc);
+            match &program[pc] {
+                Instruction::Jump(target) => stack.push(*target),
+                Instruction::Split(first, second) => {
+                    stack.push(*second);
+                    stack.push(*first);
+                }
+                Instruction::Start if position == 0 => stack.push(pc + 1),
+                Instruction::End if position == input.len() => stack.push(pc + 1),
+                _ => {}
+            }
+        }
+    }
+}
+
+impl<'a> Parser<&'a str> for Regex {
+    type Output = &'a str;
+
+    fn parse(&mut self, input: &'a str) -> ParseResult<&'a str, &'a str> {
+        match self.run(input, false) {
+            Some(end) => {
+                let (parsed, remainder) = input.split_at(end);
+                Ok((remainder, parsed))
+            }
+            None => Err(ParserError::new(
+                0,
+                ErrorSource::Regex,
+                "input does not match the pattern",
+            )),
+        }
+    }
+}
+
+/// Compiles a pattern into a parser, see [`Regex`] for the supported syntax
+pub fn regex(pattern: &str) -> Result<Regex, ParserError<&str>> {
+    Regex::new(pattern)
+}
+
+impl Node {
+    /// Appends the instructions of the node to `program`, they continue after the last one
+    fn compile(&self, program: &mut Vec<Instruction>) {
+        match self {
+            Node::Empty => {}
+            Node::Char(c) => program.push(Instruction::Char(*c)),
+            Node::Any => program.push(Instruction::Any),
+            Node::Class { items, negated } => program.push(Instruction::Class {
+                items: items.clone(),
+                negated: *negated,
+            }),
+            Node::Concat(nodes) => nodes.iter().for_each(|node| node.compile(program)),
+            Node::Alternation(nodes) => {
+                let mut jumps = vec![];
+                for (index, node) in nodes.iter().enumerate() {
+                    if index == nodes.len() - 1 {
+                        node.compile(program);
+                        break;
+                    }
+                    let split = program.len();
+                    program.push(Instruction::Split(split + 1, 0));
+                    node.compile(program);
+                    jumps.push(program.len());
+                    program.push(Instruction::Jump(0));
+                    program[split] = Instruction::Split(split + 1, program.len());
+                }
+                let end = program.len();
+                for jump in jumps {
+                    program[jump] = Instruction::Jump(end);
+                }
+            }
+            // `*` and `?` are `Split(node, next)` looping back or not, `+` is `node` then a
+            // split back to it
+            Node::Repeat { node, min, max } => {
+                let start = program.len();
+                if *min > 0 {
+                    node.compile(program);
+                    program.push(Instruction::Split(start, program.len() + 1));
+                    return;
+                }
+                program.push(Instruction::Split(start + 1, 0));
+                node.compile(program);
+                if max.is_none() {
+                    program.push(Instruction::Jump(start));
+                }
+                program[start] = Instruction::Split(start + 1, program.len());
+            }
+            Node::Start => program.push(Instruction::Start),
+            Node::End => program.push(Instruction::End),
+        }
+    }
+}
+
+impl ClassItem {
+    fn contains(&self, c: char) -> bool {
+        match self {
+            ClassItem::Range(start, end) => (*start..=*end).contains(&c),
+            ClassItem::Digit(negated) => c.is_ascii_digit() != *negated,
+            ClassItem::Word(negated) => (c.is_alphanumeric() || c == '_') != *negated,
+            ClassItem::Space(negated) => c.is_whitespace() != *negated,
+        }
+    }
+}
+
+/// Deepest nesting of groups accepted, so that a hostile pattern cannot exhaust the stack
+const MAX_DEPTH: usize = 128;
+
+/// The branches of a pattern within `depth` groups
+fn alternation(input: &str, depth: usize) -> ParseResult<&str, Node> {
+    sep_by(|input| concatenation(input, depth), sequence("|"))
+        .map(|mut branches| match branches.len() {
+            1 => branches.remove(0),
+            _ => Node::Alternation(branches),
+        })
+        .parse(input)
+}
+
+fn concatenation(input: &str, depth: usize) -> ParseResult<&str, Node> {
+    (|input| repetition(input, depth))
+        .many0()
+        .map(|mut nodes| match nodes.len() {
+            0 => Node::Empty,
+            1 => nodes.remove(0),
+            _ => Node::Concat(nodes),
+        })
+        .parse(input)
+}
+
+/// An atom and its quantifier, if any. Stacked quantifiers such as `a**` or `a+?` are a
+/// committed error, a group repeats a repetition as in `(a*)*`
+fn repetition(input: &str, depth: usize) -> ParseResult<&str, Node> {
+    let (remainder, atom) = atom(input, depth)?;
+    let Ok((remainder, (min, max))) = quantifier(remainder) else {
+        return Ok((remainder, atom));
+    };
+    if quantifier(remainder).is_ok() {
+        let mut error = ParserError::new(
+            input.len() - remainder.len(),
+            ErrorSource::Regex,
+            "a quantifier follows a quantifier",
+        );
+        error.committed = true;
+        return Err(error);
+    }
+    Ok((
+        remainder,
+        Node::Repeat {
+            node: Box::new(atom),
+            min,
+            max,
+        },
+    ))
+}
+
+fn quantifier(input: &str) -> ParseResult<&str, (usize, Option<usize>)> {
+    sequence("*")
+        .map(|_| (0, None))
+        .or(sequence("+").map(|_| (1, None)))
+        .or(sequence("?").map(|_| (0, Some(1))))
+        .parse(input)
+}
+
+fn atom(input: &str, depth: usize) -> ParseResult<&str, Node> {
+    if depth >= MAX_DEPTH && input.starts_with('(') {
+        let mut error = ParserError::new(0, ErrorSource::LimitExceeded, "group nested too deeply");
+        error.committed = true;
+        return Err(error);
+    }
+    wrapped(
+        sequence("("),
+        |input| alternation(input, depth + 1),
+        sequence(")"),
+    )
+    .or(class)
+    .or(sequence(".").map(|_| Node::Any))
+    .or(sequence("^").map(|_| Node::Start))
+    .or(sequence("$").map(|_| Node::End))
+    .or(escape.map(|item| match item {
+        ClassItem::Range(c, _) => Node::Char(c),
+        item => Node::Class {
+            items: vec![item],
+            negated: false,
+        },
+    }))
+    .or(literal("()[]|*+?.^$\\").map(Node::Char))
+    .parse(input)
+}
+
+fn class(input: &str) -> ParseResult<&str, Node> {
+    wrapped(
+        sequence("["),
+        opt(sequence("^")).and(class_item.many0()),
+        sequence("]"),
+    )
+    .map(|(negated, items)| Node::Class {
+        items,
+        negated: negated.is_some(),
+    })
+    .parse(input)
+}
+
+fn class_item(input: &str) -> ParseResult<&str, ClassItem> {
+    escape
+        .or(|input| {
+            let (input, start) = literal("]\\")(input)?;
+            let (input, end) = opt(discard(sequence("-"), literal("]\\"))).parse(input)?;
+            Ok((input, ClassItem::Range(start, end.unwrap_or(start))))
+        })
+        .parse(input)
+}
+
+/// Parses `\d`, `\w`, `\s` (and their uppercase negations) into class items, any other
+/// escaped character is a literal
+fn escape(input: &str) -> ParseResult<&str, ClassItem> {
+    let (remainder, _) = sequence("\\").parse(input)?;
+    let c = remainder
+        .chars()
+        .next()
+        .ok_or_else(|| ParserError::new(1, ErrorSource::Regex, "pattern ends with an escape"))?;
+    let item = match c {
+        'd' | 'D' => ClassItem::Digit(c == 'D'),
+        'w' | 'W' => ClassItem::Word(c == 'W'),
+        's' | 'S' => ClassItem::Space(c == 'S'),
+        'n' => ClassItem::Range('\n', '\n'),
+        't' => ClassItem::Range('\t', '\t'),
+        c => ClassItem::Range(c, c),
+    };
+    Ok((&remainder[c.len_utf8()..], item))
+}
+
+/// A single character other than the `special` ones
+fn literal<'a>(special: &'a str) -> impl FnMut(&'a str) -> ParseResult<&'a str, char> {
+    move |input: &'a str| {
+        let (remainder, parsed) = take_while_m_n(1, 1, |c| !special.contains(c)).parse(input)?;
+        Ok((remainder, parsed.chars().next().unwrap_or_default()))
+    }
+}
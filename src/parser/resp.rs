@@ -0,0 +1,277 @@
+use alloc::vec::Vec;
+use core::str;
+
+use super::{
+    errors::{ErrorSource, ParserError},
+    traits::ParseResult,
+};
+
+/// Deepest nesting of aggregates accepted, so that a hostile frame cannot exhaust the stack
+const MAX_DEPTH: usize = 128;
+
+/// Reason of the errors caused by a frame ending before its last byte
+const INCOMPLETE: &str = "incomplete frame";
+
+/// A frame of the Redis serialization protocol, RESP2 or RESP3. Strings are bytes, as bulk
+/// strings are binary safe and simple strings need not be UTF-8
+#[derive(Debug, PartialEq, Clone)]
+pub enum Frame<'a> {
+    /// `+OK`
+    SimpleString(&'a [u8]),
+    /// `-ERR unknown command`
+    Error(&'a [u8]),
+    /// `:42`
+    Integer(i64),
+    /// `$3\r\nfoo`, `None` for the RESP2 null bulk string `$-1`
+    Bulk(Option<&'a [u8]>),
+    /// `*2\r\n...`, `None` for the RESP2 null array `*-1`
+    Array(Option<Vec<Frame<'a>>>),
+    /// `_`
+    Null,
+    /// `#t` or `#f`
+    Boolean(bool),
+    /// `,3.14`, `,inf`, `,-inf` or `,nan`
+    Double(f64),
+    /// `(3492890328409238509324850943850943825024385`, kept as its digits
+    BigNumber(&'a [u8]),
+    /// `!21\r\nSYNTAX invalid syntax`
+    BulkError(&'a [u8]),
+    /// `=15\r\ntxt:Some string`, whose format is the three bytes before the colon
+    Verbatim { format: &'a [u8], text: &'a [u8] },
+    /// `%1\r\n...`, the pairs in the order of the frame
+    Map(Vec<(Frame<'a>, Frame<'a>)>),
+    /// `~2\r\n...`
+    Set(Vec<Frame<'a>>),
+    /// `>2\r\n...`, data pushed by the server out of band
+    Push(Vec<Frame<'a>>),
+}
+
+/// Parses a single frame. A frame cut short fails with the reason `"incomplete frame"`, see
+/// [`decode`] to read frames from a buffer filled by a socket. Attributes (`|`) are not
+/// supported. Error indices are offsets in the input
+///
+/// # Examples
+/// ```rust
+///
+/// use pepser::parser::resp::{frame, Frame};
+/// let (remainder, reply) = frame(b"*2\r\n$3\r\nGET\r\n:7\r\n+PONG\r\n").unwrap();
+///
+/// assert_eq!(
+///     reply,
+///     Frame::Array(Some(vec![Frame::Bulk(Some(b"GET")), Frame::Integer(7)]))
+/// );
+/// assert_eq!(remainder, b"+PONG\r\n");
+/// assert_eq!(frame(b"$5\r\nab").unwrap_err().reason, "incomplete frame");
+///
+///
+/// ```
+pub fn frame(input: &[u8]) -> ParseResult<&[u8], Frame<'_>> {
+    frame_at(input, input, 0)
+}
+
+/// Decodes the frame at the start of `buffer`, outputting it with the number of bytes it
+/// takes or `None` when the buffer only holds the start of a frame and more must be read.
+/// Other errors mean the stream is not valid RESP
+///
+/// # Examples
+/// ```rust
+///
+/// use pepser::parser::resp::{decode, Frame};
+/// let mut buffer = b"+OK\r\n:1".to_vec();
+///
+/// assert_eq!(decode(&buffer), Ok(Some((Frame::SimpleString(b"OK"), 5))));
+/// buffer.drain(..5);
+/// assert_eq!(decode(&buffer), Ok(None));
+/// buffer.extend(b"2\r\n");
+/// assert_eq!(decode(&buffer), Ok(Some((Frame::Integer(12), 5))));
+/// assert!(decode(b"?\r\n").is_err());
+///
+///
+/// ```
+pub fn decode(buffer: &[u8]) -> Result<Option<(Frame<'_>, usize)>, ParserError<&[u8]>> {
+    match frame(buffer) {
+        Ok((remainder, parsed)) => Ok(Some((parsed, buffer.len() - remainder.len()))),
+        Err(error) if error.reason == INCOMPLETE => Ok(None),
+        Err(error) => Err(error),
+    }
+}
+
+/// Offset of `input` in `packet`, of which it is a suffix
+fn position(packet: &[u8], input: &[u8]) -> usize {
+    packet.len() - input.len()
+}
+
+fn invalid<'a>(index: usize, reason: &'static str) -> ParserError<&'a [u8]> {
+    ParserError::new(index, ErrorSource::TakeWhile, reason)
+}
+
+fn frame_at<'a>(
+    packet: &'a [u8],
+    input: &'a [u8],
+    depth: usize,
+) -> ParseResult<&'a [u8], Frame<'a>> {
+    let start = position(packet, input);
+    let Some((&kind, rest)) = input.split_first() else {
+        return Err(invalid(start, INCOMPLETE));
+    };
+    if depth > MAX_DEPTH {
+        return Err(invalid(start, "frame nested too deeply"));
+    }
+    match kind {
+        b'+' => line(packet, rest).map(|(rest, line)| (rest, Frame::SimpleString(line))),
+        b'-' => line(packet, rest).map(|(rest, line)| (rest, Frame::Error(line))),
+        b':' => integer(packet, rest).map(|(rest, integer)| (rest, Frame::Integer(integer))),
+        b'$' => bulk(packet, rest).map(|(rest, bulk)| (rest, Frame::Bulk(bulk))),
+        b'*' => {
+            let (rest, count) = integer(packet, rest)?;
+            if count == -1 {
+                return Ok((rest, Frame::Array(None)));
+            }
+            let (rest, frames) = frames(packet, rest, count, depth)?;
+            Ok((rest, Frame::Array(Some(frames))))
+        }
+        b'_' => {
+            let (rest, line) = line(packet, rest)?;
+            match line {
+                b"" => Ok((rest, Frame::Null)),
+                _ => Err(invalid(start + 1, "expected an empty null")),
+            }
+        }
+        b'#' => {
+            let (rest, line) = line(packet, rest)?;
+            match line {
+                b"t" => Ok((rest, Frame::Boolean(true))),
+                b"f" => Ok((rest, Frame::Boolean(false))),
+                _ => Err(invalid(start + 1, "expected t or f")),
+            }
+        }
+        b',' => {
+            let (rest, line) = line(packet, rest)?;
+            str::from_utf8(line)
+                .ok()
+                .and_then(|double| double.parse::<f64>().ok())
+                .map(|double| (rest, Frame::Double(double)))
+                .ok_or_else(|| invalid(start + 1, "invalid double"))
+        }
+        b'(' => {
+            let (rest, line) = line(packet, rest)?;
+            let digits = line.strip_prefix(b"-").unwrap_or(line);
+            if digits.is_empty() || !digits.iter().all(u8::is_ascii_digit) {
+                return Err(invalid(start + 1, "invalid big number"));
+            }
+            Ok((rest, Frame::BigNumber(line)))
+        }
+        b'!' => blob(packet, rest).map(|(rest, blob)| (rest, Frame::BulkError(blob))),
+        b'=' => {
+            let (rest, blob) = blob(packet, rest)?;
+            match blob.get(3) {
+                Some(b':') => Ok((
+                    rest,
+                    Frame::Verbatim {
+                        format: &blob[..3],
+                        text: &blob[4..],
+                    },
+                )),
+                _ => Err(invalid(start + 1, "expected a format before the text")),
+            }
+        }
+        b'%' => {
+            let (rest, count) = length(packet, rest)?;
+            let (rest, frames) = frames(packet, rest, count.saturating_mul(2), depth)?;
+            let mut frames = frames.into_iter();
+            let mut pairs = Vec::new();
+            while let (Some(key), Some(value)) = (frames.next(), frames.next()) {
+                pairs.push((key, value));
+            }
+            Ok((rest, Frame::Map(pairs)))
+        }
+        b'~' => {
+            let (rest, count) = length(packet, rest)?;
+            let (rest, frames) = frames(packet, rest, count, depth)?;
+            Ok((rest, Frame::Set(frames)))
+        }
+        b'>' => {
+            let (rest, count) = length(packet, rest)?;
+            let (rest, frames) = frames(packet, rest, count, depth)?;
+            Ok((rest, Frame::Push(frames)))
+        }
+        _ => Err(invalid(start, "unknown frame type")),
+    }
+}
+
+/// The `count` frames of an aggregate. The vector is not allocated ahead from `count`, which
+/// the sender chooses
+fn frames<'a>(
+    packet: &'a [u8],
+    mut input: &'a [u8],
+    count: i64,
+    depth: usize,
+) -> ParseResult<&'a [u8], Vec<Frame<'a>>> {
+    if count < 0 {
+        return Err(invalid(position(packet, input), "negative length"));
+    }
+    let mut frames = Vec::new();
+    for _ in 0..count {
+        let (rest, parsed) = frame_at(packet, input, depth + 1)?;
+        frames.push(parsed);
+        input = rest;
+    }
+    Ok((input, frames))
+}
+
+/// Takes the bytes up to the next `\r\n`, which is consumed
+fn line<'a>(packet: &'a [u8], input: &'a [u8]) -> ParseResult<&'a [u8], &'a [u8]> {
+    match input.windows(2).position(|window| window == b"\r\n") {
+        Some(end) => Ok((&input[end + 2..], &input[..end])),
+        None => Err(invalid(packet.len(), INCOMPLETE)),
+    }
+}
+
+fn integer<'a>(packet: &'a [u8], input: &'a [u8]) -> ParseResult<&'a [u8], i64> {
+    let (rest, line) = line(packet, input)?;
+    str::from_utf8(line)
+        .ok()
+        .and_then(|integer| integer.parse::<i64>().ok())
+        .map(|integer| (rest, integer))
+        .ok_or_else(|| invalid(position(packet, input), "invalid integer"))
+}
+
+/// The length of an aggregate which has no null form
+fn length<'a>(packet: &'a [u8], input: &'a [u8]) -> ParseResult<&'a [u8], i64> {
+    let (rest, length) = integer(packet, input)?;
+    match length {
+        0.. => Ok((rest, length)),
+        _ => Err(invalid(position(packet, input), "negative length")),
+    }
+}
+
+fn bulk<'a>(packet: &'a [u8], input: &'a [u8]) -> ParseResult<&'a [u8], Option<&'a [u8]>> {
+    let (_, length) = integer(packet, input)?;
+    if length == -1 {
+        let (rest, _) = line(packet, input)?;
+        return Ok((rest, None));
+    }
+    blob(packet, input).map(|(rest, blob)| (rest, Some(blob)))
+}
+
+/// A length followed by that many bytes and a `\r\n`
+fn blob<'a>(packet: &'a [u8], input: &'a [u8]) -> ParseResult<&'a [u8], &'a [u8]> {
+    let (rest, length) = length(packet, input)?;
+    let Ok(length) = usize::try_from(length) else {
+        return Err(invalid(
+            position(packet, input),
+            "length does not fit in memory",
+        ));
+    };
+    let Some(blob) = rest.get(..length) else {
+        return Err(invalid(packet.len(), INCOMPLETE));
+    };
+    match &rest[length..] {
+        [b'\r', b'\n', rest @ ..] => Ok((rest, blob)),
+        [] | [b'\r'] => Err(invalid(packet.len(), INCOMPLETE)),
+        _ => Err(invalid(
+            position(packet, rest) + length,
+            "expected \\r\\n after the data",
+        )),
+    }
+}
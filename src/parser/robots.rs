@@ -0,0 +1,255 @@
+use super::{
+    errors::{ErrorSource, ParserError},
+    traits::ParseResult,
+    xml::{xml_document, XmlElement},
+};
+
+/// An `allow` or `disallow` rule, whose path may hold `*` wildcards and end with `$`
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Rule {
+    pub allow: bool,
+    pub path: String,
+}
+
+/// The rules of the crawlers named by consecutive `user-agent` lines
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct Group {
+    pub user_agents: Vec<String>,
+    pub rules: Vec<Rule>,
+    /// Seconds to wait between requests
+    pub crawl_delay: Option<f64>,
+}
+
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct Robots {
+    pub groups: Vec<Group>,
+    /// The `sitemap` URLs, which belong to no group
+    pub sitemaps: Vec<String>,
+}
+
+impl Robots {
+    /// The groups which apply to a crawler: those naming its product token, the part of
+    /// `user_agent` before any `/` or space, or else those of `*`
+    pub fn groups_for<'a>(&'a self, user_agent: &str) -> Vec<&'a Group> {
+        let token = user_agent.split(['/', ' ']).next().unwrap_or(user_agent);
+        let named = |name: &str| {
+            self.groups
+                .iter()
+                .filter(|group| {
+                    group
+                        .user_agents
+                        .iter()
+                        .any(|agent| agent.eq_ignore_ascii_case(name))
+                })
+                .collect::<Vec<_>>()
+        };
+        match named(token) {
+            groups if groups.is_empty() => named("*"),
+            groups => groups,
+        }
+    }
+
+    /// Whether a crawler may fetch `path`, as RFC 9309 decides: the rule of the groups for the
+    /// crawler matching the longest part of the path wins, `allow` winning ties. Paths no rule
+    /// matches and `/robots.txt` are allowed
+    ///
+    /// # Examples
+    /// ```rust
+    ///
+    /// use pepser::parser::robots::robots;
+    /// let rules = robots(
+    ///     "User-agent: *\nDisallow: /private/\nAllow: /private/public$\n\n\
+    ///     user-agent: BadBot\ndisallow: /\n",
+    /// );
+    ///
+    /// assert!(!rules.is_allowed("Mozilla/5.0", "/private/data"));
+    /// assert!(rules.is_allowed("Mozilla/5.0", "/private/public"));
+    /// assert!(!rules.is_allowed("badbot/1.0", "/index.html"));
+    ///
+    ///
+    /// ```
+    pub fn is_allowed(&self, user_agent: &str, path: &str) -> bool {
+        if path == "/robots.txt" {
+            return true;
+        }
+        self.groups_for(user_agent)
+            .into_iter()
+            .flat_map(|group| &group.rules)
+            .filter(|rule| !rule.path.is_empty() && matches(&rule.path, path))
+            .max_by_key(|rule| (rule.path.len(), rule.allow))
+            .is_none_or(|rule| rule.allow)
+    }
+
+    /// The crawl delay of the first group for the crawler which sets one
+    pub fn crawl_delay(&self, user_agent: &str) -> Option<f64> {
+        self.groups_for(user_agent)
+            .into_iter()
+            .find_map(|group| group.crawl_delay)
+    }
+}
+
+/// Parses a robots.txt file into its groups. Keys ignore their case, `#` starts a comment, and
+/// like crawlers do, lines which are not `key: value`, unknown keys, rules before the first
+/// `user-agent` and invalid crawl delays are skipped
+pub fn robots(input: &str) -> Robots {
+    let mut robots = Robots::default();
+    // Whether the last group has lines after its user agents, a user agent then starts another
+    let mut has_rules = false;
+    let input = input.strip_prefix('\u{feff}').unwrap_or(input);
+    for line in input.lines() {
+        let line = line.split('#').next().unwrap_or_default();
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim().to_string();
+        let group = robots.groups.last_mut();
+        match key.trim().to_ascii_lowercase().as_str() {
+            "user-agent" => match group {
+                Some(group) if !has_rules => group.user_agents.push(value),
+                _ => {
+                    robots.groups.push(Group {
+                        user_agents: vec![value],
+                        ..Group::default()
+                    });
+                    has_rules = false;
+                }
+            },
+            key @ ("allow" | "disallow") => {
+                if let Some(group) = group {
+                    group.rules.push(Rule {
+                        allow: key == "allow",
+                        path: value,
+                    });
+                    has_rules = true;
+                }
+            }
+            "crawl-delay" => {
+                if let Some(group) = group {
+                    group.crawl_delay = value.parse().ok().filter(|delay: &f64| *delay >= 0.0);
+                    has_rules = true;
+                }
+            }
+            "sitemap" => robots.sitemaps.push(value),
+            _ => {}
+        }
+    }
+    robots
+}
+
+/// Whether a rule path matches the start of `path`, `*` matching any characters and a final
+/// `$` the end of the path
+fn matches(pattern: &str, path: &str) -> bool {
+    let (pattern, anchored) = match pattern.strip_suffix('$') {
+        Some(pattern) => (pattern, true),
+        None => (pattern, false),
+    };
+    let mut parts = pattern.split('*');
+    let Some(mut rest) = path.strip_prefix(parts.next().unwrap_or_default()) else {
+        return false;
+    };
+    let parts: Vec<_> = parts.collect();
+    for (index, part) in parts.iter().enumerate() {
+        if anchored && index == parts.len() - 1 {
+            return rest.ends_with(part);
+        }
+        match rest.find(part) {
+            Some(start) => rest = &rest[start + part.len()..],
+            None => return false,
+        }
+    }
+    !anchored || rest.is_empty()
+}
+
+/// An entry of a sitemap, a page of a `<urlset>` or a sitemap of a `<sitemapindex>`
+#[derive(Debug, PartialEq, Clone)]
+pub struct SitemapEntry {
+    /// The `<loc>` URL
+    pub location: String,
+    /// The `<lastmod>` date as written, in the W3C datetime format
+    pub last_modified: Option<String>,
+    /// The `<changefreq>` of a page, `daily`, `weekly`...
+    pub change_frequency: Option<String>,
+    /// The `<priority>` of a page, from `0.0` to `1.0`
+    pub priority: Option<f64>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Sitemap {
+    /// The pages of a `<urlset>`
+    Urls(Vec<SitemapEntry>),
+    /// The sitemaps of a `<sitemapindex>`
+    Index(Vec<SitemapEntry>),
+}
+
+/// Parses an XML sitemap, a `<urlset>` of pages or a `<sitemapindex>` of sitemaps. Elements
+/// are matched by their local name, children other than those of [`SitemapEntry`] are
+/// skipped. Errors of the document itself are offsets in the input, those of its content are
+/// at `0`
+///
+/// # Examples
+/// ```rust
+///
+/// use pepser::parser::robots::{sitemap, Sitemap};
+/// let (_, parsed) = sitemap(
+///     "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+///     <urlset xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n\
+///     <url><loc>https://example.com/</loc><priority>0.8</priority></url>\n\
+///     </urlset>",
+/// )
+/// .unwrap();
+///
+/// let Sitemap::Urls(urls) = parsed else { panic!("expected a urlset") };
+/// assert_eq!(urls[0].location, "https://example.com/");
+/// assert_eq!(urls[0].priority, Some(0.8));
+///
+///
+/// ```
+pub fn sitemap(input: &str) -> ParseResult<&str, Sitemap> {
+    let (remainder, document) = xml_document(input)?;
+    let (entry, wrap): (_, fn(Vec<SitemapEntry>) -> Sitemap) = match local_name(&document.root) {
+        "urlset" => ("url", Sitemap::Urls),
+        "sitemapindex" => ("sitemap", Sitemap::Index),
+        _ => return Err(invalid("expected a urlset or a sitemapindex")),
+    };
+    let entries = document
+        .root
+        .elements()
+        .filter(|element| local_name(element) == entry)
+        .map(sitemap_entry)
+        .collect::<Result<_, _>>()?;
+    Ok((remainder, wrap(entries)))
+}
+
+fn invalid<'a>(reason: &'static str) -> ParserError<&'a str> {
+    ParserError::new(0, ErrorSource::TakeWhile, reason)
+}
+
+/// The name of an element without its namespace prefix
+fn local_name(element: &XmlElement) -> &str {
+    element
+        .name
+        .rsplit_once(':')
+        .map_or(element.name.as_str(), |(_, name)| name)
+}
+
+fn sitemap_entry<'a>(element: &XmlElement) -> Result<SitemapEntry, ParserError<&'a str>> {
+    let child = |name: &str| {
+        element
+            .elements()
+            .find(|child| local_name(child) == name)
+            .map(|child| child.text().trim().to_string())
+    };
+    let priority = match child("priority") {
+        Some(priority) => match priority.parse::<f64>() {
+            Ok(priority) if (0.0..=1.0).contains(&priority) => Some(priority),
+            _ => return Err(invalid("invalid priority")),
+        },
+        None => None,
+    };
+    Ok(SitemapEntry {
+        location: child("loc").ok_or_else(|| invalid("entry without a loc"))?,
+        last_modified: child("lastmod"),
+        change_frequency: child("changefreq"),
+        priority,
+    })
+}
@@ -0,0 +1,410 @@
+use alloc::{
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use core::{
+    cmp::Ordering,
+    fmt::{self, Display, Formatter},
+};
+
+use super::{
+    errors::{ErrorSource, ParserError},
+    impls::{sequence, take_while1, ws},
+    traits::{discard, opt, parse_if, parse_rest, sep_by, wrapped, ParseResult, Parser},
+};
+
+/// A pre-release identifier, numeric identifiers have lower precedence than alphanumeric ones
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone)]
+pub enum Identifier {
+    Numeric(u64),
+    AlphaNumeric(String),
+}
+
+/// A semantic version. Ordering follows the semver precedence rules, build metadata is only
+/// used to break ties so that the ordering stays consistent with equality
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Version {
+    pub major: u64,
+    pub minor: u64,
+    pub patch: u64,
+    pub pre: Vec<Identifier>,
+    pub build: Vec<String>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Op {
+    Exact,
+    Greater,
+    GreaterEq,
+    Less,
+    LessEq,
+    Tilde,
+    Caret,
+    Wildcard,
+}
+
+/// A single comparator of a requirement, missing components act as wildcards
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Comparator {
+    pub op: Op,
+    pub major: u64,
+    pub minor: Option<u64>,
+    pub patch: Option<u64>,
+    pub pre: Vec<Identifier>,
+}
+
+/// Comma separated comparators which must all match, an empty list matches any version
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct VersionReq {
+    pub comparators: Vec<Comparator>,
+}
+
+impl Version {
+    /// Compares two versions by precedence only, ignoring build metadata
+    pub fn cmp_precedence(&self, other: &Version) -> Ordering {
+        (self.major, self.minor, self.patch)
+            .cmp(&(other.major, other.minor, other.patch))
+            .then_with(|| cmp_pre(&self.pre, &other.pre))
+    }
+}
+
+impl Ord for Version {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.cmp_precedence(other)
+            .then_with(|| self.build.cmp(&other.build))
+    }
+}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Display for Identifier {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Identifier::Numeric(value) => write!(f, "{}", value),
+            Identifier::AlphaNumeric(value) => f.write_str(value),
+        }
+    }
+}
+
+impl Display for Version {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)?;
+        for (i, identifier) in self.pre.iter().enumerate() {
+            write!(f, "{}{}", if i == 0 { "-" } else { "." }, identifier)?;
+        }
+        if !self.build.is_empty() {
+            write!(f, "+{}", self.build.join("."))?;
+        }
+        Ok(())
+    }
+}
+
+impl VersionReq {
+    /// A pre-release version only matches if a comparator targets the same
+    /// `major.minor.patch` with a pre-release of its own
+    pub fn matches(&self, version: &Version) -> bool {
+        self.comparators
+            .iter()
+            .all(|comparator| comparator.matches(version))
+            && (version.pre.is_empty()
+                || self.comparators.iter().any(|comparator| {
+                    comparator.major == version.major
+                        && comparator.minor == Some(version.minor)
+                        && comparator.patch == Some(version.patch)
+                        && !comparator.pre.is_empty()
+                }))
+    }
+}
+
+impl Comparator {
+    pub fn matches(&self, version: &Version) -> bool {
+        match self.op {
+            Op::Exact | Op::Wildcard => self.matches_exact(version),
+            Op::Greater => self.matches_greater(version),
+            Op::GreaterEq => self.matches_exact(version) || self.matches_greater(version),
+            Op::Less => self.matches_less(version),
+            Op::LessEq => self.matches_exact(version) || self.matches_less(version),
+            Op::Tilde => self.matches_tilde(version),
+            Op::Caret => self.matches_caret(version),
+        }
+    }
+
+    fn matches_exact(&self, version: &Version) -> bool {
+        version.major == self.major
+            && self.minor.is_none_or(|minor| version.minor == minor)
+            && self
+                .patch
+                .is_none_or(|patch| version.patch == patch && version.pre == self.pre)
+    }
+
+    fn matches_greater(&self, version: &Version) -> bool {
+        if version.major != self.major {
+            return version.major > self.major;
+        }
+        let Some(minor) = self.minor else {
+            return false;
+        };
+        if version.minor != minor {
+            return version.minor > minor;
+        }
+        let Some(patch) = self.patch else {
+            return false;
+        };
+        if version.patch != patch {
+            return version.patch > patch;
+        }
+        cmp_pre(&version.pre, &self.pre) == Ordering::Greater
+    }
+
+    fn matches_less(&self, version: &Version) -> bool {
+        if version.major != self.major {
+            return version.major < self.major;
+        }
+        let Some(minor) = self.minor else {
+            return false;
+        };
+        if version.minor != minor {
+            return version.minor < minor;
+        }
+        let Some(patch) = self.patch else {
+            return false;
+        };
+        if version.patch != patch {
+            return version.patch < patch;
+        }
+        cmp_pre(&version.pre, &self.pre) == Ordering::Less
+    }
+
+    fn matches_tilde(&self, version: &Version) -> bool {
+        if version.major != self.major {
+            return false;
+        }
+        if self.minor.is_some_and(|minor| version.minor != minor) {
+            return false;
+        }
+        match self.patch {
+            Some(patch) if version.patch != patch => version.patch > patch,
+            _ => cmp_pre(&version.pre, &self.pre) != Ordering::Less,
+        }
+    }
+
+    fn matches_caret(&self, version: &Version) -> bool {
+        if version.major != self.major {
+            return false;
+        }
+        let Some(minor) = self.minor else {
+            return true;
+        };
+        let Some(patch) = self.patch else {
+            return if self.major > 0 {
+                version.minor >= minor
+            } else {
+                version.minor == minor
+            };
+        };
+        if self.major > 0 {
+            if version.minor != minor {
+                return version.minor > minor;
+            }
+            if version.patch != patch {
+                return version.patch > patch;
+            }
+        } else if minor > 0 {
+            if version.minor != minor {
+                return false;
+            }
+            if version.patch != patch {
+                return version.patch > patch;
+            }
+        } else if version.minor != minor || version.patch != patch {
+            return false;
+        }
+        cmp_pre(&version.pre, &self.pre) != Ordering::Less
+    }
+}
+
+/// A version without pre-release identifiers has a higher precedence than one with them
+fn cmp_pre(first: &[Identifier], second: &[Identifier]) -> Ordering {
+    match (first.is_empty(), second.is_empty()) {
+        (true, true) => Ordering::Equal,
+        (true, false) => Ordering::Greater,
+        (false, true) => Ordering::Less,
+        (false, false) => first.cmp(second),
+    }
+}
+
+/// Parses a full `MAJOR.MINOR.PATCH[-pre][+build]` version. Error indices are offsets in the
+/// input
+///
+/// # Examples
+/// ```rust
+///
+/// use pepser::parser::semver::version;
+///
+/// assert_eq!(version("1.2.3-rc.1+abc").unwrap().1.to_string(), "1.2.3-rc.1+abc");
+/// assert_eq!(version("1.2.3-alpha.01").unwrap_err().index, 12);
+///
+///
+/// ```
+pub fn version(input: &str) -> ParseResult<&str, Version> {
+    let (rest, ((major, minor), patch)) = numeric
+        .and(discard(sequence("."), numeric))
+        .and(discard(sequence("."), numeric))
+        .parse(input)?;
+    let mut pre = parse_if(sequence("-"), pre_release).map(Option::unwrap_or_default);
+    let (rest, pre) = parse_rest(&mut pre, &input, rest)?;
+    let mut build = parse_if(sequence("+"), build_metadata).map(Option::unwrap_or_default);
+    let (rest, build) = parse_rest(&mut build, &input, rest)?;
+    Ok((
+        rest,
+        Version {
+            major,
+            minor,
+            patch,
+            pre,
+            build,
+        },
+    ))
+}
+
+/// Parses a requirement such as `^1.2`, `>=1.0, <2.0` or `1.*`. A bare version is treated
+/// as a caret requirement
+pub fn version_req(input: &str) -> ParseResult<&str, VersionReq> {
+    if let Ok((remainder, _)) = wrapped(ws(), wildcard, ws()).parse(input) {
+        return Ok((remainder, VersionReq::default()));
+    }
+    let (remainder, comparators) =
+        sep_by(wrapped(ws(), comparator, ws()), sequence(",")).parse(input)?;
+    if comparators.is_empty() {
+        return Err(ParserError::new(
+            0,
+            ErrorSource::TakeWhile,
+            "expected at least one comparator",
+        ));
+    }
+    Ok((remainder, VersionReq { comparators }))
+}
+
+/// Parses a single comparator such as `>=1.2.0-rc.1` or `~1.2`. Error indices are offsets in
+/// the input
+pub fn comparator(input: &str) -> ParseResult<&str, Comparator> {
+    let (rest, op) = opt(op).parse(input)?;
+    let (rest, major) = parse_rest(&mut discard(ws(), numeric), &input, rest)?;
+    let (rest, minor) = parse_rest(&mut opt(discard(sequence("."), part)), &input, rest)?;
+    let (rest, patch) = match minor {
+        Some(Some(_)) => parse_rest(&mut opt(discard(sequence("."), part)), &input, rest)?,
+        _ => (rest, None),
+    };
+    let (rest, pre) = match patch {
+        Some(Some(_)) => parse_rest(
+            &mut parse_if(sequence("-"), pre_release).map(Option::unwrap_or_default),
+            &input,
+            rest,
+        )?,
+        _ => (rest, vec![]),
+    };
+    let (rest, _) = parse_rest(&mut parse_if(sequence("+"), build_metadata), &input, rest)?;
+
+    let wildcard = matches!(minor, Some(None)) || matches!(patch, Some(None));
+    let op = match op {
+        Some(op) => op,
+        None if wildcard => Op::Wildcard,
+        None => Op::Caret,
+    };
+    Ok((
+        rest,
+        Comparator {
+            op,
+            major,
+            minor: minor.flatten(),
+            patch: patch.flatten(),
+            pre,
+        },
+    ))
+}
+
+fn op(input: &str) -> ParseResult<&str, Op> {
+    sequence(">=")
+        .map(|_| Op::GreaterEq)
+        .or(sequence("<=").map(|_| Op::LessEq))
+        .or(sequence(">").map(|_| Op::Greater))
+        .or(sequence("<").map(|_| Op::Less))
+        .or(sequence("=").map(|_| Op::Exact))
+        .or(sequence("~").map(|_| Op::Tilde))
+        .or(sequence("^").map(|_| Op::Caret))
+        .parse(input)
+}
+
+/// A version component which is either a number or a wildcard (`None`)
+fn part(input: &str) -> ParseResult<&str, Option<u64>> {
+    wildcard.map(|_| None).or(numeric.map(Some)).parse(input)
+}
+
+fn wildcard(input: &str) -> ParseResult<&str, &str> {
+    sequence("*")
+        .or(sequence("x"))
+        .or(sequence("X"))
+        .parse(input)
+}
+
+fn numeric(input: &str) -> ParseResult<&str, u64> {
+    let (remainder, digits) = take_while1(|c| c.is_ascii_digit()).parse(input)?;
+    if digits.len() > 1 && digits.starts_with('0') {
+        return Err(ParserError::new(
+            0,
+            ErrorSource::TakeWhile,
+            "numeric identifiers must not have leading zeros",
+        ));
+    }
+    digits
+        .parse::<u64>()
+        .map(|value| (remainder, value))
+        .map_err(|_| ParserError::new(0, ErrorSource::TakeWhile, "number overflow"))
+}
+
+fn pre_release(input: &str) -> ParseResult<&str, Vec<Identifier>> {
+    let (remainder, identifiers) = identifiers(input)?;
+    let mut start = 0;
+    identifiers
+        .into_iter()
+        .map(|identifier| {
+            let offset = start;
+            start += identifier.len() + 1;
+            if identifier.bytes().all(|b| b.is_ascii_digit()) {
+                numeric(identifier)
+                    .map(|(_, value)| Identifier::Numeric(value))
+                    .map_err(|error| ParserError::from_error(error, offset))
+            } else {
+                Ok(Identifier::AlphaNumeric(identifier.to_string()))
+            }
+        })
+        .collect::<Result<Vec<_>, _>>()
+        .map(|identifiers| (remainder, identifiers))
+}
+
+fn build_metadata(input: &str) -> ParseResult<&str, Vec<String>> {
+    identifiers
+        .map(|identifiers| identifiers.into_iter().map(str::to_string).collect())
+        .parse(input)
+}
+
+fn identifiers(input: &str) -> ParseResult<&str, Vec<&str>> {
+    let (remainder, identifiers) = sep_by(
+        take_while1(|c| c.is_ascii_alphanumeric() || c == '-'),
+        sequence("."),
+    )
+    .parse(input)?;
+    if identifiers.is_empty() {
+        return Err(ParserError::new(
+            0,
+            ErrorSource::TakeWhile,
+            "expected at least one identifier",
+        ));
+    }
+    Ok((remainder, identifiers))
+}
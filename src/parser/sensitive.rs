@@ -0,0 +1,39 @@
+use core::fmt::{self, Debug, Display, Formatter};
+
+/// Wrapper for parsed values that must not end up in logs, such as passwords or tokens.
+/// `Debug` and `Display` print `<redacted>`, the value is only reachable through
+/// [`Sensitive::expose`] or [`Sensitive::into_inner`]
+#[derive(Clone, PartialEq, Eq, Hash, Default)]
+pub struct Sensitive<T>(T);
+
+impl<T> Sensitive<T> {
+    pub fn new(value: T) -> Self {
+        Sensitive(value)
+    }
+
+    pub fn expose(&self) -> &T {
+        &self.0
+    }
+
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> From<T> for Sensitive<T> {
+    fn from(value: T) -> Self {
+        Sensitive(value)
+    }
+}
+
+impl<T> Debug for Sensitive<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("Sensitive(<redacted>)")
+    }
+}
+
+impl<T> Display for Sensitive<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("<redacted>")
+    }
+}
@@ -0,0 +1,133 @@
+use alloc::{
+    boxed::Box,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use super::{
+    errors::{ErrorSource, ParserError},
+    impls::{eof, escaped_transform, none_of, sequence, take_while1},
+    traits::{discard, opt, wrapped, ParseResult, Parser},
+};
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum SExpr {
+    Symbol(String),
+    Integer(i64),
+    Float(f64),
+    String(String),
+    List(Vec<SExpr>),
+    /// `'x`, read as the quoted expression
+    Quote(Box<SExpr>),
+}
+
+const DELIMITERS: &str = "()\";'";
+
+/// Deepest nesting of lists and quotes accepted, so that a hostile program cannot exhaust
+/// the stack
+const MAX_DEPTH: usize = 128;
+
+/// Parses a single expression, skipping leading whitespace and `;` comments
+pub fn sexpr(input: &str) -> ParseResult<&str, SExpr> {
+    expression(input, 0)
+}
+
+/// An expression within `depth` lists and quotes
+fn expression(input: &str, depth: usize) -> ParseResult<&str, SExpr> {
+    let (remainder, _) = trivia(input)?;
+    let offset = input.len() - remainder.len();
+    let parsed = match remainder.chars().next() {
+        Some('\'' | '(') if depth >= MAX_DEPTH => {
+            let mut error = ParserError::new(
+                0,
+                ErrorSource::LimitExceeded,
+                "expression nested too deeply",
+            );
+            error.committed = true;
+            Err(error)
+        }
+        Some('\'') => quote(remainder, depth + 1),
+        Some('(') => list(remainder, depth + 1),
+        Some('"') => string(remainder),
+        _ => atom(remainder),
+    };
+    parsed.map_err(|error| ParserError::from_error(error, offset))
+}
+
+/// Parses every expression of a program until the end of the input
+pub fn sexprs(input: &str) -> ParseResult<&str, Vec<SExpr>> {
+    sexpr.many0().skip(discard(trivia, eof())).parse(input)
+}
+
+fn quote(input: &str, depth: usize) -> ParseResult<&str, SExpr> {
+    discard(sequence("'"), |input| expression(input, depth))
+        .map(|quoted| SExpr::Quote(Box::new(quoted)))
+        .parse(input)
+}
+
+fn list(input: &str, depth: usize) -> ParseResult<&str, SExpr> {
+    let (remainder, _) = sequence("(").parse(input)?;
+    let (remainder, elements) = (|input| expression(input, depth))
+        .many0()
+        .parse(remainder)
+        .map_err(|error| ParserError::from_error(error, "(".len()))?;
+    let (remainder, _) = discard(trivia, sequence(")"))
+        .parse(remainder)
+        .map_err(|error| ParserError::from_error(error, input.len() - remainder.len()))?;
+    Ok((remainder, SExpr::List(elements)))
+}
+
+fn string(input: &str) -> ParseResult<&str, SExpr> {
+    wrapped(
+        sequence("\""),
+        escaped_transform(none_of("\"\\"), '\\', escaped),
+        sequence("\""),
+    )
+    .map(SExpr::String)
+    .parse(input)
+}
+
+/// The character following a backslash
+fn escaped(input: &str) -> ParseResult<&str, &str> {
+    sequence("\\")
+        .or(sequence("\""))
+        .or(sequence("n").map(|_| "\n"))
+        .or(sequence("t").map(|_| "\t"))
+        .parse(input)
+}
+
+/// Numbers are atoms which start with a digit, optionally after a sign or a dot, every other
+/// atom is a symbol
+fn atom(input: &str) -> ParseResult<&str, SExpr> {
+    let (remainder, atom) =
+        take_while1(|c| !c.is_whitespace() && !DELIMITERS.contains(c)).parse(input)?;
+    let numeric = atom
+        .trim_start_matches(['+', '-'])
+        .trim_start_matches('.')
+        .starts_with(|c: char| c.is_ascii_digit());
+    if !numeric {
+        return Ok((remainder, SExpr::Symbol(atom.to_string())));
+    }
+    if let Ok(value) = atom.parse::<i64>() {
+        return Ok((remainder, SExpr::Integer(value)));
+    }
+    atom.parse::<f64>()
+        .map(|value| (remainder, SExpr::Float(value)))
+        .map_err(|_| ParserError::new(0, ErrorSource::TakeWhile, "invalid number"))
+}
+
+fn trivia(input: &str) -> ParseResult<&str, ()> {
+    take_while1(char::is_whitespace)
+        .or(comment)
+        .many0()
+        .map(|_| ())
+        .parse(input)
+}
+
+fn comment(input: &str) -> ParseResult<&str, &str> {
+    discard(
+        sequence(";"),
+        opt(none_of("\n")).map(Option::unwrap_or_default),
+    )
+    .parse(input)
+}
@@ -0,0 +1,122 @@
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use super::{
+    errors::{ErrorSource, ParserError},
+    impls::{any_char, escaped_transform, none_of, take_while1},
+    traits::{ParseResult, Parser},
+};
+
+fn invalid<'a>(index: usize, reason: &'static str) -> ParserError<&'a str> {
+    ParserError::new(index, ErrorSource::TakeWhile, reason)
+}
+
+/// Splits a command line into its arguments, the way a POSIX shell does without expanding
+/// anything. Arguments are separated by whitespace, and a `#` starting an argument comments
+/// out the rest of the line
+///
+/// # Examples
+/// ```rust
+///
+/// use pepser::parser::shlex::split;
+///
+/// assert_eq!(
+///     split(r#"grep -e 'a b' "\"c\"" d\ e # search"#).unwrap().1,
+///     ["grep", "-e", "a b", "\"c\"", "d e"],
+/// );
+/// assert_eq!(split("echo 'unterminated").unwrap_err().index, 18);
+///
+///
+/// ```
+pub fn split(input: &str) -> ParseResult<&str, Vec<String>> {
+    let mut words = Vec::new();
+    let mut remainder = blanks(input);
+    while !remainder.is_empty() {
+        let offset = input.len() - remainder.len();
+        let (next, word) =
+            word(remainder).map_err(|error| ParserError::from_error(error, offset))?;
+        words.push(word);
+        remainder = blanks(next);
+    }
+    Ok((remainder, words))
+}
+
+/// Parses a single argument, made of unquoted, single quoted and double quoted parts
+pub fn word(input: &str) -> ParseResult<&str, String> {
+    let mut word = String::new();
+    let mut remainder = input;
+    loop {
+        let offset = input.len() - remainder.len();
+        let (next, part) = match remainder.chars().next() {
+            Some('\'') => single_quoted(remainder),
+            Some('"') => double_quoted(remainder),
+            Some(c) if !c.is_whitespace() => unquoted(remainder),
+            _ => break,
+        }
+        .map_err(|error| ParserError::from_error(error, offset))?;
+        word.push_str(&part);
+        remainder = next;
+    }
+    if remainder.len() == input.len() {
+        return Err(invalid(0, "expected a word"));
+    }
+    Ok((remainder, word))
+}
+
+/// Skips whitespace, comments and line continuations
+pub(crate) fn blanks(mut input: &str) -> &str {
+    loop {
+        input = input.trim_start();
+        if let Some(comment) = input.strip_prefix('#') {
+            input = comment.find('\n').map_or("", |end| &comment[end..]);
+        } else if let Some(next) = input.strip_prefix("\\\n") {
+            input = next;
+        } else {
+            return input;
+        }
+    }
+}
+
+/// A backslash quotes any character, and removes a newline
+fn unquoted(input: &str) -> ParseResult<&str, String> {
+    escaped_transform(
+        take_while1(|c: char| !c.is_whitespace() && !"'\"\\".contains(c)),
+        '\\',
+        any_char().map(|c| match c {
+            '\n' => String::new(),
+            c => c.to_string(),
+        }),
+    )
+    .parse(input)
+}
+
+/// Everything up to the next single quote is literal
+fn single_quoted(input: &str) -> ParseResult<&str, String> {
+    let content = &input[1..];
+    match content.find('\'') {
+        Some(end) => Ok((&content[end + 1..], content[..end].to_string())),
+        None => Err(invalid(input.len(), "unterminated quote")),
+    }
+}
+
+/// A backslash only quotes `\`, `"`, `$`, `` ` `` and newlines, and is kept before anything
+/// else
+fn double_quoted(input: &str) -> ParseResult<&str, String> {
+    let (remainder, content) = escaped_transform(
+        none_of("\"\\"),
+        '\\',
+        any_char().map(|c| match c {
+            '\n' => String::new(),
+            '\\' | '"' | '$' | '`' => c.to_string(),
+            c => ['\\', c].iter().collect(),
+        }),
+    )
+    .parse(&input[1..])
+    .map_err(|error| ParserError::from_error(error, 1))?;
+    match remainder.strip_prefix('"') {
+        Some(remainder) => Ok((remainder, content)),
+        None => Err(invalid(input.len(), "unterminated quote")),
+    }
+}
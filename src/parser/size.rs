@@ -0,0 +1,90 @@
+use super::{
+    errors::{ErrorSource, ParserError},
+    impls::{sequence, take_while1},
+    traits::{discard, opt, ParseResult, Parser},
+};
+
+/// Suffixes of [`byte_size`] with their multiplier, matched case insensitively. Binary
+/// prefixes come first so that `KiB` is not read as `K`
+const UNITS: &[(&str, u64)] = &[
+    ("kib", 1 << 10),
+    ("mib", 1 << 20),
+    ("gib", 1 << 30),
+    ("tib", 1 << 40),
+    ("pib", 1 << 50),
+    ("eib", 1 << 60),
+    ("ki", 1 << 10),
+    ("mi", 1 << 20),
+    ("gi", 1 << 30),
+    ("ti", 1 << 40),
+    ("pi", 1 << 50),
+    ("ei", 1 << 60),
+    ("kb", 1_000),
+    ("mb", 1_000_000),
+    ("gb", 1_000_000_000),
+    ("tb", 1_000_000_000_000),
+    ("pb", 1_000_000_000_000_000),
+    ("eb", 1_000_000_000_000_000_000),
+    ("k", 1_000),
+    ("m", 1_000_000),
+    ("g", 1_000_000_000),
+    ("t", 1_000_000_000_000),
+    ("p", 1_000_000_000_000_000),
+    ("e", 1_000_000_000_000_000_000),
+    ("b", 1),
+];
+
+/// Parses a size such as `512`, `10MiB` or `3.5 GB` into a number of bytes. SI prefixes
+/// (`kB`, `MB`, ...) are powers of 1000 and binary prefixes (`KiB`, `MiB`, ...) powers of
+/// 1024, up to exa, and the `B` may be omitted. Fractions of a byte are truncated and a size
+/// which does not fit a `u64` is an error
+///
+/// # Examples
+/// ```rust
+///
+/// use pepser::parser::size::byte_size;
+///
+/// assert_eq!(byte_size("10MiB"), Ok(("", 10 * 1024 * 1024)));
+/// assert_eq!(byte_size("3.5 GB;"), Ok((";", 3_500_000_000)));
+/// assert_eq!(byte_size("2k"), Ok(("", 2000)));
+/// assert_eq!(byte_size("16EiB").unwrap_err().reason, "size overflow");
+///
+///
+/// ```
+pub fn byte_size(input: &str) -> ParseResult<&str, u64> {
+    let (remainder, digits) = take_while1(|c| c.is_ascii_digit()).parse(input)?;
+    let (remainder, fraction) =
+        opt(discard(sequence("."), take_while1(|c| c.is_ascii_digit()))).parse(remainder)?;
+    let overflow = || ParserError::new(0, ErrorSource::TakeWhile, "size overflow");
+    let whole = digits.parse::<u64>().map_err(|_| overflow())?;
+
+    let suffix = remainder.trim_start();
+    let unit = UNITS.iter().find(|(unit, _)| {
+        suffix
+            .get(..unit.len())
+            .is_some_and(|prefix| prefix.eq_ignore_ascii_case(unit))
+            && !suffix[unit.len()..].starts_with(|c: char| c.is_alphabetic())
+    });
+    let (remainder, multiplier) = match unit {
+        Some((unit, multiplier)) => (&suffix[unit.len()..], *multiplier),
+        None if suffix.starts_with(|c: char| c.is_alphabetic()) => {
+            return Err(ParserError::new(
+                input.len() - suffix.len(),
+                ErrorSource::TakeWhile,
+                "unknown size unit",
+            ))
+        }
+        None => (remainder, 1),
+    };
+
+    // digits past the nineteenth cannot add a byte, even to an exbibyte
+    let fraction = fraction.unwrap_or_default();
+    let fraction = &fraction[..fraction.len().min(19)];
+    let scale = 10u128.pow(fraction.len() as u32);
+    let numerator = fraction.parse::<u128>().unwrap_or_default();
+    let size =
+        u128::from(whole) * u128::from(multiplier) + numerator * u128::from(multiplier) / scale;
+    u64::try_from(size)
+        .map(|size| (remainder, size))
+        .map_err(|_| overflow())
+}
@@ -0,0 +1,104 @@
+use super::traits::{sealed::Sealed, Input, ParseResult, Parser, WithSpan};
+
+/// Range `start..end` of the input, in bytes for text
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Spanned<T> {
+    pub span: Span,
+    pub value: T,
+}
+
+/// An input which keeps its offset in the original input, so that [`Parser::spanned`] can
+/// report absolute spans. Parsers of the wrapped input run on it with [`located`]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Located<I> {
+    pub input: I,
+    pub offset: usize,
+}
+
+impl<I> Located<I> {
+    /// Starts tracking offsets at the beginning of `input`
+    pub fn new(input: I) -> Self {
+        Located { input, offset: 0 }
+    }
+}
+
+impl<I: Input> Sealed for Located<I> {}
+
+impl<I: Input> Input for Located<I> {
+    fn input_len(&self) -> usize {
+        self.input.input_len()
+    }
+
+    fn drop(&self, size: usize) -> Self {
+        Located {
+            input: self.input.drop(size),
+            offset: self.offset + size,
+        }
+    }
+
+    fn take(&self, size: usize) -> Self {
+        Located {
+            input: self.input.take(size),
+            offset: self.offset,
+        }
+    }
+
+    fn split_at(&self, size: usize) -> (Self, Self) {
+        (self.take(size), self.drop(size))
+    }
+
+    fn first_len(&self) -> usize {
+        self.input.first_len()
+    }
+
+    fn address(&self) -> usize {
+        self.input.address()
+    }
+
+    fn offset_from(&self, base: &Self) -> usize {
+        self.offset - base.offset
+    }
+}
+
+impl<I, P> Parser<Located<I>> for WithSpan<P>
+where
+    I: Input,
+    P: Parser<Located<I>>,
+{
+    type Output = (Span, P::Output);
+
+    fn parse(&mut self, input: Located<I>) -> ParseResult<Located<I>, Self::Output> {
+        let start = input.offset;
+        let (remainder, output) = self.parser.parse(input.clone())?;
+        let end = start + remainder.offset_from(&input);
+        Ok((remainder, (Span { start, end }, output)))
+    }
+}
+
+/// Runs a parser of the wrapped input on a [`Located`] input, advancing its offset by what
+/// the parser consumed
+pub fn located<I, P>(mut parser: P) -> impl Parser<Located<I>, Output = P::Output>
+where
+    I: Input,
+    P: Parser<I>,
+{
+    move |input: Located<I>| {
+        let Located { input, offset } = input;
+        match parser.parse(input.clone()) {
+            Ok((remainder, output)) => Ok((
+                Located {
+                    offset: offset + remainder.offset_from(&input),
+                    input: remainder,
+                },
+                output,
+            )),
+            Err(error) => Err(error.map_input(move |input| Located { input, offset })),
+        }
+    }
+}
@@ -0,0 +1,729 @@
+use alloc::{
+    boxed::Box,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+
+use super::{
+    errors::{ErrorSource, ParserError},
+    impls::{none_of, sequence, take_while1, ws},
+    lexer::{Lexer, Span, Spanned, Token},
+    traits::{chainl1, discard, opt, parse_rest, ParseResult, Parser},
+};
+
+/// A `SELECT` statement, the clauses which are absent are `None` or empty
+#[derive(Debug, PartialEq, Clone)]
+pub struct Select {
+    pub distinct: bool,
+    pub columns: Vec<SelectItem>,
+    pub from: Option<FromClause>,
+    /// The `WHERE` clause
+    pub filter: Option<Expr>,
+    pub order_by: Vec<Ordering>,
+    pub limit: Option<u64>,
+    pub offset: Option<u64>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum SelectItem {
+    /// `*`
+    Wildcard,
+    /// `table.*`
+    QualifiedWildcard(String),
+    Expr {
+        expr: Expr,
+        alias: Option<String>,
+    },
+}
+
+/// The first table of the `FROM` clause and the tables joined to it, in order
+#[derive(Debug, PartialEq, Clone)]
+pub struct FromClause {
+    pub table: TableRef,
+    pub joins: Vec<Join>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct TableRef {
+    pub name: String,
+    pub alias: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Join {
+    pub kind: JoinKind,
+    pub table: TableRef,
+    /// The join condition, absent for cross joins
+    pub on: Option<Expr>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum JoinKind {
+    Inner,
+    Left,
+    Right,
+    Full,
+    Cross,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Ordering {
+    pub expr: Expr,
+    pub descending: bool,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Expr {
+    Column {
+        table: Option<String>,
+        name: String,
+    },
+    Integer(i64),
+    Float(f64),
+    String(String),
+    Boolean(bool),
+    Null,
+    /// The `*` of `COUNT(*)`
+    Wildcard,
+    Function {
+        name: String,
+        args: Vec<Expr>,
+    },
+    Unary {
+        op: UnaryOp,
+        expr: Box<Expr>,
+    },
+    Binary {
+        left: Box<Expr>,
+        op: BinaryOp,
+        right: Box<Expr>,
+    },
+    IsNull {
+        expr: Box<Expr>,
+        negated: bool,
+    },
+    InList {
+        expr: Box<Expr>,
+        list: Vec<Expr>,
+        negated: bool,
+    },
+    Between {
+        expr: Box<Expr>,
+        low: Box<Expr>,
+        high: Box<Expr>,
+        negated: bool,
+    },
+    Like {
+        expr: Box<Expr>,
+        pattern: Box<Expr>,
+        negated: bool,
+    },
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum UnaryOp {
+    Not,
+    Minus,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum BinaryOp {
+    Or,
+    And,
+    Eq,
+    NotEq,
+    Lt,
+    LtEq,
+    Gt,
+    GtEq,
+    Plus,
+    Minus,
+    Multiply,
+    Divide,
+    Modulo,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum Kind {
+    Keyword,
+    Identifier,
+    Number,
+    String,
+    Symbol,
+    /// Appended after the last token, so that errors at the end of the statement have a
+    /// position. It is never consumed
+    End,
+}
+
+type Tokens<'t, 'a> = &'t [Spanned<Token<'a, Kind>>];
+
+/// Reserved words, matched case insensitively. Other words, such as function names, are
+/// identifiers
+const KEYWORDS: &[&str] = &[
+    "ALL", "AND", "AS", "ASC", "BETWEEN", "BY", "CROSS", "DESC", "DISTINCT", "FALSE", "FROM",
+    "FULL", "IN", "INNER", "IS", "JOIN", "LEFT", "LIKE", "LIMIT", "NOT", "NULL", "OFFSET", "ON",
+    "OR", "ORDER", "OUTER", "RIGHT", "SELECT", "TRUE", "WHERE",
+];
+
+const SYMBOLS: &[&str] = &[
+    "<=", ">=", "<>", "!=", "=", "<", ">", "+", "-", "*", "/", "%", ",", ".", "(", ")", ";",
+];
+
+/// Deepest nesting of parentheses, calls and unary operators accepted, so that a hostile
+/// query cannot exhaust the stack. Each level takes many frames, hence a lower bound
+const MAX_DEPTH: usize = 32;
+
+/// Parses a single `SELECT` statement, optionally ended by `;`, up to the end of the input.
+/// Keywords are case insensitive, identifiers may be double quoted, strings are single quoted
+/// and `--` starts a comment. Operators bind from `OR`, the loosest, to `AND`, `NOT`,
+/// comparisons, additions and multiplications, the tightest
+///
+/// # Examples
+/// ```rust
+///
+/// use pepser::parser::sql::{select, BinaryOp, Expr, JoinKind};
+/// let (_, query) = select(
+///     "SELECT u.name, COUNT(*) AS total FROM users u \
+///      LEFT JOIN orders o ON o.user_id = u.id \
+///      WHERE u.age >= 18 AND o.status IN ('paid', 'sent') \
+///      ORDER BY total DESC LIMIT 10",
+/// )
+/// .unwrap();
+///
+/// let from = query.from.unwrap();
+/// assert_eq!(from.table.alias.as_deref(), Some("u"));
+/// assert_eq!(from.joins[0].kind, JoinKind::Left);
+/// assert!(matches!(query.filter, Some(Expr::Binary { op: BinaryOp::And, .. })));
+/// assert_eq!(query.limit, Some(10));
+/// assert_eq!(select("SELECT a FROM").unwrap_err().index, 13);
+///
+///
+/// ```
+pub fn select(input: &str) -> ParseResult<&str, Select> {
+    let mut tokens = lexer().tokenize(input)?;
+    tokens.push(Spanned {
+        span: Span {
+            start: input.len(),
+            end: input.len(),
+        },
+        value: Token {
+            kind: Kind::End,
+            text: "",
+        },
+    });
+    let (_, select) = statement(&tokens).map_err(|error| {
+        let index = tokens
+            .get(error.index)
+            .map_or(input.len(), |token| token.span.start);
+        let error = error.map_input(|tokens: Tokens| {
+            &input[tokens.first().map_or(input.len(), |t| t.span.start)..]
+        });
+        ParserError { index, ..error }
+    })?;
+    Ok(("", select))
+}
+
+fn lexer<'a>() -> Lexer<'a, Kind> {
+    Lexer::new()
+        .token(Kind::Keyword, |input: &'a str| {
+            let (remainder, word) = word(input)?;
+            match KEYWORDS
+                .iter()
+                .any(|keyword| keyword.eq_ignore_ascii_case(word))
+            {
+                true => Ok((remainder, word)),
+                false => Err(ParserError::new(0, ErrorSource::Lexer, "not a keyword")),
+            }
+        })
+        .token(Kind::Identifier, |input: &'a str| {
+            match input.strip_prefix('"') {
+                Some(_) => quoted(input, '"'),
+                None => word(input),
+            }
+        })
+        .token(Kind::Number, number)
+        .token(Kind::String, |input: &'a str| quoted(input, '\''))
+        .token(Kind::Symbol, |input: &'a str| {
+            SYMBOLS
+                .iter()
+                .find_map(|symbol| sequence(symbol).parse(input).ok())
+                .ok_or_else(|| ParserError::new(0, ErrorSource::Lexer, "unknown symbol"))
+        })
+        .trivia(ws())
+        .trivia(discard(sequence("--"), opt(none_of("\n"))))
+}
+
+/// Letters, digits and underscores, not starting with a digit
+fn word(input: &str) -> ParseResult<&str, &str> {
+    if input.starts_with(|c: char| c.is_ascii_digit()) {
+        return Err(ParserError::new(0, ErrorSource::Lexer, "expected a word"));
+    }
+    take_while1(|c: char| c.is_ascii_alphanumeric() || c == '_').parse(input)
+}
+
+/// Digits, optionally followed by a fractional part
+fn number(input: &str) -> ParseResult<&str, &str> {
+    let digits = |input| take_while1(|c: char| c.is_ascii_digit()).parse(input);
+    let (remainder, _) = digits(input)?;
+    let remainder = match discard(sequence("."), digits).parse(remainder) {
+        Ok((remainder, _)) => remainder,
+        Err(_) => remainder,
+    };
+    Ok((remainder, &input[..input.len() - remainder.len()]))
+}
+
+/// Text between `quote`s, where a doubled quote stands for itself
+fn quoted(input: &str, quote: char) -> ParseResult<&str, &str> {
+    if !input.starts_with(quote) {
+        return Err(ParserError::new(0, ErrorSource::Lexer, "expected a quote"));
+    }
+    let mut end = 1;
+    loop {
+        match input[end..].find(quote) {
+            Some(position) if input[end + position + 1..].starts_with(quote) => end += position + 2,
+            Some(position) => {
+                let (text, remainder) = input.split_at(end + position + 1);
+                return Ok((remainder, text));
+            }
+            None => {
+                return Err(ParserError::new(
+                    0,
+                    ErrorSource::Lexer,
+                    "unterminated quote",
+                ))
+            }
+        }
+    }
+}
+
+/// The content of a lexeme lexed by [`quoted`]
+fn unquote(text: &str, quote: char) -> String {
+    let doubled = [quote, quote].iter().collect::<String>();
+    text[1..text.len() - 1].replace(&doubled, &quote.to_string())
+}
+
+/// An error at the first token of the input, which expected `expected`. Errors index tokens
+/// until [`select`] converts the index into the position of the token
+fn unexpected<'t, 'a: 't>(expected: &'static str) -> ParserError<Tokens<'t, 'a>> {
+    let mut error = ParserError::new(0, ErrorSource::Token, "unexpected token");
+    error.expected = Box::new([expected]);
+    error
+}
+
+/// An error at the first token of `remainder`, relative to `input`
+fn unexpected_at<'t, 'a: 't>(
+    input: Tokens<'t, 'a>,
+    remainder: Tokens<'t, 'a>,
+    expected: &'static str,
+) -> ParserError<Tokens<'t, 'a>> {
+    ParserError::from_error(unexpected(expected), input.len() - remainder.len())
+}
+
+/// Parses a token of the given kind whose text satisfies `predicate`
+fn lexeme<'t, 'a: 't, P>(
+    kind: Kind,
+    expected: &'static str,
+    mut predicate: P,
+) -> impl Parser<Tokens<'t, 'a>, Output = &'a str>
+where
+    P: FnMut(&str) -> bool,
+{
+    move |input: Tokens<'t, 'a>| match input.split_first() {
+        Some((token, remainder)) if token.value.kind == kind && predicate(token.value.text) => {
+            Ok((remainder, token.value.text))
+        }
+        _ => Err(unexpected(expected)),
+    }
+}
+
+fn keyword<'t, 'a: 't>(keyword: &'static str) -> impl Parser<Tokens<'t, 'a>, Output = &'a str> {
+    lexeme(Kind::Keyword, keyword, |text| {
+        text.eq_ignore_ascii_case(keyword)
+    })
+}
+
+fn symbol<'t, 'a: 't>(symbol: &'static str) -> impl Parser<Tokens<'t, 'a>, Output = &'a str> {
+    lexeme(Kind::Symbol, symbol, move |text| text == symbol)
+}
+
+fn identifier<'t, 'a: 't>(input: Tokens<'t, 'a>) -> ParseResult<Tokens<'t, 'a>, String> {
+    lexeme(Kind::Identifier, "an identifier", |_| true)
+        .map(|text| match text.starts_with('"') {
+            true => unquote(text, '"'),
+            false => text.to_string(),
+        })
+        .parse(input)
+}
+
+fn integer<'t, 'a: 't>(input: Tokens<'t, 'a>) -> ParseResult<Tokens<'t, 'a>, u64> {
+    let (remainder, text) = lexeme(Kind::Number, "an integer", |_| true).parse(input)?;
+    text.parse()
+        .map(|value| (remainder, value))
+        .map_err(|_| unexpected("an integer"))
+}
+
+/// Parses `parser` if the input starts with `keyword`, failing if it then does not match
+fn clause<'t, 'a: 't, O, P>(
+    input: Tokens<'t, 'a>,
+    word: &'static str,
+    parser: P,
+) -> ParseResult<Tokens<'t, 'a>, Option<O>>
+where
+    P: Parser<Tokens<'t, 'a>, Output = O>,
+{
+    match keyword(word).parse(input) {
+        Ok((remainder, _)) => parse_rest(&mut parser.map(Some), &input, remainder),
+        Err(_) => Ok((input, None)),
+    }
+}
+
+/// Parses one or more `parser` separated by commas, each comma must be followed by an element
+fn list<'t, 'a: 't, O, P>(mut parser: P) -> impl Parser<Tokens<'t, 'a>, Output = Vec<O>>
+where
+    P: Parser<Tokens<'t, 'a>, Output = O>,
+{
+    move |start: Tokens<'t, 'a>| {
+        let (mut input, first) = parser.parse(start)?;
+        let mut elements = vec![first];
+        while let Ok((remainder, _)) = symbol(",").parse(input) {
+            let (remainder, element) = parse_rest(&mut parser.by_ref(), &start, remainder)?;
+            elements.push(element);
+            input = remainder;
+        }
+        Ok((input, elements))
+    }
+}
+
+/// An optional alias, introduced by an optional `AS`
+fn alias<'t, 'a: 't>(input: Tokens<'t, 'a>) -> ParseResult<Tokens<'t, 'a>, Option<String>> {
+    match clause(input, "AS", identifier)? {
+        (remainder, Some(alias)) => Ok((remainder, Some(alias))),
+        (_, None) => opt(identifier).parse(input),
+    }
+}
+
+fn statement<'t, 'a: 't>(start: Tokens<'t, 'a>) -> ParseResult<Tokens<'t, 'a>, Select> {
+    let (input, _) = keyword("SELECT").parse(start)?;
+    let (input, distinct) = match keyword("DISTINCT").parse(input) {
+        Ok((input, _)) => (input, true),
+        Err(_) => (opt(keyword("ALL")).parse(input)?.0, false),
+    };
+    let (input, columns) = parse_rest(&mut list(select_item), &start, input)?;
+    let (input, from) = parse_rest(&mut |input| clause(input, "FROM", from), &start, input)?;
+    let (input, filter) = parse_rest(
+        &mut |input| clause(input, "WHERE", expression),
+        &start,
+        input,
+    )?;
+    let mut order_by = |input| clause(input, "ORDER", discard(keyword("BY"), list(ordering)));
+    let (input, order_by) = parse_rest(&mut order_by, &start, input)?;
+    let (input, limit) = parse_rest(&mut |input| clause(input, "LIMIT", integer), &start, input)?;
+    let (input, offset) = parse_rest(&mut |input| clause(input, "OFFSET", integer), &start, input)?;
+    let (input, _) = opt(symbol(";")).parse(input)?;
+    if input[0].value.kind != Kind::End {
+        return Err(unexpected_at(start, input, "the end of the statement"));
+    }
+    Ok((
+        input,
+        Select {
+            distinct,
+            columns,
+            from,
+            filter,
+            order_by: order_by.unwrap_or_default(),
+            limit,
+            offset,
+        },
+    ))
+}
+
+fn select_item<'t, 'a: 't>(input: Tokens<'t, 'a>) -> ParseResult<Tokens<'t, 'a>, SelectItem> {
+    let qualified = identifier
+        .and(discard(symbol("."), symbol("*")))
+        .map(|(table, _)| SelectItem::QualifiedWildcard(table));
+    let aliased = |start| {
+        let (input, expr) = expression(start)?;
+        let (input, alias) = parse_rest(&mut alias, &start, input)?;
+        Ok((input, SelectItem::Expr { expr, alias }))
+    };
+    symbol("*")
+        .map(|_| SelectItem::Wildcard)
+        .or(qualified)
+        .or(aliased)
+        .parse(input)
+}
+
+fn from<'t, 'a: 't>(start: Tokens<'t, 'a>) -> ParseResult<Tokens<'t, 'a>, FromClause> {
+    let (mut input, table) = table_ref(start)?;
+    let mut joins = vec![];
+    while let Ok((remainder, kind)) = join_kind(input) {
+        let (remainder, table) = parse_rest(&mut table_ref, &start, remainder)?;
+        let (remainder, on) = match kind {
+            JoinKind::Cross => (remainder, None),
+            _ => parse_rest(
+                &mut discard(keyword("ON"), expression).map(Some),
+                &start,
+                remainder,
+            )?,
+        };
+        joins.push(Join { kind, table, on });
+        input = remainder;
+    }
+    Ok((input, FromClause { table, joins }))
+}
+
+fn table_ref<'t, 'a: 't>(start: Tokens<'t, 'a>) -> ParseResult<Tokens<'t, 'a>, TableRef> {
+    let (input, name) = identifier(start)?;
+    let (input, alias) = parse_rest(&mut alias, &start, input)?;
+    Ok((input, TableRef { name, alias }))
+}
+
+fn join_kind<'t, 'a: 't>(input: Tokens<'t, 'a>) -> ParseResult<Tokens<'t, 'a>, JoinKind> {
+    let outer = |kind| discard(opt(keyword("OUTER")), keyword("JOIN")).map(move |_| kind);
+    keyword("JOIN")
+        .map(|_| JoinKind::Inner)
+        .or(discard(keyword("INNER"), keyword("JOIN")).map(|_| JoinKind::Inner))
+        .or(discard(keyword("LEFT"), outer(JoinKind::Left)))
+        .or(discard(keyword("RIGHT"), outer(JoinKind::Right)))
+        .or(discard(keyword("FULL"), outer(JoinKind::Full)))
+        .or(discard(keyword("CROSS"), keyword("JOIN")).map(|_| JoinKind::Cross))
+        .parse(input)
+}
+
+fn ordering<'t, 'a: 't>(input: Tokens<'t, 'a>) -> ParseResult<Tokens<'t, 'a>, Ordering> {
+    let (input, expr) = expression(input)?;
+    let (input, descending) = opt(keyword("ASC").or(keyword("DESC")))
+        .map(|direction| direction.is_some_and(|direction| direction.eq_ignore_ascii_case("DESC")))
+        .parse(input)?;
+    Ok((input, Ordering { expr, descending }))
+}
+
+/// Folds two operands with `op`, for [`chainl1`]
+fn binary(op: BinaryOp) -> impl FnOnce(Expr, Expr) -> Expr {
+    move |left, right| Expr::Binary {
+        left: Box::new(left),
+        op,
+        right: Box::new(right),
+    }
+}
+
+/// The committed error of an expression nested deeper than [`MAX_DEPTH`]
+fn too_deep<'t, 'a: 't>() -> ParserError<Tokens<'t, 'a>> {
+    let mut error = ParserError::new(
+        0,
+        ErrorSource::LimitExceeded,
+        "expression nested too deeply",
+    );
+    error.committed = true;
+    error
+}
+
+fn expression<'t, 'a: 't>(input: Tokens<'t, 'a>) -> ParseResult<Tokens<'t, 'a>, Expr> {
+    nested(input, 0)
+}
+
+/// An expression within `depth` parentheses, calls and unary operators
+fn nested<'t, 'a: 't>(input: Tokens<'t, 'a>, depth: usize) -> ParseResult<Tokens<'t, 'a>, Expr> {
+    let or = keyword("OR").map(|_| binary(BinaryOp::Or));
+    chainl1(|input| conjunction(input, depth), or).parse(input)
+}
+
+fn conjunction<'t, 'a: 't>(
+    input: Tokens<'t, 'a>,
+    depth: usize,
+) -> ParseResult<Tokens<'t, 'a>, Expr> {
+    let and = keyword("AND").map(|_| binary(BinaryOp::And));
+    chainl1(|input| negation(input, depth), and).parse(input)
+}
+
+fn negation<'t, 'a: 't>(input: Tokens<'t, 'a>, depth: usize) -> ParseResult<Tokens<'t, 'a>, Expr> {
+    if depth > MAX_DEPTH {
+        return Err(too_deep());
+    }
+    if let Ok((remainder, _)) = keyword("NOT").parse(input) {
+        let (input, expr) = parse_rest(&mut |input| negation(input, depth + 1), &input, remainder)?;
+        return Ok((
+            input,
+            Expr::Unary {
+                op: UnaryOp::Not,
+                expr: Box::new(expr),
+            },
+        ));
+    }
+    predicate(input, depth)
+}
+
+/// A comparison, or one of the `IS`, `IN`, `BETWEEN` and `LIKE` tests
+fn predicate<'t, 'a: 't>(start: Tokens<'t, 'a>, depth: usize) -> ParseResult<Tokens<'t, 'a>, Expr> {
+    let mut operand = |input| additive(input, depth);
+    let (input, left) = operand(start)?;
+    let mut comparison = symbol("=")
+        .map(|_| BinaryOp::Eq)
+        .or(symbol("<>").or(symbol("!=")).map(|_| BinaryOp::NotEq))
+        .or(symbol("<=").map(|_| BinaryOp::LtEq))
+        .or(symbol(">=").map(|_| BinaryOp::GtEq))
+        .or(symbol("<").map(|_| BinaryOp::Lt))
+        .or(symbol(">").map(|_| BinaryOp::Gt));
+    if let Ok((input, op)) = comparison.parse(input) {
+        let (input, right) = parse_rest(&mut operand, &start, input)?;
+        return Ok((input, binary(op)(left, right)));
+    }
+    if let Ok((input, _)) = keyword("IS").parse(input) {
+        let (input, negated) = opt(keyword("NOT")).parse(input)?;
+        let (input, _) = parse_rest(&mut keyword("NULL"), &start, input)?;
+        return Ok((
+            input,
+            Expr::IsNull {
+                expr: Box::new(left),
+                negated: negated.is_some(),
+            },
+        ));
+    }
+
+    let (after_not, negated) = opt(keyword("NOT")).parse(input)?;
+    let negated = negated.is_some();
+    let expr = Box::new(left);
+    if let Ok((input, _)) = keyword("IN").parse(after_not) {
+        let (input, _) = parse_rest(&mut symbol("("), &start, input)?;
+        let (input, list) = parse_rest(&mut list(|input| nested(input, depth + 1)), &start, input)?;
+        let (input, _) = parse_rest(&mut symbol(")"), &start, input)?;
+        return Ok((
+            input,
+            Expr::InList {
+                expr,
+                list,
+                negated,
+            },
+        ));
+    }
+    if let Ok((input, _)) = keyword("BETWEEN").parse(after_not) {
+        let (input, low) = parse_rest(&mut operand, &start, input)?;
+        let (input, _) = parse_rest(&mut keyword("AND"), &start, input)?;
+        let (input, high) = parse_rest(&mut operand, &start, input)?;
+        return Ok((
+            input,
+            Expr::Between {
+                expr,
+                low: Box::new(low),
+                high: Box::new(high),
+                negated,
+            },
+        ));
+    }
+    if let Ok((input, _)) = keyword("LIKE").parse(after_not) {
+        let (input, pattern) = parse_rest(&mut operand, &start, input)?;
+        return Ok((
+            input,
+            Expr::Like {
+                expr,
+                pattern: Box::new(pattern),
+                negated,
+            },
+        ));
+    }
+    if negated {
+        return Err(unexpected_at(start, after_not, "IN, BETWEEN or LIKE"));
+    }
+    Ok((input, *expr))
+}
+
+fn additive<'t, 'a: 't>(input: Tokens<'t, 'a>, depth: usize) -> ParseResult<Tokens<'t, 'a>, Expr> {
+    let op = symbol("+")
+        .map(|_| binary(BinaryOp::Plus))
+        .or(symbol("-").map(|_| binary(BinaryOp::Minus)));
+    chainl1(|input| multiplicative(input, depth), op).parse(input)
+}
+
+fn multiplicative<'t, 'a: 't>(
+    input: Tokens<'t, 'a>,
+    depth: usize,
+) -> ParseResult<Tokens<'t, 'a>, Expr> {
+    let op = symbol("*")
+        .map(|_| binary(BinaryOp::Multiply))
+        .or(symbol("/").map(|_| binary(BinaryOp::Divide)))
+        .or(symbol("%").map(|_| binary(BinaryOp::Modulo)));
+    chainl1(|input| unary(input, depth), op).parse(input)
+}
+
+fn unary<'t, 'a: 't>(input: Tokens<'t, 'a>, depth: usize) -> ParseResult<Tokens<'t, 'a>, Expr> {
+    if depth > MAX_DEPTH {
+        return Err(too_deep());
+    }
+    if let Ok((remainder, _)) = symbol("-").parse(input) {
+        let (input, expr) = parse_rest(&mut |input| unary(input, depth + 1), &input, remainder)?;
+        return Ok((
+            input,
+            Expr::Unary {
+                op: UnaryOp::Minus,
+                expr: Box::new(expr),
+            },
+        ));
+    }
+    primary(input, depth)
+}
+
+fn primary<'t, 'a: 't>(input: Tokens<'t, 'a>, depth: usize) -> ParseResult<Tokens<'t, 'a>, Expr> {
+    let Some(token) = input.first().map(|token| &token.value) else {
+        return Err(unexpected("an expression"));
+    };
+    let remainder = &input[1..];
+    match token.kind {
+        Kind::Number => {
+            let parsed = match token.text.parse() {
+                Ok(value) => Some(Expr::Integer(value)),
+                Err(_) => token.text.parse().ok().map(Expr::Float),
+            };
+            parsed
+                .map(|expr| (remainder, expr))
+                .ok_or_else(|| unexpected("a number"))
+        }
+        Kind::String => Ok((remainder, Expr::String(unquote(token.text, '\'')))),
+        Kind::Keyword if token.text.eq_ignore_ascii_case("NULL") => Ok((remainder, Expr::Null)),
+        Kind::Keyword if token.text.eq_ignore_ascii_case("TRUE") => {
+            Ok((remainder, Expr::Boolean(true)))
+        }
+        Kind::Keyword if token.text.eq_ignore_ascii_case("FALSE") => {
+            Ok((remainder, Expr::Boolean(false)))
+        }
+        Kind::Symbol if token.text == "(" => {
+            let (remainder, expr) =
+                parse_rest(&mut |input| nested(input, depth + 1), &input, remainder)?;
+            let (remainder, _) = parse_rest(&mut symbol(")"), &input, remainder)?;
+            Ok((remainder, expr))
+        }
+        Kind::Identifier => column_or_call(input, depth),
+        _ => Err(unexpected("an expression")),
+    }
+}
+
+/// `name`, `table.name` or `name(args)`
+fn column_or_call<'t, 'a: 't>(
+    start: Tokens<'t, 'a>,
+    depth: usize,
+) -> ParseResult<Tokens<'t, 'a>, Expr> {
+    let (input, name) = identifier(start)?;
+    if let Ok((input, _)) = symbol("(").parse(input) {
+        let (input, args) = match symbol(")").parse(input) {
+            Ok((input, _)) => (input, vec![]),
+            Err(_) => {
+                let argument = symbol("*")
+                    .map(|_| Expr::Wildcard)
+                    .or(|input| nested(input, depth + 1));
+                let (input, args) = parse_rest(&mut list(argument), &start, input)?;
+                (parse_rest(&mut symbol(")"), &start, input)?.0, args)
+            }
+        };
+        return Ok((input, Expr::Function { name, args }));
+    }
+    match discard(symbol("."), identifier).parse(input) {
+        Ok((input, column)) => Ok((
+            input,
+            Expr::Column {
+                table: Some(name),
+                name: column,
+            },
+        )),
+        Err(_) => Ok((input, Expr::Column { table: None, name })),
+    }
+}
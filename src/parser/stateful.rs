@@ -0,0 +1,142 @@
+use super::{
+    errors::{ErrorSource, ParserError},
+    traits::{sealed::Sealed, Input, ParseResult, Parser},
+};
+
+/// An input carrying user state, such as a symbol table or an indentation stack.
+///
+/// The state travels with the input, so when a combinator backtracks to an earlier input it
+/// also gets the state as it was at that point. Cloning the state must therefore be cheap,
+/// wrap it in an `Rc` if it is not. Stateless parsers run on the wrapped input with [`lift`]
+#[derive(Debug, PartialEq, Clone)]
+pub struct Stateful<I, S> {
+    pub input: I,
+    pub state: S,
+}
+
+impl<I, S> Stateful<I, S> {
+    pub fn new(input: I, state: S) -> Self {
+        Stateful { input, state }
+    }
+}
+
+impl<I: Input, S: Clone> Sealed for Stateful<I, S> {}
+
+impl<I: Input, S: Clone> Input for Stateful<I, S> {
+    fn input_len(&self) -> usize {
+        self.input.input_len()
+    }
+
+    fn drop(&self, size: usize) -> Self {
+        Stateful::new(self.input.drop(size), self.state.clone())
+    }
+
+    fn take(&self, size: usize) -> Self {
+        Stateful::new(self.input.take(size), self.state.clone())
+    }
+
+    fn split_at(&self, size: usize) -> (Self, Self) {
+        let (first, second) = self.input.split_at(size);
+        (
+            Stateful::new(first, self.state.clone()),
+            Stateful::new(second, self.state.clone()),
+        )
+    }
+
+    fn first_len(&self) -> usize {
+        self.input.first_len()
+    }
+
+    fn address(&self) -> usize {
+        self.input.address()
+    }
+}
+
+/// Runs a stateless parser on the wrapped input, leaving the state untouched
+///
+/// # Examples
+/// ```rust
+///
+/// use pepser::parser::impls::sequence;
+/// use pepser::parser::stateful::{lift, Stateful};
+/// use pepser::parser::traits::Parser;
+/// let mut parser = lift(sequence("ab"));
+///
+/// assert_eq!(
+///     parser.parse(Stateful::new("abc", 1)),
+///     Ok((Stateful::new("c", 1), "ab"))
+/// );
+///
+///
+/// ```
+pub fn lift<I, S, P>(mut parser: P) -> impl Parser<Stateful<I, S>, Output = P::Output>
+where
+    I: Input,
+    S: Clone,
+    P: Parser<I>,
+{
+    move |input: Stateful<I, S>| {
+        let Stateful { input, state } = input;
+        match parser.parse(input) {
+            Ok((input, output)) => Ok((Stateful { input, state }, output)),
+            Err(error) => Err(error.map_input(move |input| Stateful { input, state })),
+        }
+    }
+}
+
+/// Gives `f` the output of the parser along with mutable access to the state. The parser
+/// fails at the end of what it consumed if `f` returns `None`
+///
+/// # Examples
+/// ```rust
+///
+/// use pepser::parser::impls::take_while1;
+/// use pepser::parser::stateful::{lift, with_state, Stateful};
+/// use pepser::parser::traits::Parser;
+/// let mut count = with_state(lift(take_while1(char::is_alphabetic)), |word: &str, seen: &mut usize| {
+///     *seen += 1;
+///     (*seen <= 2).then(|| word.len())
+/// });
+///
+/// let (input, first) = count.parse(Stateful::new("ab", 0)).unwrap();
+/// assert_eq!((input.state, first), (1, 2));
+/// assert_eq!(count.parse(Stateful::new("abc", 2)).is_err(), true);
+///
+///
+/// ```
+pub fn with_state<I, S, O, R, P, F>(
+    mut parser: P,
+    mut f: F,
+) -> impl Parser<Stateful<I, S>, Output = R>
+where
+    I: Input,
+    S: Clone,
+    P: Parser<Stateful<I, S>, Output = O>,
+    F: FnMut(O, &mut S) -> Option<R>,
+{
+    move |input: Stateful<I, S>| {
+        let len = input.input_len();
+        let (mut remainder, output) = parser.parse(input)?;
+        match f(output, &mut remainder.state) {
+            Some(output) => Ok((remainder, output)),
+            None => Err(ParserError::new(
+                len - remainder.input_len(),
+                ErrorSource::State,
+                "rejected by the parser state",
+            )),
+        }
+    }
+}
+
+/// Reads the state without consuming any input
+pub fn state<I, S, R, F>(mut f: F) -> impl FnMut(Stateful<I, S>) -> ParseResult<Stateful<I, S>, R>
+where
+    I: Input,
+    S: Clone,
+    F: FnMut(&S) -> R,
+{
+    move |input: Stateful<I, S>| {
+        let output = f(&input.state);
+        Ok((input, output))
+    }
+}
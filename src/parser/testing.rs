@@ -0,0 +1,303 @@
+use std::{
+    collections::HashMap,
+    fmt::{self, Debug, Display, Formatter},
+};
+
+use super::{
+    json::{JsonNumber, JsonValue},
+    traits::{Input, ParseResult, Parser},
+};
+
+/// A deterministic generator of pseudo random numbers, xorshift64* seeded through splitmix64.
+/// The same seed always yields the same values, so that a failing case is replayed from the
+/// seed [`check`] reports
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        let mut z = seed.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        // Xorshift never leaves a zero state
+        Rng {
+            state: (z ^ (z >> 31)).max(1),
+        }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state ^= self.state >> 12;
+        self.state ^= self.state << 25;
+        self.state ^= self.state >> 27;
+        self.state.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+
+    /// A number from `0` to `bound` excluded, `0` when `bound` is `0`
+    pub fn below(&mut self, bound: usize) -> usize {
+        match bound {
+            0 => 0,
+            bound => (self.next_u64() % bound as u64) as usize,
+        }
+    }
+
+    /// True once in `times` on average
+    pub fn one_in(&mut self, times: usize) -> bool {
+        self.below(times) == 0
+    }
+
+    /// One of `items`, which must not be empty
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len())]
+    }
+}
+
+/// The first case which did not hold. `Rng::new(seed)` generates its value again
+#[derive(Debug, Clone)]
+pub struct Failure<T> {
+    pub case: usize,
+    pub seed: u64,
+    pub value: T,
+    pub reason: String,
+}
+
+impl<T: Debug> Display for Failure<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "case {} (seed {}) failed on {:?}: {}",
+            self.case, self.seed, self.value, self.reason
+        )
+    }
+}
+
+/// Checks `property` on `cases` values built by `generate`, each from its own seed drawn from
+/// `seed`. Stops at the first value for which the property returns an error
+///
+/// # Examples
+/// ```rust
+///
+/// use pepser::parser::testing::check;
+///
+/// assert!(check(100, 7, |rng| rng.below(10), |n| match *n < 10 {
+///     true => Ok(()),
+///     false => Err(format!("{n} is too large")),
+/// })
+/// .is_ok());
+///
+/// let failure = check(100, 7, |rng| rng.below(10), |n| match *n < 5 {
+///     true => Ok(()),
+///     false => Err(format!("{n} is too large")),
+/// })
+/// .unwrap_err();
+/// assert!(failure.value >= 5);
+///
+///
+/// ```
+pub fn check<T, G, P>(
+    cases: usize,
+    seed: u64,
+    mut generate: G,
+    mut property: P,
+) -> Result<(), Failure<T>>
+where
+    G: FnMut(&mut Rng) -> T,
+    P: FnMut(&T) -> Result<(), String>,
+{
+    let mut seeds = Rng::new(seed);
+    for case in 0..cases {
+        let seed = seeds.next_u64();
+        let value = generate(&mut Rng::new(seed));
+        if let Err(reason) = property(&value) {
+            return Err(Failure {
+                case,
+                seed,
+                value,
+                reason,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Checks that `parser` reads `text`, the serialized form of `value`, back to `value` and
+/// consumes all of it
+///
+/// # Examples
+/// ```rust
+///
+/// use pepser::parser::{
+///     json::json_value,
+///     testing::{check, json, round_trip},
+/// };
+///
+/// check(200, 1, |rng| json(rng, 3), |value| {
+///     round_trip(value, &value.to_string(), json_value)
+/// })
+/// .unwrap();
+///
+///
+/// ```
+pub fn round_trip<'a, T, P>(value: &T, text: &'a str, mut parser: P) -> Result<(), String>
+where
+    T: PartialEq + Debug,
+    P: Parser<&'a str, Output = T>,
+{
+    match parser.parse(text) {
+        Ok(("", parsed)) if parsed == *value => Ok(()),
+        Ok(("", parsed)) => Err(format!("{text:?} parsed to {parsed:?}")),
+        Ok((rest, _)) => Err(format!("{text:?} left {rest:?}")),
+        Err(error) => Err(format!("{text:?} failed: {error}")),
+    }
+}
+
+/// Joins up to `max` fragments picked from `fragments`
+pub fn text(rng: &mut Rng, fragments: &[&str], max: usize) -> String {
+    let count = rng.below(max + 1);
+    (0..count).map(|_| *rng.pick(fragments)).collect()
+}
+
+const CHARS: &[char] = &[
+    'a', 'Z', '0', ' ', '"', '\\', '/', '\n', '\t', '\r', '\u{8}', '\u{c}', 'é', '日', '🦀',
+    '\u{0}', '\u{1f}',
+];
+
+/// A JSON value nesting arrays and objects at most `depth` times. Floats are finite, as JSON
+/// has no infinities nor NaN
+pub fn json(rng: &mut Rng, depth: usize) -> JsonValue {
+    let kinds = if depth == 0 { 4 } else { 6 };
+    match rng.below(kinds) {
+        0 => match rng.below(3) {
+            0 => JsonValue::Null,
+            _ => JsonValue::Boolean(rng.one_in(2)),
+        },
+        1 => JsonValue::Number(match rng.below(3) {
+            0 => JsonNumber::Int(rng.next_u64() as i64 >> rng.below(64)),
+            1 => JsonNumber::UInt(rng.next_u64() >> rng.below(64)),
+            _ => JsonNumber::Float(loop {
+                let float = f64::from_bits(rng.next_u64());
+                if float.is_finite() {
+                    break float;
+                }
+            }),
+        }),
+        2 | 3 => JsonValue::String(string(rng)),
+        4 => JsonValue::Array((0..rng.below(4)).map(|_| json(rng, depth - 1)).collect()),
+        _ => JsonValue::Object(
+            (0..rng.below(4))
+                .map(|_| (string(rng), json(rng, depth - 1)))
+                .collect::<HashMap<_, _>>(),
+        ),
+    }
+}
+
+fn string(rng: &mut Rng) -> String {
+    (0..rng.below(8)).map(|_| *rng.pick(CHARS)).collect()
+}
+
+/// Checks that `parser().or(parser())` parses `input` as `parser()` does. Errors are compared
+/// without the order and the repetitions of their expected labels, which `or` merges
+///
+/// # Examples
+/// ```rust
+///
+/// use pepser::parser::{impls::sequence, testing::or_idempotent, traits::Parser};
+///
+/// assert!(or_idempotent(|| sequence("ab").expect("ab"), "abc").is_ok());
+/// assert!(or_idempotent(|| sequence("ab").expect("ab"), "b").is_ok());
+///
+///
+/// ```
+pub fn or_idempotent<I, P>(parser: impl Fn() -> P, input: I) -> Result<(), String>
+where
+    I: Input + PartialEq + Debug,
+    P: Parser<I>,
+    P::Output: PartialEq + Debug,
+{
+    let alone = parser().parse(input.clone());
+    let twice = parser().or(parser()).parse(input);
+    equivalent(&alone, &twice)
+}
+
+/// Checks that `a.and(b).and(c)` and `a.and(b.and(c))` parse `input` the same, once their
+/// outputs are flattened to `(a, b, c)`
+///
+/// # Examples
+/// ```rust
+///
+/// use pepser::parser::{impls::sequence, testing::and_associative};
+///
+/// for input in ["abc", "abx", "x"] {
+///     let law = and_associative(|| sequence("a"), || sequence("b"), || sequence("c"), input);
+///     assert!(law.is_ok());
+/// }
+///
+///
+/// ```
+pub fn and_associative<I, A, B, C>(
+    a: impl Fn() -> A,
+    b: impl Fn() -> B,
+    c: impl Fn() -> C,
+    input: I,
+) -> Result<(), String>
+where
+    I: Input + PartialEq + Debug,
+    A: Parser<I>,
+    B: Parser<I>,
+    C: Parser<I>,
+    A::Output: PartialEq + Debug,
+    B::Output: PartialEq + Debug,
+    C::Output: PartialEq + Debug,
+{
+    let left = a()
+        .and(b())
+        .and(c())
+        .map(|((a, b), c)| (a, b, c))
+        .parse(input.clone());
+    let right = a()
+        .and(b().and(c()))
+        .map(|(a, (b, c))| (a, b, c))
+        .parse(input);
+    equivalent(&left, &right)
+}
+
+/// Checks that `parser().map(|output| output)` parses `input` as `parser()` does
+pub fn map_identity<I, P>(parser: impl Fn() -> P, input: I) -> Result<(), String>
+where
+    I: Input + PartialEq + Debug,
+    P: Parser<I>,
+    P::Output: PartialEq + Debug,
+{
+    let alone = parser().parse(input.clone());
+    let mapped = parser().map(|output| output).parse(input);
+    equivalent(&alone, &mapped)
+}
+
+fn equivalent<I, O>(left: &ParseResult<I, O>, right: &ParseResult<I, O>) -> Result<(), String>
+where
+    I: Input + PartialEq + Debug,
+    O: PartialEq + Debug,
+{
+    let labels = |expected: &[&'static str]| {
+        let mut labels = expected.to_vec();
+        labels.sort_unstable();
+        labels.dedup();
+        labels
+    };
+    let same = match (left, right) {
+        (Ok(left), Ok(right)) => left == right,
+        (Err(left), Err(right)) => {
+            left.index == right.index
+                && left.source == right.source
+                && left.reason == right.reason
+                && left.committed == right.committed
+                && labels(&left.expected) == labels(&right.expected)
+        }
+        _ => false,
+    };
+    match same {
+        true => Ok(()),
+        false => Err(format!("{left:?} differs from {right:?}")),
+    }
+}
@@ -0,0 +1,385 @@
+//! Records the rules of a parse session into a [`Trace`], rendered as an indented tree or
+//! exported as DOT and JSON. Only the parsers wrapped with [`Recorder::rule`] are recorded:
+//! the combinators are not instrumented, so the rules of a grammar must be wrapped one by one
+use std::{
+    cell::{Cell, RefCell},
+    fmt::{self, Display, Formatter},
+    fs, io,
+    path::Path,
+    rc::Rc,
+};
+
+use super::{
+    errors::{ErrorSource, ParserError},
+    impls::{eof, none_of, sequence, take_while1},
+    traits::{discard, opt, Input, ParseResult, Parser},
+};
+
+const HEADER: &str = "pepser-trace 1\n";
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum TraceEvent {
+    Enter {
+        rule: String,
+        offset: usize,
+    },
+    /// On failure `offset` is where the rule reported its error
+    Exit {
+        rule: String,
+        offset: usize,
+        success: bool,
+    },
+}
+
+/// A rule invocation reconstructed from a trace, spanning `start..end` of the input
+#[derive(Debug, PartialEq, Clone)]
+pub struct TraceNode {
+    pub rule: String,
+    pub start: usize,
+    pub end: usize,
+    pub success: bool,
+    pub children: Vec<TraceNode>,
+}
+
+/// The ordered events of a recorded parse session. Its `Display` output is the trace file
+/// format, which [`Trace::parse`] reads back
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct Trace {
+    pub events: Vec<TraceEvent>,
+}
+
+/// Collects the events of the rules it wraps. Clones share the same event list, so a
+/// recorder can be handed to every rule of a grammar
+#[derive(Debug, Clone, Default)]
+pub struct Recorder {
+    events: Rc<RefCell<Vec<TraceEvent>>>,
+    depth: Rc<Cell<usize>>,
+    input_len: Rc<Cell<usize>>,
+}
+
+pub struct Recorded<P> {
+    recorder: Recorder,
+    rule: String,
+    parser: P,
+}
+
+impl Recorder {
+    pub fn new() -> Self {
+        Recorder::default()
+    }
+
+    /// Wraps a parser so that its invocations are recorded under the given rule name.
+    /// Offsets are relative to the input given to the outermost recorded rule
+    pub fn rule<P>(&self, rule: &str, parser: P) -> Recorded<P> {
+        Recorded {
+            recorder: self.clone(),
+            rule: rule.to_string(),
+            parser,
+        }
+    }
+
+    /// Returns a snapshot of the events recorded so far
+    pub fn trace(&self) -> Trace {
+        Trace {
+            events: self.events.borrow().clone(),
+        }
+    }
+
+    pub fn clear(&self) {
+        self.events.borrow_mut().clear();
+    }
+}
+
+impl<I, P> Parser<I> for Recorded<P>
+where
+    I: Input,
+    P: Parser<I>,
+{
+    type Output = P::Output;
+
+    fn parse(&mut self, input: I) -> ParseResult<I, P::Output> {
+        let depth = self.recorder.depth.get();
+        if depth == 0 {
+            self.recorder.input_len.set(input.input_len());
+        }
+        let start = self.recorder.input_len.get() - input.input_len();
+        self.recorder.events.borrow_mut().push(TraceEvent::Enter {
+            rule: self.rule.clone(),
+            offset: start,
+        });
+
+        self.recorder.depth.set(depth + 1);
+        let result = self.parser.parse(input);
+        self.recorder.depth.set(depth);
+
+        let (offset, success) = match &result {
+            Ok((remainder, _)) => (self.recorder.input_len.get() - remainder.input_len(), true),
+            Err(error) => (start + error.index, false),
+        };
+        self.recorder.events.borrow_mut().push(TraceEvent::Exit {
+            rule: self.rule.clone(),
+            offset,
+            success,
+        });
+        result
+    }
+}
+
+impl Trace {
+    /// Rebuilds the decision tree of the session. Rules still open at the end of the trace,
+    /// e.g. because the parse panicked, are closed as failures at their last known offset
+    pub fn tree(&self) -> Vec<TraceNode> {
+        let mut roots = vec![];
+        let mut stack: Vec<TraceNode> = vec![];
+        for event in &self.events {
+            match event {
+                TraceEvent::Enter { rule, offset } => stack.push(TraceNode {
+                    rule: rule.clone(),
+                    start: *offset,
+                    end: *offset,
+                    success: false,
+                    children: vec![],
+                }),
+                TraceEvent::Exit {
+                    offset, success, ..
+                } => {
+                    if let Some(mut node) = stack.pop() {
+                        node.end = *offset;
+                        node.success = *success;
+                        push_node(&mut stack, &mut roots, node);
+                    }
+                }
+            }
+        }
+        while let Some(mut node) = stack.pop() {
+            node.end = node
+                .children
+                .last()
+                .map_or(node.start, |child: &TraceNode| child.end);
+            push_node(&mut stack, &mut roots, node);
+        }
+        roots
+    }
+
+    /// Reads a trace from its file format
+    pub fn parse(input: &str) -> Result<Trace, ParserError<&str>> {
+        let (_, events) = discard(sequence(HEADER), event.many0())
+            .skip(eof())
+            .parse(input)?;
+        Ok(Trace { events })
+    }
+
+    /// Renders the tree as one indented line per rule: `rule start..end ok|err`
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        for root in self.tree() {
+            render_node(&root, 0, &mut out);
+        }
+        out
+    }
+
+    /// Exports the tree as a Graphviz digraph, failed rules are drawn in red
+    pub fn to_dot(&self) -> String {
+        let mut out = String::from("digraph trace {\n    node [shape=box];\n");
+        let mut next_id = 0;
+        for root in self.tree() {
+            dot_node(&root, None, &mut next_id, &mut out);
+        }
+        out.push_str("}\n");
+        out
+    }
+
+    /// Exports the tree as a JSON array of `{"rule", "start", "end", "success", "children"}`
+    /// objects
+    pub fn to_json(&self) -> String {
+        let roots = self.tree();
+        let mut out = String::new();
+        json_nodes(&roots, &mut out);
+        out
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        fs::write(path, self.to_string())
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Trace> {
+        let content = fs::read_to_string(path)?;
+        Trace::parse(&content)
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error.to_string()))
+    }
+}
+
+impl Display for Trace {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(HEADER)?;
+        for event in &self.events {
+            match event {
+                TraceEvent::Enter { rule, offset } => writeln!(f, "+{} {}", offset, escape(rule))?,
+                TraceEvent::Exit {
+                    rule,
+                    offset,
+                    success,
+                } => writeln!(f, "-{} {} {}", offset, outcome(*success), escape(rule))?,
+            }
+        }
+        Ok(())
+    }
+}
+
+fn push_node(stack: &mut [TraceNode], roots: &mut Vec<TraceNode>, node: TraceNode) {
+    match stack.last_mut() {
+        Some(parent) => parent.children.push(node),
+        None => roots.push(node),
+    }
+}
+
+fn outcome(success: bool) -> &'static str {
+    if success {
+        "ok"
+    } else {
+        "err"
+    }
+}
+
+fn render_node(node: &TraceNode, depth: usize, out: &mut String) {
+    out.push_str(&format!(
+        "{}{} {}..{} {}\n",
+        "  ".repeat(depth),
+        node.rule,
+        node.start,
+        node.end,
+        outcome(node.success)
+    ));
+    for child in &node.children {
+        render_node(child, depth + 1, out);
+    }
+}
+
+fn dot_node(node: &TraceNode, parent: Option<usize>, next_id: &mut usize, out: &mut String) {
+    let id = *next_id;
+    *next_id += 1;
+    out.push_str(&format!(
+        "    n{} [label=\"{}\\n{}..{}\"{}];\n",
+        id,
+        dot_quote(&node.rule),
+        node.start,
+        node.end,
+        if node.success { "" } else { ", color=red" }
+    ));
+    if let Some(parent) = parent {
+        out.push_str(&format!("    n{} -> n{};\n", parent, id));
+    }
+    for child in &node.children {
+        dot_node(child, Some(id), next_id, out);
+    }
+}
+
+fn json_nodes(nodes: &[TraceNode], out: &mut String) {
+    out.push('[');
+    for (position, node) in nodes.iter().enumerate() {
+        if position > 0 {
+            out.push(',');
+        }
+        out.push_str(&format!(
+            "{{\"rule\":\"{}\",\"start\":{},\"end\":{},\"success\":{},\"children\":",
+            json_quote(&node.rule),
+            node.start,
+            node.end,
+            node.success
+        ));
+        json_nodes(&node.children, out);
+        out.push('}');
+    }
+    out.push(']');
+}
+
+/// Escapes a rule name inside a double quoted DOT string. DOT has no escape for other control
+/// characters, Graphviz decodes them from HTML entities instead
+fn dot_quote(rule: &str) -> String {
+    rule.chars()
+        .map(|c| match c {
+            '"' => "\\\"".to_string(),
+            '\\' => "\\\\".to_string(),
+            '\n' => "\\n".to_string(),
+            '&' => "&amp;".to_string(),
+            c if c.is_control() => format!("&#{};", c as u32),
+            c => c.to_string(),
+        })
+        .collect()
+}
+
+/// Escapes a rule name inside a JSON string
+fn json_quote(rule: &str) -> String {
+    rule.chars()
+        .map(|c| match c {
+            '"' => "\\\"".to_string(),
+            '\\' => "\\\\".to_string(),
+            '\n' => "\\n".to_string(),
+            c if c.is_control() => format!("\\u{:04x}", c as u32),
+            c => c.to_string(),
+        })
+        .collect()
+}
+
+fn escape(rule: &str) -> String {
+    rule.replace('\\', "\\\\").replace('\n', "\\n")
+}
+
+/// Reverts `escape` in a single pass, a backslash before any other character is kept as is
+fn unescape(rule: &str) -> String {
+    let mut out = String::with_capacity(rule.len());
+    let mut chars = rule.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => out.push('\n'),
+            Some('\\') => out.push('\\'),
+            Some(other) => {
+                out.push('\\');
+                out.push(other);
+            }
+            None => out.push('\\'),
+        }
+    }
+    out
+}
+
+fn event(input: &str) -> ParseResult<&str, TraceEvent> {
+    let enter = discard(sequence("+"), offset.and(discard(sequence(" "), rule_name)))
+        .map(|(offset, rule)| TraceEvent::Enter { rule, offset });
+    let exit = discard(
+        sequence("-"),
+        offset
+            .and(discard(
+                sequence(" "),
+                sequence("ok")
+                    .map(|_| true)
+                    .or(sequence("err").map(|_| false)),
+            ))
+            .and(discard(sequence(" "), rule_name)),
+    )
+    .map(|((offset, success), rule)| TraceEvent::Exit {
+        rule,
+        offset,
+        success,
+    });
+    enter.or(exit).skip(sequence("\n")).parse(input)
+}
+
+fn offset(input: &str) -> ParseResult<&str, usize> {
+    let (remainder, digits) = take_while1(|c| c.is_ascii_digit()).parse(input)?;
+    digits
+        .parse::<usize>()
+        .map(|offset| (remainder, offset))
+        .map_err(|_| ParserError::new(0, ErrorSource::TakeWhile, "offset overflow"))
+}
+
+/// Rule names may be empty
+fn rule_name(input: &str) -> ParseResult<&str, String> {
+    opt(none_of("\n"))
+        .map(|name| unescape(name.unwrap_or_default()))
+        .parse(input)
+}
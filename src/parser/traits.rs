@@ -1,10 +1,23 @@
-use super::errors::ParserError;
+use alloc::{borrow::Cow, boxed::Box};
+
+use super::{errors::ParserError, sensitive::Sensitive};
 
 pub type ParseResult<I, O> = Result<(I, O), ParserError<I>>;
 
-pub trait Input: Clone {
-    fn to_string_value(&self) -> String;
+/// A type erased parser, see [`Parser::boxed`]
+pub type BoxedParser<'a, I, O> = Box<dyn Parser<I, Output = O> + 'a>;
+
+pub(crate) mod sealed {
+    pub trait Sealed {}
 
+    impl Sealed for &str {}
+
+    impl<T> Sealed for &[T] {}
+}
+
+/// The input types parsers can run on. This trait is sealed: combinators rely on how inputs
+/// are sliced, so new input types are provided by this crate rather than implemented outside
+pub trait Input: Clone + sealed::Sealed {
     fn input_len(&self) -> usize;
 
     fn drop(&self, size: usize) -> Self;
@@ -12,13 +25,84 @@ pub trait Input: Clone {
     fn take(&self, size: usize) -> Self;
 
     fn split_at(&self, size: usize) -> (Self, Self);
+
+    /// Size of the first element of the input (a char for `&str`), 0 if it is empty
+    fn first_len(&self) -> usize;
+
+    /// Where the input starts in memory. While the parsed text is borrowed, its slices with the
+    /// same address and length hold the same elements, which memoized parsers key results by
+    fn address(&self) -> usize;
+
+    /// Offset of `self` in `base`, where `self` is a remainder left by parsing `base`
+    fn offset_from(&self, base: &Self) -> usize {
+        base.input_len() - self.input_len()
+    }
+
+    /// Saves the position of the input before handing it to a parser which may fail, so that
+    /// backtracking combinators can [`rewind`](Input::rewind) to it. The default keeps a clone
+    /// of the input
+    fn checkpoint(&self) -> Checkpoint<Self> {
+        Checkpoint {
+            input: self.clone(),
+        }
+    }
+
+    /// Moves the input back to a position saved by [`Input::checkpoint`]
+    fn rewind(&mut self, checkpoint: Checkpoint<Self>) {
+        *self = checkpoint.input;
+    }
+}
+
+/// A saved position of an input, see [`Input::checkpoint`]
+///
+/// # Examples
+/// ```rust
+///
+/// use pepser::parser::impls::sequence;
+/// use pepser::parser::traits::{Input, Parser};
+/// let mut input = "let x";
+/// let checkpoint = input.checkpoint();
+/// (input, _) = sequence("let").parse(input).unwrap();
+/// assert_eq!(input, " x");
+///
+/// input.rewind(checkpoint);
+/// assert_eq!(input, "let x");
+///
+///
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Checkpoint<I> {
+    input: I,
 }
 
 impl Input for &str {
-    fn to_string_value(&self) -> String {
-        self.to_string()
+    fn input_len(&self) -> usize {
+        self.len()
+    }
+
+    fn drop(&self, size: usize) -> Self {
+        &self[size..]
     }
 
+    fn take(&self, size: usize) -> Self {
+        &self[..size]
+    }
+
+    fn split_at(&self, size: usize) -> (Self, Self) {
+        str::split_at(self, size)
+    }
+
+    fn first_len(&self) -> usize {
+        self.chars().next().map_or(0, char::len_utf8)
+    }
+
+    fn address(&self) -> usize {
+        self.as_ptr() as usize
+    }
+}
+
+/// Slices of bytes or of tokens produced by a lexer
+impl<T> Input for &[T] {
     fn input_len(&self) -> usize {
         self.len()
     }
@@ -32,12 +116,24 @@ impl Input for &str {
     }
 
     fn split_at(&self, size: usize) -> (Self, Self) {
-        str::split_at(self, size)
+        <[T]>::split_at(self, size)
+    }
+
+    fn first_len(&self) -> usize {
+        self.len().min(1)
+    }
+
+    fn address(&self) -> usize {
+        self.as_ptr() as usize
     }
 }
 
 /// Combinatory parser trait
 /// All parsers must implement this trait
+///
+/// This is the extension point of the crate: a custom parser is a function or closure taking
+/// the input, or a type implementing [`Parser::parse`]. Every other method is provided and
+/// requires `Self: Sized`, so new combinators can be added without breaking implementors
 pub trait Parser<I: Input> {
     type Output;
 
@@ -68,6 +164,58 @@ pub trait Parser<I: Input> {
         }
     }
 
+    /// Chains two parsers and keeps the output of the first, such as a value before its
+    /// delimiter
+    ///
+    /// # Examples
+    /// ```rust
+    ///
+    /// use pepser::parser::impls::{sequence, take_while1};
+    /// use pepser::parser::traits::Parser;
+    /// let mut parser = take_while1(char::is_alphabetic).skip(sequence(";"));
+    ///
+    /// assert_eq!(parser.parse("let;x"), Ok(("x", "let")));
+    /// assert_eq!(parser.parse("let").is_err(), true);
+    ///
+    ///
+    /// ```
+    fn skip<G>(self, parser: G) -> Skip<Self, G>
+    where
+        G: Parser<I>,
+        Self: Sized,
+    {
+        Skip {
+            parser: self,
+            skipped: parser,
+        }
+    }
+
+    /// Chains two parsers and keeps the output of the second, such as a value after its
+    /// prefix. This is [`discard`] as a method
+    ///
+    /// # Examples
+    /// ```rust
+    ///
+    /// use pepser::parser::impls::{sequence, take_while1};
+    /// use pepser::parser::traits::Parser;
+    /// let mut parser = sequence("$").with(take_while1(char::is_alphabetic));
+    ///
+    /// assert_eq!(parser.parse("$home/"), Ok(("/", "home")));
+    /// assert_eq!(parser.parse("home").is_err(), true);
+    ///
+    ///
+    /// ```
+    fn with<G>(self, parser: G) -> Discard<Self, G>
+    where
+        G: Parser<I>,
+        Self: Sized,
+    {
+        Discard {
+            discard: self,
+            parser,
+        }
+    }
+
     /// Chains a second parser to be tested if the first one fails.
     /// Returns an error if both parsers fail
     ///  
@@ -96,6 +244,36 @@ pub trait Parser<I: Input> {
         }
     }
 
+    /// Tries both parsers on the same input and keeps the result of the one which consumes the
+    /// most, the first on a tie. Unlike [`Parser::or`], the order of the alternatives does not
+    /// decide between prefixes of each other. When both fail, the error reaching furthest is
+    /// returned, and a committed error of either is returned as is
+    ///
+    /// # Examples
+    /// ```rust
+    ///
+    /// use pepser::parser::impls::sequence;
+    /// use pepser::parser::traits::Parser;
+    /// let mut operator = sequence("<").or_longest(sequence("<=")).or_longest(sequence("<<"));
+    ///
+    /// assert_eq!(operator.parse("<= 1"), Ok((" 1", "<=")));
+    /// assert_eq!(operator.parse("<<2"), Ok(("2", "<<")));
+    /// assert_eq!(operator.parse("< 3"), Ok((" 3", "<")));
+    /// assert!(operator.parse("> 4").is_err());
+    ///
+    ///
+    /// ```
+    fn or_longest<G>(self, parser: G) -> OrLongest<Self, G>
+    where
+        G: Parser<I, Output = Self::Output>,
+        Self: Sized,
+    {
+        OrLongest {
+            first: self,
+            second: parser,
+        }
+    }
+
     /// Applies a function to be applied to the output of the parser
     ///  
     /// # Examples
@@ -117,91 +295,526 @@ pub trait Parser<I: Input> {
         Map { f, parser: self }
     }
 
-    /// Applies a peeking function on the input
+    /// Calls `f` with the input each time the parser is attempted, before it parses. The
+    /// input and the result are unchanged, so this is a place for metrics or logging
+    fn inspect_input<F>(self, f: F) -> Peek<F, Self>
+    where
+        F: FnMut(&I),
+        Self: Sized,
+    {
+        Peek { f, parser: self }
+    }
+
+    /// Calls `f` with the result each time the parser has parsed, whether it succeeded or not.
+    /// The result is returned unchanged
+    fn inspect_result<F>(self, f: F) -> PeekOut<F, Self>
+    where
+        F: FnMut(&ParseResult<I, Self::Output>),
+        Self: Sized,
+    {
+        PeekOut { f, parser: self }
+    }
+
+    /// Outputs the span of the input consumed by the parser along with its output. Spans are
+    /// offsets in the original input, which [`Located`](crate::parser::span::Located) tracks
+    ///
+    /// # Examples
+    /// ```rust
+    ///
+    /// use pepser::parser::impls::{sequence, take_while1};
+    /// use pepser::parser::span::{located, Located, Span};
+    /// use pepser::parser::traits::{sep_by, Parser};
+    /// let word = located(take_while1(char::is_alphabetic)).spanned();
+    /// let mut words = sep_by(word, located(sequence(" ")));
+    /// let (_, words) = words.parse(Located::new("ab cde")).unwrap();
+    ///
+    /// assert_eq!(words[1], (Span { start: 3, end: 6 }, "cde"));
+    ///
+    ///
+    /// ```
+    fn spanned(self) -> WithSpan<Self>
+    where
+        Self: Sized,
+    {
+        WithSpan { parser: self }
+    }
+
+    /// Names what the parser expects, reported by the error when the parser fails without
+    /// consuming input. When both branches of an `or` fail at the same index their
+    /// expectations are merged
+    ///
+    /// # Examples
+    /// ```rust
+    ///
+    /// use pepser::parser::impls::{sequence, take_while1};
+    /// use pepser::parser::traits::Parser;
+    /// let boolean = sequence("true").or(sequence("false")).expect("a boolean");
+    /// let number = take_while1(|c| c.is_ascii_digit()).expect("a number");
+    /// let mut value = boolean.or(number).or(sequence("null").expect("null"));
+    ///
+    /// let error = value.parse("maybe").unwrap_err();
+    /// assert_eq!(&*error.expected, ["a boolean", "a number", "null"]);
+    /// assert_eq!(error.to_string(), "expected a boolean, a number or null at index 0 (expected \"null\")");
+    ///
+    ///
+    /// ```
+    fn expect(self, expected: &'static str) -> Expect<Self>
+    where
+        Self: Sized,
+    {
+        Expect {
+            expected,
+            parser: self,
+        }
+    }
+
+    /// Applies a function to the error of the parser, to adjust or enrich it at the boundary
+    /// of a grammar
+    ///
+    /// # Examples
+    /// ```rust
+    ///
+    /// use pepser::parser::errors::ParserError;
+    /// use pepser::parser::impls::{sequence, take_while1};
+    /// use pepser::parser::traits::{discard, Parser};
+    /// let mut field = discard(sequence("port="), take_while1(|c| c.is_ascii_digit()))
+    ///     .map_err(|error| ParserError::custom(error.index, "port", "invalid port"));
+    ///
+    /// assert_eq!(field.parse("port=x").unwrap_err().to_string(), "invalid port at index 5 (port)");
+    ///
+    ///
+    /// ```
+    fn map_err<F>(self, f: F) -> MapErr<F, Self>
+    where
+        F: FnMut(ParserError<I>) -> ParserError<I>,
+        Self: Sized,
+    {
+        MapErr { f, parser: self }
+    }
+
+    /// Replaces the reason of the error of the parser by the one built by `f`, keeping its
+    /// index and source. This is where low level failures become domain errors
+    ///
+    /// # Examples
+    /// ```rust
+    ///
+    /// use pepser::parser::impls::take_while1;
+    /// use pepser::parser::traits::Parser;
+    /// let mut port = take_while1(|c| c.is_ascii_digit())
+    ///     .or_fail_with(|error| format!("invalid port at {}", error.index));
+    ///
+    /// assert_eq!(port.parse("http").unwrap_err().reason, "invalid port at 0");
+    ///
+    ///
+    /// ```
+    fn or_fail_with<F, R>(self, f: F) -> OrFailWith<F, Self>
+    where
+        F: FnMut(&ParserError<I>) -> R,
+        R: Into<Cow<'static, str>>,
+        Self: Sized,
+    {
+        OrFailWith { f, parser: self }
+    }
+
+    /// Alias of [`Parser::inspect_input`]
     ///  
     /// # Examples
     /// ```rust
     ///
     /// use pepser::parser::impls::sequence;
     /// use pepser::parser::traits::Parser;
-    /// let mut parser = sequence("123").map(str::parse::<u32>).map(Result::unwrap).map(|v| v * 2);
+    /// let mut seen = vec![];
+    /// let mut parser = sequence("123").peek_in(|input: &&str| seen.push(input.len()));
     ///
-    /// assert_eq!(parser.parse("123"), Ok(("", 246)));
+    /// assert_eq!(parser.parse("1234"), Ok(("4", "123")));
+    /// assert_eq!(parser.parse("12").is_err(), true);
+    /// drop(parser);
+    /// assert_eq!(seen, vec![4, 2]);
     ///
     ///
     /// ```
     fn peek_in<F>(self, f: F) -> Peek<F, Self>
     where
-        F: FnMut(&I) -> (),
+        F: FnMut(&I),
         Self: Sized,
     {
-        Peek { f, parser: self }
+        self.inspect_input(f)
     }
 
-    /// Applies a peeking function on the input
+    /// Alias of [`Parser::inspect_result`]
     ///  
     /// # Examples
     /// ```rust
     ///
     /// use pepser::parser::impls::sequence;
-    /// use pepser::parser::traits::Parser;
-    /// let mut parser = sequence("123").map(str::parse::<u32>).map(Result::unwrap).map(|v| v * 2);
+    /// use pepser::parser::traits::{ParseResult, Parser};
+    /// let mut failures = 0;
+    /// let mut parser = sequence("123").peek_out(|result: &ParseResult<&str, &str>| {
+    ///     failures += result.is_err() as usize
+    /// });
     ///
-    /// assert_eq!(parser.parse("123"), Ok(("", 246)));
+    /// assert_eq!(parser.parse("123"), Ok(("", "123")));
+    /// assert_eq!(parser.parse("456").is_err(), true);
+    /// drop(parser);
+    /// assert_eq!(failures, 1);
     ///
     ///
     /// ```
     fn peek_out<F>(self, f: F) -> PeekOut<F, Self>
     where
-        F: FnMut(&ParseResult<I, Self::Output>) -> (),
+        F: FnMut(&ParseResult<I, Self::Output>),
         Self: Sized,
     {
-        PeekOut { f, parser: self }
+        self.inspect_result(f)
     }
 
-    /// Retries a parser until it fails.
-    /// Returns an error if the parser fails on the first time
+    #[cfg(feature = "std")]
+    /// Logs the entry and exit of the parser to stderr under the given name, with a preview of
+    /// the input, the outcome and an indentation showing how traced parsers are nested
+    fn trace(self, name: &str) -> Traced<Self, fn(&str)>
+    where
+        Self: Sized,
+    {
+        self.trace_with(name, |line| eprintln!("{}", line))
+    }
+
+    #[cfg(feature = "std")]
+    /// Like [`Parser::trace`], sending each line to `log` instead of stderr
     ///
     /// # Examples
     /// ```rust
     ///
+    /// use std::cell::RefCell;
     /// use pepser::parser::impls::sequence;
     /// use pepser::parser::traits::Parser;
-    /// let mut parser = sequence("123").many();
+    /// let lines = RefCell::new(vec![]);
+    /// let log = |line: &str| lines.borrow_mut().push(line.to_string());
+    /// let mut parser = sequence("a")
+    ///     .trace_with("a", log)
+    ///     .and(sequence("b").trace_with("b", log))
+    ///     .trace_with("ab", log);
+    ///
+    /// assert_eq!(parser.parse("abc"), Ok(("c", ("a", "b"))));
+    /// assert_eq!(
+    ///     lines.borrow().as_slice(),
+    ///     [
+    ///         "-> ab \"abc\"",
+    ///         "  -> a \"abc\"",
+    ///         "  <- a ok \"bc\"",
+    ///         "  -> b \"bc\"",
+    ///         "  <- b ok \"c\"",
+    ///         "<- ab ok \"c\"",
+    ///     ]
+    /// );
+    ///
+    ///
+    /// ```
+    fn trace_with<L>(self, name: &str, log: L) -> Traced<Self, L>
+    where
+        L: FnMut(&str),
+        Self: Sized,
+    {
+        Traced {
+            name: name.to_string(),
+            parser: self,
+            log,
+        }
+    }
+
+    /// Retries a parser until it fails, returning an empty vector if it fails the first time.
+    /// The repetition also stops at the end of the input, or at a repetition which succeeds
+    /// without consuming anything, whose output is dropped, so that it cannot loop forever.
+    /// A [committed](Parser::cut) error is returned rather than ending the repetition
+    ///
+    /// # Examples
+    /// ```rust
+    ///
+    /// use pepser::parser::impls::sequence;
+    /// use pepser::parser::traits::Parser;
+    /// let mut parser = sequence("123").many0();
     ///
     /// assert_eq!(parser.parse("123123123123"), Ok(("", vec!["123", "123", "123", "123"])));
     /// assert_eq!(parser.parse("123"), Ok(("", vec!["123"])));
     /// assert_eq!(parser.parse("1231234"), Ok(("4", vec!["123","123"])));
-    /// assert_eq!(parser.parse("").is_err(), true);
+    /// assert_eq!(parser.parse(""), Ok(("", vec![])));
+    ///
+    ///
+    /// ```
+    fn many0(self) -> Many<Self>
+    where
+        Self: Sized,
+    {
+        Many {
+            parser: self,
+            min: 0,
+        }
+    }
+
+    /// Like [`Parser::many0`], but fails if the parser does not succeed at least once. The
+    /// error is the one of the first attempt, or an [`ErrorSource::Many`] error if it
+    /// consumed nothing
+    ///
+    /// [`ErrorSource::Many`]: crate::parser::errors::ErrorSource::Many
+    ///
+    /// # Examples
+    /// ```rust
+    ///
+    /// use pepser::parser::impls::{sequence, ws};
+    /// use pepser::parser::traits::Parser;
+    /// let mut parser = sequence("ab").many1();
+    ///
+    /// assert_eq!(parser.parse("ababc"), Ok(("c", vec!["ab", "ab"])));
+    /// assert_eq!(parser.parse("ac").unwrap_err().index, 1);
+    /// assert!(ws().many1().parse("x").is_err());
+    ///
+    ///
+    /// ```
+    fn many1(self) -> Many<Self>
+    where
+        Self: Sized,
+    {
+        Many {
+            parser: self,
+            min: 1,
+        }
+    }
+
+    /// Commits to the parser: its errors are marked as
+    /// [`committed`](crate::parser::errors::ParserError::committed), so that the backtracking
+    /// combinators (`or`, `opt`, `many0`, `sep_by`, ...) return them instead of trying an
+    /// alternative. Used once a prefix has decided which rule is being parsed
+    ///
+    /// # Examples
+    /// ```rust
+    ///
+    /// use pepser::parser::impls::{sequence, take_while1};
+    /// use pepser::parser::traits::{discard, Parser};
+    /// let digits = || take_while1(|c| c.is_ascii_digit());
+    /// let mut value = discard(sequence("#"), digits().cut()).or(take_while1(char::is_alphabetic));
+    ///
+    /// assert_eq!(value.parse("#12"), Ok(("", "12")));
+    /// assert_eq!(value.parse("abc"), Ok(("", "abc")));
+    /// let error = value.parse("#abc").unwrap_err();
+    /// assert_eq!((error.index, error.committed), (1, true));
+    ///
+    ///
+    /// ```
+    fn cut(self) -> Cut<Self>
+    where
+        Self: Sized,
+    {
+        Cut { parser: self }
+    }
+
+    /// Runs the parser exactly `N` times, outputting an array rather than a vector. Fails with
+    /// the first error met
+    ///
+    /// # Examples
+    /// ```rust
+    ///
+    /// use pepser::parser::impls::satisfy_token;
+    /// use pepser::parser::traits::Parser;
+    /// let mut hex = satisfy_token(u8::is_ascii_hexdigit).count_exact::<4>();
+    ///
+    /// assert_eq!(hex.parse(&b"00e9 x"[..]), Ok((&b" x"[..], [&b'0', &b'0', &b'e', &b'9'])));
+    /// assert!(hex.parse(&b"00e"[..]).is_err());
+    ///
+    ///
+    /// ```
+    fn count_exact<const N: usize>(self) -> CountExact<Self, N>
+    where
+        Self: Sized,
+    {
+        CountExact { parser: self }
+    }
+
+    /// Retries a parser until it fails like [`Parser::many0`], accumulating the outputs into
+    /// the value created by `init` instead of a vector
+    ///
+    /// # Examples
+    /// ```rust
+    ///
+    /// use pepser::parser::impls::{any, sequence};
+    /// use pepser::parser::traits::Parser;
+    /// let mut count = sequence("ab").fold(|| 0, |count, _| count + 1);
+    /// let mut digits = any("0123456789").fold(String::new, |mut acc, digits| {
+    ///     acc.push_str(digits);
+    ///     acc
+    /// });
+    ///
+    /// assert_eq!(count.parse("ababac"), Ok(("ac", 2)));
+    /// assert_eq!(count.parse("c"), Ok(("c", 0)));
+    /// assert_eq!(digits.parse("42!"), Ok(("!", "42".to_string())));
     ///
     ///
     /// ```
-    fn many(self) -> Many<Self>
+    fn fold<R, H, F>(self, init: H, f: F) -> Fold<Self, H, F>
     where
+        H: FnMut() -> R,
+        F: FnMut(R, Self::Output) -> R,
         Self: Sized,
     {
-        Many { parser: self }
+        Fold {
+            parser: self,
+            init,
+            f,
+        }
+    }
+
+    /// Wraps the output in [`Sensitive`] so it is redacted from `Debug` and `Display` output
+    ///
+    /// # Examples
+    /// ```rust
+    ///
+    /// use pepser::parser::impls::{sequence, take_while1};
+    /// use pepser::parser::traits::Parser;
+    /// let mut parser = sequence("token=").and(take_while1(char::is_alphanumeric).sensitive());
+    ///
+    /// let (_, (_, token)) = parser.parse("token=s3cr3t").unwrap();
+    /// assert_eq!(token.expose(), &"s3cr3t");
+    /// assert_eq!(format!("{:?}", token), "Sensitive(<redacted>)");
+    ///
+    ///
+    /// ```
+    #[allow(clippy::type_complexity)]
+    fn sensitive(self) -> Map<fn(Self::Output) -> Sensitive<Self::Output>, Self>
+    where
+        Self: Sized,
+    {
+        Map {
+            f: Sensitive::new,
+            parser: self,
+        }
+    }
+
+    /// On failure, skips the input until `sync` matches (without consuming it) or the input
+    /// ends, and outputs the error instead of returning it. This lets a grammar carry on after
+    /// an error and report every error it met
+    ///
+    /// # Examples
+    /// ```rust
+    ///
+    /// use pepser::parser::impls::{any, sequence, take_while1};
+    /// use pepser::parser::traits::{sep_by, Parser};
+    /// let digits = || take_while1(|c| c.is_ascii_digit());
+    /// let decimal = digits().and(sequence(".")).and(digits()).map(|((int, _), frac)| (int, frac));
+    /// let mut parser = sep_by(decimal.recover_with(any(",")), sequence(","));
+    ///
+    /// let (input, decimals) = parser.parse("1.5,2x,3.0").unwrap();
+    /// assert_eq!(input, "");
+    /// assert_eq!(decimals[0], Ok(("1", "5")));
+    /// assert_eq!(decimals[1].is_err(), true);
+    /// assert_eq!(decimals[2], Ok(("3", "0")));
+    ///
+    ///
+    /// ```
+    fn recover_with<S>(self, sync: S) -> RecoverWith<Self, S>
+    where
+        S: Parser<I>,
+        Self: Sized,
+    {
+        RecoverWith { parser: self, sync }
+    }
+
+    /// Borrows the parser so that it can be composed with other combinators and still be
+    /// used afterwards. `&mut P` cannot implement [`Parser`] directly as it would overlap
+    /// with the implementation for closures
+    ///
+    /// # Examples
+    /// ```rust
+    ///
+    /// use pepser::parser::impls::{sequence, take_while1};
+    /// use pepser::parser::traits::Parser;
+    /// let mut number = take_while1(|c| c.is_ascii_digit());
+    ///
+    /// let (input, (left, _)) = number.by_ref().and(sequence("+")).parse("12+34").unwrap();
+    /// assert_eq!(left, "12");
+    /// assert_eq!(number.by_ref().map(str::len).parse(input), Ok(("", 2)));
+    /// assert_eq!(number.parse("5"), Ok(("", "5")));
+    ///
+    ///
+    /// ```
+    fn by_ref(&mut self) -> ByRef<'_, Self>
+    where
+        Self: Sized,
+    {
+        ByRef { parser: self }
+    }
+
+    /// Erases the type of the parser, which allows storing different parsers together or
+    /// returning one from a recursive function. The parser may borrow for `'a`
+    ///
+    /// # Examples
+    /// ```rust
+    ///
+    /// use pepser::parser::impls::{sequence, take_while1};
+    /// use pepser::parser::traits::{BoxedParser, Parser};
+    /// let mut parsers: Vec<BoxedParser<&str, &str>> = vec![
+    ///     sequence("abc").boxed(),
+    ///     take_while1(|c| c.is_ascii_digit()).boxed(),
+    /// ];
+    ///
+    /// assert_eq!(parsers[0].parse("abcd"), Ok(("d", "abc")));
+    /// assert_eq!(parsers[1].parse("42a"), Ok(("a", "42")));
+    ///
+    /// fn nested<'a>() -> BoxedParser<'a, &'a str, usize> {
+    ///     sequence("(")
+    ///         .and(|input| nested().parse(input))
+    ///         .and(sequence(")"))
+    ///         .map(|((_, depth), _)| depth + 1)
+    ///         .or(sequence("").map(|_| 0))
+    ///         .boxed()
+    /// }
+    /// assert_eq!(nested().map(|depth| depth * 2).parse("((()))"), Ok(("", 6)));
+    ///
+    ///
+    /// ```
+    fn boxed<'a>(self) -> BoxedParser<'a, I, Self::Output>
+    where
+        Self: Sized + 'a,
+    {
+        Box::new(self)
     }
 
     fn parse(&mut self, input: I) -> ParseResult<I, Self::Output>;
 }
 
-pub fn parse_if<I, O, C, P>(
-    mut cond: C,
-    mut parser: P,
-) -> impl FnMut(I) -> ParseResult<I, Option<O>>
+/// Runs `parser` on `remainder`, what a previous parser left of `base`, offsetting its errors
+/// so that their index is relative to `base` like the errors of the previous parser
+pub(crate) fn parse_rest<I, P>(parser: &mut P, base: &I, remainder: I) -> ParseResult<I, P::Output>
+where
+    I: Input,
+    P: Parser<I>,
+{
+    let offset = remainder.offset_from(base);
+    parser
+        .parse(remainder)
+        .map_err(|error| ParserError::from_error(error, offset))
+}
+
+/// Free function version of [`Parser::fold`]
+pub fn fold_many0<I, R, P, H, F>(parser: P, init: H, f: F) -> Fold<P, H, F>
+where
+    I: Input,
+    P: Parser<I>,
+    H: FnMut() -> R,
+    F: FnMut(R, P::Output) -> R,
+{
+    Fold { parser, init, f }
+}
+
+/// Parses `parser` after `cond` if `cond` matches, outputting `None` without consuming
+/// anything otherwise. Once `cond` has matched, a failure of `parser` is an error
+pub fn parse_if<I, O, C, P>(cond: C, parser: P) -> ParseIf<C, P>
 where
     I: Input,
     C: Parser<I>,
     P: Parser<I, Output = O>,
 {
-    move |ipt| match cond.parse(ipt.clone()) {
-        Ok((i, _)) => parser.parse(i).map(|(i, r)| (i, Some(r))),
-        Err(_) => Ok((ipt, None)),
-    }
+    ParseIf { cond, parser }
 }
 
-pub fn sep_by<'a, I, O, P, S>(parser: P, separator: S) -> Sep<P, S>
+pub fn sep_by<I, O, P, S>(parser: P, separator: S) -> Sep<P, S>
 where
     I: Input,
     P: Parser<I, Output = O>,
@@ -210,48 +823,267 @@ where
     Sep { parser, separator }
 }
 
-pub fn wrapped<I, O, L, P, R>(
-    mut left: L,
-    mut parser: P,
-    mut right: R,
-) -> impl Parser<I, Output = O>
+/// Parses one or more `term` separated by `op`, folding them from the left with the function
+/// returned by `op`, so that `1 - 2 - 3` is read as `(1 - 2) - 3`.
+/// Returns an error if the first term fails
+///
+/// # Examples
+/// ```rust
+///
+/// use pepser::parser::impls::{sequence, take_while1};
+/// use pepser::parser::traits::{chainl1, Parser};
+/// let number = take_while1(|c| c.is_ascii_digit()).map(|n: &str| n.parse::<i32>().unwrap());
+/// let op = sequence("-").map(|_| (|a, b| a - b) as fn(i32, i32) -> i32);
+/// let mut parser = chainl1(number, op);
+///
+/// assert_eq!(parser.parse("10-2-3"), Ok(("", 5)));
+/// assert_eq!(parser.parse("7-"), Ok(("-", 7)));
+/// assert_eq!(parser.parse("-1").is_err(), true);
+///
+///
+/// ```
+pub fn chainl1<I, O, F, P, S>(term: P, op: S) -> ChainL<P, S>
+where
+    I: Input,
+    F: FnOnce(O, O) -> O,
+    P: Parser<I, Output = O>,
+    S: Parser<I, Output = F>,
+{
+    ChainL { term, op }
+}
+
+/// Parses one or more `term` separated by `op`, folding them from the right with the function
+/// returned by `op`, so that `2 ^ 3 ^ 2` is read as `2 ^ (3 ^ 2)`.
+/// Returns an error if the first term fails
+///
+/// # Examples
+/// ```rust
+///
+/// use pepser::parser::impls::{sequence, take_while1};
+/// use pepser::parser::traits::{chainr1, Parser};
+/// let number = take_while1(|c| c.is_ascii_digit()).map(|n: &str| n.parse::<u32>().unwrap());
+/// let op = sequence("^").map(|_| u32::pow as fn(u32, u32) -> u32);
+/// let mut parser = chainr1(number, op);
+///
+/// assert_eq!(parser.parse("2^3^2"), Ok(("", 512)));
+/// assert_eq!(parser.parse("4"), Ok(("", 4)));
+///
+///
+/// ```
+pub fn chainr1<I, O, F, P, S>(term: P, op: S) -> ChainR<P, S>
+where
+    I: Input,
+    F: FnOnce(O, O) -> O,
+    P: Parser<I, Output = O>,
+    S: Parser<I, Output = F>,
+{
+    ChainR { term, op }
+}
+
+/// Free function version of [`Parser::and`]
+pub fn pair<I, A, B>(first: A, second: B) -> And<A, B>
+where
+    I: Input,
+    A: Parser<I>,
+    B: Parser<I>,
+{
+    And { first, second }
+}
+
+/// Parses `first`, `separator` and `second`, keeping the outputs of `first` and `second`
+///
+/// # Examples
+/// ```rust
+///
+/// use pepser::parser::impls::{sequence, take_while1};
+/// use pepser::parser::traits::{separated_pair, Parser};
+/// let mut parser = separated_pair(
+///     take_while1(char::is_alphabetic),
+///     sequence("="),
+///     take_while1(|c| c.is_ascii_digit()),
+/// );
+///
+/// assert_eq!(parser.parse("x=42;"), Ok((";", ("x", "42"))));
+/// assert_eq!(parser.parse("x42").is_err(), true);
+///
+///
+/// ```
+pub fn separated_pair<I, A, S, B>(first: A, separator: S, second: B) -> And<A, Discard<S, B>>
+where
+    I: Input,
+    A: Parser<I>,
+    S: Parser<I>,
+    B: Parser<I>,
+{
+    And {
+        first,
+        second: Discard {
+            discard: separator,
+            parser: second,
+        },
+    }
+}
+
+/// Parses `parser` followed by `terminator`, keeping the output of `parser`
+///
+/// # Examples
+/// ```rust
+///
+/// use pepser::parser::impls::{sequence, take_while1};
+/// use pepser::parser::traits::{terminated, Parser};
+/// let mut parser = terminated(take_while1(char::is_alphabetic), sequence(";"));
+///
+/// assert_eq!(parser.parse("let;x"), Ok(("x", "let")));
+/// assert_eq!(parser.parse("let").is_err(), true);
+///
+///
+/// ```
+pub fn terminated<I, O, P, T>(parser: P, terminator: T) -> Skip<P, T>
+where
+    I: Input,
+    P: Parser<I, Output = O>,
+    T: Parser<I>,
+{
+    Skip {
+        parser,
+        skipped: terminator,
+    }
+}
+
+/// Parses `left`, `parser` and `right`, keeping the output of `parser`
+///
+/// # Examples
+/// ```rust
+///
+/// use pepser::parser::impls::{sequence, take_while1};
+/// use pepser::parser::traits::{wrapped, Parser, Wrapped};
+/// struct Grammar<P> {
+///     group: Wrapped<P, P, P>,
+/// }
+/// let word = |c| take_while1(move |found| found == c);
+/// let mut grammar = Grammar { group: wrapped(word('('), word('a'), word(')')) };
+///
+/// assert_eq!(grammar.group.parse("(aa)"), Ok(("", "aa")));
+/// assert_eq!(grammar.group.parse("(aa").unwrap_err().index, 3);
+///
+///
+/// ```
+pub fn wrapped<I, O, L, P, R>(left: L, parser: P, right: R) -> Wrapped<L, P, R>
 where
     L: Parser<I>,
     P: Parser<I, Output = O>,
     R: Parser<I>,
     I: Input,
 {
-    move |input: I| {
-        let (input, _) = left.parse(input)?;
-        let (input, res) = parser.parse(input)?;
-        let (input, _) = right.parse(input)?;
-        return Ok((input, res));
+    Wrapped {
+        left,
+        parser,
+        right,
     }
 }
 
-pub fn opt<I, O, F>(mut f: F) -> impl Parser<I, Output = Option<O>>
+/// Outputs `None` without consuming anything when `parser` fails, unless its error is
+/// committed
+pub fn opt<I, O, P>(parser: P) -> Opt<P>
 where
     I: Input,
-    F: Parser<I, Output = O>,
+    P: Parser<I, Output = O>,
 {
-    move |input: I| {
-        let i = input.clone();
-        match f.parse(input) {
-            Ok((i, o)) => Ok((i, Some(o))),
-            Err(_) => Ok((i, None)),
-        }
+    Opt { parser }
+}
+
+/// Peeks a key with `key`, then hands the unconsumed input to `select` which matches on the
+/// key to run a single branch. Unlike a chain of `or`, the other branches are not attempted
+///
+/// # Examples
+/// ```rust
+///
+/// use pepser::parser::impls::{any_char, sequence, take_while1};
+/// use pepser::parser::traits::{dispatch, Parser};
+/// let mut literal = dispatch(any_char(), |c, input| match c {
+///     '"' => take_while1(|c| c != ' ').parse(input),
+///     't' => sequence("true").parse(input),
+///     _ => take_while1(|c| c.is_ascii_digit()).parse(input),
+/// });
+///
+/// assert_eq!(literal.parse("\"a\" b"), Ok((" b", "\"a\"")));
+/// assert_eq!(literal.parse("true"), Ok(("", "true")));
+/// assert_eq!(literal.parse("12"), Ok(("", "12")));
+/// assert!(literal.parse("").is_err());
+///
+///
+/// ```
+pub fn dispatch<I, O, K, F>(mut key: K, mut select: F) -> impl Parser<I, Output = O>
+where
+    I: Input,
+    K: Parser<I>,
+    F: FnMut(K::Output, I) -> ParseResult<I, O>,
+{
+    move |mut input: I| {
+        let checkpoint = input.checkpoint();
+        let (_, key) = key.parse(input.clone())?;
+        input.rewind(checkpoint);
+        select(key, input)
     }
 }
 
-pub fn value<V: Clone, I, O, F>(v: V, mut f: F) -> impl Parser<I, Output = V>
+/// The positive syntactic predicate `&p` of PEGs: succeeds with the output of `parser` when it
+/// matches, without consuming anything. Its errors are returned unchanged
+///
+/// # Examples
+/// ```rust
+///
+/// use pepser::parser::impls::{sequence, take_while1};
+/// use pepser::parser::traits::{followed_by, Parser};
+/// let mut call = take_while1(char::is_alphabetic).skip(followed_by(sequence("(")));
+///
+/// assert_eq!(call.parse("print(x)"), Ok(("(x)", "print")));
+/// assert!(call.parse("print x").is_err());
+///
+///
+/// ```
+pub fn followed_by<I, P>(parser: P) -> FollowedBy<P>
+where
+    I: Input,
+    P: Parser<I>,
+{
+    FollowedBy { parser }
+}
+
+/// The negative syntactic predicate `!p` of PEGs: succeeds without consuming anything when
+/// `parser` fails, and fails at the start of the input when it matches. Committed errors of
+/// `parser` are returned as is
+///
+/// # Examples
+/// ```rust
+///
+/// use pepser::parser::impls::{sequence, take_while1};
+/// use pepser::parser::traits::{not_followed_by, Parser};
+/// let mut keyword = sequence("if").skip(not_followed_by(take_while1(char::is_alphanumeric)));
+///
+/// assert_eq!(keyword.parse("if x"), Ok((" x", "if")));
+/// assert_eq!(keyword.parse("iffy").unwrap_err().index, 2);
+///
+///
+/// ```
+pub fn not_followed_by<I, P>(parser: P) -> NotFollowedBy<P>
+where
+    I: Input,
+    P: Parser<I>,
+{
+    NotFollowedBy { parser }
+}
+
+/// Outputs a clone of `value` when `parser` succeeds, discarding its output
+pub fn value<V: Clone, I, O, P>(value: V, parser: P) -> Value<V, P>
 where
     I: Input,
-    F: Parser<I, Output = O>,
+    P: Parser<I, Output = O>,
 {
-    move |input: I| f.parse(input).map(|(i, _)| (i, v.clone()))
+    Value { value, parser }
 }
 
-pub fn discard<'a, I: 'a, O: 'a, D, P>(discard: D, parser: P) -> Discard<D, P>
+pub fn discard<I, O, D, P>(discard: D, parser: P) -> Discard<D, P>
 where
     P: Parser<I, Output = O>,
     D: Parser<I>,
@@ -260,6 +1092,8 @@ where
     Discard { discard, parser }
 }
 
+/// Tries `until` at each position of the input until it matches, outputting its result. The
+/// input is advanced by whole elements, a char for `&str`
 pub fn drop_until<P, I>(until: P) -> DropUntil<P>
 where
     P: Parser<I>,
@@ -268,8 +1102,56 @@ where
     DropUntil { until }
 }
 
+/// Like [`drop_until`], but also outputs the skipped prefix, e.g. to report what was dropped
+/// while recovering from an error
+///
+/// # Examples
+/// ```rust
+///
+/// use pepser::parser::impls::sequence;
+/// use pepser::parser::traits::{skip_until, Parser};
+/// let mut statement_end = skip_until(sequence(";"));
+///
+/// assert_eq!(statement_end.parse("x = é; y"), Ok((" y", ("x = é", ";"))));
+/// assert_eq!(statement_end.parse(";"), Ok(("", ("", ";"))));
+/// assert!(statement_end.parse("x = 1").is_err());
+///
+///
+/// ```
+pub fn skip_until<P, I>(until: P) -> SkipUntil<P>
+where
+    P: Parser<I>,
+    I: Input,
+{
+    SkipUntil { until }
+}
+
+pub struct ByRef<'p, P> {
+    pub(crate) parser: &'p mut P,
+}
+
+pub struct RecoverWith<P, S> {
+    pub(crate) parser: P,
+    pub(crate) sync: S,
+}
+
+pub struct Fold<P, H, F> {
+    pub(crate) parser: P,
+    pub(crate) init: H,
+    pub(crate) f: F,
+}
+
 pub struct Many<P> {
     pub(crate) parser: P,
+    pub(crate) min: usize,
+}
+
+pub struct Cut<P> {
+    pub(crate) parser: P,
+}
+
+pub struct CountExact<P, const N: usize> {
+    pub(crate) parser: P,
 }
 
 pub struct Sep<P, S> {
@@ -277,6 +1159,16 @@ pub struct Sep<P, S> {
     pub(crate) separator: S,
 }
 
+pub struct ChainL<P, S> {
+    pub(crate) term: P,
+    pub(crate) op: S,
+}
+
+pub struct ChainR<P, S> {
+    pub(crate) term: P,
+    pub(crate) op: S,
+}
+
 pub struct And<F, S> {
     pub(crate) first: F,
     pub(crate) second: S,
@@ -287,25 +1179,93 @@ pub struct Or<F, S> {
     pub(crate) second: S,
 }
 
+pub struct OrLongest<F, S> {
+    pub(crate) first: F,
+    pub(crate) second: S,
+}
+
+pub struct FollowedBy<P> {
+    pub(crate) parser: P,
+}
+
+pub struct NotFollowedBy<P> {
+    pub(crate) parser: P,
+}
+
 pub struct Map<F, P> {
     pub(crate) f: F,
     pub(crate) parser: P,
 }
 
+pub struct Wrapped<L, P, R> {
+    pub(crate) left: L,
+    pub(crate) parser: P,
+    pub(crate) right: R,
+}
+
+pub struct Opt<P> {
+    pub(crate) parser: P,
+}
+
+pub struct Value<V, P> {
+    pub(crate) value: V,
+    pub(crate) parser: P,
+}
+
+pub struct ParseIf<C, P> {
+    pub(crate) cond: C,
+    pub(crate) parser: P,
+}
+
 pub struct DropUntil<U> {
     pub(crate) until: U,
 }
 
+pub struct SkipUntil<U> {
+    pub(crate) until: U,
+}
+
 pub struct Discard<D, P> {
     pub(crate) discard: D,
     pub(crate) parser: P,
 }
 
+pub struct Skip<P, S> {
+    pub(crate) parser: P,
+    pub(crate) skipped: S,
+}
+
+pub struct Expect<P> {
+    pub(crate) expected: &'static str,
+    pub(crate) parser: P,
+}
+
+pub struct MapErr<F, P> {
+    pub(crate) f: F,
+    pub(crate) parser: P,
+}
+
+pub struct OrFailWith<F, P> {
+    pub(crate) f: F,
+    pub(crate) parser: P,
+}
+
+pub struct WithSpan<P> {
+    pub(crate) parser: P,
+}
+
 pub struct Peek<F, P> {
     pub(crate) f: F,
     pub(crate) parser: P,
 }
 
+#[cfg(feature = "std")]
+pub struct Traced<P, L> {
+    pub(crate) name: String,
+    pub(crate) parser: P,
+    pub(crate) log: L,
+}
+
 pub struct PeekOut<F, P> {
     pub(crate) f: F,
     pub(crate) parser: P,
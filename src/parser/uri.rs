@@ -0,0 +1,206 @@
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+use super::{
+    errors::{ErrorSource, ParserError},
+    impls::{sequence, take_while1},
+    net::{ipv4, ipv6},
+    sensitive::Sensitive,
+    traits::{discard, opt, parse_if, parse_rest, ParseResult, Parser},
+};
+
+const UNRESERVED: &str = "-._~";
+const SUB_DELIMS: &str = "!$&'()*+,;=";
+
+/// A URI split into its RFC 3986 components. Components keep their percent-encoding,
+/// use [`percent_decode`] to get the decoded text
+#[derive(Debug, PartialEq)]
+pub struct Uri {
+    pub scheme: String,
+    pub authority: Option<Authority>,
+    pub path: String,
+    pub query: Option<String>,
+    pub fragment: Option<String>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Authority {
+    /// Redacted from `Debug` output since it may hold a password
+    pub userinfo: Option<Sensitive<String>>,
+    pub host: Host,
+    pub port: Option<u16>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Host {
+    Ipv4(Ipv4Addr),
+    Ipv6(Ipv6Addr),
+    RegName(String),
+}
+
+impl Uri {
+    /// Iterates over the segments of the path, without the leading empty segment of
+    /// absolute paths
+    pub fn path_segments(&self) -> impl Iterator<Item = &str> {
+        self.path
+            .strip_prefix('/')
+            .unwrap_or(&self.path)
+            .split('/')
+            .filter(|_| !self.path.is_empty())
+    }
+
+    /// Iterates over the `key=value` pairs of the query, a key without `=` has an empty value
+    pub fn query_pairs(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.query
+            .iter()
+            .flat_map(|query| query.split('&'))
+            .filter(|pair| !pair.is_empty())
+            .map(|pair| pair.split_once('=').unwrap_or((pair, "")))
+    }
+}
+
+/// Decodes `%XX` triplets, returns `None` on a malformed triplet or if the decoded bytes
+/// are not valid UTF-8
+pub fn percent_decode(input: &str) -> Option<String> {
+    let mut bytes = Vec::with_capacity(input.len());
+    let mut iter = input.bytes();
+    while let Some(byte) = iter.next() {
+        if byte == b'%' {
+            let high = (iter.next()? as char).to_digit(16)?;
+            let low = (iter.next()? as char).to_digit(16)?;
+            bytes.push((high * 16 + low) as u8);
+        } else {
+            bytes.push(byte);
+        }
+    }
+    String::from_utf8(bytes).ok()
+}
+
+pub fn uri(input: &str) -> ParseResult<&str, Uri> {
+    let (rest, scheme) = scheme(input)?;
+    let (rest, authority) = match rest.strip_prefix("//") {
+        Some(remainder) => parse_rest(&mut authority, &input, remainder)
+            .map(|(rest, authority)| (rest, Some(authority)))?,
+        None => (rest, None),
+    };
+    let path_start = input.len() - rest.len();
+    let (rest, path) = parse_rest(&mut encoded(":@/"), &input, rest)?;
+    let mut query = parse_if(sequence("?"), encoded(":@/?"));
+    let (rest, query) = parse_rest(&mut query, &input, rest)?;
+    let mut fragment = parse_if(sequence("#"), encoded(":@/?"));
+    let (rest, fragment) = parse_rest(&mut fragment, &input, rest)?;
+
+    if authority.is_some() && !path.is_empty() && !path.starts_with('/') {
+        return Err(ParserError::new(
+            path_start,
+            ErrorSource::Sequence("/"),
+            "path must be absolute when an authority is present",
+        ));
+    }
+
+    Ok((
+        rest,
+        Uri {
+            scheme: scheme.to_string(),
+            authority,
+            path: path.to_string(),
+            query: query.map(str::to_string),
+            fragment: fragment.map(str::to_string),
+        },
+    ))
+}
+
+pub fn authority(input: &str) -> ParseResult<&str, Authority> {
+    let (rest, userinfo) = opt(encoded(":").skip(sequence("@"))).parse(input)?;
+    let (rest, host) = parse_rest(&mut host, &input, rest)?;
+    let mut port = opt(discard(sequence(":"), opt(port)));
+    let (rest, port) = parse_rest(&mut port, &input, rest)?;
+    Ok((
+        rest,
+        Authority {
+            userinfo: userinfo.map(|userinfo| Sensitive::new(userinfo.to_string())),
+            host,
+            port: port.flatten(),
+        },
+    ))
+}
+
+pub fn host(input: &str) -> ParseResult<&str, Host> {
+    if let Some(literal) = input.strip_prefix('[') {
+        let (remainder, address) =
+            ipv6(literal).map_err(|error| ParserError::from_error(error, 1))?;
+        let offset = input.len() - remainder.len();
+        let (remainder, _) = sequence("]")
+            .parse(remainder)
+            .map_err(|error| ParserError::from_error(error, offset))?;
+        return Ok((remainder, Host::Ipv6(address)));
+    }
+
+    let (remainder, name) = encoded("").parse(input)?;
+    match ipv4(name) {
+        Ok(("", address)) => Ok((remainder, Host::Ipv4(address))),
+        _ => Ok((remainder, Host::RegName(name.to_string()))),
+    }
+}
+
+fn scheme(input: &str) -> ParseResult<&str, &str> {
+    let (remainder, scheme) =
+        take_while1(|c| c.is_ascii_alphanumeric() || "+-.".contains(c)).parse(input)?;
+    if !scheme.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        return Err(ParserError::new(
+            0,
+            ErrorSource::TakeWhile,
+            "scheme must start with a letter",
+        ));
+    }
+    let (remainder, _) = parse_rest(&mut sequence(":"), &input, remainder)?;
+    Ok((remainder, scheme))
+}
+
+fn port(input: &str) -> ParseResult<&str, u16> {
+    let (remainder, digits) = take_while1(|c| c.is_ascii_digit()).parse(input)?;
+    digits
+        .parse::<u16>()
+        .map(|port| (remainder, port))
+        .map_err(|_| {
+            let mut error = ParserError::new(
+                0,
+                ErrorSource::TakeWhile,
+                format!("port '{}' is out of range", digits),
+            );
+            // An optional port must not backtrack over digits which are not one
+            error.committed = true;
+            error
+        })
+}
+
+/// Takes unreserved characters, sub-delimiters, the given extra characters and valid
+/// percent-encoded triplets. Succeeds with an empty slice if none match, and fails at the first
+/// character of a triplet which is not a hex digit
+fn encoded<'a>(extra: &'a str) -> impl Parser<&'a str, Output = &'a str> {
+    move |input: &'a str| {
+        let end = input
+            .find(|c: char| {
+                !(c.is_ascii_alphanumeric()
+                    || UNRESERVED.contains(c)
+                    || SUB_DELIMS.contains(c)
+                    || extra.contains(c)
+                    || c == '%')
+            })
+            .unwrap_or(input.len());
+        let (parsed, remainder) = input.split_at(end);
+        let invalid = parsed.match_indices('%').find_map(|(position, _)| {
+            let hex = &parsed.as_bytes()[position + 1..];
+            (1..3)
+                .find(|&digit| !hex.get(digit - 1).is_some_and(u8::is_ascii_hexdigit))
+                .map(|digit| position + digit)
+        });
+        match invalid {
+            Some(position) => Err(ParserError::new(
+                position,
+                ErrorSource::TakeWhile,
+                "invalid percent-encoding",
+            )),
+            None => Ok((remainder, parsed)),
+        }
+    }
+}
@@ -0,0 +1,163 @@
+use super::{
+    binary::{be_u16, be_u64, take_bytes, u8},
+    errors::{ErrorSource, ParserError},
+    traits::{parse_rest, ParseResult},
+};
+
+/// Longest payload of a control frame
+const MAX_CONTROL_PAYLOAD: u64 = 125;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Opcode {
+    Continuation,
+    Text,
+    Binary,
+    Close,
+    Ping,
+    Pong,
+    /// An opcode RFC 6455 reserves for extensions, `3` to `7` and `11` to `15`
+    Reserved(u8),
+}
+
+impl Opcode {
+    /// Control frames, whose opcode has its high bit set, cannot be fragmented
+    pub fn is_control(self) -> bool {
+        matches!(self, Opcode::Close | Opcode::Ping | Opcode::Pong)
+            || matches!(self, Opcode::Reserved(opcode) if opcode >= 8)
+    }
+}
+
+impl From<u8> for Opcode {
+    fn from(opcode: u8) -> Self {
+        match opcode {
+            0 => Opcode::Continuation,
+            1 => Opcode::Text,
+            2 => Opcode::Binary,
+            8 => Opcode::Close,
+            9 => Opcode::Ping,
+            10 => Opcode::Pong,
+            opcode => Opcode::Reserved(opcode),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct FrameHeader {
+    /// Set on the last frame of a message
+    pub fin: bool,
+    /// The bits reserved for extensions, `rsv1` first
+    pub rsv: [bool; 3],
+    pub opcode: Opcode,
+    /// The key masking the payload, which every frame sent by a client has
+    pub mask: Option<[u8; 4]>,
+    pub payload_length: u64,
+}
+
+impl FrameHeader {
+    /// Masks or unmasks `payload` in place with the key of the header, `offset` being the
+    /// position of `payload` in the payload of the frame when it is processed in parts
+    pub fn unmask(&self, payload: &mut [u8], offset: u64) {
+        let Some(key) = self.mask else {
+            return;
+        };
+        for (position, byte) in payload.iter_mut().enumerate() {
+            *byte ^= key[((offset + position as u64) % 4) as usize];
+        }
+    }
+}
+
+/// Parses the header of a frame (RFC 6455), leaving the payload unparsed. Extended payload
+/// lengths must use their shortest encoding and control frames must be final with at most
+/// 125 bytes of payload. Error indices are offsets in the frame
+///
+/// # Examples
+/// ```rust
+///
+/// use pepser::parser::websocket::{frame_header, Opcode};
+/// let frame = [0x81, 0x85, 0x37, 0xfa, 0x21, 0x3d, 0x7f, 0x9f, 0x4d, 0x51, 0x58];
+/// let (payload, header) = frame_header(&frame).unwrap();
+///
+/// assert!(header.fin);
+/// assert_eq!(header.opcode, Opcode::Text);
+/// assert_eq!(header.payload_length, 5);
+/// let mut payload = payload.to_vec();
+/// header.unmask(&mut payload, 0);
+/// assert_eq!(payload, b"Hello");
+/// assert!(frame_header(&[0x09, 0x00]).is_err());
+///
+///
+/// ```
+pub fn frame_header(input: &[u8]) -> ParseResult<&[u8], FrameHeader> {
+    let frame = input;
+    let (input, first) = u8(input)?;
+    let (input, second) = parse_rest(&mut u8, &frame, input)?;
+    let opcode = Opcode::from(first & 0x0F);
+    let fin = first & 0x80 != 0;
+    let (input, payload_length) = match second & 0x7F {
+        126 => {
+            let (input, length) = parse_rest(&mut be_u16, &frame, input)?;
+            if length < 126 {
+                return Err(invalid(2, "payload length not in its shortest encoding"));
+            }
+            (input, u64::from(length))
+        }
+        127 => {
+            let (input, length) = parse_rest(&mut be_u64, &frame, input)?;
+            if length >> 63 != 0 {
+                return Err(invalid(2, "payload length with its high bit set"));
+            }
+            if length <= u64::from(u16::MAX) {
+                return Err(invalid(2, "payload length not in its shortest encoding"));
+            }
+            (input, length)
+        }
+        length => (input, u64::from(length)),
+    };
+    if opcode.is_control() && (!fin || payload_length > MAX_CONTROL_PAYLOAD) {
+        return Err(invalid(0, "fragmented or oversized control frame"));
+    }
+    let (input, mask) = match second & 0x80 {
+        0 => (input, None),
+        _ => {
+            let (input, key) = parse_rest(&mut take_bytes(4), &frame, input)?;
+            (input, key.try_into().ok())
+        }
+    };
+    Ok((
+        input,
+        FrameHeader {
+            fin,
+            rsv: [first & 0x40 != 0, first & 0x20 != 0, first & 0x10 != 0],
+            opcode,
+            mask,
+            payload_length,
+        },
+    ))
+}
+
+/// Parses the header of a frame and takes its payload, still masked
+///
+/// # Examples
+/// ```rust
+///
+/// use pepser::parser::websocket::{frame, Opcode};
+/// let (remainder, (header, payload)) = frame(&[0x8A, 0x02, b'h', b'i', 0x88]).unwrap();
+///
+/// assert_eq!(header.opcode, Opcode::Pong);
+/// assert_eq!(payload, b"hi");
+/// assert_eq!(remainder, [0x88]);
+///
+///
+/// ```
+pub fn frame(input: &[u8]) -> ParseResult<&[u8], (FrameHeader, &[u8])> {
+    let (payload, header) = frame_header(input)?;
+    let Ok(length) = usize::try_from(header.payload_length) else {
+        return Err(invalid(2, "length does not fit in memory"));
+    };
+    let (remainder, payload) = parse_rest(&mut take_bytes(length), &input, payload)?;
+    Ok((remainder, (header, payload)))
+}
+
+fn invalid<'a>(index: usize, reason: &'static str) -> ParserError<&'a [u8]> {
+    ParserError::new(index, ErrorSource::TakeWhile, reason)
+}
@@ -0,0 +1,269 @@
+use super::{
+    errors::{ErrorSource, ParserError},
+    impls::{eof, none_of, sequence, take_until, take_while1, ws},
+    traits::{discard, wrapped, ParseResult, Parser},
+};
+
+#[derive(Debug, PartialEq)]
+pub enum XmlNode {
+    Element(XmlElement),
+    Text(String),
+    CData(String),
+    Comment(String),
+    ProcessingInstruction { target: String, data: String },
+}
+
+#[derive(Debug, PartialEq)]
+pub struct XmlElement {
+    pub name: String,
+    pub attributes: Vec<(String, String)>,
+    /// Child nodes in document order, whitespace-only text between nodes is dropped
+    pub children: Vec<XmlNode>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct XmlDocument {
+    /// Comments and processing instructions (including the `<?xml ...?>` declaration) before the root
+    pub prolog: Vec<XmlNode>,
+    pub root: XmlElement,
+    /// Comments and processing instructions after the root
+    pub epilog: Vec<XmlNode>,
+}
+
+impl XmlElement {
+    /// Returns the decoded value of the first attribute with the given name
+    pub fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Iterates over the child elements, skipping text, comments and other nodes
+    pub fn elements(&self) -> impl Iterator<Item = &XmlElement> {
+        self.children.iter().filter_map(|node| match node {
+            XmlNode::Element(element) => Some(element),
+            _ => None,
+        })
+    }
+
+    /// Concatenation of the direct text and CDATA children
+    pub fn text(&self) -> String {
+        self.children
+            .iter()
+            .filter_map(|node| match node {
+                XmlNode::Text(text) | XmlNode::CData(text) => Some(text.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+/// Parses a complete document: the prolog, a single root element and the trailing misc nodes.
+/// Fails if anything else remains in the input. Error indices are offsets in the input
+pub fn xml_document(input: &str) -> ParseResult<&str, XmlDocument> {
+    let (rest, prolog) = misc.many0().parse(input)?;
+    let (rest, root) = within(input, rest, discard(ws(), xml_element))?;
+    let (rest, epilog) = within(input, rest, misc.many0())?;
+    let (rest, _) = within(input, rest, discard(ws(), eof()))?;
+    Ok((
+        rest,
+        XmlDocument {
+            prolog,
+            root,
+            epilog,
+        },
+    ))
+}
+
+/// Deepest nesting of elements accepted, so that a hostile document cannot exhaust the stack
+const MAX_DEPTH: usize = 128;
+
+/// Parses an element and its content. A closing tag which does not match the opening one is
+/// a committed error at its name, error indices are offsets in the input
+pub fn xml_element(input: &str) -> ParseResult<&str, XmlElement> {
+    element(input, 1)
+}
+
+/// An element within `depth - 1` others. Once its start tag is read, an element deeper than
+/// [`MAX_DEPTH`] is a committed error
+fn element(input: &str, depth: usize) -> ParseResult<&str, XmlElement> {
+    let (rest, (tag, attributes)) =
+        discard(sequence("<"), name.and(discard(ws(), attribute).many0())).parse(input)?;
+    if depth > MAX_DEPTH {
+        let mut error =
+            ParserError::new(0, ErrorSource::LimitExceeded, "element nested too deeply");
+        error.committed = true;
+        return Err(error);
+    }
+    let (rest, _) = within(input, rest, ws())?;
+
+    if let Ok((rest, _)) = sequence("/>").parse(rest) {
+        return Ok((
+            rest,
+            XmlElement {
+                name: tag.to_string(),
+                attributes,
+                children: vec![],
+            },
+        ));
+    }
+
+    let (rest, children) = within(
+        input,
+        rest,
+        discard(sequence(">"), (|input| node(input, depth)).many0()),
+    )?;
+    let closing_start = input.len() - rest.len();
+    let (rest, closing) = within(
+        input,
+        rest,
+        wrapped(sequence("</"), name, discard(ws(), sequence(">"))),
+    )?;
+    if closing != tag {
+        let mut error = ParserError::new(
+            closing_start + "</".len(),
+            ErrorSource::Sequence(closing),
+            format!("mismatched closing tag '{}', expected '{}'", closing, tag),
+        );
+        error.committed = true;
+        return Err(error);
+    }
+
+    Ok((
+        rest,
+        XmlElement {
+            name: tag.to_string(),
+            attributes,
+            children: children
+                .into_iter()
+                .filter(|node| !matches!(node, XmlNode::Text(text) if text.trim().is_empty()))
+                .collect(),
+        },
+    ))
+}
+
+/// Runs `parser` on `rest`, the end of `input`, with its errors as offsets in `input`
+fn within<'a, O>(
+    input: &'a str,
+    rest: &'a str,
+    mut parser: impl Parser<&'a str, Output = O>,
+) -> ParseResult<&'a str, O> {
+    parser
+        .parse(rest)
+        .map_err(|error| ParserError::from_error(error, input.len() - rest.len()))
+}
+
+pub fn xml_node(input: &str) -> ParseResult<&str, XmlNode> {
+    node(input, 0)
+}
+
+/// A node within `depth` elements
+fn node(input: &str, depth: usize) -> ParseResult<&str, XmlNode> {
+    (|input| element(input, depth + 1))
+        .map(XmlNode::Element)
+        .or(comment)
+        .or(cdata)
+        .or(processing_instruction)
+        .or(text)
+        .parse(input)
+}
+
+fn misc(input: &str) -> ParseResult<&str, XmlNode> {
+    wrapped(ws(), comment.or(processing_instruction), ws()).parse(input)
+}
+
+fn name(input: &str) -> ParseResult<&str, &str> {
+    take_while1(|c: char| c.is_alphanumeric() || matches!(c, '_' | '-' | '.' | ':')).parse(input)
+}
+
+fn attribute(input: &str) -> ParseResult<&str, (String, String)> {
+    name.map(str::to_string)
+        .and(discard(
+            wrapped(ws(), sequence("="), ws()),
+            quoted("\"", "<&\"").or(quoted("'", "<&'")),
+        ))
+        .parse(input)
+}
+
+fn quoted<'a>(quote: &'a str, delimiters: &'a str) -> impl Parser<&'a str, Output = String> {
+    wrapped(
+        sequence(quote),
+        char_data(delimiters).many0().map(|parts| parts.concat()),
+        sequence(quote),
+    )
+}
+
+fn text(input: &str) -> ParseResult<&str, XmlNode> {
+    char_data("<&")
+        .many0()
+        .map(|parts| XmlNode::Text(parts.concat()))
+        .parse(input)
+}
+
+fn char_data(delimiters: &str) -> impl Parser<&str, Output = String> {
+    none_of(delimiters).map(str::to_string).or(entity)
+}
+
+fn comment(input: &str) -> ParseResult<&str, XmlNode> {
+    wrapped(sequence("<!--"), take_until("-->"), sequence("-->"))
+        .map(|comment| XmlNode::Comment(comment.to_string()))
+        .parse(input)
+}
+
+fn cdata(input: &str) -> ParseResult<&str, XmlNode> {
+    wrapped(sequence("<![CDATA["), take_until("]]>"), sequence("]]>"))
+        .map(|data| XmlNode::CData(data.to_string()))
+        .parse(input)
+}
+
+fn processing_instruction(input: &str) -> ParseResult<&str, XmlNode> {
+    wrapped(
+        sequence("<?"),
+        name.and(discard(ws(), take_until("?>"))),
+        sequence("?>"),
+    )
+    .map(|(target, data)| XmlNode::ProcessingInstruction {
+        target: target.to_string(),
+        data: data.trim_end().to_string(),
+    })
+    .parse(input)
+}
+
+fn entity(input: &str) -> ParseResult<&str, String> {
+    let (remainder, reference) = wrapped(
+        sequence("&"),
+        take_while1(|c: char| c.is_alphanumeric() || c == '#'),
+        sequence(";"),
+    )
+    .parse(input)?;
+
+    match decode_reference(reference) {
+        Some(c) => Ok((remainder, c.to_string())),
+        None => Err(ParserError::new(
+            0,
+            ErrorSource::Sequence(reference),
+            format!("unknown entity '&{};'", reference),
+        )),
+    }
+}
+
+fn decode_reference(reference: &str) -> Option<char> {
+    match reference {
+        "lt" => Some('<'),
+        "gt" => Some('>'),
+        "amp" => Some('&'),
+        "quot" => Some('"'),
+        "apos" => Some('\''),
+        _ => {
+            let code = match reference
+                .strip_prefix("#x")
+                .or(reference.strip_prefix("#X"))
+            {
+                Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                None => reference.strip_prefix('#')?.parse::<u32>().ok()?,
+            };
+            char::from_u32(code)
+        }
+    }
+}
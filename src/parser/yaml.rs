@@ -0,0 +1,239 @@
+use std::collections::HashMap;
+
+use super::{
+    errors::{ErrorSource, ParserError},
+    impls::{eof, escaped_transform, none_of, sequence, take_while1, take_while_m_n},
+    json::{JsonNumber, JsonValue},
+    traits::{discard, opt, ParseResult, Parser},
+};
+
+/// Parses a YAML document written in flow style (`{a: 1, b: [2, 3]}`) or made of a single
+/// scalar. Plain scalars are resolved with the core schema: `null`/`~`, booleans and numbers,
+/// anything else is a string. Block collections, anchors and tags are not supported
+pub fn yaml(input: &str) -> ParseResult<&str, JsonValue> {
+    yaml_value.skip(discard(trivia, eof())).parse(input)
+}
+
+/// Deepest nesting of flow collections accepted, so that a hostile document cannot exhaust
+/// the stack
+const MAX_DEPTH: usize = 128;
+
+/// Parses a single flow node, skipping leading whitespace and `#` comments
+pub fn yaml_value(input: &str) -> ParseResult<&str, JsonValue> {
+    node(input, 0)
+}
+
+/// A node within `depth` collections
+fn node(input: &str, depth: usize) -> ParseResult<&str, JsonValue> {
+    let (remainder, _) = trivia(input)?;
+    let offset = input.len() - remainder.len();
+    let parsed = match remainder.chars().next() {
+        Some('{' | '[') if depth >= MAX_DEPTH => Err(ParserError::new(
+            0,
+            ErrorSource::LimitExceeded,
+            "collection nested too deeply",
+        )),
+        Some('{') => flow_mapping(remainder, depth + 1),
+        Some('[') => flow_sequence(remainder, depth + 1),
+        Some('"') => double_quoted.map(JsonValue::String).parse(remainder),
+        Some('\'') => single_quoted.map(JsonValue::String).parse(remainder),
+        _ => plain.map(resolve).parse(remainder),
+    };
+    parsed.map_err(|error| ParserError::from_error(error, offset))
+}
+
+fn flow_mapping(input: &str, depth: usize) -> ParseResult<&str, JsonValue> {
+    let (remainder, entries) = collection("{", "}", |input| entry(input, depth), input)?;
+    Ok((
+        remainder,
+        JsonValue::Object(entries.into_iter().collect::<HashMap<_, _>>()),
+    ))
+}
+
+fn flow_sequence(input: &str, depth: usize) -> ParseResult<&str, JsonValue> {
+    collection("[", "]", |input| node(input, depth), input)
+        .map(|(remainder, values)| (remainder, JsonValue::Array(values)))
+}
+
+/// Parses comma separated elements between brackets, a trailing comma is allowed. Once an
+/// element has started its errors are returned as is, offset from the opening bracket
+fn collection<'a, O, P>(
+    open: &'a str,
+    close: &'a str,
+    mut element: P,
+    input: &'a str,
+) -> ParseResult<&'a str, Vec<O>>
+where
+    P: Parser<&'a str, Output = O>,
+{
+    let (mut remainder, _) = discard(sequence(open), trivia).parse(input)?;
+    let mut elements = vec![];
+    while !remainder.starts_with(close) {
+        let offset = input.len() - remainder.len();
+        let (next, parsed) = element
+            .parse(remainder)
+            .map_err(|error| ParserError::from_error(error, offset))?;
+        elements.push(parsed);
+        let (next, separator) = discard(trivia, opt(sequence(","))).parse(next)?;
+        let (next, _) = trivia(next)?;
+        remainder = next;
+        if separator.is_none() {
+            break;
+        }
+    }
+    let (remainder, _) = closing(close, input, remainder)?;
+    Ok((remainder, elements))
+}
+
+/// A `key: value` pair of a flow mapping, the value may be omitted in which case it is null
+fn entry(input: &str, depth: usize) -> ParseResult<&str, (String, JsonValue)> {
+    let (remainder, key) = discard(trivia, key).parse(input)?;
+    let (remainder, separator) = discard(trivia, opt(sequence(":"))).parse(remainder)?;
+    let (remainder, _) = trivia(remainder)?;
+    if separator.is_none() || remainder.is_empty() || remainder.starts_with([',', '}']) {
+        return Ok((remainder, (key, JsonValue::Null)));
+    }
+    let offset = input.len() - remainder.len();
+    let (remainder, value) =
+        node(remainder, depth).map_err(|error| ParserError::from_error(error, offset))?;
+    let (remainder, _) = trivia(remainder)?;
+    Ok((remainder, (key, value)))
+}
+
+fn key(input: &str) -> ParseResult<&str, String> {
+    double_quoted
+        .or(single_quoted)
+        .or(plain.map(str::to_string))
+        .parse(input)
+}
+
+/// Parses the delimiter closing the node which began at `start`
+fn closing<'a>(delimiter: &'a str, start: &'a str, input: &'a str) -> ParseResult<&'a str, ()> {
+    sequence(delimiter)
+        .map(|_| ())
+        .parse(input)
+        .map_err(|error| ParserError::from_error(error, start.len() - input.len()))
+}
+
+/// A plain scalar ends at a flow indicator, a `: ` separator, a ` #` comment or a line break
+fn plain(input: &str) -> ParseResult<&str, &str> {
+    let mut end = 0;
+    let mut chars = input.char_indices().peekable();
+    while let Some((position, c)) = chars.next() {
+        let next = chars.peek().map(|(_, next)| *next);
+        let separator =
+            c == ':' && next.is_none_or(|next| next.is_whitespace() || ",[]{}".contains(next));
+        let comment = c.is_whitespace() && next == Some('#');
+        if ",[]{}\n".contains(c) || separator || comment {
+            break;
+        }
+        if !c.is_whitespace() {
+            end = position + c.len_utf8();
+        }
+    }
+    if end == 0 {
+        return Err(ParserError::new(
+            0,
+            ErrorSource::TakeWhile,
+            "expected a scalar",
+        ));
+    }
+    Ok((&input[end..], &input[..end]))
+}
+
+fn resolve(scalar: &str) -> JsonValue {
+    match scalar {
+        "~" | "null" | "Null" | "NULL" => return JsonValue::Null,
+        "true" | "True" | "TRUE" => return JsonValue::Boolean(true),
+        "false" | "False" | "FALSE" => return JsonValue::Boolean(false),
+        ".inf" | ".Inf" | ".INF" | "+.inf" => return float(f64::INFINITY),
+        "-.inf" | "-.Inf" | "-.INF" => return float(f64::NEG_INFINITY),
+        ".nan" | ".NaN" | ".NAN" => return float(f64::NAN),
+        _ => {}
+    }
+    if let Some(hex) = scalar.strip_prefix("0x") {
+        if let Ok(value) = i64::from_str_radix(hex, 16) {
+            return JsonValue::Number(JsonNumber::Int(value));
+        }
+    }
+    if let Some(octal) = scalar.strip_prefix("0o") {
+        if let Ok(value) = i64::from_str_radix(octal, 8) {
+            return JsonValue::Number(JsonNumber::Int(value));
+        }
+    }
+    let numeric = scalar
+        .trim_start_matches(['+', '-'])
+        .trim_start_matches('.')
+        .starts_with(|c: char| c.is_ascii_digit());
+    if let Ok(value) = scalar.parse::<i64>() {
+        return JsonValue::Number(JsonNumber::Int(value));
+    }
+    match scalar.parse::<f64>() {
+        Ok(value) if numeric => float(value),
+        _ => JsonValue::String(scalar.to_string()),
+    }
+}
+
+fn float(value: f64) -> JsonValue {
+    JsonValue::Number(JsonNumber::Float(value))
+}
+
+fn double_quoted(input: &str) -> ParseResult<&str, String> {
+    let (remainder, _) = sequence("\"").parse(input)?;
+    let (remainder, text) = escaped_transform(none_of("\"\\"), '\\', escaped).parse(remainder)?;
+    let (remainder, _) = closing("\"", input, remainder)?;
+    Ok((remainder, text))
+}
+
+/// The escape sequence following a backslash
+fn escaped(input: &str) -> ParseResult<&str, String> {
+    let unescaped = match input.chars().next() {
+        Some('\\') => '\\',
+        Some('"') => '"',
+        Some('/') => '/',
+        Some('n') => '\n',
+        Some('t') => '\t',
+        Some('r') => '\r',
+        Some('0') => '\0',
+        Some(' ') => ' ',
+        Some('u') => {
+            let invalid =
+                || ParserError::new(0, ErrorSource::Sequence("u"), "invalid unicode escape");
+            let (remainder, hex) = take_while_m_n(4, 4, |c| c.is_ascii_hexdigit())
+                .parse(&input[1..])
+                .map_err(|_| invalid())?;
+            return u32::from_str_radix(hex, 16)
+                .ok()
+                .and_then(char::from_u32)
+                .map(|c| (remainder, c.to_string()))
+                .ok_or_else(invalid);
+        }
+        _ => {
+            return Err(ParserError::new(
+                0,
+                ErrorSource::Sequence("\\"),
+                "unknown escape",
+            ))
+        }
+    };
+    Ok((&input[1..], unescaped.to_string()))
+}
+
+/// Single quoted scalars have no escapes besides `''` for a quote
+fn single_quoted(input: &str) -> ParseResult<&str, String> {
+    let (remainder, _) = sequence("'").parse(input)?;
+    let (remainder, parts) = none_of("'")
+        .or(sequence("''").map(|_| "'"))
+        .many0()
+        .parse(remainder)?;
+    let (remainder, _) = closing("'", input, remainder)?;
+    Ok((remainder, parts.concat()))
+}
+
+fn trivia(input: &str) -> ParseResult<&str, ()> {
+    take_while1(char::is_whitespace)
+        .or(discard(sequence("#"), opt(none_of("\n"))).map(|_| ""))
+        .many0()
+        .map(|_| ())
+        .parse(input)
+}
@@ -0,0 +1,68 @@
+use pepser::{
+    errors::ErrorSource,
+    impls::{balanced, balanced_with_strings, sequence, take_until},
+    traits::Parser,
+};
+
+#[test]
+fn match_nested_delimiters() {
+    assert_eq!(balanced("[", "]").parse("[]"), Ok(("", "")));
+    assert_eq!(
+        balanced("[", "]").parse("[[a][b[c]]] [d]"),
+        Ok((" [d]", "[a][b[c]]"))
+    );
+    assert_eq!(
+        balanced("(", ")").parse("(日本(語))x"),
+        Ok(("x", "日本(語)"))
+    );
+    assert_eq!(
+        balanced("<!--", "-->").parse("<!-- a <!-- b --> -->"),
+        Ok(("", " a <!-- b --> "))
+    );
+    // A close equal to the open delimiter never nests
+    assert_eq!(balanced("|", "|").parse("|a|b|"), Ok(("b|", "a")));
+    // Without strings, quotes and escapes are plain characters
+    assert_eq!(balanced("(", ")").parse("(\")\" x"), Ok(("\" x", "\"")));
+
+    let error = balanced("(", ")").parse("x(a)").unwrap_err();
+    assert_eq!((error.index, error.source), (0, ErrorSource::Sequence("(")));
+    let error = balanced("(", ")").parse("((a)").unwrap_err();
+    assert_eq!(
+        (error.index, error.source),
+        (0, ErrorSource::TakeUntil(")"))
+    );
+    assert!(balanced("", ")").parse("a)").is_err());
+    assert!(balanced("(", "").parse("(a").is_err());
+}
+
+#[test]
+fn skip_strings_and_escapes() {
+    let mut block = balanced_with_strings("{", "}", "\"'", '\\');
+    assert_eq!(
+        block.parse(r#"{ "{\"" '}' { "a'b" } }; next"#),
+        Ok(("; next", r#" "{\"" '}' { "a'b" } "#))
+    );
+    // User agent comments escape their parentheses without strings
+    let mut comment = balanced_with_strings("(", ")", "", '\\');
+    assert_eq!(
+        comment.parse(r"(Linux \(x86\); (nested)) rest"),
+        Ok((" rest", r"Linux \(x86\); (nested)"))
+    );
+    assert_eq!(comment.parse("(a\\"), Err(comment.parse("(a").unwrap_err()));
+
+    assert_eq!(block.parse("{ 'open }").unwrap_err().index, 2);
+    assert_eq!(block.parse("{ \"é\" \"open").unwrap_err().index, 7);
+    assert_eq!(block.parse("{ { } ").unwrap_err().index, 0);
+}
+
+#[test]
+fn skip_regions_for_recovery() {
+    // Skips a malformed call to reach what follows it
+    let mut call = sequence("call")
+        .and(balanced("(", ")"))
+        .and(take_until(";"));
+    assert_eq!(
+        call.parse("call(1, [2, oops) ; ok"),
+        Ok(("; ok", (("call", "1, [2, oops"), " ")))
+    );
+}
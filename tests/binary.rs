@@ -0,0 +1,67 @@
+use pepser::{
+    binary::{be_f64, be_u32, i8, le_i16, le_u16, le_u32, length_data, take_bytes, u8},
+    traits::{Input, Parser},
+};
+
+#[derive(Debug, PartialEq)]
+struct Chunk<'a> {
+    kind: &'a [u8],
+    data: &'a [u8],
+    crc: u32,
+}
+
+/// A PNG chunk: the length of the data, the chunk type, the data and its CRC
+fn chunk(input: &[u8]) -> pepser::traits::ParseResult<&[u8], Chunk<'_>> {
+    let (input, length) = be_u32(input)?;
+    let (input, kind) = take_bytes(4).parse(input)?;
+    let (input, data) = take_bytes(length as usize).parse(input)?;
+    let (input, crc) = be_u32(input)?;
+    Ok((input, Chunk { kind, data, crc }))
+}
+
+#[test]
+fn parse_png_chunks() {
+    let bytes = [
+        0, 0, 0, 2, b'g', b'A', b'M', b'A', 0xAB, 0xCD, 0xDE, 0xAD, 0xBE, 0xEF, 0, 0, 0, 0, b'I',
+        b'E', b'N', b'D', 0xAE, 0x42, 0x60, 0x82,
+    ];
+    let (remainder, chunks) = chunk.many0().parse(&bytes[..]).unwrap();
+    assert_eq!(remainder.input_len(), 0);
+    assert_eq!(
+        chunks,
+        vec![
+            Chunk {
+                kind: b"gAMA",
+                data: &[0xAB, 0xCD],
+                crc: 0xDEADBEEF
+            },
+            Chunk {
+                kind: b"IEND",
+                data: &[],
+                crc: 0xAE426082
+            },
+        ]
+    );
+}
+
+#[test]
+fn read_endianness() {
+    let bytes = [0x01, 0x02, 0x03, 0x04];
+    assert_eq!(le_u32(&bytes), Ok((&[][..], 0x04030201)));
+    assert_eq!(be_u32(&bytes), Ok((&[][..], 0x01020304)));
+    assert_eq!(le_u16(&bytes), Ok((&bytes[2..], 0x0201)));
+    assert_eq!(le_i16(&[0xFE, 0xFF]), Ok((&[][..], -2)));
+    assert_eq!(u8.and(i8).parse(&[7, 0xFF]), Ok((&[][..], (7, -1))));
+    assert_eq!(be_f64(&1.5f64.to_be_bytes()), Ok((&[][..], 1.5)));
+}
+
+#[test]
+fn report_truncated_input() {
+    let error = be_u32(&[1, 2]).unwrap_err();
+    assert_eq!(
+        (error.index, error.reason.as_ref()),
+        (2, "unexpected end of input")
+    );
+    let error = length_data(u8).parse(&[5, 1, 2]).unwrap_err();
+    assert_eq!(error.index, 3);
+}
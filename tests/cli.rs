@@ -0,0 +1,55 @@
+use pepser::cli::{arguments, Argument};
+
+fn long(name: &str, value: Option<&str>) -> Argument {
+    Argument::Long {
+        name: name.to_string(),
+        value: value.map(String::from),
+    }
+}
+
+fn positional(word: &str) -> Argument {
+    Argument::Positional(word.to_string())
+}
+
+#[test]
+fn parse_flags() {
+    let (remainder, parsed) =
+        arguments("-xvf archive.tar --dry-run --level=9 --name='a b' - -3 # note").unwrap();
+    assert_eq!(remainder, "");
+    assert_eq!(
+        parsed,
+        [
+            Argument::Short('x'),
+            Argument::Short('v'),
+            Argument::Short('f'),
+            positional("archive.tar"),
+            long("dry-run", None),
+            long("level", Some("9")),
+            long("name", Some("a b")),
+            positional("-"),
+            positional("-3"),
+        ]
+    );
+    assert_eq!(arguments("--empty=").unwrap().1, [long("empty", Some(""))]);
+}
+
+#[test]
+fn stop_options_after_separator() {
+    let (_, parsed) = arguments("rm -- -f --force --").unwrap();
+    assert_eq!(
+        parsed,
+        [
+            positional("rm"),
+            positional("-f"),
+            positional("--force"),
+            positional("--")
+        ]
+    );
+}
+
+#[test]
+fn report_errors() {
+    assert_eq!(arguments("a --=x").unwrap_err().index, 2);
+    assert_eq!(arguments("echo \"open").unwrap_err().index, 10);
+    assert_eq!(arguments("   "), Ok(("", vec![])));
+}
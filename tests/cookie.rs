@@ -0,0 +1,152 @@
+#![cfg(feature = "std")]
+
+use pepser::{
+    datetime::{Date, DateTime, Time, UtcOffset},
+    http::cookie::{cookies, http_date, set_cookie, SameSite, SetCookie},
+};
+
+#[test]
+fn parse_cookie_pairs() {
+    assert_eq!(
+        cookies("SID=31d4d96e407aad42; lang=en-US;  empty=;quoted=\"a b\""),
+        Ok((
+            "",
+            vec![
+                ("SID".to_string(), "31d4d96e407aad42".to_string()),
+                ("lang".to_string(), "en-US".to_string()),
+                ("empty".to_string(), String::new()),
+                ("quoted".to_string(), "a b".to_string()),
+            ]
+        ))
+    );
+    assert_eq!(cookies(""), Ok(("", vec![])));
+    assert_eq!(cookies("a=1;; b=2;").unwrap().1.len(), 2);
+    assert_eq!(cookies("a=1; =2").unwrap_err().index, 5);
+    assert_eq!(cookies("a=1; b c=2").unwrap_err().index, 5);
+}
+
+#[test]
+fn parse_set_cookie() {
+    assert_eq!(
+        set_cookie(
+            "theme=\"dark\"; path=/; DOMAIN=Example.COM; max-age=-1; \
+            expires=Thu, 01 Jan 1970 00:00:00 GMT; secure; HTTPONLY; samesite=strict; Priority=High"
+        ),
+        Ok((
+            "",
+            SetCookie {
+                name: "theme".to_string(),
+                value: "dark".to_string(),
+                expires: Some(DateTime {
+                    date: Date {
+                        year: 1970,
+                        month: 1,
+                        day: 1,
+                    },
+                    time: Time {
+                        hour: 0,
+                        minute: 0,
+                        second: 0,
+                        nanosecond: 0,
+                    },
+                    offset: Some(UtcOffset { minutes: 0 }),
+                }),
+                max_age: Some(-1),
+                domain: Some("example.com".to_string()),
+                path: Some("/".to_string()),
+                secure: true,
+                http_only: true,
+                same_site: Some(SameSite::Strict),
+            }
+        ))
+    );
+
+    let (_, cookie) = set_cookie("a=b; Path=relative; Domain=; SameSite=None").unwrap();
+    assert_eq!(cookie.path, None);
+    assert_eq!(cookie.domain, None);
+    assert_eq!(cookie.same_site, Some(SameSite::None));
+}
+
+#[test]
+fn invalid_set_cookies() {
+    assert_eq!(set_cookie("").unwrap_err().index, 0);
+    assert_eq!(set_cookie("novalue; Secure").unwrap_err().index, 0);
+    assert_eq!(set_cookie("a=1; SameSite").unwrap_err().index, 5);
+    assert_eq!(set_cookie("a=1; SameSite=Loose").unwrap_err().index, 14);
+    assert_eq!(set_cookie("a=1; Max-Age=+5").unwrap_err().index, 13);
+    assert_eq!(
+        set_cookie("a=1; Expires=Wed, 21 Oct 2015 07:28:00 UTC")
+            .unwrap_err()
+            .index,
+        38
+    );
+    assert_eq!(
+        set_cookie("a=1; Expires=Wed, 21 Oct 2015 07:28:00 GMT+1")
+            .unwrap_err()
+            .index,
+        42
+    );
+}
+
+#[test]
+fn parse_http_dates() {
+    let (rest, date) = http_date("Tue, 29 Feb 2000 23:59:60 GMT; x").unwrap();
+    assert_eq!(rest, "; x");
+    assert_eq!(
+        (date.date, date.time.second),
+        (
+            Date {
+                year: 2000,
+                month: 2,
+                day: 29
+            },
+            60
+        )
+    );
+    assert_eq!(
+        http_date("mon, 01-jan-69 00:00:00 GMT")
+            .unwrap()
+            .1
+            .date
+            .year,
+        2069
+    );
+    assert_eq!(
+        http_date("Someday, 01 Jan 2000 00:00:00 GMT")
+            .unwrap_err()
+            .index,
+        0
+    );
+    assert_eq!(
+        http_date("Mon 01 Jan 2000 00:00:00 GMT").unwrap_err().index,
+        3
+    );
+    assert_eq!(
+        http_date("Mon, 01 Jun. 2000 00:00:00 GMT")
+            .unwrap_err()
+            .index,
+        11
+    );
+    assert_eq!(
+        http_date("Mon, 01 Foo 2000 00:00:00 GMT")
+            .unwrap_err()
+            .index,
+        8
+    );
+    assert_eq!(
+        http_date("Mon, 01 Jan 200 00:00:00 GMT").unwrap_err().index,
+        12
+    );
+    assert_eq!(
+        http_date("Mon, 01 Jan 2000 24:00:00 GMT")
+            .unwrap_err()
+            .index,
+        17
+    );
+    assert_eq!(
+        http_date("Mon, 29 Feb 1900 00:00:00 GMT")
+            .unwrap_err()
+            .index,
+        5
+    );
+}
@@ -0,0 +1,53 @@
+use pepser::css::{color, Rgba};
+
+fn rgba(red: u8, green: u8, blue: u8, alpha: u8) -> Rgba {
+    Rgba {
+        red,
+        green,
+        blue,
+        alpha,
+    }
+}
+
+#[test]
+fn parse_hex_colors() {
+    assert_eq!(color("#fff"), Ok(("", rgba(255, 255, 255, 255))));
+    assert_eq!(color("#0F08"), Ok(("", rgba(0, 255, 0, 136))));
+    assert_eq!(color("#1a2B3c"), Ok(("", rgba(0x1A, 0x2B, 0x3C, 255))));
+    assert_eq!(
+        color("#1a2b3c80 x"),
+        Ok((" x", rgba(0x1A, 0x2B, 0x3C, 0x80)))
+    );
+}
+
+#[test]
+fn parse_functions() {
+    assert_eq!(
+        color("RGB( 10 , 20 , 30 )"),
+        Ok(("", rgba(10, 20, 30, 255)))
+    );
+    assert_eq!(
+        color("rgb(10 20 30 / 50%)"),
+        Ok(("", rgba(10, 20, 30, 128)))
+    );
+    assert_eq!(
+        color("rgba(300, -5, 50%, 2)"),
+        Ok(("", rgba(255, 0, 128, 255)))
+    );
+    assert_eq!(color("hsl(120, 100%, 25%)"), Ok(("", rgba(0, 128, 0, 255))));
+    assert_eq!(
+        color("hsla(-120 100% 50% / 0)"),
+        Ok(("", rgba(0, 0, 255, 0)))
+    );
+    assert_eq!(color("hsl(0 0% 100%)"), Ok(("", rgba(255, 255, 255, 255))));
+}
+
+#[test]
+fn reject_invalid_colors() {
+    assert_eq!(color("red").unwrap_err().index, 0);
+    assert_eq!(color("#ggg").unwrap_err().index, 1);
+    assert_eq!(color("rgb(1, 2 3)").unwrap_err().index, 8);
+    assert_eq!(color("rgb(1 2 3").unwrap_err().index, 9);
+    assert_eq!(color("hsl(1, 2, 3%)").unwrap_err().index, 8);
+    assert_eq!(color("rgb(1, x, 3)").unwrap_err().index, 7);
+}
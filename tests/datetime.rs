@@ -0,0 +1,116 @@
+use pepser::datetime::{
+    date, datetime, duration, human_duration, rfc3339, time, Date, DateTime, IsoDuration, Time,
+    UtcOffset,
+};
+
+#[test]
+fn parse_timestamps() {
+    assert_eq!(
+        Ok((
+            "",
+            DateTime {
+                date: Date {
+                    year: 2024,
+                    month: 2,
+                    day: 29
+                },
+                time: Time {
+                    hour: 23,
+                    minute: 59,
+                    second: 60,
+                    nanosecond: 120_000_000
+                },
+                offset: Some(UtcOffset { minutes: -330 })
+            }
+        )),
+        rfc3339("2024-02-29T23:59:60.12-05:30")
+    );
+    assert_eq!(
+        datetime("1999-12-31 08:00:00Z rest").map(|(i, dt)| (i, dt.offset)),
+        Ok((" rest", Some(UtcOffset { minutes: 0 })))
+    );
+    assert_eq!(
+        datetime("2000-01-01T00:00:00").map(|(_, dt)| dt.offset),
+        Ok(None)
+    );
+    assert!(rfc3339("2000-01-01T00:00:00").is_err());
+}
+
+#[test]
+fn validate_ranges() {
+    assert!(date("2023-02-29").is_err());
+    assert!(date("2023-13-01").is_err());
+    assert!(date("23-01-01").is_err());
+    assert!(time("24:00:00").is_err());
+    assert!(time("12:60:00").is_err());
+    assert_eq!(
+        time("12:30:15,5").map(|(_, t)| t.nanosecond),
+        Ok(500_000_000)
+    );
+}
+
+#[test]
+fn parse_durations() {
+    assert_eq!(
+        duration("P1Y2M3DT4H5M6.25S"),
+        Ok((
+            "",
+            IsoDuration {
+                years: 1,
+                months: 2,
+                days: 3,
+                hours: 4,
+                minutes: 5,
+                seconds: 6,
+                nanoseconds: 250_000_000,
+                ..Default::default()
+            }
+        ))
+    );
+    assert_eq!(
+        duration("P2W"),
+        Ok((
+            "",
+            IsoDuration {
+                weeks: 2,
+                ..Default::default()
+            }
+        ))
+    );
+    assert!(duration("P").is_err());
+    assert!(duration("P1DT").is_err());
+}
+
+#[test]
+fn parse_human_durations() {
+    use std::time::Duration;
+
+    assert_eq!(
+        human_duration("1w 2d 3 hours 4min 5s 6ms 7us 8ns"),
+        Ok((
+            "",
+            Duration::new(604_800 + 2 * 86_400 + 3 * 3_600 + 4 * 60 + 5, 6_007_008)
+        ))
+    );
+    assert_eq!(human_duration("0.25h"), Ok(("", Duration::from_secs(900))));
+    assert_eq!(
+        human_duration("1.000000001 s"),
+        Ok(("", Duration::new(1, 1)))
+    );
+    assert_eq!(
+        human_duration("90s later"),
+        Ok((" later", Duration::from_secs(90)))
+    );
+    assert_eq!(
+        human_duration("1m2"),
+        Err(human_duration("1m2").unwrap_err())
+    );
+    assert_eq!(human_duration("1m2").unwrap_err().index, 3);
+    assert_eq!(human_duration("5 mo").unwrap_err().index, 2);
+    assert_eq!(human_duration("h").unwrap_err().index, 0);
+
+    let error = human_duration("1s 18446744073709551615s").unwrap_err();
+    assert_eq!((error.index, &*error.reason), (3, "duration overflow"));
+    let error = human_duration("99999999999999999999ns").unwrap_err();
+    assert_eq!(error.reason, "duration overflow");
+}
@@ -0,0 +1,76 @@
+use pepser::dns::{message, Question, RecordData};
+
+/// A response for `www.example.com`, whose records refer to the question name with pointers
+fn response() -> Vec<u8> {
+    let mut packet = vec![
+        0xAB, 0xCD, 0x85, 0x80, 0, 1, 0, 3, 0, 0, 0, 1, // header
+        3, b'w', b'w', b'w', 7, b'e', b'x', b'a', b'm', b'p', b'l', b'e', 3, b'c', b'o', b'm', 0,
+        0, 5, 0, 1, // question: www.example.com CNAME IN
+        0xC0, 12, 0, 5, 0, 1, 0, 0, 0, 60, 0, 6, 3, b'w', b'e', b'b', 0xC0, 16, // CNAME
+        0xC0, 16, 0, 15, 0, 1, 0, 0, 1, 0, 0, 4, 0, 10, 0xC0, 45, // MX 10 web.example.com
+    ];
+    packet.extend([
+        0xC0, 16, 0, 16, 0, 1, 0, 0, 0, 0, 0, 6, 2, b'h', b'i', 2, b'y', b'o',
+    ]);
+    packet.extend([0, 0, 41, 0x10, 0, 0, 0, 0, 0, 0, 0]); // OPT record to the root
+    packet
+}
+
+#[test]
+fn parse_compressed_response() {
+    let packet = response();
+    let (remainder, parsed) = message(&packet).unwrap();
+    assert!(remainder.is_empty());
+    assert_eq!(parsed.header.id, 0xABCD);
+    assert!(parsed.header.response && parsed.header.authoritative);
+    assert!(parsed.header.recursion_desired && parsed.header.recursion_available);
+    assert_eq!(
+        parsed.questions,
+        vec![Question {
+            name: "www.example.com".to_string(),
+            qtype: 5,
+            qclass: 1
+        }]
+    );
+    let data: Vec<_> = parsed.answers.iter().map(|record| &record.data).collect();
+    assert_eq!(
+        data,
+        vec![
+            &RecordData::Cname("web.example.com".to_string()),
+            &RecordData::Mx {
+                preference: 10,
+                exchange: "web.example.com".to_string()
+            },
+            &RecordData::Txt(vec![b"hi", b"yo"]),
+        ]
+    );
+    assert_eq!(parsed.answers[1].name, "example.com");
+    assert_eq!(parsed.answers[1].ttl, 256);
+    assert_eq!(parsed.additionals[0].name, "");
+    assert_eq!(parsed.additionals[0].data, RecordData::Other(&[]));
+}
+
+#[test]
+fn reject_pointer_loops() {
+    let mut packet = vec![0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0];
+    packet.extend([1, b'a', 0xC0, 12, 0, 1, 0, 1]);
+    let error = message(&packet).unwrap_err();
+    assert_eq!(
+        (error.index, error.reason.as_ref()),
+        (14, "invalid compression pointer")
+    );
+
+    packet[15] = 20;
+    assert_eq!(
+        message(&packet).unwrap_err().reason,
+        "invalid compression pointer"
+    );
+}
+
+#[test]
+fn report_truncated_messages() {
+    let packet = response();
+    let error = message(&packet[..40]).unwrap_err();
+    assert_eq!(error.index, 40);
+    assert!(message(&packet[..10]).is_err());
+}
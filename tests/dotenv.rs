@@ -0,0 +1,90 @@
+use pepser::{
+    dotenv::{dotenv, dotenv_with, Variable},
+    span::{Span, Spanned},
+};
+
+#[test]
+fn parse_values() {
+    let input = "A=plain value  # comment\n\
+        B=no#comment\n\
+        export  C = 'single $A \\n' \n\
+        D=\"tab\\there \\\"quoted\\\" \\$A\"\r\n\
+        E=\"multi\nline\"\n\
+        F=\n\
+        G= # only a comment\n\
+        exported=1\n";
+    let (remainder, env) = dotenv(input).unwrap();
+    assert_eq!(remainder, "");
+    assert_eq!(
+        env.iter().collect::<Vec<_>>(),
+        [
+            ("A", "plain value"),
+            ("B", "no#comment"),
+            ("C", "single $A \\n"),
+            ("D", "tab\there \"quoted\" $A"),
+            ("E", "multi\nline"),
+            ("F", ""),
+            ("G", ""),
+            ("exported", "1"),
+        ]
+    );
+
+    assert_eq!(
+        env.variable("C"),
+        Some(&Variable {
+            key: Spanned {
+                span: Span { start: 46, end: 47 },
+                value: "C",
+            },
+            value: Spanned {
+                span: Span { start: 50, end: 64 },
+                value: "single $A \\n".to_string(),
+            },
+        })
+    );
+    let empty = env.variable("G").unwrap().value.span;
+    assert_eq!(empty.start, empty.end);
+}
+
+#[test]
+fn replace_references() {
+    let (_, env) = dotenv(
+        "USER=me\nUSER_HOME=/home/$USER\nDIR=${USER_HOME}/src\n\
+        LEVEL=${LOG_LEVEL:-info}\nEMPTY=\nFALLBACK=${EMPTY:-set}\n\
+        MISSING=[$NOPE]\nPRICE=$5 and $\nUSER=you\nAGAIN=$USER\n",
+    )
+    .unwrap();
+    assert_eq!(env.get("USER_HOME"), Some("/home/me"));
+    assert_eq!(env.get("DIR"), Some("/home/me/src"));
+    assert_eq!(env.get("LEVEL"), Some("info"));
+    assert_eq!(env.get("FALLBACK"), Some("set"));
+    assert_eq!(env.get("MISSING"), Some("[]"));
+    assert_eq!(env.get("PRICE"), Some("$5 and $"));
+    // Setting a key again keeps its place and changes later references
+    assert_eq!(env.variables[0].key.value, "USER");
+    assert_eq!(env.get("USER"), Some("you"));
+    assert_eq!(env.get("AGAIN"), Some("you"));
+
+    let mut looked_up = Vec::new();
+    let (_, env) = dotenv_with("A=$B-${C:-c}", |key| {
+        looked_up.push(key.to_string());
+        (key == "B").then(|| "b".to_string())
+    })
+    .unwrap();
+    assert_eq!(env.get("A"), Some("b-c"));
+    assert_eq!(looked_up, ["B", "C"]);
+}
+
+#[test]
+fn invalid_lines() {
+    assert_eq!(dotenv("A=1\n1A=2").unwrap_err().index, 4);
+    assert_eq!(dotenv("A=1\nexport\n").unwrap_err().index, 10);
+    assert_eq!(dotenv("KEY value").unwrap_err().index, 4);
+    assert_eq!(dotenv("A='open\nB=2").unwrap_err().index, 2);
+    assert_eq!(dotenv("A=\"open\\\"").unwrap_err().index, 2);
+    assert_eq!(dotenv("A=\"\\q\"").unwrap_err().index, 3);
+    assert_eq!(dotenv("A=\"x\" y").unwrap_err().index, 6);
+    assert_eq!(dotenv("A=${B\nC=1").unwrap_err().index, 2);
+    assert_eq!(dotenv("A=${B C}").unwrap_err().index, 2);
+    assert_eq!(dotenv("A=${}").unwrap_err().index, 2);
+}
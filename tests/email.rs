@@ -0,0 +1,38 @@
+use std::net::Ipv4Addr;
+
+use pepser::email::{dot_atom, email, Domain, LocalPart};
+
+#[test]
+fn parse_components() {
+    let (remainder, address) = email("first.last+tag@mail.example.org").unwrap();
+    assert_eq!(remainder, "");
+    assert_eq!(address.local, LocalPart::DotAtom("first.last+tag"));
+    assert_eq!(address.domain, Domain::Name("mail.example.org"));
+
+    let (_, address) = email("user@[192.0.2.1]").unwrap();
+    assert_eq!(address.domain, Domain::Ipv4(Ipv4Addr::new(192, 0, 2, 1)));
+    let (_, address) = email("user@[example:tag]").unwrap();
+    assert_eq!(address.domain, Domain::Literal("example:tag"));
+
+    assert_eq!(dot_atom("a.b.c d"), Ok((" d", "a.b.c")));
+}
+
+#[test]
+fn unescape_quoted_local_parts() {
+    let (_, address) = email(r#""a \"b\" \\ c"@example.com"#).unwrap();
+    assert_eq!(address.local, LocalPart::Quoted(r#"a "b" \ c"#.to_string()));
+    let (_, address) = email(r#""@"@example.com"#).unwrap();
+    assert_eq!(address.local, LocalPart::Quoted("@".to_string()));
+}
+
+#[test]
+fn reject_invalid_addresses() {
+    assert_eq!(email(".a@example.com").unwrap_err().index, 0);
+    assert_eq!(email("a.@example.com").unwrap_err().index, 2);
+    assert_eq!(email("a@").unwrap_err().index, 2);
+    assert_eq!(email("example.com").unwrap_err().index, 11);
+    assert_eq!(email("\"a@example.com").unwrap_err().index, 14);
+    assert_eq!(email("\"a\u{e9}\"@example.com").unwrap_err().index, 2);
+    assert_eq!(email("a@[1.2.3.4").unwrap_err().index, 10);
+    assert_eq!(email("a@[IPv6:1:2]").unwrap_err().index, 8);
+}
@@ -0,0 +1,45 @@
+use pepser::{
+    encoding::{base64, base64url, hex, Padding},
+    impls::sequence,
+    traits::{wrapped, Parser},
+};
+
+#[test]
+fn decode_base64_variants() {
+    let text = b"any carnal pleasure.".to_vec();
+    assert_eq!(
+        base64(Padding::Required).parse("YW55IGNhcm5hbCBwbGVhc3VyZS4="),
+        Ok(("", text.clone()))
+    );
+    assert_eq!(
+        base64(Padding::Forbidden).parse("YW55IGNhcm5hbCBwbGVhc3VyZS4="),
+        Ok(("=", text))
+    );
+    assert_eq!(base64(Padding::Required).parse(""), Ok(("", vec![])));
+    assert_eq!(
+        base64url(Padding::Optional).parse("-_8"),
+        Ok(("", vec![0xFB, 0xFF]))
+    );
+    assert_eq!(base64(Padding::Optional).parse("-_8"), Ok(("-_8", vec![])));
+}
+
+#[test]
+fn reject_invalid_base64() {
+    let error = base64(Padding::Optional).parse("aGk==").unwrap_err();
+    assert_eq!(
+        (error.index, error.reason.as_ref()),
+        (3, "invalid base64 padding")
+    );
+    let error = base64(Padding::Optional).parse("aGVsb").unwrap_err();
+    assert_eq!(
+        (error.index, error.reason.as_ref()),
+        (4, "truncated base64 group")
+    );
+}
+
+#[test]
+fn embed_in_grammars() {
+    let mut payload = wrapped(sequence("\""), hex, sequence("\""));
+    assert_eq!(payload.parse("\"CAFE\","), Ok((",", vec![0xCA, 0xFE])));
+    assert!(payload.parse("\"CAF\"").is_err());
+}
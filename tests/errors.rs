@@ -0,0 +1,195 @@
+use std::error::Error;
+
+use pepser::{
+    errors::ErrorSnippet,
+    impls::{eof, sequence, take_until, take_while1},
+    traits::Parser,
+};
+
+#[test]
+fn display_error() {
+    let error = sequence("abc").parse("abd").unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        format!(
+            "{} at index {} (expected \"abc\")",
+            error.reason, error.index
+        )
+    );
+    assert_eq!(
+        eof().parse("x").unwrap_err().source.to_string(),
+        "expected end of input"
+    );
+}
+
+#[test]
+fn propagate_as_dyn_error() {
+    fn keyword() -> Result<&'static str, Box<dyn Error>> {
+        let (_, parsed) = sequence("let").parse("fn")?;
+        Ok(parsed)
+    }
+    assert!(keyword()
+        .unwrap_err()
+        .to_string()
+        .contains("expected \"let\""));
+}
+
+#[test]
+fn structured_sequence_error() {
+    let error = sequence("héllo").parse("hélp").unwrap_err();
+    assert_eq!(error.index, 4);
+    assert_eq!(error.reason, "could not parse sequence");
+}
+
+#[test]
+fn render_diagnostics() {
+    use pepser::errors::line_column;
+
+    let input = "[1,\n  2,\n  ✓]";
+    assert_eq!(line_column(input, 0), (1, 1));
+    assert_eq!(line_column(input, 4), (2, 1));
+    assert_eq!(line_column(input, 12), (3, 3));
+    assert_eq!(line_column(input, 13), (3, 3));
+    assert_eq!(line_column(input, 100), (3, 5));
+
+    let error = eof().parse("x").unwrap_err();
+    assert_eq!(
+        error.render("x"),
+        "error: input is not empty\n --> 1:1\n  |\n1 | x\n  | ^ expected end of input\n"
+    );
+}
+
+#[test]
+fn custom_error_source() {
+    use pepser::{
+        errors::{ErrorSource, ParserError},
+        impls::{any_char, fixed_digits},
+        traits::ParseResult,
+    };
+
+    fn vowel(input: &str) -> ParseResult<&str, char> {
+        let (remainder, c) = any_char().parse(input)?;
+        if !"aeiou".contains(c) {
+            return Err(ParserError::custom(
+                0,
+                format!("vowel {:?}", "aeiou"),
+                "not a vowel",
+            ));
+        }
+        Ok((remainder, c))
+    }
+    let error = vowel("x").unwrap_err();
+    assert_eq!(error.source, ErrorSource::Custom("vowel \"aeiou\"".into()));
+    assert_eq!(
+        error.to_string(),
+        "not a vowel at index 0 (vowel \"aeiou\")"
+    );
+    assert_eq!(
+        error.map_input(str::as_bytes).source,
+        ErrorSource::Custom("vowel \"aeiou\"".into())
+    );
+
+    assert_eq!(vowel("").unwrap_err().source, ErrorSource::Char);
+    assert_eq!(
+        fixed_digits(2).parse("1a").unwrap_err().source,
+        ErrorSource::Digits
+    );
+}
+
+#[test]
+fn nested_error_offsets() {
+    use pepser::{
+        impls::{fixed_digits, take_while1, ws},
+        traits::{discard, parse_if, wrapped},
+    };
+
+    let digits = || take_while1(|c| c.is_ascii_digit());
+    let mut assignment = discard(sequence("let"), ws())
+        .with(take_while1(char::is_alphabetic))
+        .and(discard(ws().and(sequence("=")).and(ws()), digits()))
+        .skip(sequence(";"));
+    assert_eq!(assignment.parse("x").unwrap_err().index, 0);
+    assert_eq!(assignment.parse("let 1").unwrap_err().index, 4);
+    assert_eq!(assignment.parse("let x = y").unwrap_err().index, 8);
+    assert_eq!(assignment.parse("let x = 12").unwrap_err().index, 10);
+
+    let mut list = wrapped(
+        sequence("["),
+        wrapped(ws(), digits().and(sequence(",").with(digits())), ws()),
+        sequence("]"),
+    );
+    assert_eq!(list.parse("[ 1,x ]").unwrap_err().index, 4);
+    assert_eq!(list.parse("[ 1,2 )").unwrap_err().index, 6);
+
+    let mut date = fixed_digits(2).skip(sequence("/")).count_exact::<3>();
+    assert_eq!(date.parse("01/02/x3/").unwrap_err().index, 6);
+
+    let mut signed = parse_if(sequence("-"), digits());
+    assert_eq!(signed.parse("-x").unwrap_err().index, 1);
+}
+
+#[test]
+fn committed_errors_stop_backtracking() {
+    use pepser::{
+        impls::take_while1,
+        traits::{chainl1, discard, opt, sep_by},
+    };
+
+    let digits = || take_while1(|c| c.is_ascii_digit());
+    // once `(` is seen the group must be closed
+    let group = || discard(sequence("("), digits().skip(sequence(")")).cut());
+
+    let error = group().many0().parse("(1)(2(3)").unwrap_err();
+    assert_eq!((error.index, error.committed), (5, true));
+    assert_eq!(group().many0().parse("(1)x"), Ok(("x", vec!["1"])));
+
+    assert_eq!(opt(group()).parse("(1").unwrap_err().index, 2);
+    assert_eq!(
+        sep_by(group(), sequence(","))
+            .parse("(1),(2")
+            .unwrap_err()
+            .index,
+        6
+    );
+    assert_eq!(group().or(digits()).parse("(x").unwrap_err().index, 1);
+
+    let mut sum = chainl1(
+        digits().map(|digits| digits.len()),
+        sequence("+").map(|_| |a, b| a + b),
+    );
+    assert_eq!(sum.parse("1+22+x"), Ok(("+x", 3)));
+    let mut strict = chainl1(
+        digits().map(|digits| digits.len()).cut(),
+        sequence("+").map(|_| |a, b| a + b),
+    );
+    assert_eq!(strict.parse("1+22+x").unwrap_err().index, 5);
+}
+
+#[test]
+fn snippets_keep_char_boundaries() {
+    let input = "é日本語 and more text";
+    // `sequence` reports byte offsets, the snippet starts at the character there
+    let error = sequence("é日x").parse(input).unwrap_err();
+    assert_eq!(error.index, 5);
+    assert_eq!(error.snippet(input).to_string(), "\"本語 and mor...\"");
+
+    let error = take_while1(|c: char| c.is_ascii())
+        .parse(input)
+        .unwrap_err();
+    assert_eq!(error.snippet(input).text, "é日本語 and m");
+    let error = take_until("!").parse(input).unwrap_err();
+    assert!(error.snippet(input).truncated);
+
+    for index in 0..=input.len() + 1 {
+        for length in 0..4 {
+            let snippet = ErrorSnippet::new(input, index, length);
+            assert!(snippet.text.chars().count() <= length);
+            assert!(input.contains(snippet.text));
+        }
+    }
+    assert_eq!(
+        ErrorSnippet::new("a\n\"b\"", 0, 10).to_string(),
+        "\"a\\n\\\"b\\\"\""
+    );
+    assert_eq!(ErrorSnippet::new("", 3, 10).to_string(), "end of input");
+}
@@ -0,0 +1,164 @@
+use pepser::git::{config, gitignore, ignore_pattern, Entry, Include, Pattern, Segment, Token};
+
+#[test]
+fn parse_config() {
+    let input = "# global settings\n\
+        [user]\n\
+        \tname = \"Jane  Doe\"   ; trailing comment\n\
+        \temail=jane@example.com\n\
+        [Core] autocrlf = input\r\n\
+        \tignoreCase\n\
+        [branch \"Feature \\\"x\\\"\"]\n\
+        \tremote = origin\n\
+        [Branch.Legacy]\n\
+        \tmerge = refs/heads/main\n\
+        [user]\n\
+        \tname = Other\n\
+        \tmessage = a\\tb \"#not a comment\" \\\n\
+        \t  end\n";
+    let (remainder, parsed) = config(input).unwrap();
+    assert_eq!(remainder, "");
+    assert_eq!(parsed.entries.len(), 8);
+    assert_eq!(
+        parsed.entries[0],
+        Entry {
+            section: "user".to_string(),
+            subsection: None,
+            key: "name".to_string(),
+            value: Some("Jane  Doe".to_string()),
+        }
+    );
+    assert_eq!(
+        parsed.get("user.name").unwrap().value.as_deref(),
+        Some("Other")
+    );
+    let names: Vec<_> = parsed
+        .get_all("USER.NAME")
+        .map(|entry| entry.value.as_deref().unwrap())
+        .collect();
+    assert_eq!(names, ["Jane  Doe", "Other"]);
+    assert_eq!(
+        parsed.get("core.autocrlf").unwrap().value.as_deref(),
+        Some("input")
+    );
+    assert_eq!(parsed.get("core.ignorecase").unwrap().value, None);
+    assert_eq!(parsed.get("core.ignorecase").unwrap().boolean(), Some(true));
+
+    // Quoted subsections keep their case, deprecated ones are lowercased
+    assert!(parsed.get("branch.Feature \"x\".remote").is_some());
+    assert!(parsed.get("branch.feature \"x\".remote").is_none());
+    assert!(parsed.get("branch.legacy.merge").is_some());
+    assert_eq!(
+        parsed.get("user.message").unwrap().value.as_deref(),
+        Some("a\tb #not a comment \t  end")
+    );
+}
+
+#[test]
+fn read_config_values() {
+    let (_, parsed) = config(
+        "[include]\n\tpath = ~/.gitconfig.local\n\
+        [includeIf \"gitdir:~/work/\"]\n\tpath = work.inc\n\
+        [flags]\n\ta = yes\n\tb = Off\n\tc =\n\td = maybe\n",
+    )
+    .unwrap();
+    let includes: Vec<_> = parsed.includes().collect();
+    assert_eq!(
+        includes,
+        [
+            Include {
+                path: "~/.gitconfig.local",
+                condition: None,
+            },
+            Include {
+                path: "work.inc",
+                condition: Some("gitdir:~/work/"),
+            },
+        ]
+    );
+    let flags: Vec<_> = ["a", "b", "c", "d"]
+        .map(|key| parsed.get(&format!("flags.{key}")).unwrap().boolean())
+        .to_vec();
+    assert_eq!(flags, [Some(true), Some(false), Some(false), None]);
+}
+
+#[test]
+fn invalid_configs() {
+    assert_eq!(config("key = value\n").unwrap_err().index, 0);
+    assert_eq!(config("[]\n").unwrap_err().index, 1);
+    assert_eq!(config("[a \"b\n\"]").unwrap_err().index, 3);
+    assert_eq!(config("[a \"b\" ]").unwrap_err().index, 6);
+    assert_eq!(config("[a]\n1key = v").unwrap_err().index, 4);
+    assert_eq!(config("[a]\nkey value").unwrap_err().index, 8);
+    assert_eq!(config("[a]\nkey = \"open\nx").unwrap_err().index, 10);
+    assert_eq!(config("[a]\nkey = bad\\qescape").unwrap_err().index, 13);
+}
+
+#[test]
+fn parse_ignore_patterns() {
+    assert_eq!(
+        ignore_pattern("!*.tmp  "),
+        Some(Pattern {
+            negated: true,
+            directory_only: false,
+            anchored: false,
+            segments: vec![Segment::Glob(vec![
+                Token::AnyChars,
+                Token::Literal(".tmp".to_string())
+            ])],
+        })
+    );
+    let pattern = ignore_pattern("cache/**/[!a-c]?\\ ").unwrap();
+    assert!(pattern.anchored && !pattern.directory_only);
+    assert_eq!(
+        pattern.segments,
+        [
+            Segment::Glob(vec![Token::Literal("cache".to_string())]),
+            Segment::AnyDirectories,
+            Segment::Glob(vec![
+                Token::Class {
+                    negated: true,
+                    ranges: vec![('a', 'c')],
+                },
+                Token::AnyChar,
+                Token::Literal(" ".to_string()),
+            ]),
+        ]
+    );
+    assert_eq!(ignore_pattern(""), None);
+    assert_eq!(ignore_pattern("   "), None);
+    assert!(ignore_pattern("build/").unwrap().directory_only);
+    assert!(!ignore_pattern("build/").unwrap().anchored);
+    // A `[` without its `]` is literal
+    assert!(ignore_pattern("a[b").unwrap().matches("a[b", false));
+}
+
+#[test]
+fn match_paths() {
+    let pattern = ignore_pattern("**/logs").unwrap();
+    assert!(pattern.matches("logs", true));
+    assert!(pattern.matches("a/b/logs", false));
+    let pattern = ignore_pattern("logs/**").unwrap();
+    assert!(!pattern.matches("logs", true));
+    assert!(pattern.matches("logs/a/b", false));
+    let pattern = ignore_pattern("a/**/b").unwrap();
+    assert!(pattern.matches("a/b", false));
+    assert!(pattern.matches("a/x/y/b", false));
+    assert!(!pattern.matches("x/a/b", false));
+    let pattern = ignore_pattern("[]a-]*.[ch]").unwrap();
+    assert!(pattern.matches("]x.c", false));
+    assert!(pattern.matches("-.h", false));
+    assert!(!pattern.matches("b.c", false));
+    assert!(!ignore_pattern("*.c").unwrap().matches("a/b", false));
+
+    let ignore = gitignore("node_modules/\n/dist\n*.log\n!important.log\nsecret/\n!secret/keep\n");
+    assert!(ignore.is_ignored("node_modules", true));
+    assert!(!ignore.is_ignored("node_modules", false));
+    assert!(ignore.is_ignored("web/node_modules/lib/index.js", false));
+    assert!(ignore.is_ignored("dist/app.js", false));
+    assert!(!ignore.is_ignored("web/dist/app.js", false));
+    assert!(ignore.is_ignored("debug.log", false));
+    assert!(!ignore.is_ignored("logs/important.log", false));
+    // What an ignored directory holds cannot be included back
+    assert!(ignore.is_ignored("secret/keep", false));
+}
@@ -0,0 +1,170 @@
+use std::cell::Cell;
+
+use pepser::{
+    errors::ErrorSource,
+    grammar::Grammar,
+    impls::{sequence, take_while1},
+    traits::{discard, sep_by, wrapped, Parser},
+};
+
+fn arithmetic<'a>() -> Grammar<'a, &'a str, i64> {
+    let grammar = Grammar::new();
+    let g = grammar.clone();
+    grammar.rule("sum", move || {
+        sep_by(g.call("product"), sequence("+")).map(|terms| terms.into_iter().sum())
+    });
+    let g = grammar.clone();
+    grammar.rule("product", move || {
+        sep_by(g.call("atom"), sequence("*")).map(|factors| factors.into_iter().product())
+    });
+    let g = grammar.clone();
+    grammar.rule("atom", move || {
+        take_while1(|c| c.is_ascii_digit())
+            .map(|digits: &str| digits.parse().unwrap())
+            .or(wrapped(sequence("("), g.call("sum"), sequence(")")))
+    });
+    grammar
+}
+
+#[test]
+fn mutually_recursive_rules() {
+    let grammar = arithmetic();
+    assert_eq!(grammar.parse("sum", "2*(3+4)+1"), Ok(("", 15)));
+    assert_eq!(grammar.parse("atom", "(1+(2*3))*2"), Ok(("*2", 7)));
+    assert_eq!(grammar.rules(), vec!["sum", "product", "atom"]);
+    assert_eq!(grammar.references("atom"), vec!["sum"]);
+    assert_eq!(grammar.reachable("product"), vec!["product", "atom", "sum"]);
+}
+
+#[test]
+fn undefined_rules() {
+    let grammar = Grammar::new();
+    let g = grammar.clone();
+    grammar.rule("document", move || discard(sequence("!"), g.call("value")));
+
+    assert_eq!(grammar.undefined(), vec!["value"]);
+    let error = grammar.parse("document", "!null").unwrap_err();
+    assert_eq!(error.source, ErrorSource::Rule);
+    assert_eq!(error.reason, "undefined rule value");
+
+    grammar.rule("value", || sequence("null"));
+    assert!(grammar.undefined().is_empty());
+    assert_eq!(grammar.parse("document", "!null"), Ok(("", "null")));
+}
+
+/// Every alternative of `expr` parses the same `term` before failing on its operator, and
+/// `term` nests `expr`, so without memoization the work triples with each parenthesis
+fn backtracking(calls: &Cell<usize>) -> Grammar<'_, &str, i64> {
+    let grammar = Grammar::new();
+    let g = grammar.clone();
+    grammar.rule("expr", move || {
+        g.call("term")
+            .skip(sequence("+"))
+            .and(g.call("expr"))
+            .map(|(a, b)| a + b)
+            .or(g
+                .call("term")
+                .skip(sequence("-"))
+                .and(g.call("expr"))
+                .map(|(a, b)| a - b))
+            .or(g.call("term"))
+    });
+    let g = grammar.clone();
+    grammar.rule("term", move || {
+        take_while1(|c| c.is_ascii_digit())
+            .map(|digits: &str| digits.parse().unwrap())
+            .or(wrapped(sequence("("), g.call("expr"), sequence(")")))
+            .inspect_input(|_| calls.set(calls.get() + 1))
+    });
+    grammar
+}
+
+/// Runs `parser` on the first byte of the input only
+fn first_char<'a, P>(mut parser: P) -> impl Parser<&'a str, Output = i64>
+where
+    P: Parser<&'a str, Output = i64>,
+{
+    move |input: &'a str| parser.parse(&input[..1])
+}
+
+#[test]
+fn memoized_rules() {
+    let input = format!("{}1{}", "(".repeat(8), ")".repeat(8));
+    let calls = Cell::new(0);
+    let grammar = backtracking(&calls);
+    assert_eq!(grammar.parse("expr", &input), Ok(("", 1)));
+    let unmemoized = calls.replace(0);
+
+    grammar.memoize();
+    assert_eq!(grammar.parse("expr", &input), Ok(("", 1)));
+    assert!(unmemoized > 3usize.pow(8));
+    assert!(calls.get() < 3 * input.len());
+    assert_eq!(
+        grammar.parse("expr", "(1+2)-(3"),
+        backtracking(&Cell::new(0)).parse("expr", "(1+2)-(3")
+    );
+}
+
+#[test]
+fn memoized_results_last_one_parse() {
+    let calls = Cell::new(0);
+    let grammar = backtracking(&calls);
+    grammar.memoize();
+    // The results are dropped once the outermost call returns
+    assert_eq!(grammar.parse("expr", "(1+2)-3"), Ok(("", 0)));
+    let first = calls.replace(0);
+    assert_eq!(grammar.parse("expr", "(1+2)-3"), Ok(("", 0)));
+    assert_eq!(calls.get(), first);
+
+    // A rule run on a prefix of its input does not reuse the results at the same address
+    let calls = Cell::new(0);
+    let grammar = backtracking(&calls);
+    grammar.memoize();
+    let (g, prefix) = (grammar.clone(), grammar.clone());
+    grammar.rule("digit", move || {
+        g.call("term")
+            .skip(sequence("!"))
+            .or(first_char(prefix.call("term")))
+    });
+    assert_eq!(grammar.parse("digit", "12"), Ok(("", 1)));
+}
+
+#[test]
+fn left_recursive_rules() {
+    let grammar = Grammar::new();
+    let g = grammar.clone();
+    grammar.rule("expr", move || {
+        g.call("expr")
+            .skip(sequence("-"))
+            .and(g.call("number"))
+            .map(|(a, b)| a - b)
+            .or(g
+                .call("expr")
+                .skip(sequence("+"))
+                .and(g.call("number"))
+                .map(|(a, b)| a + b))
+            .or(g.call("number"))
+    });
+    grammar.rule("number", || {
+        take_while1(|c| c.is_ascii_digit()).map(|digits: &str| digits.parse::<i64>().unwrap())
+    });
+    grammar.memoize();
+
+    // Subtraction is left associative, as written
+    assert_eq!(grammar.parse("expr", "10-2-3"), Ok(("", 5)));
+    assert_eq!(grammar.parse("expr", "1+2-3+4"), Ok(("", 4)));
+    assert_eq!(grammar.parse("expr", "7"), Ok(("", 7)));
+    assert_eq!(grammar.parse("expr", "7-"), Ok(("-", 7)));
+    assert!(grammar.parse("expr", "-7").is_err());
+
+    // Left recursion through another rule stops at the seed rather than looping
+    let indirect = Grammar::new();
+    let (a, b) = (indirect.clone(), indirect.clone());
+    indirect
+        .rule("a", move || {
+            a.call("b").skip(sequence("x")).or(sequence("y"))
+        })
+        .rule("b", move || b.call("a").skip(sequence("z")));
+    indirect.memoize();
+    assert_eq!(indirect.parse("a", "yzx"), Ok(("zx", "y")));
+}
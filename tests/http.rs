@@ -0,0 +1,77 @@
+#![cfg(feature = "std")]
+
+use pepser::http::{
+    chunk_size, http_request, http_response, parse_bytes, Headers, HttpVersion, Request,
+};
+
+#[test]
+fn parse_request() {
+    assert_eq!(
+        Ok((
+            "{\"id\": 1}",
+            Request {
+                method: "POST".to_string(),
+                target: "/api/items?sort=asc".to_string(),
+                version: HttpVersion { major: 1, minor: 1 },
+                headers: Headers(vec![
+                    ("Host".to_string(), "example.com".to_string()),
+                    ("Content-Type".to_string(), "application/json".to_string()),
+                    ("X-Folded".to_string(), "first second".to_string()),
+                ])
+            }
+        )),
+        http_request(
+            "POST /api/items?sort=asc HTTP/1.1\r\n\
+             Host: example.com\r\n\
+             Content-Type:application/json  \r\n\
+             X-Folded: first\r\n\
+             \tsecond\r\n\
+             \r\n\
+             {\"id\": 1}"
+        )
+    );
+}
+
+#[test]
+fn parse_response() {
+    let (body, response) = http_response(
+        "HTTP/1.0 404 Not Found\nset-cookie: a=1\nSet-Cookie: b=2\nContent-Length: 0\n\n",
+    )
+    .unwrap();
+    assert_eq!(body, "");
+    assert_eq!(response.status, 404);
+    assert_eq!(response.reason, "Not Found");
+    assert_eq!(response.headers.get("content-length"), Some("0"));
+    assert_eq!(
+        response.headers.get_all("SET-COOKIE").collect::<Vec<_>>(),
+        vec!["a=1", "b=2"]
+    );
+    assert!(http_response("HTTP/1.1 20 OK\r\n\r\n").is_err());
+}
+
+#[test]
+fn parse_chunk_sizes() {
+    assert_eq!(chunk_size("1A\r\ndata"), Ok(("data", 26)));
+    assert_eq!(chunk_size("0;name=value\r\n"), Ok(("", 0)));
+    assert!(chunk_size("zz\r\n").is_err());
+    assert!(chunk_size("FFFFFFFFFFFFFFFFFFFF\r\n").is_err());
+}
+
+#[test]
+fn parse_from_bytes() {
+    let input: &[u8] = b"GET / HTTP/1.1\r\nHost: a\r\n\r\n\xff\xfe";
+    let (body, request) = parse_bytes(http_request, input).unwrap();
+    assert_eq!(body, b"\xff\xfe");
+    assert_eq!(request.headers.get("host"), Some("a"));
+}
+
+#[test]
+fn report_error_indexes() {
+    let request = |input| http_request(input).unwrap_err().index;
+    assert_eq!(request("GET /index.html HTTP/x.1\r\n\r\n"), 21);
+    assert_eq!(request("GET / HTTP/1.1\r\nHost: a\r\nBad line\r\n\r\n"), 28);
+    let response = |input| http_response(input).unwrap_err().index;
+    assert_eq!(response("HTTP/1.1 200 OK\r!\n\r\n"), 15);
+    assert_eq!(response("HTTP/1.1 2x0 OK\r\n\r\n"), 10);
+    assert_eq!(chunk_size("1A;ext\r!").unwrap_err().index, 6);
+}
@@ -0,0 +1,120 @@
+use pepser::icalendar::{component, components, content_line, Parameter, Property};
+
+#[test]
+fn unfold_content_lines() {
+    let (remainder, property) =
+        content_line("DESCRIPTION:This is a lo\r\n ng description\n\t that exists\r\nEND").unwrap();
+    assert_eq!(property.value, "This is a long description that exists");
+    assert_eq!(remainder, "END");
+
+    let (remainder, property) = content_line("X-EMPTY:").unwrap();
+    assert_eq!(
+        (property.name.as_str(), property.value.as_str()),
+        ("X-EMPTY", "")
+    );
+    assert_eq!(remainder, "");
+
+    // Indices are offsets in the folded input
+    assert_eq!(content_line("SUMMARY\r\n  oops").unwrap_err().index, 10);
+    assert_eq!(content_line("SUMMARY;\r\n\t=x:y").unwrap_err().index, 11);
+    assert_eq!(content_line("NOTE:a\u{7}b").unwrap_err().index, 6);
+    assert_eq!(content_line(":value").unwrap_err().index, 0);
+}
+
+#[test]
+fn parse_parameters() {
+    let (_, property) =
+        content_line("DTSTART;TZID=Europe/Paris;X-LIST=a,\"b;c:d\",:20240101T090000").unwrap();
+    assert_eq!(
+        property,
+        Property {
+            name: "DTSTART".to_string(),
+            parameters: vec![
+                Parameter {
+                    name: "TZID".to_string(),
+                    values: vec!["Europe/Paris".to_string()],
+                },
+                Parameter {
+                    name: "X-LIST".to_string(),
+                    values: vec!["a".to_string(), "b;c:d".to_string(), String::new()],
+                },
+            ],
+            value: "20240101T090000".to_string(),
+        }
+    );
+    assert_eq!(property.parameter("tzid"), Some("Europe/Paris"));
+    assert_eq!(property.parameter("VALUE"), None);
+
+    assert_eq!(content_line("A;B:c").unwrap_err().index, 3);
+    assert_eq!(content_line("A;B=\"c:d").unwrap_err().index, 4);
+    assert_eq!(content_line("A;B=c\"d\":e").unwrap_err().index, 5);
+}
+
+#[test]
+fn unescape_values() {
+    let (_, property) = content_line("SUMMARY:a\\,b\\;c\\nd\\\\e\\x").unwrap();
+    assert_eq!(property.text(), "a,b;c\nd\\e\\x");
+
+    let (_, property) = content_line("CATEGORIES:work,a\\,b,").unwrap();
+    assert_eq!(property.split(','), ["work", "a,b", ""]);
+    let (_, property) = content_line("N:Doe;John;;Dr.\\;;").unwrap();
+    assert_eq!(property.split(';'), ["Doe", "John", "", "Dr.;", ""]);
+}
+
+#[test]
+fn parse_components() {
+    let calendar = "BEGIN:VCALENDAR\r\n\
+        VERSION:2.0\r\n\
+        BEGIN:VEVENT\r\n\
+        UID:1@example.com\r\n\
+        BEGIN:VALARM\r\n\
+        ACTION:DISPLAY\r\n\
+        END:valarm\r\n\
+        END:VEVENT\r\n\
+        BEGIN:VTODO\r\n\
+        SUMMARY:Review\r\n\
+        END:VTODO\r\n\
+        END:VCALENDAR\r\n";
+    let (remainder, calendar) = component(calendar).unwrap();
+    assert_eq!(remainder, "");
+    assert_eq!(calendar.name, "VCALENDAR");
+    assert_eq!(calendar.properties.len(), 1);
+    assert_eq!(calendar.components.len(), 2);
+
+    let event = calendar.children("vevent").next().unwrap();
+    assert_eq!(event.property("UID").unwrap().value, "1@example.com");
+    let alarm = event.children("VALARM").next().unwrap();
+    assert_eq!(alarm.property("ACTION").unwrap().value, "DISPLAY");
+    let todo = calendar.children("VTODO").next().unwrap();
+    assert_eq!(todo.property("SUMMARY").unwrap().text(), "Review");
+    assert!(todo.components.is_empty());
+
+    let cards =
+        "BEGIN:VCARD\nVERSION:4.0\nFN:A\nEND:VCARD\nBEGIN:VCARD\nVERSION:4.0\nFN:B\nEND:VCARD";
+    let (_, cards) = components(cards).unwrap();
+    let names: Vec<_> = cards
+        .iter()
+        .map(|card| card.property("FN").unwrap().value.as_str())
+        .collect();
+    assert_eq!(names, ["A", "B"]);
+}
+
+#[test]
+fn invalid_components() {
+    assert_eq!(component("VERSION:2.0\r\n").unwrap_err().index, 0);
+    assert_eq!(component("BEGIN:\r\nEND:\r\n").unwrap_err().index, 0);
+    let unterminated = "BEGIN:VEVENT\r\nUID:1\r\n";
+    assert_eq!(
+        component(unterminated).unwrap_err().index,
+        unterminated.len()
+    );
+    // An error in a nested component is an offset in the whole document
+    let nested = "BEGIN:VCALENDAR\r\nBEGIN:VEVENT\r\nUID\r\nEND:VEVENT\r\nEND:VCALENDAR";
+    assert_eq!(component(nested).unwrap_err().index, 34);
+    assert_eq!(components("BEGIN:A\nEND:A\nX:y\n").unwrap_err().index, 14);
+
+    let deep = "BEGIN:A\n".repeat(65);
+    let error = component(&deep).unwrap_err();
+    assert_eq!(error.index, 64 * 8);
+    assert_eq!(error.reason, "component nested too deeply");
+}
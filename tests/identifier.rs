@@ -0,0 +1,91 @@
+#![cfg(feature = "std")]
+
+use pepser::{
+    errors::ErrorSource,
+    impls::{ascii_identifier, identifier, identifier_except, sequence},
+    testing::{check, text},
+    traits::{sep_by, Parser},
+};
+
+#[test]
+fn unicode_identifiers() {
+    assert_eq!(identifier().parse("x"), Ok(("", "x")));
+    assert_eq!(identifier().parse("_"), Ok(("", "_")));
+    assert_eq!(identifier().parse("π_2r)"), Ok((")", "π_2r")));
+    assert_eq!(identifier().parse("變數 = 1"), Ok((" = 1", "變數")));
+    assert_eq!(identifier().parse("a-b"), Ok(("-b", "a")));
+
+    let error = identifier().parse("9lives").unwrap_err();
+    assert_eq!((error.index, error.source), (0, ErrorSource::TakeWhile));
+    assert!(identifier().parse("").is_err());
+    assert!(identifier().parse("€uro").is_err());
+
+    let mut names = sep_by(identifier(), sequence(", "));
+    assert_eq!(
+        names.parse("a, été, _b7"),
+        Ok(("", vec!["a", "été", "_b7"]))
+    );
+}
+
+#[test]
+fn unicode_identifier_classes() {
+    // Combining marks and connector punctuation continue an identifier but do not start one
+    assert_eq!(identifier().parse("e\u{301}"), Ok(("", "e\u{301}")));
+    assert_eq!(identifier().parse("a\u{203f}b"), Ok(("", "a\u{203f}b")));
+    assert!(identifier().parse("\u{301}e").is_err());
+    assert!(identifier().parse("\u{203f}").is_err());
+    assert_eq!(identifier().parse("x\u{0663}"), Ok(("", "x\u{0663}")));
+
+    // Numbers other than digits are neither, letter numbers are letters
+    assert!(identifier().parse("½").is_err());
+    assert_eq!(identifier().parse("a½"), Ok(("½", "a")));
+    assert_eq!(identifier().parse("Ⅻ"), Ok(("", "Ⅻ")));
+}
+
+#[test]
+fn ascii_identifiers_agree_on_ascii_input() {
+    let fragments = ["a", "Z", "_", "0", "9", " ", "-", "é", "日"];
+    let result = check(
+        2000,
+        8,
+        |rng| text(rng, &fragments, 6),
+        |input| {
+            let input = input.as_str();
+            let ascii = ascii_identifier().parse(input);
+            let unicode = identifier().parse(input);
+            match (ascii, unicode) {
+                (Ok(ascii), Ok(unicode)) if input.is_ascii() && ascii != unicode => {
+                    Err(format!("{ascii:?} and {unicode:?}"))
+                }
+                // ASCII identifiers are a prefix of the Unicode ones
+                (Ok((_, ascii)), Ok((_, unicode))) if !unicode.starts_with(ascii) => {
+                    Err(format!("{ascii:?} is not a prefix of {unicode:?}"))
+                }
+                (Ok(ascii), Err(_)) => Err(format!("only ASCII parsed {ascii:?}")),
+                (Err(_), Ok(unicode)) if input.is_ascii() => {
+                    Err(format!("only Unicode parsed {unicode:?}"))
+                }
+                _ => Ok(()),
+            }
+        },
+    );
+    if let Err(failure) = result {
+        panic!("{failure}");
+    }
+}
+
+#[test]
+fn exclude_keywords() {
+    let mut name = identifier_except(&["let", "in", "日"]);
+    assert_eq!(name.parse("letter"), Ok(("", "letter")));
+    assert_eq!(name.parse("i"), Ok(("", "i")));
+    assert_eq!(name.parse("日本"), Ok(("", "日本")));
+
+    for reserved in ["let x", "in", "日 "] {
+        let error = name.parse(reserved).unwrap_err();
+        assert_eq!((error.index, error.source), (0, ErrorSource::Predicate));
+        assert_eq!(error.reason, "reserved keyword");
+    }
+    // Errors of the identifier itself are kept
+    assert_eq!(name.parse("1").unwrap_err().source, ErrorSource::TakeWhile);
+}
@@ -0,0 +1,67 @@
+use pepser::{
+    impls::{sequence, take_while1},
+    indent::{indented_block, parse_indented, same_indent, Indented},
+    stateful::lift,
+    traits::{opt, ParseResult, Parser},
+};
+
+#[derive(Debug, PartialEq)]
+enum Statement {
+    Simple(String),
+    Block(String, Vec<Statement>),
+}
+
+/// `name` or `name:` followed by an indented block of statements
+fn statement(input: Indented) -> ParseResult<Indented, Statement> {
+    let (input, name) = lift(take_while1(char::is_alphanumeric)).parse(input)?;
+    let (input, block) = opt(lift(sequence(":\n"))).parse(input)?;
+    if block.is_none() {
+        let (input, _) = lift(sequence("\n")).parse(input)?;
+        return Ok((input, Statement::Simple(name.to_string())));
+    }
+    indented_block(statement)
+        .map(|body| Statement::Block(name.to_string(), body))
+        .parse(input)
+}
+
+fn program(input: Indented) -> ParseResult<Indented, Vec<Statement>> {
+    same_indent()
+        .and(statement)
+        .map(|(_, statement)| statement)
+        .many0()
+        .parse(input)
+}
+
+#[test]
+fn parse_nested_blocks() {
+    use Statement::*;
+    let source = "if:\n    a\n    while:\n        b\n\n        c\n    d\ne\n";
+    let (remainder, statements) = parse_indented(program, source).unwrap();
+    assert_eq!(remainder.input, "");
+    assert_eq!(remainder.state.depth(), 0);
+    assert_eq!(
+        statements,
+        vec![
+            Block(
+                "if".to_string(),
+                vec![
+                    Simple("a".to_string()),
+                    Block(
+                        "while".to_string(),
+                        vec![Simple("b".to_string()), Simple("c".to_string())]
+                    ),
+                    Simple("d".to_string()),
+                ]
+            ),
+            Simple("e".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn reject_bad_indentation() {
+    assert!(parse_indented(statement, "if:\nb\n").is_err());
+    let (remainder, statements) = parse_indented(program, "a\n  b\n").unwrap();
+    assert_eq!(statements, vec![Statement::Simple("a".to_string())]);
+    assert_eq!(remainder.input, "  b\n");
+}
@@ -0,0 +1,49 @@
+use std::{cell::Cell, rc::Rc};
+
+use pepser::{
+    impls::{sequence, take_while1},
+    traits::{sep_by, Parser},
+};
+
+#[derive(Default)]
+struct Metrics {
+    attempts: Cell<usize>,
+    failures: Cell<usize>,
+}
+
+#[test]
+fn count_rule_attempts() {
+    let metrics = Rc::new(Metrics::default());
+    let (attempts, failures) = (metrics.clone(), metrics.clone());
+    let number = take_while1(|c| c.is_ascii_digit())
+        .inspect_input(move |_: &&str| attempts.attempts.set(attempts.attempts.get() + 1))
+        .inspect_result(move |result: &_| {
+            if Result::is_err(result) {
+                failures.failures.set(failures.failures.get() + 1)
+            }
+        });
+    let mut list = sep_by(number.or(sequence("x")), sequence(","));
+
+    assert_eq!(list.parse("1,x,23;"), Ok((";", vec!["1", "x", "23"])));
+    assert_eq!(metrics.attempts.get(), 3);
+    assert_eq!(metrics.failures.get(), 1);
+
+    // the parser keeps counting when it is reused
+    assert_eq!(list.parse("x"), Ok(("", vec!["x"])));
+    assert_eq!(metrics.attempts.get(), 4);
+    assert_eq!(metrics.failures.get(), 2);
+}
+
+#[test]
+fn inspection_does_not_change_results() {
+    let mut seen = vec![];
+    let mut plain = sequence("ab");
+    let mut inspected = sequence("ab")
+        .inspect_input(|input: &&str| seen.push(input.to_string()))
+        .inspect_result(|_: &_| {});
+    for input in ["abc", "a", "", "ba"] {
+        assert_eq!(inspected.parse(input), plain.parse(input));
+    }
+    drop(inspected);
+    assert_eq!(seen, vec!["abc", "a", "", "ba"]);
+}
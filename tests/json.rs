@@ -1,145 +1,16 @@
+#![cfg(feature = "std")]
+
 use std::collections::HashMap;
 
 use pepser::{
-    impls::{any, none_of, sequence, take_while, ws},
-    traits::{discard, opt, parse_if, sep_by, value, wrapped, ParseResult, Parser},
+    errors::ErrorSource,
+    json,
+    json::{
+        from_json, json_limited, json_resilient, json_value, JsonError, JsonNumber, JsonValue,
+        Limits, ToJson,
+    },
 };
 
-#[derive(Debug, PartialEq)]
-pub enum JsonValue {
-    Array(Vec<JsonValue>),
-    Boolean(bool),
-    String(String),
-    Number(f64),
-    Object(HashMap<String, JsonValue>),
-    Null,
-}
-
-pub fn json_object<'a>(input: &'a str) -> ParseResult<&'a str, JsonValue> {
-    wrapped(
-        sequence("{"),
-        sep_by(json_pair, sequence(",")),
-        discard(ws(), sequence("}")),
-    )
-    .map(Vec::into_iter)
-    .map(Iterator::collect::<HashMap<String, JsonValue>>)
-    .map(JsonValue::Object)
-    .parse(input)
-}
-
-pub fn json_pair<'a>(input: &'a str) -> ParseResult<&'a str, (String, JsonValue)> {
-    wrapped(
-        ws(),
-        string
-            .map(String::from)
-            .and(discard(wrapped(ws(), sequence(":"), ws()), json_value)),
-        ws(),
-    )
-    .parse(input)
-}
-
-pub fn null<'a>(input: &'a str) -> ParseResult<&'a str, JsonValue> {
-    sequence("null").map(|_| JsonValue::Null).parse(input)
-}
-
-fn escaped<'a>(input: &'a str) -> ParseResult<&'a str, &'a str> {
-    sequence("\\\\")
-        .map(|_| "\\")
-        .or(sequence("\\\"").map(|_| "\""))
-        .or(sequence("\\n").map(|_| "\n"))
-        .or(sequence("\\t").map(|_| "\t"))
-        .or(sequence("\\r").map(|_| "\r"))
-        .or(sequence("\\/").map(|_| "/"))
-        .or(sequence("\\f").map(|_| "\u{000C}"))
-        .or(sequence("\\b").map(|_| "\u{0008}"))
-        .parse(input)
-}
-
-pub fn string<'a>(input: &'a str) -> ParseResult<&'a str, String> {
-    wrapped(
-        sequence("\""),
-        none_of("\"\\")
-            .or(escaped)
-            .many()
-            .map(|vec| vec.into_iter().collect::<String>()),
-        sequence("\""),
-    )
-    .parse(input)
-}
-
-pub fn json_value<'a>(input: &'a str) -> ParseResult<&'a str, JsonValue> {
-    discard(
-        ws(),
-        null.or(boolean)
-            .or(array)
-            .or(json_object)
-            .or(string.map(JsonValue::String))
-            .or(json_number),
-    )
-    .parse(input)
-}
-
-pub fn array<'a>(input: &'a str) -> ParseResult<&'a str, JsonValue> {
-    wrapped(
-        sequence("["),
-        wrapped(ws(), sep_by(json_value, sequence(",")), ws()).map(JsonValue::Array),
-        sequence("]"),
-    )
-    .parse(input)
-}
-
-pub fn boolean<'a>(input: &'a str) -> ParseResult<&'a str, JsonValue> {
-    sequence("true")
-        .or(sequence("false"))
-        .map(|str_bool| JsonValue::Boolean(str_bool == "true"))
-        .parse(input)
-}
-
-#[rustfmt::skip]
-pub fn json_number<'a>(input: &'a str) -> ParseResult<&'a str, JsonValue> {
-    opt(sequence("-"))
-        .map(|opt| if opt.is_some() { -1 } else { 1 })
-        .and(integral_part)
-        .and(decimal_part)
-        .and(exponent)
-        .map(|(((sign, integral), decimal), exponent)| JsonValue::Number(calculate_number(sign, integral, decimal, exponent)))
-        .parse(input)
-}
-
-fn calculate_number(sign: i64, integral: u64, decimal: f64, exponent: i32) -> f64 {
-    (sign as f64 * (integral as f64 + decimal)).powi(exponent)
-}
-#[rustfmt::skip]
-fn integral_part<'a>(input: &'a str) -> ParseResult<&'a str, u64> {
-    sequence("0")
-                .or(digits)
-                .map(str::parse::<u64>)
-                .map(Result::unwrap).parse(input)
-}
-
-#[rustfmt::skip]
-fn decimal_part<'a>(input: &'a str) -> ParseResult<&'a str, f64> {
-    parse_if(sequence("."), digits).map(|opt| {
-        opt.map(|double_str| format!("0.{}", double_str).parse::<f64>())
-            .map(Result::unwrap)
-            .unwrap_or(0.0)
-    }).parse(input)
-}
-
-#[rustfmt::skip]
-fn exponent<'a>(input: &'a str) -> ParseResult<&'a str, i32> {
-    opt(discard(any("eE"), 
-    opt(
-            value(-1, sequence("-")).or(value(1 as i32, sequence("+")
-            ))).map(|opt| opt.unwrap_or(1))
-        ).and(digits).map(|(a, b)| a * b.parse::<i32>().unwrap())
-    ).map(|opt| opt.unwrap_or(1))
-    .parse(input)
-}
-pub fn digits<'a>(input: &'a str) -> ParseResult<&'a str, &'a str> {
-    take_while(|c| c.is_digit(10)).parse(input)
-}
-
 #[test]
 fn parse_object() {
     use JsonValue::*;
@@ -186,7 +57,7 @@ fn parse_object() {
                                             "another specific test this time, invalid".to_string()
                                         )
                                     ),
-                                    ("data".to_string(), Number(-15.0)),
+                                    ("data".to_string(), Number(JsonNumber::Int(-15))),
                                     ("valid".to_string(), Boolean(false))
                                 ]
                                 .into_iter()
@@ -219,3 +90,350 @@ fn parse_object() {
         )
     );
 }
+
+#[test]
+fn resilient_partial_document() {
+    use JsonValue::*;
+    let (parsed, errors) = json_resilient("{\"a\": [1, tru, 3], \"b\" 2, \"c\": [null}");
+    assert_eq!(
+        parsed,
+        Some(Object(
+            vec![
+                (
+                    "a".to_string(),
+                    Array(vec![Number(JsonNumber::Int(1)), Number(JsonNumber::Int(3))])
+                ),
+                ("c".to_string(), Array(vec![Null])),
+            ]
+            .into_iter()
+            .collect()
+        ))
+    );
+    assert_eq!(
+        errors.iter().map(|error| error.index).collect::<Vec<_>>(),
+        vec![10, 23, 36]
+    );
+}
+
+#[test]
+fn resilient_valid_document() {
+    let input = "[{}, [], {\"a\": \"b\"}]";
+    let (parsed, errors) = json_resilient(input);
+    assert_eq!(parsed, json_value(input).ok().map(|(_, value)| value));
+    assert!(errors.is_empty());
+
+    let (parsed, errors) = json_resilient("[1] 2");
+    assert_eq!(
+        parsed,
+        Some(JsonValue::Array(vec![JsonValue::Number(JsonNumber::Int(
+            1
+        ))]))
+    );
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].index, 4);
+}
+
+#[test]
+fn typed_extraction() {
+    let points: Vec<HashMap<String, Option<i32>>> =
+        from_json(r#" [{"x": 1, "y": -2}, {"x": 0, "y": null}] "#).unwrap();
+    assert_eq!(points[0]["y"], Some(-2));
+    assert_eq!(points[1]["y"], None);
+    assert_eq!(from_json::<String>(r#""a\nb""#), Ok("a\nb".to_string()));
+    assert_eq!(from_json::<bool>("false"), Ok(false));
+}
+
+#[test]
+fn typed_extraction_errors() {
+    assert_eq!(
+        from_json::<HashMap<String, Vec<u32>>>(r#"{"b": [1], "a/b": [2, 1.5]}"#),
+        Err(JsonError::Type {
+            pointer: "/a~1b/1".to_string(),
+            expected: "u32"
+        })
+    );
+    assert_eq!(
+        from_json::<i8>("[]").unwrap_err().to_string(),
+        "expected i8"
+    );
+    assert!(matches!(
+        from_json::<bool>("true false"),
+        Err(JsonError::Parse { .. })
+    ));
+}
+
+#[test]
+fn build_documents() {
+    let id: Option<u32> = None;
+    let tags = vec!["a".to_string(), "b".to_string()];
+    let value = json!({
+        "id": id,
+        "tags": tags,
+        "nested": [[], {}, -1.5, [true]]
+    });
+    assert_eq!(
+        json_value(r#"{"id": null, "tags": ["a", "b"], "nested": [[], {}, -1.5, [true]]}"#),
+        Ok(("", value.clone()))
+    );
+    assert_eq!(
+        from_json::<HashMap<String, Vec<String>>>(r#"{"tags": ["a", "b"]}"#)
+            .unwrap()
+            .to_json(),
+        json!({ "tags": tags })
+    );
+}
+
+#[test]
+fn enforce_limits() {
+    let limits = Limits {
+        max_string_length: 3,
+        max_elements: 4,
+        ..Limits::default()
+    };
+    assert!(json_limited(limits)(r#"{"abc": ["de", 1]}"#).is_ok());
+
+    let error = json_limited(limits)(r#"{"abc": ["long", 1]}"#).unwrap_err();
+    assert_eq!(error.source, ErrorSource::LimitExceeded);
+    assert_eq!((error.index, error.reason.as_ref()), (9, "string too long"));
+
+    let error = json_limited(limits)("[1, 2, 3, 4]").unwrap_err();
+    assert_eq!(
+        (error.index, error.reason.as_ref()),
+        (10, "too many elements")
+    );
+}
+
+#[test]
+fn reject_deep_nesting() {
+    let limits = Limits {
+        max_depth: 64,
+        ..Limits::default()
+    };
+    let deep = "[".repeat(10_000);
+    let error = json_limited(limits)(&deep).unwrap_err();
+    assert_eq!(error.source, ErrorSource::LimitExceeded);
+    assert_eq!(error.index, 64);
+}
+
+#[test]
+fn whitespace_is_optional_everywhere() {
+    use pepser::{
+        impls::{sequence, ws},
+        traits::{wrapped, Parser},
+    };
+    use JsonValue::*;
+
+    for input in ["[]", " [ ] ", "[\n]", "\t[ ]"] {
+        assert_eq!(
+            json_value(input).map(|(_, value)| value),
+            Ok(Array(vec![])),
+            "{:?}",
+            input
+        );
+    }
+    assert_eq!(json_value("{}"), Ok(("", Object(HashMap::new()))));
+    assert_eq!(
+        json_value("[1 ,2 ]"),
+        Ok((
+            "",
+            Array(vec![Number(JsonNumber::Int(1)), Number(JsonNumber::Int(2))])
+        ))
+    );
+    assert_eq!(json_value("{\"a\":1}").unwrap().0, "");
+    assert_eq!(from_json::<bool>("true"), Ok(true));
+    assert_eq!(from_json::<Vec<bool>>(" [ true ] "), Ok(vec![true]));
+    assert_eq!(ws().parse(""), Ok(("", "")));
+    assert_eq!(wrapped(ws(), sequence("x"), ws()).parse("x"), Ok(("", "x")));
+}
+
+#[test]
+fn borrowed_values() {
+    use std::borrow::Cow;
+
+    use pepser::json::{json_value_ref, JsonValueRef};
+
+    let input = r#" {"a": [1, -2.5e1, true, null], "b" : {"c": "d\n"}, "a": "é"} "#;
+    let (remainder, value) = json_value_ref(input).unwrap();
+    assert_eq!(remainder, " ");
+    assert_eq!(
+        value.get("a"),
+        Some(&JsonValueRef::String(Cow::Borrowed("é")))
+    );
+    assert!(matches!(
+        value.get("b").and_then(|b| b.get("c")),
+        Some(JsonValueRef::String(Cow::Owned(c))) if c == "d\n"
+    ));
+    assert_eq!(value.into_owned(), json_value(input).unwrap().1);
+    assert_eq!(json_value_ref("[1, \"x]").unwrap_err().index, 4);
+    assert_eq!(json_value("[1, \"x]").unwrap_err().index, 4);
+}
+
+#[test]
+fn pull_events() {
+    use pepser::json::{JsonEvent::*, JsonEvents};
+
+    let events = JsonEvents::new(" {\"a\": [1, {}, []], \"b\\n\": {\"c\": \"d\"}, \"e\": true} ")
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(
+        events,
+        vec![
+            StartObject,
+            Key("a".into()),
+            StartArray,
+            Number(JsonNumber::Int(1)),
+            StartObject,
+            EndObject,
+            StartArray,
+            EndArray,
+            EndArray,
+            Key("b\n".into()),
+            StartObject,
+            Key("c".into()),
+            String("d".into()),
+            EndObject,
+            Key("e".into()),
+            Boolean(true),
+            EndObject,
+        ]
+    );
+    assert_eq!(JsonEvents::new("null").collect::<Vec<_>>(), vec![Ok(Null)]);
+
+    let errors = |input| {
+        JsonEvents::new(input)
+            .filter_map(Result::err)
+            .map(|error| error.index)
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(errors("[1,]"), vec![3]);
+    assert_eq!(errors("{\"a\" 1}"), vec![5]);
+    assert_eq!(errors("{\"a\": 1]"), vec![7]);
+    assert_eq!(errors("[1] 2"), vec![4]);
+    assert_eq!(errors("[\"a"), vec![3]);
+    assert_eq!(errors("[1"), vec![2]);
+}
+
+#[test]
+fn extract_paths() {
+    use pepser::json::{json_extract, JsonValueRef};
+
+    let input = r#"{"tests": [{"valid": 1, "skip": {"s": "}\"]"}}, {"valid": 2}, {}], "b": [3]}"#;
+    let numbers = |path| {
+        json_extract(input, path)
+            .unwrap()
+            .1
+            .into_iter()
+            .map(|value| match value {
+                JsonValueRef::Number(number) => number.as_f64(),
+                value => panic!("{value:?}"),
+            })
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(numbers("/tests/*/valid"), vec![1.0, 2.0]);
+    assert_eq!(numbers("/tests/1/valid"), vec![2.0]);
+    assert_eq!(numbers("/b/*"), vec![3.0]);
+    assert_eq!(numbers("/missing"), vec![]);
+    assert_eq!(numbers("tests"), vec![]);
+    assert_eq!(
+        json_extract(input, "/tests/0/skip").unwrap().1[0].get("s"),
+        Some(&JsonValueRef::String("}\"]".into()))
+    );
+
+    let (remainder, everything) = json_extract("[1, [2]] tail", "").unwrap();
+    assert_eq!(remainder, " tail");
+    assert_eq!(
+        everything[0].clone().into_owned(),
+        json_value("[1, [2]]").unwrap().1
+    );
+
+    assert_eq!(json_extract(r#"{"a": [1, {"#, "/b").unwrap_err().index, 11);
+    assert_eq!(json_extract(r#"{"a": [1,]}"#, "/a/*").unwrap_err().index, 9);
+}
+
+#[test]
+fn values_key_maps() {
+    use std::collections::HashSet;
+
+    assert_eq!(
+        JsonValue::Number(JsonNumber::Float(f64::NAN)),
+        JsonValue::Number(JsonNumber::Float(-f64::NAN))
+    );
+    assert_eq!(
+        JsonValue::Number(JsonNumber::Float(-0.0)),
+        JsonValue::Number(JsonNumber::Int(0))
+    );
+    assert_ne!(
+        JsonValue::Number(JsonNumber::Int(1)),
+        JsonValue::String("1".to_string())
+    );
+
+    let documents = [
+        r#"{"a": [1, 2], "b": {"c": null}}"#,
+        r#"{"b": {"c": null}, "a": [1.0, 2e0]}"#,
+        r#"{"a": [2, 1], "b": {"c": null}}"#,
+        "-0",
+        "0",
+    ];
+    let values = documents
+        .iter()
+        .map(|document| json_value(document).unwrap().1)
+        .collect::<HashSet<_>>();
+    assert_eq!(values.len(), 3);
+    assert!(values.contains(&json_value(r#"{"a": [2, 1], "b": {"c": null}}"#).unwrap().1));
+}
+
+#[test]
+fn exact_numbers() {
+    use JsonNumber::*;
+
+    let number = |input| match json_value(input) {
+        Ok(("", JsonValue::Number(number))) => number,
+        parsed => panic!("{parsed:?}"),
+    };
+    assert!(matches!(number("9007199254740993"), Int(9007199254740993)));
+    assert!(matches!(number("-9223372036854775808"), Int(i64::MIN)));
+    assert!(matches!(number("18446744073709551615"), UInt(u64::MAX)));
+    assert!(matches!(number("18446744073709551616"), Float(_)));
+    assert!(matches!(number("-0"), Int(0)));
+    assert!(matches!(number("2e0"), Float(2.0)));
+    assert!(matches!(number("-1.5E-2"), Float(-0.015)));
+    assert!(matches!(number("12.5e+1"), Float(125.0)));
+
+    assert_eq!(number("1e19").as_u64(), Some(10_000_000_000_000_000_000));
+    assert_eq!(number("-1").as_u64(), None);
+    assert_eq!(from_json::<u64>("18446744073709551615"), Ok(u64::MAX));
+    assert!(matches!(
+        from_json::<i8>("128"),
+        Err(JsonError::Type { expected: "i8", .. })
+    ));
+    assert_eq!(u64::MAX.to_json(), JsonValue::Number(UInt(u64::MAX)));
+
+    let document = r#"{"id": 9007199254740993, "values": [-0.1, 1e300, 3], "s": "\"\\\/\b"}"#;
+    let (_, value) = json_value(document).unwrap();
+    assert_eq!(json_value(&value.to_string()), Ok(("", value)));
+    assert_eq!(JsonValue::Number(Float(f64::NAN)).to_string(), "null");
+    assert_eq!(vec![1.0, 0.5].to_json().to_string(), "[1.0,0.5]");
+}
+
+#[test]
+fn unicode_escapes_round_trip() {
+    let string = |input| match json_value(input) {
+        Ok(("", JsonValue::String(string))) => string,
+        parsed => panic!("{parsed:?}"),
+    };
+    assert_eq!(string(r#""caf\u00e9 \u65E5""#), "café 日");
+    assert_eq!(string(r#""\ud83e\udd80""#), "🦀");
+    assert!(json_value(r#""\ud83e""#).is_err());
+    assert!(json_value(r#""\udd80\ud83e""#).is_err());
+    assert!(json_value(r#""\u12""#).is_err());
+
+    let control = JsonValue::String("\u{0}a\u{1f}\u{7f}\n".to_string());
+    assert_eq!(control.to_string(), r#""\u0000a\u001f\u007f\n""#);
+    assert_eq!(json_value(&control.to_string()), Ok(("", control)));
+}
+
+#[test]
+fn members_are_written_in_key_order() {
+    let (_, value) = json_value(r#"{"b": [], "c": {"z": 1, "y": 2}, "a": null}"#).unwrap();
+    assert_eq!(value.to_string(), r#"{"a":null,"b":[],"c":{"y":2,"z":1}}"#);
+}
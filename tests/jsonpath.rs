@@ -0,0 +1,74 @@
+#![cfg(feature = "std")]
+
+use pepser::{
+    json::{json_value, JsonNumber, JsonValue},
+    jsonpath::JsonPath,
+};
+
+fn document() -> JsonValue {
+    json_value(
+        "{
+            \"description\": \"suite\",
+            \"a/b\": {\"m~n\": 1},
+            \"tests\": [
+                {\"data\": \"first\", \"valid\": true},
+                {\"data\": \"second\", \"valid\": false, \"nested\": {\"data\": \"deep\"}}
+            ]
+        }",
+    )
+    .unwrap()
+    .1
+}
+
+#[test]
+fn resolve_json_pointer() {
+    let document = document();
+    assert_eq!(
+        document.pointer("/tests/0/data"),
+        Some(&JsonValue::String("first".to_string()))
+    );
+    assert_eq!(
+        document.pointer("/a~1b/m~0n"),
+        Some(&JsonValue::Number(JsonNumber::Int(1)))
+    );
+    assert_eq!(document.pointer(""), Some(&document));
+    assert_eq!(document.pointer("/tests/01"), None);
+    assert_eq!(document.pointer("/tests/2"), None);
+    assert_eq!(document.pointer("tests"), None);
+}
+
+#[test]
+fn query_json_path() {
+    let document = document();
+    let strings = |path: &str| {
+        JsonPath::new(path)
+            .unwrap()
+            .query(&document)
+            .into_iter()
+            .map(|value| format!("{:?}", value))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        strings("$.tests[*].valid"),
+        vec!["Boolean(true)", "Boolean(false)"]
+    );
+    assert_eq!(strings("$.tests[-1]['data']"), vec!["String(\"second\")"]);
+    let mut deep = strings("$..data");
+    deep.sort();
+    assert_eq!(
+        deep,
+        vec![
+            "String(\"deep\")",
+            "String(\"first\")",
+            "String(\"second\")"
+        ]
+    );
+    assert!(strings("$.missing[0]").is_empty());
+}
+
+#[test]
+fn reject_invalid_paths() {
+    assert!(JsonPath::new("tests").is_err());
+    assert!(JsonPath::new("$.tests[").is_err());
+    assert!(JsonPath::new("$.tests[x]").is_err());
+}
@@ -0,0 +1,65 @@
+#![cfg(feature = "std")]
+
+use pepser::{
+    errors::ErrorSource,
+    impls::{keywords, sequence, take_while1},
+    testing::{check, text},
+    traits::{not_followed_by, Parser},
+};
+
+const KEYWORDS: [&str; 7] = ["let", "letter", "le", "日本", "日", "é", "lettuce"];
+
+#[test]
+fn match_the_longest_keyword() {
+    let mut keyword = keywords(&KEYWORDS);
+    assert_eq!(keyword.parse("letters"), Ok(("s", "letter")));
+    assert_eq!(keyword.parse("lett"), Ok(("t", "let")));
+    assert_eq!(keyword.parse("lex"), Ok(("x", "le")));
+    assert_eq!(keyword.parse("日本語"), Ok(("語", "日本")));
+    assert_eq!(keyword.parse("日曜"), Ok(("曜", "日")));
+
+    let error = keyword.parse("l").unwrap_err();
+    assert_eq!((error.index, error.source), (0, ErrorSource::Keyword));
+    assert_eq!(
+        keyword.parse("").unwrap_err().reason,
+        "unexpected end of input"
+    );
+    // Keywords sharing a first byte with a multibyte character never split it
+    assert!(keywords(&["\u{e8}"]).parse("é").is_err());
+
+    // A word boundary is required with a negative predicate
+    let mut whole = keywords(&["true", "false"])
+        .skip(not_followed_by(take_while1(|c: char| c.is_alphanumeric())));
+    assert_eq!(whole.parse("true)"), Ok((")", "true")));
+    assert!(whole.parse("trueish").is_err());
+    assert_eq!(
+        keywords(&[]).parse("a").unwrap_err().source,
+        ErrorSource::Keyword
+    );
+}
+
+#[test]
+fn agree_with_chained_alternatives() {
+    let fragments = ["l", "e", "t", "ter", "tuce", "日", "本", "é", "x"];
+    let result = check(
+        2000,
+        5,
+        |rng| text(rng, &fragments, 5),
+        |input| {
+            let input = input.as_str();
+            let chained = KEYWORDS
+                .iter()
+                .map(|keyword| sequence(keyword).parse(input))
+                .filter_map(Result::ok)
+                .min_by_key(|(rest, _)| rest.len());
+            match (keywords(&KEYWORDS).parse(input), chained) {
+                (Ok(matched), Some(chained)) if matched == chained => Ok(()),
+                (Err(_), None) => Ok(()),
+                (matched, chained) => Err(format!("{matched:?} and {chained:?}")),
+            }
+        },
+    );
+    if let Err(failure) = result {
+        panic!("{failure}");
+    }
+}
@@ -0,0 +1,82 @@
+use pepser::{
+    errors::ErrorSource,
+    impls::{any, eof, none_of, sequence, take_while1, ws},
+    lexer::{kind, Lexer, Span, Spanned, Token},
+    traits::{chainl1, discard, opt, ParseResult, Parser},
+};
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum Kind {
+    Let,
+    Identifier,
+    Number,
+    Operator,
+}
+
+fn lexer<'a>() -> Lexer<'a, Kind> {
+    Lexer::new()
+        .token(Kind::Let, sequence("let"))
+        .token(Kind::Number, take_while1(|c| c.is_ascii_digit()))
+        .token(
+            Kind::Identifier,
+            take_while1(|c| c.is_ascii_alphanumeric() || c == '_'),
+        )
+        .token(Kind::Operator, any("+-=").or(sequence("==")))
+        .trivia(ws())
+        .trivia(discard(sequence("#"), opt(none_of("\n"))))
+}
+
+#[test]
+fn tokenize_with_spans() {
+    let tokens = lexer()
+        .tokenize("let letter = 12 # comment\n  + x")
+        .unwrap();
+    let lexemes = tokens
+        .iter()
+        .map(|token| (token.value.kind, token.value.text))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        lexemes,
+        vec![
+            (Kind::Let, "let"),
+            (Kind::Identifier, "letter"),
+            (Kind::Operator, "="),
+            (Kind::Number, "12"),
+            (Kind::Operator, "+"),
+            (Kind::Identifier, "x"),
+        ]
+    );
+    assert_eq!(
+        tokens[1],
+        Spanned {
+            span: Span { start: 4, end: 10 },
+            value: Token {
+                kind: Kind::Identifier,
+                text: "letter"
+            }
+        }
+    );
+    assert_eq!(tokens[4].span, Span { start: 28, end: 29 });
+}
+
+#[test]
+fn report_lexical_error() {
+    let error = lexer().tokenize("let x = 1 ? 2").unwrap_err();
+    assert_eq!(error.index, 10);
+    assert_eq!(error.source, ErrorSource::Lexer);
+    assert_eq!(lexer().tokenize("  # only trivia"), Ok(vec![]));
+}
+
+#[test]
+fn parse_token_stream() {
+    type Tokens<'t, 'a> = &'t [Spanned<Token<'a, Kind>>];
+
+    fn sum<'t, 'a>(input: Tokens<'t, 'a>) -> ParseResult<Tokens<'t, 'a>, i64> {
+        let number = kind(Kind::Number).map(|token| token.value.text.parse::<i64>().unwrap());
+        let plus = kind(Kind::Operator).map(|_| (|a, b| a + b) as fn(i64, i64) -> i64);
+        chainl1(number, plus).parse(input)
+    }
+
+    let tokens = lexer().tokenize("1 + 2 + 39").unwrap();
+    assert_eq!(sum.and(eof()).parse(&tokens), Ok((&tokens[5..], (42, ()))));
+}
@@ -0,0 +1,92 @@
+use std::net::{IpAddr, Ipv6Addr};
+
+use pepser::{
+    datetime::{Date, Time, UtcOffset},
+    logs::{combined_log, common_log, syslog, SdElement},
+};
+
+#[test]
+fn parse_syslog_messages() {
+    let (remainder, message) =
+        syslog("<34>1 - host su 12 - - \u{feff}'su root' failed\nnext").unwrap();
+    assert_eq!(remainder, "\nnext");
+    assert_eq!(
+        (message.facility, message.severity, message.version),
+        (4, 2, 1)
+    );
+    assert_eq!(message.timestamp, None);
+    assert_eq!(message.app_name, Some("su"));
+    assert_eq!(message.proc_id, Some("12"));
+    assert_eq!(message.msg_id, None);
+    assert!(message.structured_data.is_empty());
+    assert_eq!(message.message, Some("'su root' failed"));
+
+    let (_, message) =
+        syslog(r#"<0>1 2024-01-02T03:04:05+01:00 - - - - [a x="q\"\]\\"][b@1]"#).unwrap();
+    assert_eq!(
+        message.timestamp.unwrap().offset,
+        Some(UtcOffset { minutes: 60 })
+    );
+    assert_eq!(
+        message.structured_data,
+        [
+            SdElement {
+                id: "a",
+                params: vec![("x", r#"q"]\"#.to_string())]
+            },
+            SdElement {
+                id: "b@1",
+                params: vec![]
+            },
+        ]
+    );
+    assert_eq!(message.message, None);
+}
+
+#[test]
+fn parse_access_logs() {
+    let line = r#"::1 - - [29/Feb/2024:23:59:59 +0530] "GET /a\"b HTTP/1.1" 404 - "https://example.com/" "curl/8.0""#;
+    let (remainder, entry) = combined_log(line).unwrap();
+    assert_eq!(remainder, "");
+    assert_eq!(entry.client, IpAddr::V6(Ipv6Addr::LOCALHOST));
+    assert_eq!((entry.ident, entry.user), (None, None));
+    assert_eq!(
+        entry.timestamp.date,
+        Date {
+            year: 2024,
+            month: 2,
+            day: 29
+        }
+    );
+    assert_eq!(
+        entry.timestamp.time,
+        Time {
+            hour: 23,
+            minute: 59,
+            second: 59,
+            nanosecond: 0
+        }
+    );
+    assert_eq!(entry.timestamp.offset, Some(UtcOffset { minutes: 330 }));
+    assert_eq!(entry.request, r#"GET /a\"b HTTP/1.1"#);
+    assert_eq!((entry.status, entry.size), (404, None));
+    assert_eq!(entry.referer, Some("https://example.com/"));
+    assert_eq!(entry.user_agent, Some("curl/8.0"));
+
+    let (remainder, entry) = common_log(&line[..line.find(" \"https").unwrap()]).unwrap();
+    assert_eq!((remainder, entry.referer), ("", None));
+}
+
+#[test]
+fn report_invalid_lines() {
+    assert_eq!(syslog("<1>0 - - - - - -").unwrap_err().index, 3);
+    assert_eq!(syslog("<1>1 - - - - -").unwrap_err().index, 14);
+    assert_eq!(syslog("<1>1 - - - - - [a x=\"1]").unwrap_err().index, 23);
+    let line = r#"10.0.0.1 - - [30/Feb/2024:00:00:00 +0000] "GET /" 200 1"#;
+    assert_eq!(common_log(line).unwrap_err().index, 14);
+    let line = r#"10.0.0.1 - - [01/Foo/2024:00:00:00 +0000] "GET /" 200 1"#;
+    assert_eq!(common_log(line).unwrap_err().index, 17);
+    let line = r#"10.0.0.1 - - [01/Jan/2024:00:00:00 +0000] "GET /" 700 1"#;
+    assert_eq!(common_log(line).unwrap_err().index, 50);
+    assert_eq!(common_log("host - - [").unwrap_err().index, 0);
+}
@@ -0,0 +1,92 @@
+#![cfg(feature = "std")]
+
+use pepser::http::media::{accept, media_type, negotiate, MediaRange, MediaType};
+
+#[test]
+fn parse_media_types() {
+    assert_eq!(
+        media_type("multipart/form-data ; boundary=\"a;b \\\"c\\\"\";;Charset=utf-8, next"),
+        Ok((
+            ", next",
+            MediaType {
+                kind: "multipart".to_string(),
+                subtype: "form-data".to_string(),
+                parameters: vec![
+                    ("boundary".to_string(), "a;b \"c\"".to_string()),
+                    ("charset".to_string(), "utf-8".to_string()),
+                ],
+            }
+        ))
+    );
+    let (_, parsed) = media_type("application/vnd.api+json; profile=\"a b\"").unwrap();
+    assert_eq!(parsed.suffix(), Some("json"));
+    assert_eq!(
+        parsed.to_string(),
+        "application/vnd.api+json; profile=\"a b\""
+    );
+    assert_eq!(media_type("text/plain").unwrap().1.suffix(), None);
+
+    assert_eq!(media_type("text").unwrap_err().index, 4);
+    assert_eq!(media_type("text/").unwrap_err().index, 5);
+    assert_eq!(media_type("text/plain; a=b c").unwrap().0, " c");
+    assert_eq!(media_type("text/plain; a=").unwrap_err().index, 14);
+    assert_eq!(media_type("text/plain; a=\"open").unwrap_err().index, 14);
+}
+
+#[test]
+fn parse_accept() {
+    let (rest, ranges) = accept("text/html;level=1;q=0.7;ext=x , ,*/*;Q=0").unwrap();
+    assert_eq!(rest, "");
+    assert_eq!(
+        ranges,
+        [
+            MediaRange {
+                media_type: MediaType {
+                    kind: "text".to_string(),
+                    subtype: "html".to_string(),
+                    parameters: vec![("level".to_string(), "1".to_string())],
+                },
+                quality: 700,
+            },
+            MediaRange {
+                media_type: MediaType {
+                    kind: "*".to_string(),
+                    subtype: "*".to_string(),
+                    parameters: vec![],
+                },
+                quality: 0,
+            },
+        ]
+    );
+    assert_eq!(accept(""), Ok(("", vec![])));
+    assert_eq!(accept("*/*;q=1.000").unwrap().1[0].quality, 1000);
+    assert_eq!(accept("*/html").unwrap_err().index, 0);
+    assert_eq!(accept("text/html, */*;q=1.5").unwrap_err().index, 17);
+    assert_eq!(accept("text/html;q=0.1234").unwrap_err().index, 12);
+    assert_eq!(accept("text/html text/plain").unwrap_err().index, 10);
+}
+
+#[test]
+fn negotiate_media_types() {
+    let available = [
+        "application/json",
+        "text/html; charset=utf-8",
+        "text/html; level=1",
+    ]
+    .map(|media| media_type(media).unwrap().1);
+    let preferred = |header| negotiate(&accept(header).unwrap().1, &available);
+
+    assert_eq!(preferred(""), Some(&available[0]));
+    assert_eq!(preferred("text/*"), Some(&available[1]));
+    assert_eq!(
+        preferred("text/*;q=0.5, text/html;level=1"),
+        Some(&available[2])
+    );
+    assert_eq!(
+        preferred("*/*;q=0.1, text/html;charset=UTF-8"),
+        Some(&available[1])
+    );
+    assert_eq!(preferred("application/*;q=0.9, */*"), Some(&available[1]));
+    assert_eq!(preferred("image/png"), None);
+    assert_eq!(preferred("*/*, application/json;q=0, text/html;q=0"), None);
+}
@@ -0,0 +1,45 @@
+#![cfg(feature = "std")]
+
+use pepser::{
+    errors::ErrorSource,
+    impls::{eof, sequence, take_while1},
+    minimize::{minimize, minimize_by},
+    traits::{discard, sep_by, wrapped, ParseResult, Parser},
+};
+
+fn list(input: &str) -> ParseResult<&str, usize> {
+    wrapped(
+        sequence("["),
+        sep_by(take_while1(|c| c.is_ascii_digit()), sequence(",")),
+        discard(sequence("]"), eof()),
+    )
+    .map(|items| items.len())
+    .parse(input)
+}
+
+#[test]
+fn minimize_failing_input() {
+    let input = "[1,22,333,4444,55555,666666,x7777777,88888888,999999999]";
+    let reproducer = minimize(list, input).unwrap();
+
+    assert!(reproducer.len() < input.len());
+    assert_eq!(
+        list(&reproducer).map_err(|error| error.source),
+        Err(ErrorSource::Sequence("]"))
+    );
+    assert_eq!(minimize(list, input), Some(reproducer));
+}
+
+#[test]
+fn keep_valid_input() {
+    assert_eq!(minimize(list, "[1,2,3]"), None);
+}
+
+#[test]
+fn minimize_with_predicate() {
+    assert_eq!(
+        minimize_by("aaaaXbbbbYcccc", |candidate| candidate.contains('X')
+            && candidate.contains('Y')),
+        "XY"
+    );
+}
@@ -0,0 +1,94 @@
+use pepser::mqtt::{fixed_header, packet, remaining_length, Connect, Packet, Subscribe, Will};
+
+#[test]
+fn parse_connect() {
+    let mut bytes = vec![0x10, 0];
+    bytes.extend([0, 4, b'M', b'Q', b'T', b'T', 4, 0b1110_1110, 0, 60]);
+    bytes.extend([0, 2, b'i', b'd']);
+    bytes.extend([0, 1, b't', 0, 3, b'b', b'y', b'e']);
+    bytes.extend([0, 1, b'u', 0, 2, 1, 2]);
+    bytes[1] = bytes.len() as u8 - 2;
+    assert_eq!(
+        packet(&bytes),
+        Ok((
+            &[][..],
+            Packet::Connect(Connect {
+                protocol_name: "MQTT",
+                protocol_level: 4,
+                clean_session: true,
+                keep_alive: 60,
+                client_id: "id",
+                will: Some(Will {
+                    topic: "t",
+                    message: b"bye",
+                    qos: 1,
+                    retain: true,
+                }),
+                username: Some("u"),
+                password: Some(&[1, 2]),
+            })
+        ))
+    );
+
+    // A password without a username
+    let mut invalid = bytes.clone();
+    invalid[9] = 0b0100_0010;
+    assert_eq!(packet(&invalid).unwrap_err().index, 9);
+    // The body ends before the password
+    let mut short = bytes[..bytes.len() - 1].to_vec();
+    short[1] -= 1;
+    assert_eq!(packet(&short).unwrap_err().index, short.len());
+}
+
+#[test]
+fn parse_subscribe() {
+    let bytes = [
+        0x82, 14, 0, 10, 0, 3, b'a', b'/', b'#', 1, 0, 3, b'+', b'/', b'b', 2,
+    ];
+    let Ok((_, Packet::Subscribe(subscribe))) = packet(&bytes) else {
+        panic!("expected a subscribe");
+    };
+    assert_eq!(
+        subscribe,
+        Subscribe {
+            packet_id: 10,
+            filters: vec![("a/#", 1), ("+/b", 2)]
+        }
+    );
+
+    assert_eq!(
+        packet(&[0x80, 2, 0, 1]).unwrap_err().reason,
+        "invalid subscribe flags"
+    );
+    assert_eq!(packet(&[0x82, 2, 0, 1]).unwrap_err().index, 4);
+    assert_eq!(
+        packet(&[0x82, 6, 0, 1, 0, 1, b'a', 3]).unwrap_err().index,
+        7
+    );
+}
+
+#[test]
+fn header_errors() {
+    assert_eq!(
+        remaining_length(&[0x80, 0x80, 0x80, 0x01]),
+        Ok((&[][..], 1 << 21))
+    );
+    assert_eq!(remaining_length(&[0x80, 0x80]).unwrap_err().index, 2);
+    assert_eq!(
+        fixed_header(&[0xF0, 0]).unwrap_err().reason,
+        "reserved packet type"
+    );
+    assert_eq!(fixed_header(&[0xE0, 0]).unwrap().1.packet_type, 14);
+    // QoS 3 and a wildcard topic name
+    assert!(packet(&[0x36, 3, 0, 1, b'a']).is_err());
+    assert_eq!(packet(&[0x30, 3, 0, 1, b'#']).unwrap_err().index, 2);
+    assert_eq!(packet(&[0x30, 3, 0, 1, 0xFF]).unwrap_err().index, 4);
+    assert_eq!(packet(&[0x30, 5, 0, 1]).unwrap_err().index, 4);
+    // A body shorter than its parts, followed by another packet
+    assert_eq!(
+        packet(&[0x32, 5, 0, 3, b'a', b'/', b'b', 0xE0, 0])
+            .unwrap_err()
+            .index,
+        7
+    );
+}
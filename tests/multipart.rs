@@ -0,0 +1,93 @@
+#![cfg(feature = "std")]
+
+use pepser::{
+    http::Headers,
+    multipart::{boundary, multipart, Part},
+    traits::Parser,
+};
+
+#[test]
+fn parse_parts() {
+    let body = "preamble, ignored\r\n\
+        --b0undary  \r\n\
+        Content-Disposition: form-data; name=\"a\"\r\n\
+        \r\n\
+        not a --b0undary\r\n\
+        --b0undary\r\n\
+        \r\n\
+        \r\n\
+        --b0undary\n\
+        Content-Disposition: form-data; name=\"b\"\n\
+        \n\
+        lf\n\
+        --b0undary--\r\n\
+        epilogue";
+    let (remainder, parts) = multipart("b0undary").parse(body).unwrap();
+    assert_eq!(remainder, "epilogue");
+    assert_eq!(
+        parts,
+        vec![
+            Part {
+                headers: Headers(vec![(
+                    "Content-Disposition".to_string(),
+                    "form-data; name=\"a\"".to_string()
+                )]),
+                body: "not a --b0undary",
+            },
+            Part {
+                headers: Headers::default(),
+                body: "",
+            },
+            Part {
+                headers: Headers(vec![(
+                    "Content-Disposition".to_string(),
+                    "form-data; name=\"b\"".to_string()
+                )]),
+                body: "lf",
+            },
+        ]
+    );
+
+    let (remainder, parts) = multipart("b").parse("--b\r\n\r\nx\r\n--b--").unwrap();
+    assert_eq!((remainder, parts[0].body), ("", "x"));
+}
+
+#[test]
+fn read_parameters() {
+    assert_eq!(
+        boundary("multipart/form-data; charset=utf-8; BOUNDARY=\"a;b c\""),
+        Some("a;b c")
+    );
+    assert_eq!(boundary("multipart/mixed;boundary=simple "), Some("simple"));
+    assert_eq!(boundary("multipart/mixed; boundary=\"\""), None);
+    assert_eq!(boundary("text/plain"), None);
+
+    let (_, parts) = multipart("x")
+        .parse(
+            "--x\r\n\
+            Content-Disposition: form-data; filename=\"a;b.txt\"; name=file\r\n\r\n\
+            data\r\n--x--",
+        )
+        .unwrap();
+    assert_eq!(parts[0].name(), Some("file"));
+    assert_eq!(parts[0].filename(), Some("a;b.txt"));
+}
+
+#[test]
+fn invalid_bodies() {
+    assert_eq!(multipart("b").parse("no boundary").unwrap_err().index, 0);
+    assert_eq!(multipart("").parse("--\r\n").unwrap_err().index, 0);
+    // The boundary must end its line
+    assert_eq!(multipart("b").parse("--bc\r\n").unwrap_err().index, 3);
+    // A header line without its colon
+    assert_eq!(
+        multipart("b")
+            .parse("--b\r\nBad header\r\n")
+            .unwrap_err()
+            .index,
+        8
+    );
+    // Parts until the end of the body without a closing boundary
+    let unclosed = "--b\r\n\r\ntext\r\n--b\r\n\r\nmore";
+    assert_eq!(multipart("b").parse(unclosed).unwrap_err().index, 20);
+}
@@ -0,0 +1,62 @@
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+
+use pepser::net::{ip_addr, ipv4, ipv6, port, socket_addr};
+
+#[test]
+fn parse_ipv6_forms() {
+    for text in [
+        "::",
+        "::1",
+        "1::",
+        "fe80::1:2",
+        "1:2:3:4:5:6:7:8",
+        "1:2:3:4:5:6:7::",
+        "::2:3:4:5:6:7:8",
+        "64:ff9b::192.0.2.33",
+        "1:2:3:4:5:6:1.2.3.4",
+        "ABCD:ef01::",
+    ] {
+        assert_eq!(
+            ipv6(text),
+            Ok(("", text.parse::<Ipv6Addr>().unwrap())),
+            "{}",
+            text
+        );
+    }
+    assert_eq!(ipv6("::1:"), Ok((":", Ipv6Addr::LOCALHOST)));
+    assert_eq!(
+        ipv6("1::2::3"),
+        Ok(("::3", Ipv6Addr::new(1, 0, 0, 0, 0, 0, 0, 2)))
+    );
+    assert_eq!(
+        ipv6("1:2:3:4:5:6:7:8::"),
+        Ok(("::", Ipv6Addr::new(1, 2, 3, 4, 5, 6, 7, 8)))
+    );
+}
+
+#[test]
+fn reject_invalid_addresses() {
+    for text in ["1:2:3:4:5:6:7", "12345::", "::1.2.3", "", ":1"] {
+        assert!(ipv6(text).is_err(), "{}", text);
+    }
+    for text in ["1.2.3", "01.2.3.4", "1.2.3.1000", "256.0.0.0"] {
+        assert!(ipv4(text).is_err(), "{}", text);
+    }
+    assert_eq!(port("65535"), Ok(("", 65535)));
+    assert_eq!(port("65536").unwrap_err().reason, "port out of range");
+}
+
+#[test]
+fn parse_socket_addresses() {
+    assert_eq!(
+        ip_addr("10.1.2.3"),
+        Ok(("", IpAddr::V4(Ipv4Addr::new(10, 1, 2, 3))))
+    );
+    assert_eq!(ip_addr("::"), Ok(("", IpAddr::V6(Ipv6Addr::UNSPECIFIED))));
+    assert_eq!(
+        socket_addr("[2001:db8::8]:65535"),
+        Ok(("", "[2001:db8::8]:65535".parse::<SocketAddr>().unwrap()))
+    );
+    let error = socket_addr("[::1]80").unwrap_err();
+    assert_eq!(error.index, 5);
+}
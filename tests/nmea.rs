@@ -0,0 +1,84 @@
+use pepser::{
+    datetime::{Date, Time},
+    errors::ErrorSource,
+    nmea::{nmea, sentence, Message, Satellite},
+};
+
+#[test]
+fn parse_rmc_and_gsv() {
+    let input = "$GPRMC,123519.50,A,4807.038,N,01131.000,W,022.4,084.4,230324,003.1,W*58\r\n$GNRMC,,V,,,,,,,,,,N*4D";
+    let (remainder, parsed) = nmea(input).unwrap();
+    let Message::Rmc(rmc) = parsed.message else {
+        panic!("expected RMC");
+    };
+    assert!(rmc.active);
+    assert_eq!(
+        rmc.time,
+        Some(Time {
+            hour: 12,
+            minute: 35,
+            second: 19,
+            nanosecond: 500_000_000
+        })
+    );
+    assert_eq!(
+        rmc.date,
+        Some(Date {
+            year: 2024,
+            month: 3,
+            day: 23
+        })
+    );
+    assert!((rmc.position.unwrap().longitude + 11.516_666_666).abs() < 1e-6);
+    assert_eq!(rmc.speed_knots, Some(22.4));
+    assert_eq!(rmc.magnetic_variation, Some(-3.1));
+
+    let (_, parsed) = nmea(remainder).unwrap();
+    assert_eq!(parsed.talker, "GN");
+    let Message::Rmc(void) = parsed.message else {
+        panic!("expected RMC");
+    };
+    assert!(!void.active);
+    assert_eq!((void.time, void.position, void.date), (None, None, None));
+
+    let (_, parsed) =
+        nmea("$GPGSV,2,1,08,01,40,083,46,02,17,308,,12,07,344,39,14,22,228,45*70").unwrap();
+    let Message::Gsv(gsv) = parsed.message else {
+        panic!("expected GSV");
+    };
+    assert_eq!((gsv.messages, gsv.message, gsv.in_view), (2, 1, 8));
+    assert_eq!(gsv.satellites.len(), 4);
+    assert_eq!(
+        gsv.satellites[1],
+        Satellite {
+            prn: 2,
+            elevation: Some(17),
+            azimuth: Some(308),
+            snr: None
+        }
+    );
+}
+
+#[test]
+fn verify_framing() {
+    let (_, parsed) = sentence("$GPXYZ,1,,3").unwrap();
+    assert_eq!((parsed.talker, parsed.kind), ("GP", "XYZ"));
+    assert_eq!(parsed.fields, vec!["1", "", "3"]);
+
+    let error =
+        sentence("$GPGGA,123519,4807.038,N,01131.000,E,1,08,0.9,545.4,M,46.9,M,,*48").unwrap_err();
+    assert_eq!(error.source, ErrorSource::Checksum);
+    assert_eq!(error.index, 62);
+    assert!(sentence("$GPGGA,1*4").is_err());
+    assert!(sentence("GPGGA,1").is_err());
+
+    let error = nmea("$GPGGA,123519,4807.038,N,01131.000,X,1,08,0.9,545.4,M,46.9,M,,").unwrap_err();
+    assert_eq!(error.reason, "invalid field");
+    assert_eq!(error.index, 35);
+    let error = nmea("$GPGGA,123519,4807.038,N").unwrap_err();
+    assert_eq!((error.reason.as_ref(), error.index), ("missing field", 24));
+    let error = nmea("$GPGGA,123519,4807.038,N\r\n").unwrap_err();
+    assert_eq!((error.reason.as_ref(), error.index), ("missing field", 24));
+    let error = nmea("$GPGGA").unwrap_err();
+    assert_eq!((error.reason.as_ref(), error.index), ("missing field", 6));
+}
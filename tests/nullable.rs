@@ -0,0 +1,93 @@
+#![cfg(feature = "std")]
+
+use pepser::{
+    errors::ErrorSource,
+    grammar::Grammar,
+    impls::{detect_nullable_loops, sequence, take_while0, take_while1},
+    traits::{chainl1, sep_by, Parser},
+};
+
+fn digits(input: &str) -> pepser::traits::ParseResult<&str, &str> {
+    take_while0(|c: char| c.is_ascii_digit()).parse(input)
+}
+
+#[test]
+fn repetitions_report_nullable_parsers() {
+    // Without the detection, the repetitions stop where their parser consumed nothing
+    assert_eq!(digits.many0().parse("12,3"), Ok((",3", vec!["12"])));
+    assert_eq!(digits.fold(|| 0, |n, _| n + 1).parse("1,"), Ok((",", 1)));
+
+    let error = detect_nullable_loops(|| digits.many0().parse("12,3")).unwrap_err();
+    assert_eq!((error.index, error.source), (2, ErrorSource::Many));
+    assert!(error.committed);
+    assert_eq!(
+        error.reason,
+        "many repeated a parser which consumed nothing"
+    );
+
+    let folded = detect_nullable_loops(|| digits.fold(|| 0, |n, _| n + 1).parse("1,"));
+    assert_eq!(folded.unwrap_err().index, 1);
+
+    let chained =
+        detect_nullable_loops(|| chainl1(digits, sequence("").map(|_| |a, _| a)).parse("1+"));
+    assert_eq!(chained.unwrap_err().index, 1);
+
+    // Repetitions which consume at every step are unaffected, as is the end of the input
+    let words = detect_nullable_loops(|| {
+        take_while1(|c: char| c != ' ')
+            .skip(sequence(" ").many0())
+            .many0()
+            .parse("a b c")
+    });
+    assert_eq!(words, Ok(("", vec!["a", "b", "c"])));
+    assert_eq!(
+        detect_nullable_loops(|| digits.many0().parse("12")),
+        Ok(("", vec!["12"]))
+    );
+}
+
+#[test]
+fn sep_by_stops_on_nullable_rounds() {
+    // An element and a separator which both consume nothing used to repeat forever
+    let mut list = sep_by(digits, take_while0(|c| c == ','));
+    assert_eq!(list.parse("1,2;"), Ok((";", vec!["1", "2", ""])));
+    assert_eq!(list.parse(";"), Ok((";", vec![""])));
+
+    let error = detect_nullable_loops(|| sep_by(digits, take_while0(|c| c == ',')).parse("1,2;"))
+        .unwrap_err();
+    assert_eq!(error.index, 3);
+    assert!(error.reason.starts_with("sep_by"));
+
+    // A separator which consumes between empty elements is progress
+    let empty = detect_nullable_loops(|| sep_by(digits, sequence(",")).parse(",,"));
+    assert_eq!(empty, Ok(("", vec!["", "", ""])));
+}
+
+#[test]
+fn errors_name_the_rules() {
+    let grammar = Grammar::new();
+    let g = grammar.clone();
+    grammar.rule("document", move || g.call("list").skip(sequence(";")));
+    let g = grammar.clone();
+    grammar.rule("list", move || {
+        g.call("item").many0().map(|items| items.concat())
+    });
+    grammar.rule("item", || {
+        take_while0(|c: char| c.is_alphabetic()).map(String::from)
+    });
+
+    assert_eq!(grammar.parse("document", "ab;"), Ok(("", "ab".to_string())));
+    assert!(grammar.parse("document", "ab1;").is_err());
+
+    let error = detect_nullable_loops(|| grammar.parse("document", "ab1;")).unwrap_err();
+    assert_eq!(error.index, 2);
+    assert_eq!(
+        error.reason,
+        "many repeated a parser which consumed nothing in rule list, called from document"
+    );
+
+    // The detection ends with the closure, even when it panics
+    let _ = std::panic::catch_unwind(|| detect_nullable_loops(|| panic!("stop")));
+    assert!(grammar.parse("document", "ab;").is_ok());
+    assert_eq!(digits.many0().parse("1,"), Ok((",", vec!["1"])));
+}
@@ -0,0 +1,186 @@
+#![cfg(feature = "std")]
+
+use pepser::{
+    datetime, dotenv,
+    errors::ErrorSource,
+    git, http, icalendar,
+    impls::{balanced_with_strings, sequence},
+    json::{json_extract, json_resilient, json_value, json_value_ref, JsonNumber, JsonValue},
+    multipart, nmea, regex, resp, robots, semver, sexpr, sql,
+    traits::{drop_until, skip_until, Parser},
+    uri, xml, yaml,
+};
+
+/// Fragments likely to reach the edge cases of the parsers: multibyte characters, unfinished
+/// escapes and numbers too large for their type
+const FRAGMENTS: &[&str] = &[
+    "{",
+    "}",
+    "[",
+    "]",
+    ",",
+    ":",
+    "\"",
+    "\\",
+    "\\u",
+    "1",
+    "0",
+    "-",
+    ".",
+    "e",
+    "é",
+    "日",
+    "\n",
+    " ",
+    "null",
+    "true",
+    "99999999999999999999",
+    "e9999999999",
+    "$GP",
+    "*",
+    "(",
+    ")",
+    "<",
+    ">",
+    "/",
+    "%",
+    "%4",
+    "HTTP/1.1",
+    "\r\n",
+    "2024-01-01T",
+    ":00Z",
+    "P",
+    "~",
+    "^",
+    "$2",
+    "#t",
+    "BEGIN:",
+    ";X=\"",
+    "--b",
+    "Sun, 06 Nov",
+];
+
+/// Openings of nested structures, repeated at the start of some inputs to reach the depth
+/// limits of the recursive parsers
+const OPENINGS: &[&str] = &[
+    "[",
+    "{\"a\":",
+    "{a: ",
+    "(",
+    "'",
+    "<a>",
+    "*1\r\n",
+    "BEGIN:A\r\n",
+];
+
+/// Every parser must fail with an error rather than panic, on any input
+#[test]
+fn never_panic_on_generated_input() {
+    let mut seed: u64 = 0x9e37_79b9_7f4a_7c15;
+    let mut next = move || {
+        seed ^= seed << 13;
+        seed ^= seed >> 7;
+        seed ^= seed << 17;
+        seed
+    };
+    for _ in 0..20_000 {
+        let length = next() % 10;
+        let mut input: String = (0..length)
+            .map(|_| FRAGMENTS[(next() % FRAGMENTS.len() as u64) as usize])
+            .collect();
+        if next() % 50 == 0 {
+            let opening = OPENINGS[(next() % OPENINGS.len() as u64) as usize];
+            input = opening.repeat((next() % 400) as usize) + &input;
+        }
+        let input = input.as_str();
+        if let Err(error) = json_value(input) {
+            error.render(input);
+            error.snippet(input).to_string();
+        }
+        json_resilient(input);
+        let _ = yaml::yaml(input);
+        let _ = xml::xml_document(input);
+        let _ = sexpr::sexprs(input);
+        let _ = sql::select(input);
+        let _ = regex::regex(input);
+        let _ = uri::uri(input);
+        let _ = git::config(input);
+        let _ = dotenv::dotenv(input);
+        git::gitignore(input).is_ignored(input, false);
+        let _ = http::http_request(input);
+        let _ = http::cookie::set_cookie(input);
+        let _ = http::cookie::cookies(input);
+        let _ = http::media::accept(input);
+        let _ = icalendar::components(input);
+        let _ = multipart::multipart("b").parse(input);
+        let _ = semver::version_req(input);
+        let _ = datetime::rfc3339(input);
+        let _ = nmea::nmea(input);
+        let _ = resp::frame(input.as_bytes());
+        robots::robots(input).is_allowed(input, input);
+        let _ = robots::sitemap(input);
+        let _ = balanced_with_strings("(", ")", "\"", '\\').parse(input);
+        let _ = drop_until(sequence("日")).parse(input);
+        let _ = skip_until(sequence("日")).parse(input);
+    }
+}
+
+/// The recursive parsers stop at a maximum depth rather than exhaust the stack
+#[test]
+fn reject_deep_nesting() {
+    #[track_caller]
+    fn exceeded(source: ErrorSource<&str>) {
+        assert_eq!(source, ErrorSource::LimitExceeded);
+    }
+    let arrays = "[".repeat(200_000);
+    exceeded(json_value(&arrays).unwrap_err().source);
+    exceeded(json_value_ref(&arrays).unwrap_err().source);
+    exceeded(json_extract(&arrays, "").unwrap_err().source);
+    exceeded(json_resilient(&arrays).1[0].source.clone());
+    exceeded(yaml::yaml(&"{a: ".repeat(200_000)).unwrap_err().source);
+    exceeded(
+        xml::xml_document(&"<a>".repeat(200_000))
+            .unwrap_err()
+            .source,
+    );
+    exceeded(sexpr::sexprs(&"'(".repeat(200_000)).unwrap_err().source);
+    exceeded(regex::regex(&"(".repeat(200_000)).unwrap_err().source);
+    let query = format!("SELECT {}", "-(".repeat(200_000));
+    exceeded(sql::select(&query).unwrap_err().source);
+
+    let nested = |depth| format!("{}{}", "[".repeat(depth), "]".repeat(depth));
+    assert!(json_value(&nested(128)).is_ok());
+    let too_deep = nested(129);
+    let error = json_value(&too_deep).unwrap_err();
+    assert_eq!(
+        (error.index, error.source),
+        (128, ErrorSource::LimitExceeded)
+    );
+    assert!(xml::xml_element(&format!("{}{}", "<a>".repeat(128), "</a>".repeat(128))).is_ok());
+    assert!(sexpr::sexpr(&format!("{}{}", "(".repeat(128), ")".repeat(128))).is_ok());
+}
+
+#[test]
+fn drop_until_steps_over_characters() {
+    assert_eq!(drop_until(sequence("b")).parse("éb"), Ok(("", "b")));
+    assert!(drop_until(sequence("b")).parse("日é").is_err());
+    assert_eq!(
+        skip_until(sequence("b")).parse("日éb!"),
+        Ok(("!", ("日é", "b")))
+    );
+}
+
+#[test]
+fn report_number_overflows() {
+    assert_eq!(
+        json_value("123456789012345678901234"),
+        Ok((
+            "",
+            JsonValue::Number(JsonNumber::Float(123456789012345678901234.0))
+        ))
+    );
+    assert_eq!(
+        json_value("1e99999999999").unwrap_err().reason,
+        "exponent overflow"
+    );
+}
@@ -0,0 +1,64 @@
+use pepser::{
+    errors::ErrorSource,
+    impls::{sequence, take_while1},
+    traits::{followed_by, not_followed_by, Parser},
+};
+
+#[test]
+fn predicates_consume_nothing() {
+    let mut ahead = followed_by(take_while1(|c: char| c.is_ascii_digit()));
+    assert_eq!(ahead.parse("12ab"), Ok(("12ab", "12")));
+    assert_eq!(ahead.parse("ab").unwrap_err().index, 0);
+
+    let mut not_digit = not_followed_by(take_while1(|c: char| c.is_ascii_digit()));
+    assert_eq!(not_digit.parse("ab"), Ok(("ab", ())));
+    let error = not_digit.parse("1").unwrap_err();
+    assert_eq!((error.index, error.source), (0, ErrorSource::Predicate));
+
+    // Nested in a sequence, the error of a predicate is an offset of the whole input
+    let mut word = sequence("a").and(not_followed_by(sequence("b")));
+    assert_eq!(word.parse("ac"), Ok(("c", ("a", ()))));
+    assert_eq!(word.parse("ab").unwrap_err().index, 1);
+}
+
+#[test]
+fn predicates_keep_committed_errors() {
+    let mut committed = not_followed_by(sequence("(").and(sequence(")").cut()));
+    assert_eq!(committed.parse("x"), Ok(("x", ())));
+    let error = committed.parse("(x").unwrap_err();
+    assert!(error.committed);
+    assert_eq!(error.index, 1);
+}
+
+#[test]
+fn or_longest_keeps_the_longest_match() {
+    let identifier = || take_while1(|c: char| c.is_ascii_alphanumeric());
+    let mut token = sequence("let").or_longest(identifier());
+    assert_eq!(token.parse("letter = 1"), Ok((" = 1", "letter")));
+    assert_eq!(token.parse("let x"), Ok((" x", "let")));
+    // Ordered choice stops at the first alternative
+    assert_eq!(
+        sequence("let").or(identifier()).parse("letter"),
+        Ok(("ter", "let"))
+    );
+
+    // A tie keeps the first alternative
+    let mut tie = sequence("ab")
+        .map(|_| 1)
+        .or_longest(sequence("ab").map(|_| 2));
+    assert_eq!(tie.parse("ab"), Ok(("", 1)));
+
+    // The error which reaches furthest wins
+    let mut failing = sequence("a").and(sequence("x")).or_longest(
+        sequence("a")
+            .and(sequence("b"))
+            .and(sequence("y"))
+            .map(|(first, _)| first),
+    );
+    assert_eq!(failing.parse("abz").unwrap_err().index, 2);
+
+    let mut committed = sequence("a")
+        .and(sequence("x").cut())
+        .or_longest(sequence("a").and(sequence("b")));
+    assert!(committed.parse("ab").unwrap_err().committed);
+}
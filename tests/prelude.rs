@@ -0,0 +1,44 @@
+use pepser::prelude::*;
+
+#[derive(Debug, PartialEq)]
+enum Token<'a> {
+    Number(&'a str),
+    Word(&'a str),
+}
+
+#[test]
+fn parse_with_the_prelude() {
+    let number = take_while1(|c| c.is_ascii_digit()).map(Token::Number);
+    let word = take_while1(|c| c.is_alphabetic()).map(Token::Word);
+    let mut tokens = terminated(sep_by(number.or(word), ws()), eof());
+
+    assert_eq!(
+        tokens.parse("12 abc 3"),
+        Ok((
+            "",
+            vec![Token::Number("12"), Token::Word("abc"), Token::Number("3")]
+        ))
+    );
+    let error: ParserError<&str> = tokens.parse("12 ?").unwrap_err();
+    assert_eq!((error.index, error.source), (3, ErrorSource::EOF));
+    assert_eq!(line_column("12 ?", error.index), (1, 4));
+}
+
+#[test]
+fn modules_reexport_the_old_paths() {
+    fn same<T>(_: T, _: T) {}
+    let error: pepser::errors::ParserError<&str> =
+        pepser::errors::ParserError::new(0, pepser::errors::ErrorSource::Many, "many");
+    same(
+        error.clone(),
+        pepser::error::ParserError::new(0, ErrorSource::Many, "many"),
+    );
+
+    let old: pepser::traits::BoxedParser<&str, &str> = Box::new(pepser::impls::sequence("a"));
+    let mut new: pepser::combinator::BoxedParser<&str, &str> = old;
+    assert_eq!(
+        new.parse("ab"),
+        pepser::primitive::sequence("a").parse("ab")
+    );
+    same(pepser::primitive::any_char, pepser::impls::any_char);
+}
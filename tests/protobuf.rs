@@ -0,0 +1,81 @@
+use pepser::protobuf::{field, message, tag, varint, zigzag, WireValue};
+
+#[test]
+fn parse_varints() {
+    assert_eq!(varint(&[0x00]), Ok((&[][..], 0)));
+    assert_eq!(varint(&[0xAC, 0x02]), Ok((&[][..], 300)));
+    let mut max = vec![0xFF; 9];
+    max.push(0x01);
+    assert_eq!(varint(&max), Ok((&[][..], u64::MAX)));
+
+    max[9] = 0x02;
+    assert_eq!(varint(&max).unwrap_err().index, 9);
+    assert_eq!(varint(&[0xFF; 11]).unwrap_err().index, 9);
+    assert_eq!(varint(&[0x80, 0x80]).unwrap_err().index, 2);
+    assert_eq!(varint(&[]).unwrap_err().index, 0);
+
+    assert_eq!(
+        [0, 1, 2, 3, u64::MAX - 1, u64::MAX].map(zigzag),
+        [0, -1, 1, -2, i64::MAX, i64::MIN]
+    );
+    assert_eq!(tag(&[0xF8, 0x01]), Ok((&[][..], (31, 0))));
+    assert_eq!(tag(&[0x02]).unwrap_err().index, 0);
+}
+
+#[test]
+fn parse_message() {
+    let mut bytes = vec![0x08, 0x96, 0x01];
+    bytes.extend([0x11, 1, 0, 0, 0, 0, 0, 0, 0]);
+    bytes.extend([0x1A, 0x02, 0x08, 0x07]);
+    bytes.extend([0x23, 0x08, 0x01, 0x24]);
+    bytes.extend([0x2D, 0x00, 0x00, 0x80, 0x3F]);
+    let (remainder, fields) = message(&bytes).unwrap();
+    assert!(remainder.is_empty());
+    assert_eq!(
+        fields,
+        vec![
+            (1, WireValue::Varint(150)),
+            (2, WireValue::Fixed64(1)),
+            (3, WireValue::LengthDelimited(&[0x08, 0x07])),
+            (4, WireValue::Group(vec![(1, WireValue::Varint(1))])),
+            (5, WireValue::Fixed32(1.0f32.to_bits())),
+        ]
+    );
+
+    // An embedded message is parsed from the bytes of its field
+    let WireValue::LengthDelimited(embedded) = fields[2].1 else {
+        panic!("expected a length delimited field")
+    };
+    assert_eq!(
+        message(embedded),
+        Ok((&[][..], vec![(1, WireValue::Varint(7))]))
+    );
+    assert_eq!(
+        field(&bytes[3..]),
+        Ok((&bytes[12..], (2, WireValue::Fixed64(1))))
+    );
+    assert_eq!(message(&[]), Ok((&[][..], vec![])));
+}
+
+#[test]
+fn invalid_messages() {
+    let mut bytes = vec![0x08, 0x01, 0x1A, 0x05, b'a'];
+    assert_eq!(message(&bytes).unwrap_err().index, 5);
+    bytes.truncate(2);
+    bytes.extend([0x2D, 0x00, 0x00]);
+    assert_eq!(message(&bytes).unwrap_err().index, 5);
+
+    // Wire types 6 and 7 do not exist
+    assert_eq!(message(&[0x08, 0x01, 0x0E]).unwrap_err().index, 2);
+    assert_eq!(message(&[0x00, 0x01]).unwrap_err().index, 0);
+    // Groups end with the field number they start with
+    assert_eq!(message(&[0x23, 0x2C]).unwrap_err().index, 1);
+    assert_eq!(message(&[0x24]).unwrap_err().index, 0);
+    assert_eq!(message(&[0x23, 0x08]).unwrap_err().index, 2);
+    assert_eq!(message(&[0x23, 0x08, 0x01]).unwrap_err().index, 3);
+
+    let deep = vec![0x0B; 101];
+    let error = message(&deep).unwrap_err();
+    assert_eq!(error.index, 100);
+    assert_eq!(error.reason, "group nested too deeply");
+}
@@ -0,0 +1,113 @@
+#![cfg(feature = "std")]
+
+use std::{env, fs, io};
+
+use pepser::{
+    impls::{sequence, take_while1},
+    json::{json_value, JsonNumber, JsonValue},
+    read::{parse_file, parse_items, parse_reader, ReadError, StreamParser},
+    traits::{discard, ParseResult, Parser},
+};
+
+#[test]
+fn parse_files() {
+    let path = env::temp_dir().join(format!("pepser-read-{}.json", std::process::id()));
+    fs::write(&path, "{\"a\": [1, 2]}\n").unwrap();
+    let value = parse_file(json_value, &path);
+    fs::remove_file(&path).unwrap();
+
+    let JsonValue::Object(members) = value.unwrap() else {
+        panic!("expected an object");
+    };
+    assert_eq!(
+        members["a"],
+        JsonValue::Array(vec![
+            JsonValue::Number(JsonNumber::Int(1)),
+            JsonValue::Number(JsonNumber::Int(2))
+        ])
+    );
+    assert!(matches!(
+        parse_file(json_value, path),
+        Err(ReadError::Io(error)) if error.kind() == io::ErrorKind::NotFound
+    ));
+}
+
+#[test]
+fn reader_errors() {
+    assert!(matches!(
+        parse_reader(json_value, &[0x22, 0xff, 0x22][..]),
+        Err(ReadError::Io(error)) if error.kind() == io::ErrorKind::InvalidData
+    ));
+    let Err(ReadError::Parse { message, .. }) = parse_reader(json_value, "[1] 2".as_bytes()) else {
+        panic!("expected a parse error");
+    };
+    assert_eq!(
+        message,
+        "input is not empty at index 4 (expected end of input)"
+    );
+}
+
+/// Hands out a single byte per read
+struct Trickle<'a>(&'a [u8]);
+
+impl io::Read for Trickle<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let Some((first, rest)) = self.0.split_first() else {
+            return Ok(0);
+        };
+        buf[0] = *first;
+        self.0 = rest;
+        Ok(1)
+    }
+}
+
+#[test]
+fn stream_items() {
+    let items = parse_items(
+        json_value,
+        Trickle("12 [\"é日\", true]\n{\"a\": null} \n".as_bytes()),
+    )
+    .collect::<Result<Vec<_>, _>>()
+    .unwrap();
+    assert_eq!(
+        items,
+        vec![
+            json_value("12").unwrap().1,
+            json_value("[\"é日\", true]").unwrap().1,
+            json_value("{\"a\": null}").unwrap().1,
+        ]
+    );
+
+    let mut items = parse_items(json_value, Trickle(b"[1] [2,, 3]"));
+    assert!(matches!(items.next(), Some(Ok(_))));
+    assert!(matches!(
+        items.next(),
+        Some(Err(ReadError::Parse { index: 7, .. }))
+    ));
+    assert!(items.next().is_none());
+
+    // A committed error is reported without waiting for the rest of the input
+    fn tag(input: &str) -> ParseResult<&str, String> {
+        discard(sequence("#"), take_while1(char::is_alphabetic).cut())
+            .map(str::to_string)
+            .parse(input)
+    }
+    let mut stream = StreamParser::new(tag);
+    stream.feed(b"#a#1");
+    assert_eq!(stream.next_item().unwrap().unwrap(), "a");
+    assert!(matches!(
+        stream.next_item(),
+        Some(Err(ReadError::Parse { index: 3, .. }))
+    ));
+
+    let mut stream = StreamParser::new(json_value);
+    stream.feed(&[b'"', 0xc3]);
+    assert!(stream.next_item().is_none());
+    stream.feed(&[0xa9, b'"', 0xff]);
+    assert!(matches!(stream.next_item(), Some(Ok(JsonValue::String(s))) if s == "é"));
+    assert!(matches!(
+        stream.next_item(),
+        Some(Err(ReadError::Io(error))) if error.kind() == io::ErrorKind::InvalidData
+    ));
+    assert!(stream.next_item().is_none());
+}
@@ -0,0 +1,87 @@
+#![cfg(feature = "std")]
+
+use pepser::{
+    errors::ErrorSource,
+    impls::sequence,
+    regex::regex,
+    traits::{sep_by, ParseResult, Parser},
+};
+
+#[test]
+fn match_prefix() {
+    let mut identifier = regex("[a-zA-Z_]\\w*").unwrap();
+    assert_eq!(identifier.parse("foo_42 = 1"), Ok((" = 1", "foo_42")));
+    assert_eq!(
+        identifier.parse("42foo").map_err(|error| error.source),
+        Err(ErrorSource::Regex)
+    );
+
+    let mut number = regex("-?\\d+(\\.\\d+)?").unwrap();
+    assert_eq!(number.parse("-12.5e3"), Ok(("e3", "-12.5")));
+    assert_eq!(number.parse("7."), Ok((".", "7")));
+}
+
+#[test]
+fn alternation_and_anchors() {
+    let keyword = regex("(true|false|null)$").unwrap();
+    assert!(keyword.is_match("false"));
+    assert!(!keyword.is_match("falsey"));
+    assert!(regex("a(b|c)*d").unwrap().is_match("abccbd"));
+    assert!(regex("[^0-9]+").unwrap().is_match("abc"));
+    assert!(!regex("[^0-9]+").unwrap().is_match("ab1"));
+    assert!(regex("(a*)*b").unwrap().is_match("aaab"));
+    assert!(regex("x?").unwrap().is_match(""));
+}
+
+#[test]
+fn compose_with_combinators() {
+    fn hex_list(input: &str) -> ParseResult<&str, Vec<&str>> {
+        sep_by(regex("0x[0-9a-fA-F]+").unwrap(), sequence(",")).parse(input)
+    }
+    assert_eq!(
+        hex_list("0x1F,0xab,0x0;"),
+        Ok((";", vec!["0x1F", "0xab", "0x0"]))
+    );
+}
+
+#[test]
+fn reject_invalid_patterns() {
+    assert!(regex("(ab").is_err());
+    assert!(regex("[a-z").is_err());
+    assert!(regex("a)").is_err());
+    assert!(regex("\\").is_err());
+}
+
+#[test]
+fn reject_stacked_quantifiers() {
+    for pattern in ["a**", "a+*", ".*?", "(ab)?+", "x|a*+"] {
+        let error = regex(pattern).unwrap_err();
+        assert_eq!(error.source, ErrorSource::Regex, "{pattern}");
+        assert_eq!(
+            error.reason, "a quantifier follows a quantifier",
+            "{pattern}"
+        );
+    }
+    assert_eq!(regex("ab+?").unwrap_err().index, 3);
+    assert!(regex("(a*)*").is_ok());
+}
+
+#[test]
+fn match_in_linear_time() {
+    let long = "a".repeat(200_000);
+    assert!(regex("a*").unwrap().is_match(&long));
+    assert!(regex("(a|b)+").unwrap().is_match(&long));
+    assert_eq!(
+        regex("a+").unwrap().parse(long.as_str()),
+        Ok(("", long.as_str()))
+    );
+
+    let input = "a".repeat(5_000);
+    assert!(!regex("(a*)*(a*)*(a*)*b").unwrap().is_match(&input));
+    assert!(!regex(".*.*.*.*.*b").unwrap().is_match(&input));
+    assert_eq!(
+        regex("(a|ab)(c|bcd)(d*)").unwrap().parse("abcd"),
+        Ok(("", "abcd"))
+    );
+    assert_eq!(regex("(a*)*b?").unwrap().parse("aaac"), Ok(("c", "aaa")));
+}
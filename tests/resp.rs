@@ -0,0 +1,78 @@
+use pepser::resp::{decode, frame, Frame};
+
+#[test]
+fn parse_resp3_frames() {
+    let input = b"%2\r\n+name\r\n$6\r\npepser\r\n+tags\r\n~2\r\n#t\r\n_\r\n\
+>2\r\n,-1.5\r\n(-12345678901234567890\r\n=8\r\ntxt:hi\r\n\r\n!5\r\nERR x\r\n";
+    let (remainder, map) = frame(input).unwrap();
+    assert_eq!(
+        map,
+        Frame::Map(vec![
+            (Frame::SimpleString(b"name"), Frame::Bulk(Some(b"pepser"))),
+            (
+                Frame::SimpleString(b"tags"),
+                Frame::Set(vec![Frame::Boolean(true), Frame::Null])
+            ),
+        ])
+    );
+    let (remainder, push) = frame(remainder).unwrap();
+    assert_eq!(
+        push,
+        Frame::Push(vec![
+            Frame::Double(-1.5),
+            Frame::BigNumber(b"-12345678901234567890")
+        ])
+    );
+    let (remainder, verbatim) = frame(remainder).unwrap();
+    assert_eq!(
+        verbatim,
+        Frame::Verbatim {
+            format: b"txt",
+            text: b"hi\r\n"
+        }
+    );
+    assert_eq!(frame(remainder), Ok((&b""[..], Frame::BulkError(b"ERR x"))));
+
+    assert_eq!(
+        frame(b"*-1\r\n$-1\r\n"),
+        Ok((&b"$-1\r\n"[..], Frame::Array(None)))
+    );
+    assert_eq!(frame(b"$-1\r\n"), Ok((&b""[..], Frame::Bulk(None))));
+    assert_eq!(frame(b"$0\r\n\r\n"), Ok((&b""[..], Frame::Bulk(Some(b"")))));
+    assert_eq!(frame(b"-ERR\r\n"), Ok((&b""[..], Frame::Error(b"ERR"))));
+    assert!(matches!(frame(b",inf\r\n"), Ok((_, Frame::Double(d))) if d == f64::INFINITY));
+}
+
+#[test]
+fn resp_errors() {
+    let index = |input: &[u8]| frame(input).unwrap_err().index;
+    assert_eq!(index(b"*2\r\n:1\r\n?\r\n"), 8);
+    assert_eq!(index(b"*1\r\n:x\r\n"), 5);
+    assert_eq!(index(b"$2\r\nabcd\r\n"), 6);
+    assert_eq!(index(b"~-1\r\n"), 1);
+    assert_eq!(index(b"#x\r\n"), 1);
+    assert_eq!(index(&b"*1\r\n".repeat(1000)), 516);
+    assert_eq!(
+        frame(b"=2\r\nab\r\n").unwrap_err().reason,
+        "expected a format before the text"
+    );
+}
+
+#[test]
+fn decode_partial_buffers() {
+    let stream = b"*3\r\n$3\r\nSET\r\n$1\r\nk\r\n$5\r\nv\r\nal\r\n:1\r\n";
+    let (first, length) = decode(stream).unwrap().unwrap();
+    assert_eq!(
+        first,
+        Frame::Array(Some(vec![
+            Frame::Bulk(Some(b"SET")),
+            Frame::Bulk(Some(b"k")),
+            Frame::Bulk(Some(b"v\r\nal")),
+        ]))
+    );
+    assert_eq!(length, stream.len() - 4);
+    for end in 0..length {
+        assert_eq!(decode(&stream[..end]), Ok(None), "{end}");
+    }
+    assert!(decode(b"$2\r\nab!").is_err());
+}
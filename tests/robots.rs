@@ -0,0 +1,138 @@
+#![cfg(feature = "std")]
+
+use pepser::{
+    robots::{robots, sitemap, Group, Rule, Sitemap, SitemapEntry},
+    xml::xml_document,
+};
+
+#[test]
+fn parse_groups() {
+    let parsed = robots(
+        "\u{feff}# rules for everyone\n\
+        User-Agent: Googlebot\n\
+        user-agent: Bingbot # and this one\n\
+        Disallow: /search\n\
+        CRAWL-DELAY: 2.5\n\
+        Sitemap: https://example.com/sitemap.xml\n\
+        no colon on this line\n\
+        Unknown: ignored\n\
+        User-agent: *\n\
+        Allow: /\n\
+        Crawl-delay: soon\n",
+    );
+    assert_eq!(
+        parsed.groups,
+        [
+            Group {
+                user_agents: vec!["Googlebot".to_string(), "Bingbot".to_string()],
+                rules: vec![Rule {
+                    allow: false,
+                    path: "/search".to_string(),
+                }],
+                crawl_delay: Some(2.5),
+            },
+            Group {
+                user_agents: vec!["*".to_string()],
+                rules: vec![Rule {
+                    allow: true,
+                    path: "/".to_string(),
+                }],
+                crawl_delay: None,
+            },
+        ]
+    );
+    assert_eq!(parsed.sitemaps, ["https://example.com/sitemap.xml"]);
+    assert_eq!(parsed.crawl_delay("bingbot"), Some(2.5));
+    assert_eq!(parsed.crawl_delay("other"), None);
+
+    // Rules before any user agent belong to no group
+    assert!(robots("Disallow: /\n").groups.is_empty());
+}
+
+#[test]
+fn allow_paths() {
+    let parsed = robots(
+        "User-agent: *\n\
+        Disallow: /*.pdf$\n\
+        Disallow: /tmp\n\
+        Allow: /tmp/shared\n\
+        Disallow: /a*b/\n\
+        Allow: /page\n\
+        Disallow: /page\n\
+        Disallow:\n\
+        \n\
+        User-agent: Crawler\n\
+        User-agent: crawler\n\
+        Disallow: /crawler-only\n\
+        User-agent: crawler\n\
+        Disallow: /also\n",
+    );
+    let allowed = |path| parsed.is_allowed("Any/1.0", path);
+    assert!(!allowed("/docs/file.pdf"));
+    assert!(allowed("/docs/file.pdf?download"));
+    assert!(!allowed("/tmp/file"));
+    assert!(allowed("/tmp/shared/file"));
+    assert!(!allowed("/axxb/c"));
+    assert!(allowed("/ab"));
+    assert!(allowed("/page"));
+    assert!(allowed("/index.html"));
+
+    // The groups of a crawler are merged, and replace those of `*`
+    assert!(!parsed.is_allowed("CRAWLER", "/crawler-only"));
+    assert!(!parsed.is_allowed("Crawler/2.0 (+https://example.com)", "/also"));
+    assert!(parsed.is_allowed("Crawler", "/tmp/file"));
+
+    let blocked = robots("User-agent: *\nDisallow: /\n");
+    assert!(!blocked.is_allowed("Any", "/"));
+    assert!(blocked.is_allowed("Any", "/robots.txt"));
+}
+
+#[test]
+fn parse_sitemaps() {
+    let (_, urls) = sitemap(
+        "<urlset>\
+        <url><loc> https://example.com/a </loc><lastmod>2024-05-01</lastmod>\
+        <changefreq>weekly</changefreq><priority>1.0</priority></url>\
+        <url><loc>https://example.com/b</loc></url>\
+        </urlset>",
+    )
+    .unwrap();
+    assert_eq!(
+        urls,
+        Sitemap::Urls(vec![
+            SitemapEntry {
+                location: "https://example.com/a".to_string(),
+                last_modified: Some("2024-05-01".to_string()),
+                change_frequency: Some("weekly".to_string()),
+                priority: Some(1.0),
+            },
+            SitemapEntry {
+                location: "https://example.com/b".to_string(),
+                last_modified: None,
+                change_frequency: None,
+                priority: None,
+            },
+        ])
+    );
+
+    let (_, index) = sitemap(
+        "<sm:sitemapindex xmlns:sm=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\
+        <sm:sitemap><sm:loc>https://example.com/pages.xml</sm:loc></sm:sitemap>\
+        </sm:sitemapindex>",
+    )
+    .unwrap();
+    let Sitemap::Index(sitemaps) = index else {
+        panic!("expected a sitemap index")
+    };
+    assert_eq!(sitemaps[0].location, "https://example.com/pages.xml");
+
+    assert!(sitemap("<feed></feed>").is_err());
+    assert!(sitemap("<urlset><url><lastmod>x</lastmod></url></urlset>").is_err());
+    assert!(sitemap("<urlset><url><loc>a</loc><priority>2</priority></url></urlset>").is_err());
+    // Errors of the document are those of the XML parser
+    let unclosed = "<urlset><url>";
+    assert_eq!(
+        sitemap(unclosed).unwrap_err(),
+        xml_document(unclosed).unwrap_err()
+    );
+}
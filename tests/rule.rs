@@ -0,0 +1,29 @@
+use pepser::{
+    errors::ErrorSource,
+    impls::{take_while1, ws},
+    rule,
+};
+
+rule! {
+    identifier -> &str = (take_while1(|c| c.is_ascii_alphabetic()));
+    argument -> &str = identifier "," ? (ws()) => |(argument, _, _)| argument;
+    pub call -> (&str, Vec<&str>) = identifier "(" argument* ")" => |(name, _, arguments, _)| (name, arguments);
+}
+
+#[test]
+fn expand_to_sequences() {
+    assert_eq!(identifier("abc("), Ok(("(", "abc")));
+    assert_eq!(call("f(a, b)"), Ok(("", ("f", vec!["a", "b"]))));
+    assert_eq!(call("f()rest"), Ok(("rest", ("f", vec![]))));
+}
+
+#[test]
+fn errors_carry_rule_context() {
+    let error = call("(a)").unwrap_err();
+    assert_eq!(error.reason, "expected call");
+    assert_eq!(error.source, ErrorSource::TakeWhile);
+
+    let error = call("f(a, b").unwrap_err();
+    assert_eq!(error.index, 6);
+    assert_eq!(error.source, ErrorSource::Sequence(")"));
+}
@@ -0,0 +1,40 @@
+use pepser::{
+    errors::ErrorSource,
+    impls::{any, none_of, not, take_while1},
+    traits::Parser,
+};
+
+#[test]
+fn ascii_sets_stop_on_char_boundaries() {
+    assert_eq!(
+        none_of("\"\\").parse("héllo wörld\"!"),
+        Ok(("\"!", "héllo wörld"))
+    );
+    assert_eq!(none_of("\"\\").parse("日本語"), Ok(("", "日本語")));
+    assert_eq!(any("ab").parse("abaé"), Ok(("é", "aba")));
+    assert_eq!(not(',').parse("ü,v"), Ok((",v", "ü")));
+    assert_eq!(not('é').parse("caé"), Ok(("é", "ca")));
+    assert_eq!(none_of("éa").parse("xyéz"), Ok(("éz", "xy")));
+}
+
+#[test]
+fn scans_match_take_while() {
+    let input = "key: \"välue\" // ¢omment\n";
+    for set in ["\"", ": ", "/\n", "é", "ü\""] {
+        for start in (0..input.len()).filter(|&i| input.is_char_boundary(i)) {
+            let input = &input[start..];
+            assert_eq!(
+                none_of(set).parse(input),
+                take_while1(|c| !set.contains(c)).parse(input)
+            );
+            assert_eq!(
+                any(set).parse(input),
+                take_while1(|c| set.contains(c)).parse(input)
+            );
+        }
+    }
+    assert_eq!(
+        none_of("a").parse("").unwrap_err().source,
+        ErrorSource::TakeWhile
+    );
+}
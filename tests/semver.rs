@@ -0,0 +1,93 @@
+use pepser::semver::{comparator, version, version_req, Identifier, Version};
+
+fn v(input: &str) -> Version {
+    version(input).unwrap().1
+}
+
+fn matches(req: &str, input: &str) -> bool {
+    version_req(req).unwrap().1.matches(&v(input))
+}
+
+#[test]
+fn parse_version() {
+    assert_eq!(
+        version("1.2.3-alpha.1+build.5 rest"),
+        Ok((
+            " rest",
+            Version {
+                major: 1,
+                minor: 2,
+                patch: 3,
+                pre: vec![
+                    Identifier::AlphaNumeric("alpha".to_string()),
+                    Identifier::Numeric(1)
+                ],
+                build: vec!["build".to_string(), "5".to_string()],
+            }
+        ))
+    );
+    assert_eq!(v("1.0.0-rc.1+001").to_string(), "1.0.0-rc.1+001");
+    assert!(version("01.2.3").is_err());
+    assert!(version("1.2.3-01").is_err());
+    assert!(version("1.2").is_err());
+}
+
+#[test]
+fn order_by_precedence() {
+    let mut versions = [
+        v("1.0.0"),
+        v("1.0.0-rc.1"),
+        v("1.0.0-beta.11"),
+        v("1.0.0-alpha"),
+        v("1.0.0-beta.2"),
+        v("1.0.0-alpha.1"),
+        v("0.9.12"),
+    ];
+    versions.sort();
+    assert_eq!(
+        versions.iter().map(Version::to_string).collect::<Vec<_>>(),
+        vec![
+            "0.9.12",
+            "1.0.0-alpha",
+            "1.0.0-alpha.1",
+            "1.0.0-beta.2",
+            "1.0.0-beta.11",
+            "1.0.0-rc.1",
+            "1.0.0"
+        ]
+    );
+}
+
+#[test]
+fn match_requirements() {
+    assert!(matches("^1.2", "1.9.0"));
+    assert!(!matches("^1.2", "2.0.0"));
+    assert!(!matches("^0.2.3", "0.3.0"));
+    assert!(matches("~1.2.3", "1.2.9"));
+    assert!(!matches("~1.2.3", "1.3.0"));
+    assert!(matches(">=1.0, <2.0", "1.5.2"));
+    assert!(!matches(">=1.0, <2.0", "2.0.0"));
+    assert!(matches("1.*", "1.4.0"));
+    assert!(matches("*", "42.0.0"));
+    assert!(matches("<=1.2", "1.2.7"));
+    assert!(!matches(">=1.0", "1.1.0-beta"));
+    assert!(matches(">=1.1.0-alpha", "1.1.0-beta"));
+    assert!(version_req(">=").is_err());
+}
+
+#[test]
+fn error_indexes_are_offsets_in_the_input() {
+    let index = |input| version(input).unwrap_err().index;
+    assert_eq!(index("1.2.3-"), 6);
+    assert_eq!(index("1.2.3-alpha.01"), 12);
+    assert_eq!(index("1.2.3-01.alpha"), 6);
+    assert_eq!(index("1.2.3-01+b"), 6);
+    assert_eq!(index("1.2.3-rc.1+"), 11);
+    assert_eq!(index("1.2.x"), 4);
+    assert_eq!(index("1.02.3"), 2);
+
+    let index = |input| comparator(input).unwrap_err().index;
+    assert_eq!(index(">= x"), 3);
+    assert_eq!(index("~1.2.3-beta.007"), 12);
+    assert_eq!(index("=1.2.3+"), 7);
+}
@@ -0,0 +1,50 @@
+use pepser::sexpr::{sexpr, sexprs, SExpr};
+
+fn symbol(name: &str) -> SExpr {
+    SExpr::Symbol(name.to_string())
+}
+
+#[test]
+fn parse_nested_lists() {
+    use SExpr::*;
+    assert_eq!(
+        sexpr("(define (square x) ; squares a number\n  (* x x)) rest"),
+        Ok((
+            " rest",
+            List(vec![
+                symbol("define"),
+                List(vec![symbol("square"), symbol("x")]),
+                List(vec![symbol("*"), symbol("x"), symbol("x")]),
+            ])
+        ))
+    );
+}
+
+#[test]
+fn parse_atoms_and_quotes() {
+    use SExpr::*;
+    assert_eq!(
+        sexprs("42 -7 3.5 .5 -x \"a \\\"b\\\"\" '(1 . 2) 'sym ()"),
+        Ok((
+            "",
+            vec![
+                Integer(42),
+                Integer(-7),
+                Float(3.5),
+                Float(0.5),
+                symbol("-x"),
+                String("a \"b\"".to_string()),
+                Quote(Box::new(List(vec![Integer(1), symbol("."), Integer(2)]))),
+                Quote(Box::new(symbol("sym"))),
+                List(vec![]),
+            ]
+        ))
+    );
+}
+
+#[test]
+fn reject_unbalanced_input() {
+    assert_eq!(sexpr("(a (b c)").map_err(|error| error.index), Err(8));
+    assert!(sexprs("(a b))").is_err());
+    assert!(sexpr("12abc").is_err());
+}
@@ -0,0 +1,33 @@
+use pepser::shlex::{split, word};
+
+#[test]
+fn split_quoted_arguments() {
+    assert_eq!(
+        split("cp  'my file.txt' \"$HOME/a \\\"b\\\"\"\t''").unwrap(),
+        (
+            "",
+            vec!["cp", "my file.txt", "$HOME/a \"b\"", ""]
+                .into_iter()
+                .map(String::from)
+                .collect()
+        )
+    );
+    assert_eq!(word("a'b'\"c\"d e").unwrap(), (" e", String::from("abcd")));
+    assert_eq!(split(r#"'\n' "\n" \n"#).unwrap().1, [r"\n", r"\n", "n"]);
+}
+
+#[test]
+fn skip_comments_and_continuations() {
+    let script = "# comment\nrun a#b \\\n  c # trailing\n\n  # indented\nnext";
+    assert_eq!(split(script).unwrap().1, ["run", "a#b", "c", "next"]);
+    assert_eq!(split("x\\\ny").unwrap().1, ["xy"]);
+    assert_eq!(split("  # only a comment").unwrap().1, Vec::<String>::new());
+}
+
+#[test]
+fn reject_unterminated_input() {
+    assert_eq!(split("echo \"abc").unwrap_err().index, 9);
+    assert_eq!(split("echo abc\\").unwrap_err().index, 9);
+    assert_eq!(split("a \"b\\").unwrap_err().index, 5);
+    assert!(word(" a").is_err());
+}
@@ -0,0 +1,33 @@
+use pepser::size::byte_size;
+
+#[test]
+fn parse_units() {
+    assert_eq!(byte_size("0"), Ok(("", 0)));
+    assert_eq!(byte_size("512B"), Ok(("", 512)));
+    assert_eq!(byte_size("1KiB"), Ok(("", 1024)));
+    assert_eq!(byte_size("1 kb"), Ok(("", 1000)));
+    assert_eq!(byte_size("1.5Gi"), Ok(("", 3 << 29)));
+    assert_eq!(byte_size("2 TB"), Ok(("", 2_000_000_000_000)));
+    assert_eq!(byte_size("1.0000001 KB"), Ok(("", 1000)));
+    assert_eq!(byte_size("7 files").unwrap_err().index, 2);
+    assert_eq!(byte_size("7 / 8"), Ok((" / 8", 7)));
+}
+
+#[test]
+fn report_overflow() {
+    assert_eq!(byte_size("15EiB"), Ok(("", 15 << 60)));
+    assert_eq!(byte_size("18446744073709551615"), Ok(("", u64::MAX)));
+    for size in [
+        "18446744073709551616",
+        "16 EiB",
+        "18.5EB",
+        "99999999999999999999999b",
+    ] {
+        assert_eq!(
+            byte_size(size).unwrap_err().reason,
+            "size overflow",
+            "{}",
+            size
+        );
+    }
+}
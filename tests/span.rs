@@ -0,0 +1,61 @@
+use pepser::{
+    impls::{sequence, take_while1},
+    span::{located, Located, Span},
+    traits::{sep_by, wrapped, Input, Parser},
+};
+
+#[derive(Debug, PartialEq)]
+enum Ast<'a> {
+    Word(Span, &'a str),
+    Group(Span, Vec<Ast<'a>>),
+}
+
+fn node(input: Located<&str>) -> pepser::traits::ParseResult<Located<&str>, Ast<'_>> {
+    located(take_while1(char::is_alphabetic))
+        .spanned()
+        .map(|(span, word)| Ast::Word(span, word))
+        .or(wrapped(
+            located(sequence("(")),
+            sep_by(node, located(sequence(" "))),
+            located(sequence(")")),
+        )
+        .spanned()
+        .map(|(span, nodes)| Ast::Group(span, nodes)))
+        .parse(input)
+}
+
+#[test]
+fn nested_spans_are_absolute() {
+    let (remainder, ast) = node.parse(Located::new("(ça (va bien))!")).unwrap();
+    assert_eq!(remainder.offset, 15);
+    assert_eq!(
+        ast,
+        Ast::Group(
+            Span { start: 0, end: 15 },
+            vec![
+                Ast::Word(Span { start: 1, end: 4 }, "ça"),
+                Ast::Group(
+                    Span { start: 5, end: 14 },
+                    vec![
+                        Ast::Word(Span { start: 6, end: 8 }, "va"),
+                        Ast::Word(Span { start: 9, end: 13 }, "bien")
+                    ]
+                )
+            ]
+        )
+    );
+}
+
+#[test]
+fn offsets_from_base() {
+    let text = "key=value";
+    let (remainder, _) = take_while1(|c| c != '=').parse(text).unwrap();
+    assert_eq!(remainder.offset_from(&text), 3);
+
+    let tokens = [1, 2, 3, 4];
+    assert_eq!((&tokens[2..]).offset_from(&&tokens[..]), 2);
+
+    let located = Located::new(text).drop(4);
+    assert_eq!(located.offset, 4);
+    assert_eq!(located.drop(2).offset_from(&located), 2);
+}
@@ -0,0 +1,183 @@
+use pepser::sql::{
+    select, BinaryOp, Expr, FromClause, Join, JoinKind, Ordering, Select, SelectItem, TableRef,
+    UnaryOp,
+};
+
+fn column(name: &str) -> Expr {
+    Expr::Column {
+        table: None,
+        name: name.to_string(),
+    }
+}
+
+fn binary(left: Expr, op: BinaryOp, right: Expr) -> Expr {
+    Expr::Binary {
+        left: Box::new(left),
+        op,
+        right: Box::new(right),
+    }
+}
+
+#[test]
+fn parse_clauses() {
+    let (remainder, query) = select(
+        "select distinct t.*, \"first name\" n from people as p \
+         cross join t inner join q on q.id = p.id -- comment\n\
+         order by n, age desc limit 5 offset 10;",
+    )
+    .unwrap();
+    assert_eq!(remainder, "");
+    assert_eq!(
+        query,
+        Select {
+            distinct: true,
+            columns: vec![
+                SelectItem::QualifiedWildcard("t".to_string()),
+                SelectItem::Expr {
+                    expr: column("first name"),
+                    alias: Some("n".to_string()),
+                },
+            ],
+            from: Some(FromClause {
+                table: TableRef {
+                    name: "people".to_string(),
+                    alias: Some("p".to_string()),
+                },
+                joins: vec![
+                    Join {
+                        kind: JoinKind::Cross,
+                        table: TableRef {
+                            name: "t".to_string(),
+                            alias: None,
+                        },
+                        on: None,
+                    },
+                    Join {
+                        kind: JoinKind::Inner,
+                        table: TableRef {
+                            name: "q".to_string(),
+                            alias: None,
+                        },
+                        on: Some(binary(
+                            Expr::Column {
+                                table: Some("q".to_string()),
+                                name: "id".to_string(),
+                            },
+                            BinaryOp::Eq,
+                            Expr::Column {
+                                table: Some("p".to_string()),
+                                name: "id".to_string(),
+                            },
+                        )),
+                    },
+                ],
+            }),
+            filter: None,
+            order_by: vec![
+                Ordering {
+                    expr: column("n"),
+                    descending: false,
+                },
+                Ordering {
+                    expr: column("age"),
+                    descending: true,
+                },
+            ],
+            limit: Some(5),
+            offset: Some(10),
+        }
+    );
+    assert_eq!(
+        select("SELECT *").unwrap().1.columns,
+        [SelectItem::Wildcard]
+    );
+}
+
+#[test]
+fn respect_precedence() {
+    let filter = |condition: &str| {
+        select(&format!("SELECT * FROM t WHERE {}", condition))
+            .unwrap()
+            .1
+            .filter
+            .unwrap()
+    };
+    assert_eq!(
+        filter("a OR b AND NOT c"),
+        binary(
+            column("a"),
+            BinaryOp::Or,
+            binary(
+                column("b"),
+                BinaryOp::And,
+                Expr::Unary {
+                    op: UnaryOp::Not,
+                    expr: Box::new(column("c")),
+                },
+            ),
+        )
+    );
+    assert_eq!(
+        filter("1 + 2 * -x < 3 - 1 - 1"),
+        binary(
+            binary(
+                Expr::Integer(1),
+                BinaryOp::Plus,
+                binary(
+                    Expr::Integer(2),
+                    BinaryOp::Multiply,
+                    Expr::Unary {
+                        op: UnaryOp::Minus,
+                        expr: Box::new(column("x")),
+                    },
+                ),
+            ),
+            BinaryOp::Lt,
+            binary(
+                binary(Expr::Integer(3), BinaryOp::Minus, Expr::Integer(1)),
+                BinaryOp::Minus,
+                Expr::Integer(1),
+            ),
+        )
+    );
+    assert_eq!(
+        filter("x NOT BETWEEN 1 AND 2.5 AND name LIKE 'o''%'"),
+        binary(
+            Expr::Between {
+                expr: Box::new(column("x")),
+                low: Box::new(Expr::Integer(1)),
+                high: Box::new(Expr::Float(2.5)),
+                negated: true,
+            },
+            BinaryOp::And,
+            Expr::Like {
+                expr: Box::new(column("name")),
+                pattern: Box::new(Expr::String("o'%".to_string())),
+                negated: false,
+            },
+        )
+    );
+    assert_eq!(
+        filter("(a IS NOT NULL) = TRUE"),
+        binary(
+            Expr::IsNull {
+                expr: Box::new(column("a")),
+                negated: true,
+            },
+            BinaryOp::Eq,
+            Expr::Boolean(true),
+        )
+    );
+}
+
+#[test]
+fn report_error_positions() {
+    let error = select("SELECT a FROM t WHERE").unwrap_err();
+    assert_eq!(error.index, 21);
+    assert_eq!(error.message(), "expected an expression");
+    assert_eq!(select("SELECT a, FROM t").unwrap_err().index, 10);
+    assert_eq!(select("SELECT a FROM t LEFT JOIN u").unwrap_err().index, 27);
+    assert_eq!(select("SELECT a b c").unwrap_err().index, 11);
+    assert_eq!(select("SELECT f(a FROM t").unwrap_err().index, 11);
+    assert_eq!(select("SELECT 'open").unwrap_err().index, 7);
+}
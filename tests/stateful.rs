@@ -0,0 +1,67 @@
+// errors hold the input and with it the state of the parser
+#![allow(clippy::result_large_err)]
+
+use std::collections::HashSet;
+
+use pepser::{
+    impls::{sequence, take_while1, ws},
+    stateful::{lift, state, with_state, Stateful},
+    traits::{discard, ParseResult, Parser},
+};
+
+type Input<'a> = Stateful<&'a str, HashSet<String>>;
+
+fn name(input: Input<'_>) -> ParseResult<Input<'_>, &str> {
+    lift(discard(ws(), take_while1(char::is_alphabetic))).parse(input)
+}
+
+/// `let x;` declares a name, `x;` uses a declared one
+fn statement(input: Input) -> ParseResult<Input, String> {
+    let declaration = with_state(
+        discard(lift(discard(ws(), sequence("let "))), name),
+        |name, declared: &mut HashSet<String>| {
+            declared.insert(name.to_string());
+            Some(format!("declare {}", name))
+        },
+    );
+    let usage = with_state(name, |name, declared: &mut HashSet<String>| {
+        declared.contains(name).then(|| format!("use {}", name))
+    });
+    declaration.or(usage).skip(lift(sequence(";"))).parse(input)
+}
+
+#[test]
+fn track_declarations() {
+    let (remainder, statements) = statement
+        .many0()
+        .parse(Stateful::new("let x; x; let y; y; x;", HashSet::new()))
+        .unwrap();
+    assert_eq!(remainder.input, "");
+    assert_eq!(
+        statements,
+        vec!["declare x", "use x", "declare y", "use y", "use x"]
+    );
+    assert_eq!(remainder.state.len(), 2);
+    assert_eq!(
+        state(|declared: &HashSet<String>| declared.contains("y"))
+            .parse(remainder)
+            .map(|(_, found)| found),
+        Ok(true)
+    );
+}
+
+#[test]
+fn reject_undeclared_and_restore_state() {
+    let error = statement
+        .parse(Stateful::new(" z;", HashSet::new()))
+        .unwrap_err();
+    assert_eq!(error.index, 2);
+
+    let (remainder, declared) = statement
+        .many0()
+        .parse(Stateful::new("let a; b;", HashSet::new()))
+        .unwrap();
+    assert_eq!(remainder.input, " b;");
+    assert_eq!(declared, vec!["declare a"]);
+    assert!(!remainder.state.contains("b"));
+}
@@ -0,0 +1,53 @@
+use pepser::{
+    errors::ErrorSource,
+    impls::{
+        take_until, take_until_consuming, take_until_consuming_escaped, take_until_keeping,
+        take_until_keeping_escaped,
+    },
+    traits::Parser,
+};
+
+#[test]
+fn keep_or_consume_the_delimiter() {
+    let input = "key = value";
+    assert_eq!(
+        take_until_keeping(" = ").parse(input),
+        Ok((" = value", "key"))
+    );
+    assert_eq!(
+        take_until_keeping(" = ").parse(input),
+        take_until(" = ").parse(input)
+    );
+    assert_eq!(
+        take_until_consuming(" = ").parse(input),
+        Ok(("value", "key"))
+    );
+    assert_eq!(take_until_consuming("日").parse("é日本"), Ok(("本", "é")));
+    assert_eq!(take_until_consuming("").parse("ab"), Ok(("ab", "")));
+
+    let error = take_until_consuming(";").parse(input).unwrap_err();
+    assert_eq!(
+        (error.index, &error.source),
+        (0, &ErrorSource::TakeUntil(";"))
+    );
+    assert_eq!(error, take_until_keeping(";").parse(input).unwrap_err());
+}
+
+#[test]
+fn skip_escaped_delimiters() {
+    let mut quoted = take_until_consuming_escaped("\"", '\\');
+    assert_eq!(
+        quoted.parse(r#"say \"hi\"" end"#),
+        Ok((" end", r#"say \"hi\""#))
+    );
+    // An escaped escape does not escape the delimiter
+    assert_eq!(quoted.parse(r#"\\" end"#), Ok((" end", r"\\")));
+    assert!(quoted.parse(r#"open \""#).is_err());
+    assert!(quoted.parse("\\").is_err());
+
+    // Escapes and delimiters may be multibyte
+    let mut cell = take_until_keeping_escaped("||", 'é');
+    assert_eq!(cell.parse("aé||b||c"), Ok(("||c", "aé||b")));
+    assert_eq!(cell.parse("éé||"), Ok(("||", "éé")));
+    assert_eq!(take_until_keeping_escaped("", '\\').parse(""), Ok(("", "")));
+}
@@ -0,0 +1,103 @@
+#![cfg(feature = "std")]
+
+use std::cell::Cell;
+
+use pepser::{
+    impls::{sequence, take_while0, take_while1},
+    json::json_value,
+    testing::{and_associative, check, json, map_identity, or_idempotent, round_trip, text, Rng},
+    traits::Parser,
+};
+
+const FRAGMENTS: &[&str] = &["a", "b", "ab", "1", "12", " ", "é", "日"];
+
+#[test]
+fn json_values_round_trip() {
+    let result = check(
+        2000,
+        42,
+        |rng| json(rng, 4),
+        |value| round_trip(value, &value.to_string(), json_value),
+    );
+    if let Err(failure) = result {
+        panic!("{failure}");
+    }
+}
+
+#[test]
+fn failures_replay_from_their_seed() {
+    let failure = check(
+        1000,
+        3,
+        |rng| text(rng, FRAGMENTS, 6),
+        |text| match text.contains("b1") {
+            true => Err("contains b1".to_string()),
+            false => Ok(()),
+        },
+    )
+    .unwrap_err();
+    assert!(failure.value.contains("b1"));
+    assert_eq!(
+        text(&mut Rng::new(failure.seed), FRAGMENTS, 6),
+        failure.value
+    );
+    assert!(failure
+        .to_string()
+        .starts_with(&format!("case {}", failure.case)));
+
+    // The same seed draws the same cases
+    let again = check(
+        1000,
+        3,
+        |rng| text(rng, FRAGMENTS, 6),
+        |text| match text.contains("b1") {
+            true => Err("contains b1".to_string()),
+            false => Ok(()),
+        },
+    )
+    .unwrap_err();
+    assert_eq!((again.case, again.seed), (failure.case, failure.seed));
+}
+
+#[test]
+fn combinator_laws() {
+    let result = check(
+        1000,
+        9,
+        |rng| text(rng, FRAGMENTS, 6),
+        |input| {
+            let input = input.as_str();
+            or_idempotent(|| sequence("ab").expect("ab"), input)?;
+            or_idempotent(|| take_while1(|c| c.is_alphabetic()), input)?;
+            or_idempotent(|| sequence("a").cut().and(sequence("b")), input)?;
+            map_identity(|| take_while0(|c| c.is_ascii_digit()), input)?;
+            and_associative(
+                || sequence("a"),
+                || take_while0(|c| c == 'b'),
+                || take_while1(|c| c.is_ascii_digit()).expect("digits"),
+                input,
+            )?;
+            and_associative(
+                || take_while1(|c| !c.is_ascii()),
+                || sequence(" ").many0(),
+                || sequence("1").cut(),
+                input,
+            )
+        },
+    );
+    if let Err(failure) = result {
+        panic!("{failure}");
+    }
+}
+
+#[test]
+fn laws_report_differences() {
+    let calls = Cell::new(0);
+    // A parser whose second instance behaves differently breaks the law
+    let broken = || {
+        calls.set(calls.get() + 1);
+        sequence(if calls.get() == 1 { "a" } else { "b" })
+    };
+    let reason = map_identity(broken, "a").unwrap_err();
+    assert!(reason.contains("differs from"), "{reason}");
+}
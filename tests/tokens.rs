@@ -0,0 +1,66 @@
+use pepser::{
+    impls::{eof, satisfy_token, token},
+    traits::{chainl1, wrapped, ParseResult, Parser},
+};
+
+#[derive(Debug, PartialEq, Clone)]
+enum Token {
+    Number(i64),
+    Plus,
+    Star,
+    Open,
+    Close,
+}
+
+fn expression(input: &[Token]) -> ParseResult<&[Token], i64> {
+    chainl1(
+        term,
+        token(Token::Plus).map(|_| (|a, b| a + b) as fn(i64, i64) -> i64),
+    )
+    .parse(input)
+}
+
+fn term(input: &[Token]) -> ParseResult<&[Token], i64> {
+    chainl1(
+        factor,
+        token(Token::Star).map(|_| (|a, b| a * b) as fn(i64, i64) -> i64),
+    )
+    .parse(input)
+}
+
+fn factor(input: &[Token]) -> ParseResult<&[Token], i64> {
+    satisfy_token(|token| matches!(token, Token::Number(_)))
+        .map(|token| match token {
+            Token::Number(value) => *value,
+            _ => unreachable!(),
+        })
+        .or(wrapped(token(Token::Open), expression, token(Token::Close)))
+        .parse(input)
+}
+
+#[test]
+fn parse_token_stream() {
+    use Token::*;
+    let tokens = [
+        Number(2),
+        Star,
+        Open,
+        Number(3),
+        Plus,
+        Number(4),
+        Close,
+        Plus,
+        Number(1),
+    ];
+    let (remainder, value) = expression.and(eof()).parse(&tokens).unwrap();
+    assert!(remainder.is_empty());
+    assert_eq!(value, (15, ()));
+}
+
+#[test]
+fn reject_unexpected_token() {
+    use Token::*;
+    let tokens = [Open, Number(1), Plus];
+    assert!(expression.and(eof()).parse(&tokens).is_err());
+    assert!(token(Close).parse(&[][..]).is_err());
+}
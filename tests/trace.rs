@@ -0,0 +1,147 @@
+#![cfg(feature = "std")]
+
+use pepser::{
+    impls::{sequence, take_while1},
+    trace::{Recorder, Trace, TraceEvent, TraceNode},
+    traits::{sep_by, Parser},
+};
+
+fn node(
+    rule: &str,
+    start: usize,
+    end: usize,
+    success: bool,
+    children: Vec<TraceNode>,
+) -> TraceNode {
+    TraceNode {
+        rule: rule.to_string(),
+        start,
+        end,
+        success,
+        children,
+    }
+}
+
+#[test]
+fn record_decision_tree() {
+    let recorder = Recorder::new();
+    let number = recorder.rule("number", take_while1(|c| c.is_ascii_digit()));
+    let word = recorder.rule("word", take_while1(|c| c.is_ascii_alphabetic()));
+    let mut list = recorder.rule("list", sep_by(number.or(word), sequence(",")));
+
+    assert_eq!(list.parse("1,ab;"), Ok((";", vec!["1", "ab"])));
+    assert_eq!(
+        recorder.trace().tree(),
+        vec![node(
+            "list",
+            0,
+            4,
+            true,
+            vec![
+                node("number", 0, 1, true, vec![]),
+                node("number", 2, 2, false, vec![]),
+                node("word", 2, 4, true, vec![]),
+            ]
+        )]
+    );
+}
+
+#[test]
+fn trace_file_round_trip() {
+    let trace = Trace {
+        events: vec![
+            TraceEvent::Enter {
+                rule: "value".to_string(),
+                offset: 0,
+            },
+            TraceEvent::Enter {
+                rule: "odd name\nwith \\ escapes".to_string(),
+                offset: 3,
+            },
+            TraceEvent::Exit {
+                rule: "odd name\nwith \\ escapes".to_string(),
+                offset: 7,
+                success: false,
+            },
+            TraceEvent::Exit {
+                rule: "value".to_string(),
+                offset: 12,
+                success: true,
+            },
+        ],
+    };
+    let text = trace.to_string();
+    assert_eq!(
+        text,
+        "pepser-trace 1\n+0 value\n+3 odd name\\nwith \\\\ escapes\n-7 err odd name\\nwith \\\\ escapes\n-12 ok value\n"
+    );
+    assert_eq!(Trace::parse(&text), Ok(trace));
+    assert!(Trace::parse("pepser-trace 1\n+x value\n").is_err());
+    assert!(Trace::parse("+0 value\n").is_err());
+}
+
+#[test]
+fn round_trip_rule_names() {
+    for rule in ["a\\n", "\\", "\\\n\\", "\n", "", " ", "tab\there"] {
+        let trace = Trace {
+            events: vec![
+                TraceEvent::Enter {
+                    rule: rule.to_string(),
+                    offset: 0,
+                },
+                TraceEvent::Exit {
+                    rule: rule.to_string(),
+                    offset: 2,
+                    success: true,
+                },
+            ],
+        };
+        assert_eq!(Trace::parse(&trace.to_string()), Ok(trace), "{:?}", rule);
+    }
+    assert!(Trace::parse("pepser-trace 1\n+99999999999999999999999 value\n").is_err());
+}
+
+#[test]
+fn close_unterminated_rules() {
+    let trace = Trace::parse("pepser-trace 1\n+0 a\n+1 b\n-3 ok b\n+3 c\n").unwrap();
+    assert_eq!(
+        trace.tree(),
+        vec![node(
+            "a",
+            0,
+            3,
+            false,
+            vec![
+                node("b", 1, 3, true, vec![]),
+                node("c", 3, 3, false, vec![])
+            ]
+        )]
+    );
+}
+
+#[test]
+fn render_and_export() {
+    let trace = Trace::parse("pepser-trace 1\n+0 list\n+0 number\n-1 ok number\n+2 \"word\"\n-2 err \"word\"\n-2 ok list\n").unwrap();
+    assert_eq!(
+        trace.render(),
+        "list 0..2 ok\n  number 0..1 ok\n  \"word\" 2..2 err\n"
+    );
+    assert_eq!(
+        trace.to_dot(),
+        "digraph trace {\n    node [shape=box];\n    n0 [label=\"list\\n0..2\"];\n    n1 [label=\"number\\n0..1\"];\n    n0 -> n1;\n    n2 [label=\"\\\"word\\\"\\n2..2\", color=red];\n    n0 -> n2;\n}\n"
+    );
+    assert_eq!(
+        trace.to_json(),
+        r#"[{"rule":"list","start":0,"end":2,"success":true,"children":[{"rule":"number","start":0,"end":1,"success":true,"children":[]},{"rule":"\"word\"","start":2,"end":2,"success":false,"children":[]}]}]"#
+    );
+
+    let trace = Trace::parse("pepser-trace 1\n+0 a\tb & c\n-1 ok a\tb & c\n").unwrap();
+    assert_eq!(
+        trace.to_dot(),
+        "digraph trace {\n    node [shape=box];\n    n0 [label=\"a&#9;b &amp; c\\n0..1\"];\n}\n"
+    );
+    assert_eq!(
+        trace.to_json(),
+        r#"[{"rule":"a\u0009b & c","start":0,"end":1,"success":true,"children":[]}]"#
+    );
+}
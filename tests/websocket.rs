@@ -0,0 +1,76 @@
+use pepser::{
+    errors::ParserError,
+    websocket::{frame, frame_header, FrameHeader, Opcode},
+};
+
+#[test]
+fn extended_lengths() {
+    let mut binary = vec![0x02, 0x7E, 0x01, 0x00];
+    binary.extend([7; 256]);
+    let (remainder, (header, payload)) = frame(&binary).unwrap();
+    assert!(remainder.is_empty());
+    assert_eq!(
+        header,
+        FrameHeader {
+            fin: false,
+            rsv: [false; 3],
+            opcode: Opcode::Binary,
+            mask: None,
+            payload_length: 256,
+        }
+    );
+    assert_eq!(payload.len(), 256);
+
+    let long = [0x80, 0xFF, 0, 0, 0, 1, 0, 0, 0, 0, 1, 2, 3, 4];
+    let (remainder, header) = frame_header(&long).unwrap();
+    assert!(remainder.is_empty());
+    assert_eq!(header.opcode, Opcode::Continuation);
+    assert_eq!(header.payload_length, 1 << 32);
+    assert_eq!(header.mask, Some([1, 2, 3, 4]));
+
+    let (_, reserved) = frame_header(&[0x63, 0x00]).unwrap();
+    assert_eq!(reserved.rsv, [true, true, false]);
+    assert_eq!(reserved.opcode, Opcode::Reserved(3));
+    assert!(!reserved.opcode.is_control());
+}
+
+#[test]
+fn unmask_in_parts() {
+    let header = FrameHeader {
+        fin: true,
+        rsv: [false; 3],
+        opcode: Opcode::Text,
+        mask: Some([1, 2, 3, 4]),
+        payload_length: 6,
+    };
+    let mut payload = *b"pepser";
+    header.unmask(&mut payload, 0);
+    let (first, second) = payload.split_at_mut(3);
+    header.unmask(first, 0);
+    header.unmask(second, 3);
+    assert_eq!(&payload, b"pepser");
+}
+
+#[test]
+fn header_errors() {
+    fn error(input: &[u8]) -> ParserError<&[u8]> {
+        frame_header(input).unwrap_err()
+    }
+    assert_eq!(error(&[0x81]).index, 1);
+    assert_eq!(error(&[0x81, 0x7E, 0x00]).index, 3);
+    assert_eq!(error(&[0x81, 0xFE, 0x00, 0x80, 1]).index, 5);
+    assert_eq!(
+        error(&[0x81, 0x7E, 0x00, 0x7D]).reason,
+        "payload length not in its shortest encoding"
+    );
+    assert_eq!(
+        error(&[0x81, 0x7F, 0x80, 0, 0, 0, 0, 0, 0, 0]).reason,
+        "payload length with its high bit set"
+    );
+    assert_eq!(
+        error(&[0x88, 0x7E, 0x00, 0x80]).reason,
+        "fragmented or oversized control frame"
+    );
+    assert!(frame_header(&[0x0B, 0x00]).is_err());
+    assert_eq!(frame(&[0x81, 0x03, b'h', b'i']).unwrap_err().index, 4);
+}
@@ -0,0 +1,83 @@
+#![cfg(feature = "std")]
+
+use pepser::xml::{xml_document, xml_element, XmlDocument, XmlElement, XmlNode};
+
+#[test]
+fn parse_document() {
+    use XmlNode::*;
+    assert_eq!(
+        Ok((
+            "",
+            XmlDocument {
+                prolog: vec![
+                    ProcessingInstruction {
+                        target: "xml".to_string(),
+                        data: "version=\"1.0\"".to_string()
+                    },
+                    Comment(" catalog ".to_string())
+                ],
+                root: XmlElement {
+                    name: "catalog".to_string(),
+                    attributes: vec![("lang".to_string(), "en".to_string())],
+                    children: vec![
+                        Element(XmlElement {
+                            name: "book".to_string(),
+                            attributes: vec![
+                                ("id".to_string(), "1".to_string()),
+                                ("title".to_string(), "Tom & Jerry".to_string())
+                            ],
+                            children: vec![Text("Cats < Mice".to_string())]
+                        }),
+                        Element(XmlElement {
+                            name: "script".to_string(),
+                            attributes: vec![],
+                            children: vec![CData("if (a < b) {}".to_string())]
+                        }),
+                        ProcessingInstruction {
+                            target: "render".to_string(),
+                            data: "fast".to_string()
+                        },
+                        Element(XmlElement {
+                            name: "empty".to_string(),
+                            attributes: vec![],
+                            children: vec![]
+                        })
+                    ]
+                },
+                epilog: vec![]
+            }
+        )),
+        xml_document(
+            "<?xml version=\"1.0\"?>
+            <!-- catalog -->
+            <catalog lang='en'>
+                <book id=\"1\" title=\"Tom &amp; Jerry\">Cats &lt; Mice</book>
+                <script><![CDATA[if (a < b) {}]]></script>
+                <?render fast ?>
+                <empty />
+            </catalog>
+            "
+        )
+    );
+}
+
+#[test]
+fn decode_character_references() {
+    let (_, element) = xml_element("<p>&#72;&#x69;&apos;&quot;</p>").unwrap();
+    assert_eq!(element.text(), "Hi'\"");
+}
+
+#[test]
+fn reject_invalid_documents() {
+    assert!(xml_element("<a><b></a></b>").is_err());
+    assert!(xml_element("<a>&unknown;</a>").is_err());
+    assert!(xml_document("<a/><b/>").is_err());
+}
+
+#[test]
+fn error_indexes_are_offsets_in_the_input() {
+    assert_eq!(xml_document("<a></a><b/>").unwrap_err().index, 7);
+    assert_eq!(xml_document("<?x?>\n<a></b>").unwrap_err().index, 11);
+    assert_eq!(xml_element("<a><b></c></a>").unwrap_err().index, 8);
+    assert_eq!(xml_element("<a x='1'>text").unwrap_err().index, 13);
+}
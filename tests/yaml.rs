@@ -0,0 +1,68 @@
+#![cfg(feature = "std")]
+
+use pepser::{
+    json::{
+        JsonNumber::{Float, Int},
+        JsonValue::{self, *},
+    },
+    yaml::yaml,
+};
+
+fn object(entries: Vec<(&str, JsonValue)>) -> JsonValue {
+    Object(
+        entries
+            .into_iter()
+            .map(|(key, value)| (key.to_string(), value))
+            .collect(),
+    )
+}
+
+#[test]
+fn parse_flow_collections() {
+    assert_eq!(
+        yaml("{a: 1, b: [2, 3.5], c: {d: true, e: ~}, f:, \"g h\": plain text here,}"),
+        Ok((
+            "",
+            object(vec![
+                ("a", Number(Int(1))),
+                ("b", Array(vec![Number(Int(2)), Number(Float(3.5))])),
+                ("c", object(vec![("d", Boolean(true)), ("e", Null)])),
+                ("f", Null),
+                ("g h", String("plain text here".to_string())),
+            ])
+        ))
+    );
+}
+
+#[test]
+fn parse_scalars_and_comments() {
+    assert_eq!(
+        yaml(
+            "# servers\n[ 'it''s', \"tab\\tand \\u00e9\", http://example.com:80, 0x1F, -.inf, 1.2.3 ] # done\n"
+        ),
+        Ok((
+            "",
+            Array(vec![
+                String("it's".to_string()),
+                String("tab\tand é".to_string()),
+                String("http://example.com:80".to_string()),
+                Number(Int(31)),
+                Number(Float(f64::NEG_INFINITY)),
+                String("1.2.3".to_string()),
+            ])
+        ))
+    );
+    assert_eq!(
+        yaml("  hello world "),
+        Ok(("", String("hello world".to_string())))
+    );
+}
+
+#[test]
+fn reject_invalid_documents() {
+    assert_eq!(yaml("{a: [1, 2}").map_err(|error| error.index), Err(9));
+    assert!(yaml("\"unterminated").is_err());
+    assert!(yaml("'bad\\q'").is_ok());
+    assert!(yaml("\"bad\\q\"").is_err());
+    assert!(yaml("[1] [2]").is_err());
+}